@@ -0,0 +1,1441 @@
+//! Write the non-spanned tree back out as javascript source text
+//!
+//! The output is not formatted to match any original source, it
+//! only aims to parse back into an equivalent tree. Parentheses are
+//! added wherever operator precedence or the start of a statement
+//! would otherwise change the meaning of an expression.
+//!
+//! ```
+//! use resast::prelude::*;
+//! use resast::codegen::WriteJs;
+//! use resast::{LogicalOp, MemberIndexer, UnaryOp};
+//!
+//! let sum = Expr::Binary(BinaryExpr {
+//!     operator: BinaryOp::Plus,
+//!     left: Box::new(Expr::ident_from("a")),
+//!     right: Box::new(Expr::ident_from("b")),
+//! });
+//! let product = Expr::Binary(BinaryExpr {
+//!     operator: BinaryOp::Times,
+//!     left: Box::new(sum.clone()),
+//!     right: Box::new(Expr::Lit(Lit::number_from("2"))),
+//! });
+//! assert_eq!(product.to_js_string(), "(a + b) * 2");
+//!
+//! let stmt = Stmt::Expr(Expr::Member(MemberExpr {
+//!     object: Box::new(Expr::Obj(Vec::new())),
+//!     property: Box::new(Expr::ident_from("a")),
+//!     indexer: MemberIndexer::Period,
+//! }));
+//! assert_eq!(stmt.to_js_string(), "({}.a);");
+//!
+//! // not a directive and not a `let` declaration
+//! let stmt = Stmt::Expr(Expr::Lit(Lit::single_string_from("use strict")));
+//! assert_eq!(stmt.to_js_string(), "('use strict');");
+//! let let_x = Expr::member(Expr::ident_from("let"), Expr::ident_from("x"), true);
+//! assert_eq!(Stmt::Expr(let_x.clone()).to_js_string(), "(let[x]);");
+//! let for_init = Stmt::For(ForStmt {
+//!     init: Some(LoopInit::Expr(Expr::Assign(AssignExpr {
+//!         operator: AssignOp::Equal,
+//!         left: AssignLeft::Expr(Box::new(let_x)),
+//!         right: Box::new(Expr::Lit(Lit::number_from("1"))),
+//!     }))),
+//!     test: None,
+//!     update: None,
+//!     body: Box::new(Stmt::Empty),
+//! });
+//! assert_eq!(for_init.to_js_string(), "for ((let[x] = 1);;) ;");
+//!
+//! let for_of = |left: &'static str| {
+//!     Stmt::ForOf(ForOfStmt {
+//!         left: LoopLeft::Expr(Expr::ident_from(left)),
+//!         right: Expr::ident_from("x"),
+//!         body: Box::new(Stmt::Empty),
+//!         is_await: false,
+//!     })
+//! };
+//! assert_eq!(for_of("let").to_js_string(), "for ((let) of x) ;");
+//! assert_eq!(for_of("async").to_js_string(), "for ((async) of x) ;");
+//! assert_eq!(for_of("a").to_js_string(), "for (a of x) ;");
+//!
+//! let power = Expr::Binary(BinaryExpr {
+//!     operator: BinaryOp::PowerOf,
+//!     left: Box::new(Expr::Unary(UnaryExpr {
+//!         operator: UnaryOp::Minus,
+//!         prefix: true,
+//!         argument: Box::new(Expr::ident_from("a")),
+//!     })),
+//!     right: Box::new(Expr::Lit(Lit::number_from("2"))),
+//! });
+//! assert_eq!(power.to_js_string(), "(-a) ** 2");
+//!
+//! let coalesce = Expr::Logical(LogicalExpr {
+//!     operator: LogicalOp::NullishCoalescing,
+//!     left: Box::new(Expr::ident_from("a")),
+//!     right: Box::new(Expr::Logical(LogicalExpr {
+//!         operator: LogicalOp::Or,
+//!         left: Box::new(Expr::ident_from("b")),
+//!         right: Box::new(Expr::Lit(Lit::single_string_from("c"))),
+//!     })),
+//! });
+//! assert_eq!(coalesce.to_js_string(), "a ?? (b || 'c')");
+//! ```
+use std::fmt::{self, Write};
+
+use crate::decl::{
+    Decl, DefaultExportDecl, ExportSpecifier, ImportAttributeKey, ImportAttributesKeyword,
+    ImportSpecifier, ModExport, ModImport, NamedExportDecl, VarDecl,
+};
+use crate::expr::{
+    ArrowFuncBody, ArrowFuncExpr, Expr, Lit, MemberExpr, ObjProp, Prop, PropKey, PropValue,
+    QuasiQuote, StringLit, TemplateLit,
+};
+use crate::pat::{ArrayPatPart, ObjPatPart, Pat};
+use crate::stmt::{LoopInit, LoopLeft, Stmt};
+use crate::{
+    AssignOp, Assoc, BinaryOp, Class, Decorator, Dir, Func, FuncArg, FuncBody, LogicalOp,
+    MemberIndexer, Program, ProgramPart, PropKind, UnaryOp, UpdateOp, VarKind,
+};
+
+#[cfg(feature = "jsx")]
+use crate::jsx::{
+    JsxAttr, JsxAttributeValue, JsxChild, JsxElement, JsxElementName, JsxExpressionContainer,
+    JsxFragment,
+};
+
+/// A node that can be written out as javascript source text
+pub trait WriteJs {
+    /// Write this node into `w`
+    fn write_js(&self, w: &mut impl Write) -> fmt::Result;
+
+    /// Write this node into a new `String`
+    fn to_js_string(&self) -> String {
+        let mut ret = String::new();
+        self.write_js(&mut ret)
+            .expect("writing to a String cannot fail");
+        ret
+    }
+}
+
+impl<T: AsRef<str>> WriteJs for Program<T> {
+    fn write_js(&self, w: &mut impl Write) -> fmt::Result {
+        let (hashbang, parts) = match self {
+            Program::Mod { hashbang, parts } => (hashbang, parts),
+            Program::Script { hashbang, parts } => (hashbang, parts),
+        };
+        if let Some(hashbang) = hashbang {
+            w.write_str("#!")?;
+            w.write_str(hashbang.as_ref())?;
+            w.write_str("\n")?;
+        }
+        Printer { out: w }.parts(parts, "\n")
+    }
+}
+
+impl<T: AsRef<str>> WriteJs for ProgramPart<T> {
+    fn write_js(&self, w: &mut impl Write) -> fmt::Result {
+        Printer { out: w }.program_part(self)
+    }
+}
+
+impl<T: AsRef<str>> WriteJs for Dir<T> {
+    fn write_js(&self, w: &mut impl Write) -> fmt::Result {
+        Printer { out: w }.dir(self)
+    }
+}
+
+impl<T: AsRef<str>> WriteJs for Decl<T> {
+    fn write_js(&self, w: &mut impl Write) -> fmt::Result {
+        Printer { out: w }.decl(self)
+    }
+}
+
+impl<T: AsRef<str>> WriteJs for Stmt<T> {
+    fn write_js(&self, w: &mut impl Write) -> fmt::Result {
+        Printer { out: w }.stmt(self)
+    }
+}
+
+impl<T: AsRef<str>> WriteJs for Expr<T> {
+    fn write_js(&self, w: &mut impl Write) -> fmt::Result {
+        Printer { out: w }.expr(self, SEQUENCE)
+    }
+}
+
+impl<T: AsRef<str>> WriteJs for Pat<T> {
+    fn write_js(&self, w: &mut impl Write) -> fmt::Result {
+        Printer { out: w }.pat(self)
+    }
+}
+
+impl<T: AsRef<str>> WriteJs for Lit<T> {
+    fn write_js(&self, w: &mut impl Write) -> fmt::Result {
+        Printer { out: w }.lit(self)
+    }
+}
+
+const SEQUENCE: u8 = 1;
+const ASSIGN: u8 = 2;
+const CONDITIONAL: u8 = 3;
+const COALESCE: u8 = 4;
+const UNARY: u8 = 15;
+const POSTFIX: u8 = 16;
+const CALL: u8 = 18;
+const PRIMARY: u8 = 19;
+/// Higher than any precedence, used to force parentheses
+const ALWAYS_WRAP: u8 = u8::MAX;
+
+fn precedence<T>(expr: &Expr<T>) -> u8 {
+    match expr {
+        Expr::Sequence(_) => SEQUENCE,
+        Expr::ArrowFunc(_)
+        | Expr::ArrowParamPlaceHolder(_, _)
+        | Expr::Assign(_)
+        | Expr::Spread(_)
+        | Expr::Yield(_) => ASSIGN,
+        Expr::Conditional(_) => CONDITIONAL,
+        Expr::Logical(inner) => inner.operator.precedence(),
+        Expr::Binary(inner) => inner.operator.precedence(),
+        Expr::Await(_) | Expr::Unary(_) => UNARY,
+        Expr::Update(inner) if inner.prefix => UNARY,
+        Expr::Update(_) => POSTFIX,
+        Expr::Call(_)
+        | Expr::Import(_)
+        | Expr::Member(_)
+        | Expr::MetaProp(_)
+        | Expr::New(_)
+        | Expr::OptionalChain(_)
+        | Expr::Super
+        | Expr::TaggedTemplate(_) => CALL,
+        Expr::Array(_)
+        | Expr::Class(_)
+        | Expr::Func(_)
+        | Expr::Ident(_)
+        | Expr::PrivateIdent(_)
+        | Expr::Lit(_)
+        | Expr::Obj(_)
+        | Expr::Paren(_)
+        | Expr::This => PRIMARY,
+        #[cfg(feature = "jsx")]
+        Expr::Jsx(_) => PRIMARY,
+        #[cfg(feature = "jsx")]
+        Expr::JsxFragment(_) => PRIMARY,
+    }
+}
+
+/// If an expression statement starts with one of these, it would
+/// be parsed as a block, declaration or pattern instead
+fn starts_ambiguous<T: AsRef<str>>(expr: &Expr<T>) -> bool {
+    match expr {
+        Expr::Obj(_) | Expr::Func(_) | Expr::Class(_) => true,
+        Expr::Assign(inner) => match &inner.left {
+            crate::expr::AssignLeft::Expr(left) => starts_ambiguous(left),
+            crate::expr::AssignLeft::Pat(Pat::Obj(_)) => true,
+            crate::expr::AssignLeft::Pat(_) => false,
+        },
+        _ if is_let_bracket(expr) => true,
+        _ => first_operand(expr).map(starts_ambiguous).unwrap_or(false),
+    }
+}
+
+/// `let [` always starts a lexical declaration, in a statement
+/// or in the head of a `for` loop
+fn starts_with_let_bracket<T: AsRef<str>>(expr: &Expr<T>) -> bool {
+    is_let_bracket(expr)
+        || first_operand(expr)
+            .map(starts_with_let_bracket)
+            .unwrap_or(false)
+}
+
+fn is_let_bracket<T: AsRef<str>>(expr: &Expr<T>) -> bool {
+    matches!(expr, Expr::Member(inner)
+        if inner.indexer == MemberIndexer::Computed && is_ident(&inner.object, "let"))
+}
+
+/// The left side of a `for...of` can't start with `let` or be
+/// a lone `async`, `for (let of x)` would read as a declaration
+/// and `for (async of` as the start of an async arrow
+fn for_of_left_needs_parens<T: AsRef<str>>(expr: &Expr<T>) -> bool {
+    is_ident(expr, "async") || starts_with_ident(expr, "let") || starts_ambiguous(expr)
+}
+
+fn starts_with_ident<T: AsRef<str>>(expr: &Expr<T>, name: &str) -> bool {
+    is_ident(expr, name)
+        || first_operand(expr)
+            .map(|first| starts_with_ident(first, name))
+            .unwrap_or(false)
+}
+
+fn is_ident<T: AsRef<str>>(expr: &Expr<T>, name: &str) -> bool {
+    matches!(expr, Expr::Ident(ident) if ident.name.as_ref() == name)
+}
+
+/// The operand that is written first, with no token before it,
+/// so both share the first token
+fn first_operand<T>(expr: &Expr<T>) -> Option<&Expr<T>> {
+    match expr {
+        Expr::Assign(inner) => match &inner.left {
+            crate::expr::AssignLeft::Expr(left) => Some(left),
+            crate::expr::AssignLeft::Pat(_) => None,
+        },
+        Expr::Binary(inner) => Some(&inner.left),
+        Expr::Logical(inner) => Some(&inner.left),
+        Expr::Conditional(inner) => Some(&inner.test),
+        Expr::Call(inner) => Some(&inner.callee),
+        Expr::Member(inner) => Some(&inner.object),
+        Expr::Sequence(inner) => inner.iter().next(),
+        Expr::TaggedTemplate(inner) => Some(&inner.tag),
+        Expr::Update(inner) if !inner.prefix => Some(&inner.argument),
+        Expr::OptionalChain(inner) => Some(inner),
+        _ => None,
+    }
+}
+
+/// A `new` callee can't contain a call unless it is wrapped,
+/// otherwise the call's arguments would be taken as `new`'s
+fn new_callee_needs_parens<T>(expr: &Expr<T>) -> bool {
+    match expr {
+        Expr::Call(_) | Expr::OptionalChain(_) | Expr::Import(_) => true,
+        Expr::Member(inner) => new_callee_needs_parens(&inner.object),
+        Expr::TaggedTemplate(inner) => new_callee_needs_parens(&inner.tag),
+        _ => precedence(expr) < CALL,
+    }
+}
+
+/// Without parentheses a decorator may only be a dotted path,
+/// optionally followed by a single call
+fn is_bare_decorator<T>(expr: &Expr<T>) -> bool {
+    match expr {
+        Expr::Call(inner) => is_decorator_path(&inner.callee),
+        _ => is_decorator_path(expr),
+    }
+}
+
+fn is_decorator_path<T>(expr: &Expr<T>) -> bool {
+    match expr {
+        Expr::Ident(_) => true,
+        Expr::Member(inner) => {
+            inner.indexer == MemberIndexer::Period && is_decorator_path(&inner.object)
+        }
+        _ => false,
+    }
+}
+
+/// An `if` without an `else` at the end of the consequent would
+/// take the outer `else` for itself
+fn ends_with_open_if<T>(stmt: &Stmt<T>) -> bool {
+    match stmt {
+        Stmt::If(inner) => match &inner.alternate {
+            Some(alt) => ends_with_open_if(alt),
+            None => true,
+        },
+        Stmt::Labeled(inner) => ends_with_open_if(&inner.body),
+        Stmt::With(inner) => ends_with_open_if(&inner.body),
+        Stmt::While(inner) => ends_with_open_if(&inner.body),
+        Stmt::For(inner) => ends_with_open_if(&inner.body),
+        Stmt::ForIn(inner) => ends_with_open_if(&inner.body),
+        Stmt::ForOf(inner) => ends_with_open_if(&inner.body),
+        _ => false,
+    }
+}
+
+type Res = fmt::Result;
+
+struct Printer<'a, W> {
+    out: &'a mut W,
+}
+
+impl<'a, W: Write> Printer<'a, W> {
+    fn s(&mut self, s: &str) -> Res {
+        self.out.write_str(s)
+    }
+
+    fn parts<T: AsRef<str>>(&mut self, parts: &[ProgramPart<T>], sep: &str) -> Res {
+        for (i, part) in parts.iter().enumerate() {
+            if i > 0 {
+                self.s(sep)?;
+            }
+            self.program_part(part)?;
+        }
+        Ok(())
+    }
+
+    fn program_part<T: AsRef<str>>(&mut self, part: &ProgramPart<T>) -> Res {
+        match part {
+            ProgramPart::Dir(inner) => self.dir(inner),
+            ProgramPart::Decl(inner) => self.decl(inner),
+            ProgramPart::Stmt(inner) => self.stmt(inner),
+        }
+    }
+
+    fn dir<T: AsRef<str>>(&mut self, dir: &Dir<T>) -> Res {
+        self.lit(&dir.expr)?;
+        self.s(";")
+    }
+
+    fn block<T: AsRef<str>>(&mut self, parts: &[ProgramPart<T>]) -> Res {
+        if parts.is_empty() {
+            return self.s("{}");
+        }
+        self.s("{ ")?;
+        self.parts(parts, " ")?;
+        self.s(" }")
+    }
+
+    fn decl<T: AsRef<str>>(&mut self, decl: &Decl<T>) -> Res {
+        match decl {
+            Decl::Var(kind, decls) => {
+                self.var_decls(*kind, decls, false)?;
+                self.s(";")
+            }
+            Decl::Func(inner) => self.func(inner),
+            Decl::Class(inner) => self.class(inner),
+            Decl::Import(inner) => self.mod_import(inner),
+            Decl::Export(inner) => self.mod_export(inner),
+        }
+    }
+
+    /// `for_init` wraps any initializer that has an `in` operator,
+    /// see [`Expr::needs_for_header_parens`]
+    fn var_decls<T: AsRef<str>>(
+        &mut self,
+        kind: VarKind,
+        decls: &[VarDecl<T>],
+        for_init: bool,
+    ) -> Res {
+        self.s(kind.as_str())?;
+        self.s(" ")?;
+        for (i, decl) in decls.iter().enumerate() {
+            if i > 0 {
+                self.s(", ")?;
+            }
+            self.var_decl(decl, for_init)?;
+        }
+        Ok(())
+    }
+
+    fn var_decl<T: AsRef<str>>(&mut self, decl: &VarDecl<T>, for_init: bool) -> Res {
+        self.pat(&decl.id)?;
+        if let Some(init) = &decl.init {
+            self.s(" = ")?;
+            if for_init && init.needs_for_header_parens() {
+                self.expr(init, ALWAYS_WRAP)?;
+            } else {
+                self.expr(init, ASSIGN)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn mod_import<T: AsRef<str>>(&mut self, import: &ModImport<T>) -> Res {
+        self.s("import ")?;
+        for (i, spec) in import.specifiers.iter().enumerate() {
+            if i > 0 {
+                self.s(", ")?;
+            }
+            match spec {
+                ImportSpecifier::Default(ident) => self.s(ident.name.as_ref())?,
+                ImportSpecifier::Namespace(ident) => {
+                    self.s("* as ")?;
+                    self.s(ident.name.as_ref())?;
+                }
+                ImportSpecifier::Normal(specs) => {
+                    self.s("{")?;
+                    for (i, spec) in specs.iter().enumerate() {
+                        if i > 0 {
+                            self.s(", ")?;
+                        }
+                        self.s(spec.imported.name.as_ref())?;
+                        if let Some(alias) = &spec.alias {
+                            self.s(" as ")?;
+                            self.s(alias.name.as_ref())?;
+                        }
+                    }
+                    self.s("}")?;
+                }
+            }
+        }
+        if !import.specifiers.is_empty() {
+            self.s(" from ")?;
+        }
+        self.lit(&import.source)?;
+        if !import.attributes.is_empty() {
+            match import.attributes_keyword {
+                ImportAttributesKeyword::With => self.s(" with {")?,
+                ImportAttributesKeyword::Assert => self.s(" assert {")?,
+            }
+            for (i, attr) in import.attributes.iter().enumerate() {
+                if i > 0 {
+                    self.s(", ")?;
+                }
+                match &attr.key {
+                    ImportAttributeKey::Ident(ident) => self.s(ident.name.as_ref())?,
+                    ImportAttributeKey::String(s) => self.string_lit(s)?,
+                }
+                self.s(": ")?;
+                self.string_lit(&attr.value)?;
+            }
+            self.s("}")?;
+        }
+        self.s(";")
+    }
+
+    fn mod_export<T: AsRef<str>>(&mut self, export: &ModExport<T>) -> Res {
+        match export {
+            ModExport::Default(DefaultExportDecl::Decl(decl)) => {
+                self.s("export default ")?;
+                self.decl(decl)
+            }
+            ModExport::Default(DefaultExportDecl::Expr(expr)) => {
+                self.s("export default ")?;
+                if starts_ambiguous(expr) {
+                    self.expr(expr, ALWAYS_WRAP)?;
+                } else {
+                    self.expr(expr, ASSIGN)?;
+                }
+                self.s(";")
+            }
+            ModExport::Named(NamedExportDecl::Decl(decl)) => {
+                self.s("export ")?;
+                self.decl(decl)
+            }
+            ModExport::Named(NamedExportDecl::Specifier(specs, source)) => {
+                self.s("export {")?;
+                for (i, spec) in specs.iter().enumerate() {
+                    if i > 0 {
+                        self.s(", ")?;
+                    }
+                    self.export_spec(spec)?;
+                }
+                self.s("}")?;
+                if let Some(source) = source {
+                    self.s(" from ")?;
+                    self.lit(source)?;
+                }
+                self.s(";")
+            }
+            ModExport::All { alias, name } => {
+                self.s("export *")?;
+                if let Some(alias) = alias {
+                    self.s(" as ")?;
+                    self.s(alias.name.as_ref())?;
+                }
+                self.s(" from ")?;
+                self.lit(name)?;
+                self.s(";")
+            }
+        }
+    }
+
+    fn export_spec<T: AsRef<str>>(&mut self, spec: &ExportSpecifier<T>) -> Res {
+        self.s(spec.local.name.as_ref())?;
+        if let Some(alias) = &spec.alias {
+            self.s(" as ")?;
+            self.s(alias.name.as_ref())?;
+        }
+        Ok(())
+    }
+
+    fn func<T: AsRef<str>>(&mut self, func: &Func<T>) -> Res {
+        if func.is_async {
+            self.s("async ")?;
+        }
+        self.s("function")?;
+        if func.generator {
+            self.s("*")?;
+        }
+        if let Some(id) = &func.id {
+            self.s(" ")?;
+            self.s(id.name.as_ref())?;
+        }
+        self.func_tail(&func.params, &func.body)
+    }
+
+    /// The parameter list and body shared by functions and methods
+    fn func_tail<T: AsRef<str>>(&mut self, params: &[FuncArg<T>], body: &FuncBody<T>) -> Res {
+        self.func_args(params)?;
+        self.s(" ")?;
+        self.block(&body.0)
+    }
+
+    fn func_args<T: AsRef<str>>(&mut self, params: &[FuncArg<T>]) -> Res {
+        self.s("(")?;
+        for (i, param) in params.iter().enumerate() {
+            if i > 0 {
+                self.s(", ")?;
+            }
+            match param {
+                FuncArg::Expr(expr) => self.expr(expr, ASSIGN)?,
+                FuncArg::Pat(pat) => self.pat(pat)?,
+            }
+        }
+        self.s(")")
+    }
+
+    fn decorators<T: AsRef<str>>(&mut self, decorators: &[Decorator<T>]) -> Res {
+        for decorator in decorators {
+            self.s("@")?;
+            if is_bare_decorator(&decorator.expr) {
+                self.expr(&decorator.expr, CALL)?;
+            } else {
+                self.s("(")?;
+                self.expr(&decorator.expr, SEQUENCE)?;
+                self.s(")")?;
+            }
+            self.s(" ")?;
+        }
+        Ok(())
+    }
+
+    fn class<T: AsRef<str>>(&mut self, class: &Class<T>) -> Res {
+        self.decorators(&class.decorators)?;
+        self.s("class")?;
+        if let Some(id) = &class.id {
+            self.s(" ")?;
+            self.s(id.name.as_ref())?;
+        }
+        if let Some(super_class) = &class.super_class {
+            self.s(" extends ")?;
+            self.expr(super_class, CALL)?;
+        }
+        if class.body.0.is_empty() {
+            return self.s(" {}");
+        }
+        self.s(" { ")?;
+        for (i, member) in class.body.0.iter().enumerate() {
+            if i > 0 {
+                self.s(" ")?;
+            }
+            self.decorators(&member.decorators)?;
+            if member.is_static && member.kind != PropKind::StaticBlock {
+                self.s("static ")?;
+            }
+            self.prop(member)?;
+            if member.kind == PropKind::Field {
+                self.s(";")?;
+            }
+        }
+        self.s(" }")
+    }
+
+    fn stmt<T: AsRef<str>>(&mut self, stmt: &Stmt<T>) -> Res {
+        match stmt {
+            Stmt::Expr(expr) => {
+                // a lone string would be read back as a directive
+                if starts_ambiguous(expr) || matches!(expr, Expr::Lit(Lit::String(_))) {
+                    self.expr(expr, ALWAYS_WRAP)?;
+                } else {
+                    self.expr(expr, SEQUENCE)?;
+                }
+                self.s(";")
+            }
+            Stmt::Block(inner) => self.block(&inner.0),
+            Stmt::Empty => self.s(";"),
+            Stmt::Debugger => self.s("debugger;"),
+            Stmt::With(inner) => {
+                self.s("with (")?;
+                self.expr(&inner.object, SEQUENCE)?;
+                self.s(") ")?;
+                self.stmt(&inner.body)
+            }
+            Stmt::Return(arg) => {
+                self.s("return")?;
+                if let Some(arg) = arg {
+                    self.s(" ")?;
+                    self.expr(arg, SEQUENCE)?;
+                }
+                self.s(";")
+            }
+            Stmt::Labeled(inner) => {
+                self.s(inner.label.name.as_ref())?;
+                self.s(": ")?;
+                self.stmt(&inner.body)
+            }
+            Stmt::Break(label) => {
+                self.s("break")?;
+                if let Some(label) = label {
+                    self.s(" ")?;
+                    self.s(label.name.as_ref())?;
+                }
+                self.s(";")
+            }
+            Stmt::Continue(label) => {
+                self.s("continue")?;
+                if let Some(label) = label {
+                    self.s(" ")?;
+                    self.s(label.name.as_ref())?;
+                }
+                self.s(";")
+            }
+            Stmt::If(inner) => {
+                self.s("if (")?;
+                self.expr(&inner.test, SEQUENCE)?;
+                self.s(") ")?;
+                if inner.alternate.is_some() && ends_with_open_if(&inner.consequent) {
+                    self.s("{ ")?;
+                    self.stmt(&inner.consequent)?;
+                    self.s(" }")?;
+                } else {
+                    self.stmt(&inner.consequent)?;
+                }
+                if let Some(alt) = &inner.alternate {
+                    self.s(" else ")?;
+                    self.stmt(alt)?;
+                }
+                Ok(())
+            }
+            Stmt::Switch(inner) => {
+                self.s("switch (")?;
+                self.expr(&inner.discriminant, SEQUENCE)?;
+                self.s(") {")?;
+                for case in &inner.cases {
+                    if let Some(test) = &case.test {
+                        self.s(" case ")?;
+                        self.expr(test, SEQUENCE)?;
+                        self.s(":")?;
+                    } else {
+                        self.s(" default:")?;
+                    }
+                    for part in &case.consequent {
+                        self.s(" ")?;
+                        self.program_part(part)?;
+                    }
+                }
+                self.s(" }")
+            }
+            Stmt::Throw(arg) => {
+                self.s("throw ")?;
+                self.expr(arg, SEQUENCE)?;
+                self.s(";")
+            }
+            Stmt::Try(inner) => {
+                self.s("try ")?;
+                self.block(&inner.block.0)?;
+                if let Some(handler) = &inner.handler {
+                    self.s(" catch ")?;
+                    if let Some(param) = &handler.param {
+                        self.s("(")?;
+                        self.pat(param)?;
+                        self.s(") ")?;
+                    }
+                    self.block(&handler.body.0)?;
+                }
+                if let Some(finalizer) = &inner.finalizer {
+                    self.s(" finally ")?;
+                    self.block(&finalizer.0)?;
+                }
+                Ok(())
+            }
+            Stmt::While(inner) => {
+                self.s("while (")?;
+                self.expr(&inner.test, SEQUENCE)?;
+                self.s(") ")?;
+                self.stmt(&inner.body)
+            }
+            Stmt::DoWhile(inner) => {
+                self.s("do ")?;
+                self.stmt(&inner.body)?;
+                self.s(" while (")?;
+                self.expr(&inner.test, SEQUENCE)?;
+                self.s(");")
+            }
+            Stmt::For(inner) => {
+                self.s("for (")?;
+                match &inner.init {
+                    Some(LoopInit::Variable(kind, decls)) => self.var_decls(*kind, decls, true)?,
+                    // `for (a in b;;)` would read as a `for...in` head
+                    // and `for (let[x] = 1;;)` as a declaration
+                    Some(LoopInit::Expr(expr))
+                        if expr.needs_for_header_parens() || starts_with_let_bracket(expr) =>
+                    {
+                        self.expr(expr, ALWAYS_WRAP)?
+                    }
+                    Some(LoopInit::Expr(expr)) => self.expr(expr, SEQUENCE)?,
+                    None => {}
+                }
+                self.s(";")?;
+                if let Some(test) = &inner.test {
+                    self.s(" ")?;
+                    self.expr(test, SEQUENCE)?;
+                }
+                self.s(";")?;
+                if let Some(update) = &inner.update {
+                    self.s(" ")?;
+                    self.expr(update, SEQUENCE)?;
+                }
+                self.s(") ")?;
+                self.stmt(&inner.body)
+            }
+            Stmt::ForIn(inner) => {
+                self.s("for (")?;
+                self.loop_left(&inner.left, false)?;
+                self.s(" in ")?;
+                self.expr(&inner.right, SEQUENCE)?;
+                self.s(") ")?;
+                self.stmt(&inner.body)
+            }
+            Stmt::ForOf(inner) => {
+                self.s("for ")?;
+                if inner.is_await {
+                    self.s("await ")?;
+                }
+                self.s("(")?;
+                self.loop_left(&inner.left, true)?;
+                self.s(" of ")?;
+                self.expr(&inner.right, ASSIGN)?;
+                self.s(") ")?;
+                self.stmt(&inner.body)
+            }
+            Stmt::Var(decls) => {
+                self.var_decls(VarKind::Var, decls, false)?;
+                self.s(";")
+            }
+        }
+    }
+
+    /// `of` for the left side of a `for...of`, which has its own
+    /// lookahead restrictions, see [`for_of_left_needs_parens`]
+    fn loop_left<T: AsRef<str>>(&mut self, left: &LoopLeft<T>, of: bool) -> Res {
+        match left {
+            LoopLeft::Expr(expr) if of && for_of_left_needs_parens(expr) => {
+                self.expr(expr, ALWAYS_WRAP)
+            }
+            LoopLeft::Expr(expr) if starts_ambiguous(expr) => self.expr(expr, ALWAYS_WRAP),
+            LoopLeft::Expr(expr) => self.expr(expr, CALL),
+            LoopLeft::Variable(kind, decl) => {
+                self.var_decls(*kind, std::slice::from_ref(decl), false)
+            }
+            LoopLeft::Pat(pat) => self.pat(pat),
+        }
+    }
+
+    fn expr<T: AsRef<str>>(&mut self, expr: &Expr<T>, min: u8) -> Res {
+        if precedence(expr) < min {
+            self.s("(")?;
+            self.expr_inner(expr)?;
+            self.s(")")
+        } else {
+            self.expr_inner(expr)
+        }
+    }
+
+    /// Write an expression that is followed by a member access, call
+    /// or template, a wrapped optional chain has to stay wrapped
+    fn callee<T: AsRef<str>>(&mut self, expr: &Expr<T>) -> Res {
+        if let Expr::OptionalChain(_) = expr {
+            return self.expr(expr, ALWAYS_WRAP);
+        }
+        self.expr(expr, CALL)
+    }
+
+    fn exprs<T: AsRef<str>>(&mut self, exprs: &[Expr<T>]) -> Res {
+        for (i, expr) in exprs.iter().enumerate() {
+            if i > 0 {
+                self.s(", ")?;
+            }
+            self.expr(expr, ASSIGN)?;
+        }
+        Ok(())
+    }
+
+    fn expr_inner<T: AsRef<str>>(&mut self, expr: &Expr<T>) -> Res {
+        match expr {
+            Expr::Array(elements) => {
+                self.s("[")?;
+                for (i, element) in elements.iter().enumerate() {
+                    if i > 0 {
+                        self.s(", ")?;
+                    }
+                    if let Some(element) = element {
+                        self.expr(element, ASSIGN)?;
+                    }
+                }
+                if let Some(None) = elements.last() {
+                    self.s(",")?;
+                }
+                self.s("]")
+            }
+            Expr::ArrowFunc(inner) => self.arrow(inner),
+            Expr::ArrowParamPlaceHolder(args, is_async) => {
+                if *is_async {
+                    self.s("async ")?;
+                }
+                self.func_args(args)
+            }
+            Expr::Assign(inner) => {
+                match &inner.left {
+                    crate::expr::AssignLeft::Pat(pat) => self.pat(pat)?,
+                    crate::expr::AssignLeft::Expr(expr) => self.expr(expr, CALL)?,
+                }
+                self.s(" ")?;
+                self.s(assign_op(inner.operator))?;
+                self.s(" ")?;
+                self.expr(&inner.right, ASSIGN)
+            }
+            Expr::Await(inner) => {
+                self.s("await ")?;
+                self.expr(inner, UNARY)
+            }
+            Expr::Binary(inner) => {
+                let prec = inner.operator.precedence();
+                let (left, right) = match inner.operator.associativity() {
+                    // `-a ** b` is a syntax error
+                    Assoc::Right => (POSTFIX, prec),
+                    Assoc::Left => (prec, prec + 1),
+                };
+                self.expr(&inner.left, left)?;
+                self.s(" ")?;
+                self.s(binary_op(inner.operator))?;
+                self.s(" ")?;
+                self.expr(&inner.right, right)
+            }
+            Expr::Class(inner) => self.class(inner),
+            Expr::Call(inner) => {
+                self.callee(&inner.callee)?;
+                if inner.optional {
+                    self.s("?.")?;
+                }
+                self.s("(")?;
+                self.exprs(&inner.arguments)?;
+                self.s(")")
+            }
+            Expr::Conditional(inner) => {
+                self.expr(&inner.test, COALESCE)?;
+                self.s(" ? ")?;
+                self.expr(&inner.alternate, ASSIGN)?;
+                self.s(" : ")?;
+                self.expr(&inner.consequent, ASSIGN)
+            }
+            Expr::Func(inner) => self.func(inner),
+            Expr::Ident(inner) => self.s(inner.name.as_ref()),
+            Expr::PrivateIdent(inner) => {
+                self.s("#")?;
+                self.s(inner.name.as_ref())
+            }
+            Expr::Import(inner) => {
+                self.s("import(")?;
+                self.expr(&inner.source, ASSIGN)?;
+                if let Some(options) = &inner.options {
+                    self.s(", ")?;
+                    self.expr(options, ASSIGN)?;
+                }
+                self.s(")")
+            }
+            Expr::Lit(inner) => self.lit(inner),
+            Expr::Logical(inner) => {
+                let prec = inner.operator.precedence();
+                self.logical_operand(&inner.left, inner.operator, prec)?;
+                self.s(" ")?;
+                self.s(match inner.operator {
+                    LogicalOp::Or => "||",
+                    LogicalOp::And => "&&",
+                    LogicalOp::NullishCoalescing => "??",
+                })?;
+                self.s(" ")?;
+                self.logical_operand(&inner.right, inner.operator, prec + 1)
+            }
+            Expr::Member(inner) => self.member(inner),
+            Expr::MetaProp(inner) => {
+                self.s(inner.meta.name.as_ref())?;
+                self.s(".")?;
+                self.s(inner.property.name.as_ref())
+            }
+            Expr::New(inner) => {
+                self.s("new ")?;
+                if new_callee_needs_parens(&inner.callee) {
+                    self.expr(&inner.callee, ALWAYS_WRAP)?;
+                } else {
+                    self.expr(&inner.callee, CALL)?;
+                }
+                self.s("(")?;
+                self.exprs(&inner.arguments)?;
+                self.s(")")
+            }
+            Expr::Obj(props) => {
+                if props.is_empty() {
+                    return self.s("{}");
+                }
+                self.s("{ ")?;
+                for (i, prop) in props.iter().enumerate() {
+                    if i > 0 {
+                        self.s(", ")?;
+                    }
+                    match prop {
+                        ObjProp::Prop(prop) => self.prop(prop)?,
+                        ObjProp::Spread(expr) => {
+                            self.s("...")?;
+                            self.expr(expr, ASSIGN)?;
+                        }
+                    }
+                }
+                self.s(" }")
+            }
+            Expr::Sequence(exprs) => {
+                for (i, expr) in exprs.iter().enumerate() {
+                    if i > 0 {
+                        self.s(", ")?;
+                    }
+                    self.expr(expr, ASSIGN)?;
+                }
+                Ok(())
+            }
+            Expr::Spread(inner) => {
+                self.s("...")?;
+                self.expr(inner, ASSIGN)
+            }
+            Expr::Super => self.s("super"),
+            Expr::TaggedTemplate(inner) => {
+                self.callee(&inner.tag)?;
+                self.template(&inner.quasi)
+            }
+            Expr::This => self.s("this"),
+            Expr::Unary(inner) => {
+                self.s(match inner.operator {
+                    UnaryOp::Minus => "-",
+                    UnaryOp::Plus => "+",
+                    UnaryOp::Not => "!",
+                    UnaryOp::Tilde => "~",
+                    UnaryOp::TypeOf => "typeof ",
+                    UnaryOp::Void => "void ",
+                    UnaryOp::Delete => "delete ",
+                })?;
+                // `- -a` and `- --a` can't be written without a space
+                let sign = match inner.operator {
+                    UnaryOp::Minus => Some((UnaryOp::Minus, UpdateOp::Decrement)),
+                    UnaryOp::Plus => Some((UnaryOp::Plus, UpdateOp::Increment)),
+                    _ => None,
+                };
+                if let Some((unary, update)) = sign {
+                    let same_sign = match &*inner.argument {
+                        Expr::Unary(arg) => arg.operator == unary,
+                        Expr::Update(arg) => arg.prefix && arg.operator == update,
+                        _ => false,
+                    };
+                    if same_sign {
+                        self.s(" ")?;
+                    }
+                }
+                self.expr(&inner.argument, UNARY)
+            }
+            Expr::Update(inner) => {
+                let op = match inner.operator {
+                    UpdateOp::Increment => "++",
+                    UpdateOp::Decrement => "--",
+                };
+                if inner.prefix {
+                    self.s(op)?;
+                    self.expr(&inner.argument, CALL)
+                } else {
+                    self.expr(&inner.argument, CALL)?;
+                    self.s(op)
+                }
+            }
+            Expr::Yield(inner) => {
+                self.s("yield")?;
+                if inner.delegate {
+                    self.s("*")?;
+                }
+                if let Some(arg) = &inner.argument {
+                    self.s(" ")?;
+                    self.expr(arg, ASSIGN)?;
+                }
+                Ok(())
+            }
+            Expr::OptionalChain(inner) => self.expr(inner, CALL),
+            Expr::Paren(inner) => {
+                self.s("(")?;
+                self.expr(inner, SEQUENCE)?;
+                self.s(")")
+            }
+            #[cfg(feature = "jsx")]
+            Expr::Jsx(inner) => self.jsx_element(inner),
+            #[cfg(feature = "jsx")]
+            Expr::JsxFragment(inner) => self.jsx_fragment(inner),
+        }
+    }
+
+    /// `??` can't be mixed with `||` or `&&` without parentheses
+    fn logical_operand<T: AsRef<str>>(
+        &mut self,
+        expr: &Expr<T>,
+        parent: LogicalOp,
+        min: u8,
+    ) -> Res {
+        let mixed = match expr {
+            Expr::Logical(inner) => !inner.operator.can_mix_with(parent),
+            _ => false,
+        };
+        self.expr(expr, if mixed { ALWAYS_WRAP } else { min })
+    }
+
+    fn arrow<T: AsRef<str>>(&mut self, arrow: &ArrowFuncExpr<T>) -> Res {
+        if arrow.is_async {
+            self.s("async ")?;
+        }
+        self.func_args(&arrow.params)?;
+        self.s(" => ")?;
+        match &arrow.body {
+            ArrowFuncBody::FuncBody(body) => self.block(&body.0),
+            ArrowFuncBody::Expr(expr) if starts_ambiguous(expr) => self.expr(expr, ALWAYS_WRAP),
+            ArrowFuncBody::Expr(expr) => self.expr(expr, ASSIGN),
+        }
+    }
+
+    fn member<T: AsRef<str>>(&mut self, member: &MemberExpr<T>) -> Res {
+        match (&*member.object, member.indexer) {
+            // `1.a` would be read as a number
+            (Expr::Lit(Lit::Number(_)), MemberIndexer::Period) => {
+                self.expr(&member.object, ALWAYS_WRAP)?
+            }
+            (object, _) => self.callee(object)?,
+        }
+        match member.indexer {
+            MemberIndexer::Period => {
+                self.s(".")?;
+                self.expr(&member.property, PRIMARY)
+            }
+            MemberIndexer::Optional => {
+                self.s("?.")?;
+                self.expr(&member.property, PRIMARY)
+            }
+            MemberIndexer::Computed => {
+                self.s("[")?;
+                self.expr(&member.property, SEQUENCE)?;
+                self.s("]")
+            }
+            MemberIndexer::OptionalComputed => {
+                self.s("?.[")?;
+                self.expr(&member.property, SEQUENCE)?;
+                self.s("]")
+            }
+        }
+    }
+
+    /// A property of an object literal, object pattern or class body
+    fn prop<T: AsRef<str>>(&mut self, prop: &Prop<T>) -> Res {
+        match prop.kind {
+            PropKind::Init if prop.short_hand => match &prop.value {
+                PropValue::Pat(pat @ Pat::Assign(_)) => self.pat(pat),
+                _ => self.prop_key(prop),
+            },
+            PropKind::Init if prop.method => self.method(prop),
+            PropKind::Init => {
+                self.prop_key(prop)?;
+                self.s(": ")?;
+                self.prop_value(&prop.value)
+            }
+            PropKind::Get => {
+                self.s("get ")?;
+                self.method(prop)
+            }
+            PropKind::Set => {
+                self.s("set ")?;
+                self.method(prop)
+            }
+            PropKind::Ctor | PropKind::Method => self.method(prop),
+            PropKind::Field => {
+                self.prop_key(prop)?;
+                if let PropValue::None = prop.value {
+                    return Ok(());
+                }
+                self.s(" = ")?;
+                self.prop_value(&prop.value)
+            }
+            PropKind::StaticBlock => {
+                self.s("static ")?;
+                match &prop.value {
+                    PropValue::Block(body) => self.block(&body.0),
+                    _ => self.s("{}"),
+                }
+            }
+        }
+    }
+
+    fn method<T: AsRef<str>>(&mut self, prop: &Prop<T>) -> Res {
+        let func = match &prop.value {
+            PropValue::Expr(Expr::Func(func)) => Some(func),
+            _ => None,
+        };
+        if let Some(func) = func {
+            if func.is_async {
+                self.s("async ")?;
+            }
+            if func.generator {
+                self.s("*")?;
+            }
+        }
+        self.prop_key(prop)?;
+        match func {
+            Some(func) => self.func_tail(&func.params, &func.body),
+            None => self.s("() {}"),
+        }
+    }
+
+    fn prop_key<T: AsRef<str>>(&mut self, prop: &Prop<T>) -> Res {
+        if prop.computed {
+            self.s("[")?;
+        }
+        match &prop.key {
+            PropKey::Lit(lit) => self.lit(lit)?,
+            PropKey::Expr(expr) => self.expr(expr, ASSIGN)?,
+            PropKey::Pat(pat) => self.pat(pat)?,
+            PropKey::None => {}
+        }
+        if prop.computed {
+            self.s("]")?;
+        }
+        Ok(())
+    }
+
+    fn prop_value<T: AsRef<str>>(&mut self, value: &PropValue<T>) -> Res {
+        match value {
+            PropValue::Expr(expr) => self.expr(expr, ASSIGN),
+            PropValue::Pat(pat) => self.pat(pat),
+            PropValue::Block(body) => self.block(&body.0),
+            PropValue::None => Ok(()),
+        }
+    }
+
+    fn lit<T: AsRef<str>>(&mut self, lit: &Lit<T>) -> Res {
+        match lit {
+            Lit::Null => self.s("null"),
+            Lit::String(inner) => self.string_lit(inner),
+            Lit::Number(inner) | Lit::BigInt(inner) => self.s(inner.as_ref()),
+            Lit::Boolean(true) => self.s("true"),
+            Lit::Boolean(false) => self.s("false"),
+            Lit::RegEx(inner) => {
+                self.s("/")?;
+                self.s(inner.pattern.as_ref())?;
+                self.s("/")?;
+                if let Some(flags) = &inner.flags {
+                    self.s(flags.as_ref())?;
+                }
+                Ok(())
+            }
+            Lit::Template(inner) => self.template(inner),
+        }
+    }
+
+    fn string_lit<T: AsRef<str>>(&mut self, lit: &StringLit<T>) -> Res {
+        let (quote, content) = match lit {
+            StringLit::Double(content) => ("\"", content),
+            StringLit::Single(content) => ("'", content),
+        };
+        self.s(quote)?;
+        self.s(content.as_ref())?;
+        self.s(quote)
+    }
+
+    #[cfg(feature = "jsx")]
+    fn jsx_element<T: AsRef<str>>(&mut self, element: &JsxElement<T>) -> Res {
+        self.s("<")?;
+        self.jsx_name(&element.opening.name)?;
+        for attr in &element.opening.attributes {
+            self.s(" ")?;
+            match attr {
+                JsxAttr::Attribute(attr) => {
+                    self.s(attr.name.name.as_ref())?;
+                    if let Some(value) = &attr.value {
+                        self.s("=")?;
+                        match value {
+                            JsxAttributeValue::String(lit) => self.string_lit(lit)?,
+                            JsxAttributeValue::Expr(container) => {
+                                self.jsx_expression_container(container)?
+                            }
+                            JsxAttributeValue::Element(element) => self.jsx_element(element)?,
+                            JsxAttributeValue::Fragment(fragment) => self.jsx_fragment(fragment)?,
+                        }
+                    }
+                }
+                JsxAttr::Spread(expr) => {
+                    self.s("{...")?;
+                    self.expr(expr, ASSIGN)?;
+                    self.s("}")?;
+                }
+            }
+        }
+        if element.opening.self_closing {
+            return self.s(" />");
+        }
+        self.s(">")?;
+        self.jsx_children(&element.children)?;
+        self.s("</")?;
+        if let Some(name) = &element.closing {
+            self.jsx_name(name)?;
+        }
+        self.s(">")
+    }
+
+    #[cfg(feature = "jsx")]
+    fn jsx_fragment<T: AsRef<str>>(&mut self, fragment: &JsxFragment<T>) -> Res {
+        self.s("<>")?;
+        self.jsx_children(&fragment.children)?;
+        self.s("</>")
+    }
+
+    #[cfg(feature = "jsx")]
+    fn jsx_name<T: AsRef<str>>(&mut self, name: &JsxElementName<T>) -> Res {
+        match name {
+            JsxElementName::Ident(ident) => self.s(ident.name.as_ref()),
+            JsxElementName::Member(object, property) => {
+                self.jsx_name(object)?;
+                self.s(".")?;
+                self.s(property.name.as_ref())
+            }
+            JsxElementName::Namespaced(namespace, name) => {
+                self.s(namespace.name.as_ref())?;
+                self.s(":")?;
+                self.s(name.name.as_ref())
+            }
+        }
+    }
+
+    #[cfg(feature = "jsx")]
+    fn jsx_children<T: AsRef<str>>(&mut self, children: &[JsxChild<T>]) -> Res {
+        for child in children {
+            match child {
+                JsxChild::Text(text) => self.s(text.value.as_ref())?,
+                JsxChild::Expr(container) => self.jsx_expression_container(container)?,
+                JsxChild::Spread(expr) => {
+                    self.s("{...")?;
+                    self.expr(expr, ASSIGN)?;
+                    self.s("}")?;
+                }
+                JsxChild::Element(element) => self.jsx_element(element)?,
+                JsxChild::Fragment(fragment) => self.jsx_fragment(fragment)?,
+            }
+        }
+        Ok(())
+    }
+
+    #[cfg(feature = "jsx")]
+    fn jsx_expression_container<T: AsRef<str>>(
+        &mut self,
+        container: &JsxExpressionContainer<T>,
+    ) -> Res {
+        self.s("{")?;
+        if let Some(expr) = &container.expr {
+            self.expr(expr, SEQUENCE)?;
+        }
+        self.s("}")
+    }
+
+    fn template<T: AsRef<str>>(&mut self, template: &TemplateLit<T>) -> Res {
+        for (i, quasi) in template.quasis.iter().enumerate() {
+            self.s(quasi_quote(&quasi.open_quote))?;
+            self.s(quasi.content.as_ref())?;
+            self.s(quasi_quote(&quasi.close_quote))?;
+            if let Some(expr) = template.expressions.get(i) {
+                self.expr(expr, SEQUENCE)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn pat<T: AsRef<str>>(&mut self, pat: &Pat<T>) -> Res {
+        match pat {
+            Pat::Ident(ident) => self.s(ident.name.as_ref()),
+            Pat::Obj(parts) => {
+                if parts.is_empty() {
+                    return self.s("{}");
+                }
+                self.s("{ ")?;
+                for (i, part) in parts.iter().enumerate() {
+                    if i > 0 {
+                        self.s(", ")?;
+                    }
+                    match part {
+                        ObjPatPart::Assign(prop) => self.prop(prop)?,
+                        ObjPatPart::Rest(rest) => {
+                            self.s("...")?;
+                            self.pat(rest)?;
+                        }
+                    }
+                }
+                self.s(" }")
+            }
+            Pat::Array(parts) => {
+                self.s("[")?;
+                for (i, part) in parts.iter().enumerate() {
+                    if i > 0 {
+                        self.s(", ")?;
+                    }
+                    match part {
+                        Some(ArrayPatPart::Pat(pat)) => self.pat(pat)?,
+                        Some(ArrayPatPart::Expr(expr)) => self.expr(expr, ASSIGN)?,
+                        None => {}
+                    }
+                }
+                if let Some(None) = parts.last() {
+                    self.s(",")?;
+                }
+                self.s("]")
+            }
+            Pat::RestElement(inner) => {
+                self.s("...")?;
+                self.pat(inner)
+            }
+            Pat::Assign(inner) => {
+                self.pat(&inner.left)?;
+                self.s(" = ")?;
+                self.expr(&inner.right, ASSIGN)
+            }
+        }
+    }
+}
+
+fn quasi_quote(quote: &QuasiQuote) -> &'static str {
+    match quote {
+        QuasiQuote::BackTick => "`",
+        QuasiQuote::OpenBrace => "${",
+        QuasiQuote::CloseBrace => "}",
+    }
+}
+
+fn assign_op(op: AssignOp) -> &'static str {
+    match op {
+        AssignOp::Equal => "=",
+        AssignOp::PlusEqual => "+=",
+        AssignOp::MinusEqual => "-=",
+        AssignOp::TimesEqual => "*=",
+        AssignOp::DivEqual => "/=",
+        AssignOp::ModEqual => "%=",
+        AssignOp::LeftShiftEqual => "<<=",
+        AssignOp::RightShiftEqual => ">>=",
+        AssignOp::UnsignedRightShiftEqual => ">>>=",
+        AssignOp::OrEqual => "|=",
+        AssignOp::XOrEqual => "^=",
+        AssignOp::AndEqual => "&=",
+        AssignOp::PowerOfEqual => "**=",
+        AssignOp::DoubleAmpersandEqual => "&&=",
+        AssignOp::DoublePipeEqual => "||=",
+        AssignOp::DoubleQuestionmarkEqual => "??=",
+    }
+}
+
+fn binary_op(op: BinaryOp) -> &'static str {
+    match op {
+        BinaryOp::Equal => "==",
+        BinaryOp::NotEqual => "!=",
+        BinaryOp::StrictEqual => "===",
+        BinaryOp::StrictNotEqual => "!==",
+        BinaryOp::LessThan => "<",
+        BinaryOp::GreaterThan => ">",
+        BinaryOp::LessThanEqual => "<=",
+        BinaryOp::GreaterThanEqual => ">=",
+        BinaryOp::LeftShift => "<<",
+        BinaryOp::RightShift => ">>",
+        BinaryOp::UnsignedRightShift => ">>>",
+        BinaryOp::Plus => "+",
+        BinaryOp::Minus => "-",
+        BinaryOp::Times => "*",
+        BinaryOp::Over => "/",
+        BinaryOp::Mod => "%",
+        BinaryOp::Or => "|",
+        BinaryOp::XOr => "^",
+        BinaryOp::And => "&",
+        BinaryOp::In => "in",
+        BinaryOp::InstanceOf => "instanceof",
+        BinaryOp::PowerOf => "**",
+    }
+}
@@ -1,13 +1,13 @@
-use crate::expr::{Expr, Lit};
+use crate::expr::{Expr, Lit, StringLit};
 use crate::pat::Pat;
+use crate::{AsBorrowed, IntoAllocated, MapSource, VarKind};
 use crate::{Class, Func, Ident};
-use crate::{IntoAllocated, VarKind};
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 /// The declaration of a variable, function, class, import or export
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub enum Decl<T> {
     /// A variable declaration
@@ -47,20 +47,49 @@ where
     type Allocated = Decl<String>;
 
     fn into_allocated(self) -> Self::Allocated {
+        self.map_source_with(&mut |source: T| source.to_string())
+    }
+}
+
+impl<'a> AsBorrowed<'a> for Decl<String> {
+    type Borrowed = Decl<&'a str>;
+
+    fn as_borrowed(&'a self) -> Self::Borrowed {
+        match self {
+            Decl::Var(k, decls) => Decl::Var(*k, decls.iter().map(|d| d.as_borrowed()).collect()),
+            Decl::Func(inner) => Decl::Func(inner.as_borrowed()),
+            Decl::Class(inner) => Decl::Class(inner.as_borrowed()),
+            Decl::Import(inner) => Decl::Import(inner.as_borrowed()),
+            Decl::Export(inner) => Decl::Export(inner.as_borrowed()),
+        }
+    }
+}
+
+impl<T> Decl<T> {
+    /// Pass every source value through `f`, see [`Program::map_source`](crate::Program::map_source)
+    pub fn map_source<U>(self, mut f: impl FnMut(T) -> U) -> Decl<U> {
+        self.map_source_with(&mut f)
+    }
+}
+
+impl<T, U> MapSource<T, U> for Decl<T> {
+    type Output = Decl<U>;
+
+    fn map_source_with(self, f: &mut impl FnMut(T) -> U) -> Self::Output {
         match self {
             Decl::Var(k, decls) => {
-                Decl::Var(k, decls.into_iter().map(|d| d.into_allocated()).collect())
+                Decl::Var(k, decls.into_iter().map(|d| d.map_source_with(f)).collect())
             }
-            Decl::Func(inner) => Decl::Func(inner.into_allocated()),
-            Decl::Class(inner) => Decl::Class(inner.into_allocated()),
-            Decl::Import(inner) => Decl::Import(inner.into_allocated()),
-            Decl::Export(inner) => Decl::Export(inner.into_allocated()),
+            Decl::Func(inner) => Decl::Func(inner.map_source_with(f)),
+            Decl::Class(inner) => Decl::Class(inner.map_source_with(f)),
+            Decl::Import(inner) => Decl::Import(inner.map_source_with(f)),
+            Decl::Export(inner) => Decl::Export(inner.map_source_with(f)),
         }
     }
 }
 
 /// The identifier and optional value of a variable declaration
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct VarDecl<T> {
     pub id: Pat<T>,
@@ -74,9 +103,28 @@ where
     type Allocated = VarDecl<String>;
 
     fn into_allocated(self) -> Self::Allocated {
+        self.map_source_with(&mut |source: T| source.to_string())
+    }
+}
+
+impl<'a> AsBorrowed<'a> for VarDecl<String> {
+    type Borrowed = VarDecl<&'a str>;
+
+    fn as_borrowed(&'a self) -> Self::Borrowed {
+        VarDecl {
+            id: self.id.as_borrowed(),
+            init: self.init.as_borrowed(),
+        }
+    }
+}
+
+impl<T, U> MapSource<T, U> for VarDecl<T> {
+    type Output = VarDecl<U>;
+
+    fn map_source_with(self, f: &mut impl FnMut(T) -> U) -> Self::Output {
         VarDecl {
-            id: self.id.into_allocated(),
-            init: self.init.map(|i| i.into_allocated()),
+            id: self.id.map_source_with(f),
+            init: self.init.map_source_with(f),
         }
     }
 }
@@ -87,11 +135,17 @@ where
 /// ```js
 /// import {Thing} from './stuff.js';
 /// ```
-#[derive(PartialEq, Debug, Clone)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct ModImport<T> {
     pub specifiers: Vec<ImportSpecifier<T>>,
     pub source: Lit<T>,
+    /// Any attributes provided with an `assert` or `with` clause,
+    /// this will be empty if neither is present
+    pub attributes: Vec<ImportAttribute<T>>,
+    /// Which keyword introduced `attributes`, ignored when
+    /// there are none
+    pub attributes_keyword: ImportAttributesKeyword,
 }
 
 impl<T> IntoAllocated for ModImport<T>
@@ -101,19 +155,318 @@ where
     type Allocated = ModImport<String>;
 
     fn into_allocated(self) -> Self::Allocated {
+        self.map_source_with(&mut |source: T| source.to_string())
+    }
+}
+
+impl<'a> AsBorrowed<'a> for ModImport<String> {
+    type Borrowed = ModImport<&'a str>;
+
+    fn as_borrowed(&'a self) -> Self::Borrowed {
+        ModImport {
+            specifiers: self.specifiers.iter().map(|s| s.as_borrowed()).collect(),
+            source: self.source.as_borrowed(),
+            attributes: self
+                .attributes
+                .iter()
+                .map(AsBorrowed::as_borrowed)
+                .collect(),
+            attributes_keyword: self.attributes_keyword,
+        }
+    }
+}
+
+impl<T, U> MapSource<T, U> for ModImport<T> {
+    type Output = ModImport<U>;
+
+    fn map_source_with(self, f: &mut impl FnMut(T) -> U) -> Self::Output {
         ModImport {
             specifiers: self
                 .specifiers
                 .into_iter()
-                .map(|s| s.into_allocated())
+                .map(|s| s.map_source_with(f))
                 .collect(),
-            source: self.source.into_allocated(),
+            source: self.source.map_source_with(f),
+            attributes: self
+                .attributes
+                .into_iter()
+                .map(|e| e.map_source_with(f))
+                .collect(),
+            attributes_keyword: self.attributes_keyword,
+        }
+    }
+}
+
+impl<T> ModImport<T> {
+    pub fn builder() -> ImportBuilder<T> {
+        Default::default()
+    }
+}
+
+/// Chainable construction of a `ModImport`
+///
+/// The default specifier is always printed first and a second
+/// `default_import` replaces the first, the same goes for
+/// `namespace`. Every `named` call shares one set of curly braces.
+/// An import can't have both a namespace and named specifiers so
+/// [`from`](Self::from) fails if both were added.
+/// ```
+/// use resast::prelude::*;
+/// use resast::codegen::WriteJs;
+///
+/// let import = ModImport::builder()
+///     .named("a", Some("b"))
+///     .default_import("D")
+///     .named("c", None)
+///     .from("m")
+///     .unwrap();
+/// assert_eq!(import.specifiers.len(), 2);
+/// assert_eq!(
+///     Decl::Import(Box::new(import)).to_js_string(),
+///     "import D, {a as b, c} from 'm';"
+/// );
+///
+/// let err = ModImport::builder()
+///     .namespace("ns")
+///     .named("a", None)
+///     .from("m")
+///     .unwrap_err();
+/// assert_eq!(err, ImportBuilderError);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ImportBuilder<T> {
+    default: Option<Ident<T>>,
+    namespace: Option<Ident<T>>,
+    named: Vec<NormalImportSpec<T>>,
+    attributes: Vec<ImportAttribute<T>>,
+    attributes_keyword: ImportAttributesKeyword,
+}
+
+impl<T> Default for ImportBuilder<T> {
+    fn default() -> Self {
+        Self {
+            default: None,
+            namespace: None,
+            named: Vec::new(),
+            attributes: Vec::new(),
+            attributes_keyword: ImportAttributesKeyword::With,
+        }
+    }
+}
+
+impl<T> ImportBuilder<T> {
+    /// `import name from ...`
+    pub fn default_import(mut self, name: T) -> Self {
+        self.default = Some(Ident { name });
+        self
+    }
+    /// `import * as name from ...`
+    pub fn namespace(mut self, name: T) -> Self {
+        self.namespace = Some(Ident { name });
+        self
+    }
+    /// `import {imported as alias} from ...`
+    pub fn named(mut self, imported: T, alias: Option<T>) -> Self {
+        self.named.push(NormalImportSpec {
+            alias: alias.map(|name| Ident { name }),
+            imported: Ident { name: imported },
+        });
+        self
+    }
+    /// `... with { key: 'value' }`
+    pub fn attribute(mut self, key: T, value: T) -> Self {
+        self.attributes.push(ImportAttribute {
+            key: ImportAttributeKey::Ident(Ident { name: key }),
+            value: StringLit::Single(value),
+        });
+        self
+    }
+    /// Introduce the attributes with `keyword`, they use `with`
+    /// unless this is called
+    pub fn attributes_keyword(mut self, keyword: ImportAttributesKeyword) -> Self {
+        self.attributes_keyword = keyword;
+        self
+    }
+    /// Finish the import with a single quoted module `source`
+    pub fn from(self, source: T) -> Result<ModImport<T>, ImportBuilderError> {
+        if self.namespace.is_some() && !self.named.is_empty() {
+            return Err(ImportBuilderError);
+        }
+        let mut specifiers = Vec::new();
+        if let Some(default) = self.default {
+            specifiers.push(ImportSpecifier::Default(default));
+        }
+        if let Some(namespace) = self.namespace {
+            specifiers.push(ImportSpecifier::Namespace(namespace));
+        }
+        if !self.named.is_empty() {
+            specifiers.push(ImportSpecifier::Normal(self.named));
+        }
+        Ok(ModImport {
+            specifiers,
+            source: Lit::single_string_from(source),
+            attributes: self.attributes,
+            attributes_keyword: self.attributes_keyword,
+        })
+    }
+}
+
+/// An [`ImportBuilder`] was given both a namespace and named
+/// specifiers, `import * as ns, {a} from 'm'` isn't valid
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ImportBuilderError;
+
+impl std::fmt::Display for ImportBuilderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "an import can't have both a namespace and named specifiers"
+        )
+    }
+}
+
+impl std::error::Error for ImportBuilderError {}
+
+/// The keyword that starts an import's attributes clause
+/// ```
+/// use resast::prelude::*;
+/// use resast::codegen::WriteJs;
+///
+/// let import = ModImport::builder()
+///     .default_import("data")
+///     .attribute("type", "json")
+///     .attributes_keyword(ImportAttributesKeyword::Assert)
+///     .from("./data.json")
+///     .unwrap();
+/// assert_eq!(
+///     Decl::Import(Box::new(import)).to_js_string(),
+///     "import data from './data.json' assert {type: 'json'};"
+/// );
+/// ```
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum ImportAttributesKeyword {
+    /// `import data from './data.json' with { type: 'json' };`
+    #[default]
+    With,
+    /// The older `import data from './data.json' assert { type: 'json' };`
+    Assert,
+}
+
+/// A single entry in an import's attributes clause
+/// ```js
+/// import data from './data.json' with { type: 'json' };
+/// //                                    ^^^^^^^^^^^^
+/// ```
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct ImportAttribute<T> {
+    pub key: ImportAttributeKey<T>,
+    pub value: StringLit<T>,
+}
+
+impl<T> IntoAllocated for ImportAttribute<T>
+where
+    T: ToString,
+{
+    type Allocated = ImportAttribute<String>;
+
+    fn into_allocated(self) -> Self::Allocated {
+        self.map_source_with(&mut |source: T| source.to_string())
+    }
+}
+
+impl<'a> AsBorrowed<'a> for ImportAttribute<String> {
+    type Borrowed = ImportAttribute<&'a str>;
+
+    fn as_borrowed(&'a self) -> Self::Borrowed {
+        ImportAttribute {
+            key: self.key.as_borrowed(),
+            value: self.value.as_borrowed(),
+        }
+    }
+}
+
+impl<T, U> MapSource<T, U> for ImportAttribute<T> {
+    type Output = ImportAttribute<U>;
+
+    fn map_source_with(self, f: &mut impl FnMut(T) -> U) -> Self::Output {
+        ImportAttribute {
+            key: self.key.map_source_with(f),
+            value: self.value.map_source_with(f),
+        }
+    }
+}
+
+/// The key of an import attribute, either an identifier
+/// or a string literal, an identifier key is visited like
+/// any other identifier
+/// ```
+/// use resast::prelude::*;
+/// use resast::visit::Visit;
+///
+/// #[derive(Default)]
+/// struct Idents(Vec<&'static str>);
+///
+/// impl<'ast> Visit<'ast, &'static str> for Idents {
+///     fn visit_ident(&mut self, ident: &'ast Ident<&'static str>) {
+///         self.0.push(ident.name);
+///     }
+/// }
+///
+/// let import = ModImport::builder()
+///     .default_import("data")
+///     .attribute("type", "json")
+///     .from("./data.json")
+///     .unwrap();
+/// let mut idents = Idents::default();
+/// idents.visit_decl(&Decl::Import(Box::new(import)));
+/// assert_eq!(idents.0, ["data", "type"]);
+/// ```
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum ImportAttributeKey<T> {
+    Ident(Ident<T>),
+    String(StringLit<T>),
+}
+
+impl<T> IntoAllocated for ImportAttributeKey<T>
+where
+    T: ToString,
+{
+    type Allocated = ImportAttributeKey<String>;
+
+    fn into_allocated(self) -> Self::Allocated {
+        self.map_source_with(&mut |source: T| source.to_string())
+    }
+}
+
+impl<'a> AsBorrowed<'a> for ImportAttributeKey<String> {
+    type Borrowed = ImportAttributeKey<&'a str>;
+
+    fn as_borrowed(&'a self) -> Self::Borrowed {
+        match self {
+            ImportAttributeKey::Ident(inner) => ImportAttributeKey::Ident(inner.as_borrowed()),
+            ImportAttributeKey::String(inner) => ImportAttributeKey::String(inner.as_borrowed()),
+        }
+    }
+}
+
+impl<T, U> MapSource<T, U> for ImportAttributeKey<T> {
+    type Output = ImportAttributeKey<U>;
+
+    fn map_source_with(self, f: &mut impl FnMut(T) -> U) -> Self::Output {
+        match self {
+            ImportAttributeKey::Ident(inner) => ImportAttributeKey::Ident(inner.map_source_with(f)),
+            ImportAttributeKey::String(inner) => {
+                ImportAttributeKey::String(inner.map_source_with(f))
+            }
         }
     }
 }
 
 /// The name of the thing being imported
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub enum ImportSpecifier<T> {
     /// A specifier in curly braces, this might
@@ -147,17 +500,41 @@ where
     type Allocated = ImportSpecifier<String>;
 
     fn into_allocated(self) -> Self::Allocated {
+        self.map_source_with(&mut |source: T| source.to_string())
+    }
+}
+
+impl<'a> AsBorrowed<'a> for ImportSpecifier<String> {
+    type Borrowed = ImportSpecifier<&'a str>;
+
+    fn as_borrowed(&'a self) -> Self::Borrowed {
+        match self {
+            ImportSpecifier::Normal(inner) => {
+                ImportSpecifier::Normal(inner.iter().map(|n| n.as_borrowed()).collect())
+            }
+            ImportSpecifier::Default(inner) => ImportSpecifier::Default(inner.as_borrowed()),
+            ImportSpecifier::Namespace(inner) => ImportSpecifier::Namespace(inner.as_borrowed()),
+        }
+    }
+}
+
+impl<T, U> MapSource<T, U> for ImportSpecifier<T> {
+    type Output = ImportSpecifier<U>;
+
+    fn map_source_with(self, f: &mut impl FnMut(T) -> U) -> Self::Output {
         match self {
             ImportSpecifier::Normal(inner) => {
-                ImportSpecifier::Normal(inner.into_iter().map(|n| n.into_allocated()).collect())
+                ImportSpecifier::Normal(inner.into_iter().map(|n| n.map_source_with(f)).collect())
+            }
+            ImportSpecifier::Default(inner) => ImportSpecifier::Default(inner.map_source_with(f)),
+            ImportSpecifier::Namespace(inner) => {
+                ImportSpecifier::Namespace(inner.map_source_with(f))
             }
-            ImportSpecifier::Default(inner) => ImportSpecifier::Default(inner.into_allocated()),
-            ImportSpecifier::Namespace(inner) => ImportSpecifier::Namespace(inner.into_allocated()),
         }
     }
 }
 
-#[derive(PartialEq, Debug, Clone)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct NormalImportSpec<T> {
     pub alias: Option<Ident<T>>,
@@ -171,15 +548,34 @@ where
     type Allocated = NormalImportSpec<String>;
 
     fn into_allocated(self) -> Self::Allocated {
+        self.map_source_with(&mut |source: T| source.to_string())
+    }
+}
+
+impl<'a> AsBorrowed<'a> for NormalImportSpec<String> {
+    type Borrowed = NormalImportSpec<&'a str>;
+
+    fn as_borrowed(&'a self) -> Self::Borrowed {
+        NormalImportSpec {
+            alias: self.alias.as_borrowed(),
+            imported: self.imported.as_borrowed(),
+        }
+    }
+}
+
+impl<T, U> MapSource<T, U> for NormalImportSpec<T> {
+    type Output = NormalImportSpec<U>;
+
+    fn map_source_with(self, f: &mut impl FnMut(T) -> U) -> Self::Output {
         NormalImportSpec {
-            alias: self.alias.map(|i| i.into_allocated()),
-            imported: self.imported.into_allocated(),
+            alias: self.alias.map_source_with(f),
+            imported: self.imported.map_source_with(f),
         }
     }
 }
 
 /// Something exported from this module
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub enum ModExport<T> {
     /// ```js
@@ -201,6 +597,8 @@ pub enum ModExport<T> {
     Named(NamedExportDecl<T>),
     /// ```js
     /// export * from 'mod';
+    /// //or
+    /// export * as ns from 'mod';
     /// ```
     All {
         alias: Option<Ident<T>>,
@@ -215,12 +613,35 @@ where
     type Allocated = ModExport<String>;
 
     fn into_allocated(self) -> Self::Allocated {
+        self.map_source_with(&mut |source: T| source.to_string())
+    }
+}
+
+impl<'a> AsBorrowed<'a> for ModExport<String> {
+    type Borrowed = ModExport<&'a str>;
+
+    fn as_borrowed(&'a self) -> Self::Borrowed {
+        match self {
+            ModExport::Default(inner) => ModExport::Default(inner.as_borrowed()),
+            ModExport::Named(inner) => ModExport::Named(inner.as_borrowed()),
+            ModExport::All { alias, name } => ModExport::All {
+                alias: alias.as_borrowed(),
+                name: name.as_borrowed(),
+            },
+        }
+    }
+}
+
+impl<T, U> MapSource<T, U> for ModExport<T> {
+    type Output = ModExport<U>;
+
+    fn map_source_with(self, f: &mut impl FnMut(T) -> U) -> Self::Output {
         match self {
-            ModExport::Default(inner) => ModExport::Default(inner.into_allocated()),
-            ModExport::Named(inner) => ModExport::Named(inner.into_allocated()),
+            ModExport::Default(inner) => ModExport::Default(inner.map_source_with(f)),
+            ModExport::Named(inner) => ModExport::Named(inner.map_source_with(f)),
             ModExport::All { alias, name } => ModExport::All {
-                alias: alias.map(|i| i.into_allocated()),
-                name: name.into_allocated(),
+                alias: alias.map(|i| i.map_source_with(f)),
+                name: name.map_source_with(f),
             },
         }
     }
@@ -230,7 +651,7 @@ where
 /// ```js
 /// export function thing() {}
 /// export {stuff} from 'place';
-#[derive(PartialEq, Debug, Clone)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub enum NamedExportDecl<T> {
     Decl(Decl<T>),
@@ -244,11 +665,33 @@ where
     type Allocated = NamedExportDecl<String>;
 
     fn into_allocated(self) -> Self::Allocated {
+        self.map_source_with(&mut |source: T| source.to_string())
+    }
+}
+
+impl<'a> AsBorrowed<'a> for NamedExportDecl<String> {
+    type Borrowed = NamedExportDecl<&'a str>;
+
+    fn as_borrowed(&'a self) -> Self::Borrowed {
+        match self {
+            NamedExportDecl::Decl(inner) => NamedExportDecl::Decl(inner.as_borrowed()),
+            NamedExportDecl::Specifier(specs, lit) => NamedExportDecl::Specifier(
+                specs.iter().map(|s| s.as_borrowed()).collect(),
+                lit.as_borrowed(),
+            ),
+        }
+    }
+}
+
+impl<T, U> MapSource<T, U> for NamedExportDecl<T> {
+    type Output = NamedExportDecl<U>;
+
+    fn map_source_with(self, f: &mut impl FnMut(T) -> U) -> Self::Output {
         match self {
-            NamedExportDecl::Decl(inner) => NamedExportDecl::Decl(inner.into_allocated()),
+            NamedExportDecl::Decl(inner) => NamedExportDecl::Decl(inner.map_source_with(f)),
             NamedExportDecl::Specifier(specs, lit) => NamedExportDecl::Specifier(
-                specs.into_iter().map(|s| s.into_allocated()).collect(),
-                lit.map(|l| l.into_allocated()),
+                specs.into_iter().map(|s| s.map_source_with(f)).collect(),
+                lit.map(|l| l.map_source_with(f)),
             ),
         }
     }
@@ -258,7 +701,7 @@ where
 /// ```js
 /// export default class Thing {}
 /// ```
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub enum DefaultExportDecl<T> {
     Decl(Decl<T>),
@@ -272,9 +715,28 @@ where
     type Allocated = DefaultExportDecl<String>;
 
     fn into_allocated(self) -> Self::Allocated {
+        self.map_source_with(&mut |source: T| source.to_string())
+    }
+}
+
+impl<'a> AsBorrowed<'a> for DefaultExportDecl<String> {
+    type Borrowed = DefaultExportDecl<&'a str>;
+
+    fn as_borrowed(&'a self) -> Self::Borrowed {
+        match self {
+            DefaultExportDecl::Decl(inner) => DefaultExportDecl::Decl(inner.as_borrowed()),
+            DefaultExportDecl::Expr(inner) => DefaultExportDecl::Expr(inner.as_borrowed()),
+        }
+    }
+}
+
+impl<T, U> MapSource<T, U> for DefaultExportDecl<T> {
+    type Output = DefaultExportDecl<U>;
+
+    fn map_source_with(self, f: &mut impl FnMut(T) -> U) -> Self::Output {
         match self {
-            DefaultExportDecl::Decl(inner) => DefaultExportDecl::Decl(inner.into_allocated()),
-            DefaultExportDecl::Expr(inner) => DefaultExportDecl::Expr(inner.into_allocated()),
+            DefaultExportDecl::Decl(inner) => DefaultExportDecl::Decl(inner.map_source_with(f)),
+            DefaultExportDecl::Expr(inner) => DefaultExportDecl::Expr(inner.map_source_with(f)),
         }
     }
 }
@@ -287,7 +749,7 @@ where
 /// //aliased
 /// export {Stuff as NewThing} from 'place'
 /// ```
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct ExportSpecifier<T> {
     pub local: Ident<T>,
@@ -301,9 +763,28 @@ where
     type Allocated = ExportSpecifier<String>;
 
     fn into_allocated(self) -> Self::Allocated {
+        self.map_source_with(&mut |source: T| source.to_string())
+    }
+}
+
+impl<'a> AsBorrowed<'a> for ExportSpecifier<String> {
+    type Borrowed = ExportSpecifier<&'a str>;
+
+    fn as_borrowed(&'a self) -> Self::Borrowed {
+        ExportSpecifier {
+            local: self.local.as_borrowed(),
+            alias: self.alias.as_borrowed(),
+        }
+    }
+}
+
+impl<T, U> MapSource<T, U> for ExportSpecifier<T> {
+    type Output = ExportSpecifier<U>;
+
+    fn map_source_with(self, f: &mut impl FnMut(T) -> U) -> Self::Output {
         ExportSpecifier {
-            local: self.local.into_allocated(),
-            alias: self.alias.map(|a| a.into_allocated()),
+            local: self.local.map_source_with(f),
+            alias: self.alias.map_source_with(f),
         }
     }
 }
@@ -1,14 +1,19 @@
 use crate::pat::Pat;
 use crate::{
-    AssignOp, BinaryOp, IntoAllocated, LogicalOp, MemberIndexer, PropKind, UnaryOp, UpdateOp,
+    AsBorrowed, AssignOp, BinaryOp, IntoAllocated, LogicalOp, MapSource, MemberIndexer, PropKind,
+    UnaryOp, UpdateOp,
 };
-use crate::{Class, Func, FuncArg, FuncBody, Ident};
+use crate::{Class, Decorator, Func, FuncArg, FuncBody, Ident, PrivateIdent};
+use std::borrow::Cow;
+
+#[cfg(feature = "jsx")]
+use crate::jsx::{JsxElement, JsxFragment};
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 /// A slightly more granular program part that a statement
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub enum Expr<T> {
     /// `[0,,]`
@@ -44,6 +49,16 @@ pub enum Expr<T> {
     Func(Func<T>),
     /// An identifier
     Ident(Ident<T>),
+    /// A private class member name
+    /// ```js
+    /// this.#x;
+    /// ```
+    PrivateIdent(PrivateIdent<T>),
+    /// A dynamic import
+    /// ```js
+    /// import('./mod.js');
+    /// ```
+    Import(ImportExpr<T>),
     /// A literal value, see `Literal`
     Lit(Lit<T>),
     /// A specialized `BinaryExpr` for logical evaluation
@@ -92,6 +107,21 @@ pub enum Expr<T> {
     /// yield a value from inside of a generator function
     Yield(YieldExpr<T>),
     OptionalChain(Box<Expr<T>>),
+    /// An expression wrapped in parentheses, these are only kept
+    /// when converting from the spanned tree with `KeepParens`
+    /// ```js
+    /// (a + b) * c
+    /// ```
+    #[cfg_attr(feature = "serde", serde(rename = "ParenthesizedExpression"))]
+    Paren(Box<Expr<T>>),
+    /// A JSX element, see `JsxElement`
+    #[cfg(feature = "jsx")]
+    #[cfg_attr(feature = "serde", serde(rename = "JSXElement"))]
+    Jsx(JsxElement<T>),
+    /// A JSX fragment, see `JsxFragment`
+    #[cfg(feature = "jsx")]
+    #[cfg_attr(feature = "serde", serde(rename = "JSXFragment"))]
+    JsxFragment(JsxFragment<T>),
 }
 
 impl<T> IntoAllocated for Expr<T>
@@ -101,51 +131,644 @@ where
     type Allocated = Expr<String>;
 
     fn into_allocated(self) -> Self::Allocated {
+        self.map_source_with(&mut |source: T| source.to_string())
+    }
+}
+
+impl<'a> AsBorrowed<'a> for Expr<String> {
+    type Borrowed = Expr<&'a str>;
+
+    fn as_borrowed(&'a self) -> Self::Borrowed {
+        match self {
+            Expr::Array(inner) => Expr::Array(inner.iter().map(|o| o.as_borrowed()).collect()),
+            Expr::ArrowFunc(inner) => Expr::ArrowFunc(inner.as_borrowed()),
+            Expr::ArrowParamPlaceHolder(args, is_async) => Expr::ArrowParamPlaceHolder(
+                args.iter().map(|a| a.as_borrowed()).collect(),
+                *is_async,
+            ),
+            Expr::Assign(inner) => Expr::Assign(inner.as_borrowed()),
+            Expr::Await(inner) => Expr::Await(inner.as_borrowed()),
+            Expr::Binary(inner) => Expr::Binary(inner.as_borrowed()),
+            Expr::Class(inner) => Expr::Class(inner.as_borrowed()),
+            Expr::Call(inner) => Expr::Call(inner.as_borrowed()),
+            Expr::Conditional(inner) => Expr::Conditional(inner.as_borrowed()),
+            Expr::Func(inner) => Expr::Func(inner.as_borrowed()),
+            Expr::Ident(inner) => Expr::Ident(inner.as_borrowed()),
+            Expr::PrivateIdent(inner) => Expr::PrivateIdent(inner.as_borrowed()),
+            Expr::Import(inner) => Expr::Import(inner.as_borrowed()),
+            Expr::Lit(inner) => Expr::Lit(inner.as_borrowed()),
+            Expr::Logical(inner) => Expr::Logical(inner.as_borrowed()),
+            Expr::Member(inner) => Expr::Member(inner.as_borrowed()),
+            Expr::MetaProp(inner) => Expr::MetaProp(inner.as_borrowed()),
+            Expr::New(inner) => Expr::New(inner.as_borrowed()),
+            Expr::Obj(inner) => Expr::Obj(inner.iter().map(|p| p.as_borrowed()).collect()),
+            Expr::Sequence(inner) => Expr::Sequence(inner.as_borrowed()),
+            Expr::Spread(inner) => Expr::Spread(inner.as_borrowed()),
+            Expr::Super => Expr::Super,
+            Expr::TaggedTemplate(inner) => Expr::TaggedTemplate(inner.as_borrowed()),
+            Expr::This => Expr::This,
+            Expr::Unary(inner) => Expr::Unary(inner.as_borrowed()),
+            Expr::Update(inner) => Expr::Update(inner.as_borrowed()),
+            Expr::Yield(inner) => Expr::Yield(inner.as_borrowed()),
+            Expr::OptionalChain(inner) => Expr::OptionalChain(inner.as_borrowed()),
+            Expr::Paren(inner) => Expr::Paren(inner.as_borrowed()),
+            #[cfg(feature = "jsx")]
+            Expr::Jsx(inner) => Expr::Jsx(inner.as_borrowed()),
+            #[cfg(feature = "jsx")]
+            Expr::JsxFragment(inner) => Expr::JsxFragment(inner.as_borrowed()),
+        }
+    }
+}
+
+impl<T, U> MapSource<T, U> for Expr<T> {
+    type Output = Expr<U>;
+
+    fn map_source_with(self, f: &mut impl FnMut(T) -> U) -> Self::Output {
         match self {
             Expr::Array(inner) => Expr::Array(
                 inner
                     .into_iter()
-                    .map(|o| o.map(|e| e.into_allocated()))
+                    .map(|o| o.map(|e| e.map_source_with(f)))
                     .collect(),
             ),
-            Expr::ArrowFunc(inner) => Expr::ArrowFunc(inner.into_allocated()),
+            Expr::ArrowFunc(inner) => Expr::ArrowFunc(inner.map_source_with(f)),
             Expr::ArrowParamPlaceHolder(args, is_async) => Expr::ArrowParamPlaceHolder(
-                args.into_iter().map(|a| a.into_allocated()).collect(),
+                args.into_iter().map(|a| a.map_source_with(f)).collect(),
                 is_async,
             ),
-            Expr::Assign(inner) => Expr::Assign(inner.into_allocated()),
-            Expr::Await(inner) => Expr::Await(inner.into_allocated()),
-            Expr::Binary(inner) => Expr::Binary(inner.into_allocated()),
-            Expr::Class(inner) => Expr::Class(inner.into_allocated()),
-            Expr::Call(inner) => Expr::Call(inner.into_allocated()),
-            Expr::Conditional(inner) => Expr::Conditional(inner.into_allocated()),
-            Expr::Func(inner) => Expr::Func(inner.into_allocated()),
-            Expr::Ident(inner) => Expr::Ident(inner.into_allocated()),
-            Expr::Lit(inner) => Expr::Lit(inner.into_allocated()),
-            Expr::Logical(inner) => Expr::Logical(inner.into_allocated()),
-            Expr::Member(inner) => Expr::Member(inner.into_allocated()),
-            Expr::MetaProp(inner) => Expr::MetaProp(inner.into_allocated()),
-            Expr::New(inner) => Expr::New(inner.into_allocated()),
-            Expr::Obj(inner) => Expr::Obj(inner.into_iter().map(|p| p.into_allocated()).collect()),
-            Expr::Sequence(inner) => {
-                Expr::Sequence(inner.into_iter().map(|e| e.into_allocated()).collect())
+            Expr::Assign(inner) => Expr::Assign(inner.map_source_with(f)),
+            Expr::Await(inner) => Expr::Await(inner.map_source_with(f)),
+            Expr::Binary(inner) => Expr::Binary(inner.map_source_with(f)),
+            Expr::Class(inner) => Expr::Class(inner.map_source_with(f)),
+            Expr::Call(inner) => Expr::Call(inner.map_source_with(f)),
+            Expr::Conditional(inner) => Expr::Conditional(inner.map_source_with(f)),
+            Expr::Func(inner) => Expr::Func(inner.map_source_with(f)),
+            Expr::Ident(inner) => Expr::Ident(inner.map_source_with(f)),
+            Expr::PrivateIdent(inner) => Expr::PrivateIdent(inner.map_source_with(f)),
+            Expr::Import(inner) => Expr::Import(inner.map_source_with(f)),
+            Expr::Lit(inner) => Expr::Lit(inner.map_source_with(f)),
+            Expr::Logical(inner) => Expr::Logical(inner.map_source_with(f)),
+            Expr::Member(inner) => Expr::Member(inner.map_source_with(f)),
+            Expr::MetaProp(inner) => Expr::MetaProp(inner.map_source_with(f)),
+            Expr::New(inner) => Expr::New(inner.map_source_with(f)),
+            Expr::Obj(inner) => {
+                Expr::Obj(inner.into_iter().map(|p| p.map_source_with(f)).collect())
             }
-            Expr::Spread(inner) => Expr::Spread(inner.into_allocated()),
+            Expr::Sequence(inner) => Expr::Sequence(inner.map_source_with(f)),
+            Expr::Spread(inner) => Expr::Spread(inner.map_source_with(f)),
             Expr::Super => Expr::Super,
-            Expr::TaggedTemplate(inner) => Expr::TaggedTemplate(inner.into_allocated()),
+            Expr::TaggedTemplate(inner) => Expr::TaggedTemplate(inner.map_source_with(f)),
             Expr::This => Expr::This,
-            Expr::Unary(inner) => Expr::Unary(inner.into_allocated()),
-            Expr::Update(inner) => Expr::Update(inner.into_allocated()),
-            Expr::Yield(inner) => Expr::Yield(inner.into_allocated()),
-            Expr::OptionalChain(inner) => Expr::OptionalChain(inner.into_allocated()),
+            Expr::Unary(inner) => Expr::Unary(inner.map_source_with(f)),
+            Expr::Update(inner) => Expr::Update(inner.map_source_with(f)),
+            Expr::Yield(inner) => Expr::Yield(inner.map_source_with(f)),
+            Expr::OptionalChain(inner) => Expr::OptionalChain(inner.map_source_with(f)),
+            Expr::Paren(inner) => Expr::Paren(inner.map_source_with(f)),
+            #[cfg(feature = "jsx")]
+            Expr::Jsx(inner) => Expr::Jsx(inner.map_source_with(f)),
+            #[cfg(feature = "jsx")]
+            Expr::JsxFragment(inner) => Expr::JsxFragment(inner.map_source_with(f)),
         }
     }
 }
 
 impl<T> Expr<T> {
+    /// Pass every source value through `f`, see [`Program::map_source`](crate::Program::map_source)
+    pub fn map_source<U>(self, mut f: impl FnMut(T) -> U) -> Expr<U> {
+        self.map_source_with(&mut f)
+    }
     pub fn ident_from(inner: T) -> Self {
         Self::Ident(Ident { name: inner })
     }
+
+    /// `callee(arguments)`
+    /// ```
+    /// use resast::prelude::*;
+    /// use resast::MemberIndexer;
+    ///
+    /// // a.b(c)
+    /// let built = Expr::call(
+    ///     Expr::member(Expr::ident_from("a"), Expr::ident_from("b"), false),
+    ///     vec![Expr::ident_from("c")],
+    /// );
+    /// let manual = Expr::Call(CallExpr {
+    ///     optional: false,
+    ///     callee: Box::new(Expr::Member(MemberExpr {
+    ///         object: Box::new(Expr::ident_from("a")),
+    ///         property: Box::new(Expr::ident_from("b")),
+    ///         indexer: MemberIndexer::Period,
+    ///     })),
+    ///     arguments: vec![Expr::ident_from("c")],
+    /// });
+    /// assert_eq!(built, manual);
+    /// ```
+    pub fn call(callee: Expr<T>, arguments: Vec<Expr<T>>) -> Self {
+        Self::Call(CallExpr {
+            optional: false,
+            callee: Box::new(callee),
+            arguments,
+        })
+    }
+
+    /// `object.property` or, when `computed`, `object[property]`
+    pub fn member(object: Expr<T>, property: Expr<T>, computed: bool) -> Self {
+        Self::Member(MemberExpr {
+            object: Box::new(object),
+            property: Box::new(property),
+            indexer: if computed {
+                MemberIndexer::Computed
+            } else {
+                MemberIndexer::Period
+            },
+        })
+    }
+
+    /// `left operator right`
+    /// ```
+    /// use resast::prelude::*;
+    ///
+    /// // x = 1 + 2
+    /// let expr = Expr::assign(
+    ///     AssignOp::Equal,
+    ///     Expr::ident_from("x"),
+    ///     Expr::binary(
+    ///         BinaryOp::Plus,
+    ///         Expr::lit(Lit::number_from("1")),
+    ///         Expr::lit(Lit::number_from("2")),
+    ///     ),
+    /// );
+    /// let manual = Expr::Assign(AssignExpr {
+    ///     operator: AssignOp::Equal,
+    ///     left: AssignLeft::Expr(Box::new(Expr::ident_from("x"))),
+    ///     right: Box::new(Expr::Binary(BinaryExpr {
+    ///         operator: BinaryOp::Plus,
+    ///         left: Box::new(Expr::Lit(Lit::number_from("1"))),
+    ///         right: Box::new(Expr::Lit(Lit::number_from("2"))),
+    ///     })),
+    /// });
+    /// assert_eq!(expr, manual);
+    /// ```
+    pub fn binary(operator: BinaryOp, left: Expr<T>, right: Expr<T>) -> Self {
+        Self::Binary(BinaryExpr {
+            operator,
+            left: Box::new(left),
+            right: Box::new(right),
+        })
+    }
+
+    /// `left operator right` where `left` is an expression
+    /// rather than a pattern
+    pub fn assign(operator: AssignOp, left: Expr<T>, right: Expr<T>) -> Self {
+        Self::Assign(AssignExpr {
+            operator,
+            left: AssignLeft::Expr(Box::new(left)),
+            right: Box::new(right),
+        })
+    }
+
+    pub fn lit(lit: Lit<T>) -> Self {
+        Self::Lit(lit)
+    }
+
+    /// If this is an immediately invoked function expression,
+    /// a call whose callee is a function or arrow function, looking
+    /// through any number of `Paren`s around the callee
+    /// ```
+    /// use resast::prelude::*;
+    /// use resast::{Func, FuncBody};
+    ///
+    /// let func = Expr::Func(Func {
+    ///     id: None,
+    ///     params: vec![],
+    ///     body: FuncBody(vec![]),
+    ///     generator: false,
+    ///     is_async: false,
+    /// });
+    /// let arrow = Expr::ArrowFunc(ArrowFuncExpr {
+    ///     id: None,
+    ///     params: vec![],
+    ///     body: ArrowFuncBody::FuncBody(FuncBody(vec![])),
+    ///     expression: false,
+    ///     generator: false,
+    ///     is_async: false,
+    /// });
+    /// let call = |callee: Expr<&'static str>| Expr::Call(CallExpr {
+    ///     optional: false,
+    ///     callee: Box::new(callee),
+    ///     arguments: vec![],
+    /// });
+    /// // (function(){})()
+    /// assert!(call(func.clone()).is_iife());
+    /// // (() => {})()
+    /// assert!(call(arrow).is_iife());
+    /// // foo()
+    /// assert!(!call(Expr::ident_from("foo")).is_iife());
+    /// // function(){}
+    /// assert!(!func.is_iife());
+    /// // ((function(){}))()
+    /// let paren = Expr::Paren(Box::new(Expr::Paren(Box::new(func))));
+    /// assert!(call(paren).is_iife());
+    /// ```
+    pub fn is_iife(&self) -> bool {
+        match self {
+            Self::Call(call) => call.callee.unwrapped_is_func(),
+            _ => false,
+        }
+    }
+
+    fn unwrapped_is_func(&self) -> bool {
+        match self {
+            Self::Func(_) | Self::ArrowFunc(_) => true,
+            Self::Paren(inner) => inner.unwrapped_is_func(),
+            _ => false,
+        }
+    }
+    /// If this expression can appear on the left of an `=`, either
+    /// as a simple target or as an array or object destructuring
+    /// pattern written as an expression
+    /// ```
+    /// use resast::prelude::*;
+    ///
+    /// // a
+    /// assert!(Expr::ident_from("a").is_valid_assignment_target());
+    /// // a.b
+    /// let a_b = Expr::member(Expr::ident_from("a"), Expr::ident_from("b"), false);
+    /// assert!(a_b.is_valid_assignment_target());
+    /// // a[b]
+    /// let a_b = Expr::member(Expr::ident_from("a"), Expr::ident_from("b"), true);
+    /// assert!(a_b.is_valid_assignment_target());
+    /// // a?.b
+    /// let optional = Expr::Member(MemberExpr {
+    ///     object: Box::new(Expr::ident_from("a")),
+    ///     property: Box::new(Expr::ident_from("b")),
+    ///     indexer: resast::MemberIndexer::Optional,
+    /// });
+    /// assert!(!optional.is_valid_assignment_target());
+    /// // f()
+    /// assert!(!Expr::call(Expr::ident_from("f"), vec![]).is_valid_assignment_target());
+    /// // 1
+    /// assert!(!Expr::lit(Lit::number_from("1")).is_valid_assignment_target());
+    /// ```
+    pub fn is_valid_assignment_target(&self) -> bool {
+        match self {
+            Self::Ident(_) | Self::Array(_) | Self::Obj(_) => true,
+            // `a?.b = 1` is an early error
+            Self::Member(m) => !m.optional(),
+            // `(a) = 1` is allowed but `([a]) = 1` is not
+            Self::Paren(inner) => {
+                !matches!(&**inner, Self::Array(_) | Self::Obj(_))
+                    && inner.is_valid_assignment_target()
+            }
+            _ => false,
+        }
+    }
+    /// If this expression holds an `in` operator that isn't inside of
+    /// any brackets, see [`BinaryExpr::needs_for_header_parens`]
+    ///
+    /// Arguments, array and object literals, function and class
+    /// bodies, templates and parenthesized expressions all bring their
+    /// own brackets so they are not searched.
+    /// ```
+    /// use resast::prelude::*;
+    /// use resast::codegen::WriteJs;
+    /// use resast::stmt::{ForStmt, LoopInit};
+    /// use resast::LogicalOp;
+    ///
+    /// let ident = Expr::ident_from;
+    /// let a_in_b = || Expr::binary(BinaryOp::In, ident("a"), ident("b"));
+    /// let for_init = |init: Expr<&'static str>| {
+    ///     assert!(init.needs_for_header_parens());
+    ///     Stmt::For(ForStmt {
+    ///         init: Some(LoopInit::Expr(init)),
+    ///         test: None,
+    ///         update: None,
+    ///         body: Box::new(Stmt::Empty),
+    ///     })
+    ///     .to_js_string()
+    /// };
+    /// // x = a in b
+    /// let assign = Expr::assign(AssignOp::Equal, ident("x"), a_in_b());
+    /// assert_eq!(for_init(assign), "for ((x = a in b);;) ;");
+    /// // c && a in b
+    /// let logical = Expr::Logical(LogicalExpr {
+    ///     operator: LogicalOp::And,
+    ///     left: Box::new(ident("c")),
+    ///     right: Box::new(a_in_b()),
+    /// });
+    /// assert_eq!(for_init(logical), "for ((c && a in b);;) ;");
+    /// // a in b, c
+    /// let sequence = Expr::Sequence(SequenceExpr::from(vec![a_in_b(), ident("c")]));
+    /// assert_eq!(for_init(sequence), "for ((a in b, c);;) ;");
+    /// // a in b ? c : d
+    /// let conditional = Expr::Conditional(ConditionalExpr {
+    ///     test: Box::new(a_in_b()),
+    ///     alternate: Box::new(ident("c")),
+    ///     consequent: Box::new(ident("d")),
+    /// });
+    /// assert_eq!(for_init(conditional), "for ((a in b ? c : d);;) ;");
+    /// // f(a in b) and [a in b] are already bracketed
+    /// assert!(!Expr::call(ident("f"), vec![a_in_b()]).needs_for_header_parens());
+    /// assert!(!Expr::Array(vec![Some(a_in_b())]).needs_for_header_parens());
+    /// ```
+    pub fn needs_for_header_parens(&self) -> bool {
+        match self {
+            Self::Binary(inner) => inner.needs_for_header_parens(),
+            Self::ArrowFunc(inner) => match &inner.body {
+                ArrowFuncBody::Expr(body) => body.needs_for_header_parens(),
+                ArrowFuncBody::FuncBody(_) => false,
+            },
+            Self::Assign(inner) => {
+                matches!(&inner.left, AssignLeft::Expr(left) if left.needs_for_header_parens())
+                    || inner.right.needs_for_header_parens()
+            }
+            Self::Logical(inner) => {
+                inner.left.needs_for_header_parens() || inner.right.needs_for_header_parens()
+            }
+            Self::Conditional(inner) => {
+                inner.test.needs_for_header_parens()
+                    || inner.consequent.needs_for_header_parens()
+                    || inner.alternate.needs_for_header_parens()
+            }
+            Self::Sequence(exprs) => exprs.iter().any(Self::needs_for_header_parens),
+            Self::Call(inner) => inner.callee.needs_for_header_parens(),
+            Self::New(inner) => inner.callee.needs_for_header_parens(),
+            Self::Member(inner) => inner.object.needs_for_header_parens(),
+            Self::TaggedTemplate(inner) => inner.tag.needs_for_header_parens(),
+            Self::Unary(inner) => inner.argument.needs_for_header_parens(),
+            Self::Update(inner) => inner.argument.needs_for_header_parens(),
+            Self::Await(inner) | Self::Spread(inner) | Self::OptionalChain(inner) => {
+                inner.needs_for_header_parens()
+            }
+            Self::Yield(inner) => {
+                matches!(&inner.argument, Some(arg) if arg.needs_for_header_parens())
+            }
+            _ => false,
+        }
+    }
+    /// Every expression nested inside this one, in pre-order
+    ///
+    /// Functions and classes are yielded but not entered, nothing
+    /// inside of their bodies or parameters will be included.
+    /// ```
+    /// use resast::prelude::*;
+    ///
+    /// // f(g(h(x)))
+    /// let call = Expr::call(
+    ///     Expr::ident_from("f"),
+    ///     vec![Expr::call(
+    ///         Expr::ident_from("g"),
+    ///         vec![Expr::call(Expr::ident_from("h"), vec![Expr::ident_from("x")])],
+    ///     )],
+    /// );
+    /// let names: Vec<_> = call
+    ///     .descendants()
+    ///     .filter_map(|expr| match expr {
+    ///         Expr::Ident(ident) => Some(ident.name),
+    ///         _ => None,
+    ///     })
+    ///     .collect();
+    /// assert_eq!(names, ["f", "g", "h", "x"]);
+    /// let calls = call
+    ///     .descendants()
+    ///     .filter(|expr| matches!(expr, Expr::Call(_)))
+    ///     .count();
+    /// assert_eq!(calls, 2);
+    /// ```
+    pub fn descendants(&self) -> impl Iterator<Item = &Expr<T>> + '_ {
+        // an explicit stack keeps very deep trees from overflowing
+        let mut stack = Vec::new();
+        self.push_children(&mut stack);
+        std::iter::from_fn(move || {
+            let next = stack.pop()?;
+            next.push_children(&mut stack);
+            Some(next)
+        })
+    }
+
+    /// Push the direct children of this expression onto the stack
+    /// so that they are popped in source order
+    fn push_children<'a>(&'a self, stack: &mut Vec<&'a Expr<T>>) {
+        let start = stack.len();
+        match self {
+            Self::Array(elements) => stack.extend(elements.iter().flatten()),
+            Self::Assign(inner) => {
+                if let AssignLeft::Expr(left) = &inner.left {
+                    stack.push(left);
+                }
+                stack.push(&inner.right);
+            }
+            Self::Await(inner)
+            | Self::Spread(inner)
+            | Self::OptionalChain(inner)
+            | Self::Paren(inner) => stack.push(inner),
+            Self::Binary(inner) => {
+                stack.push(&inner.left);
+                stack.push(&inner.right);
+            }
+            Self::Call(inner) => {
+                stack.push(&inner.callee);
+                stack.extend(&inner.arguments);
+            }
+            Self::Conditional(inner) => {
+                stack.push(&inner.test);
+                stack.push(&inner.consequent);
+                stack.push(&inner.alternate);
+            }
+            Self::Import(inner) => {
+                stack.push(&inner.source);
+                stack.extend(inner.options.as_deref());
+            }
+            Self::Lit(Lit::Template(inner)) => stack.extend(&inner.expressions),
+            Self::Logical(inner) => {
+                stack.push(&inner.left);
+                stack.push(&inner.right);
+            }
+            Self::Member(inner) => {
+                stack.push(&inner.object);
+                stack.push(&inner.property);
+            }
+            Self::New(inner) => {
+                stack.push(&inner.callee);
+                stack.extend(&inner.arguments);
+            }
+            Self::Obj(props) => {
+                for prop in props {
+                    match prop {
+                        ObjProp::Prop(prop) => {
+                            if let PropKey::Expr(key) = &prop.key {
+                                stack.push(key);
+                            }
+                            if let PropValue::Expr(value) = &prop.value {
+                                stack.push(value);
+                            }
+                        }
+                        ObjProp::Spread(expr) => stack.push(expr),
+                    }
+                }
+            }
+            Self::Sequence(exprs) => stack.extend(exprs),
+            Self::TaggedTemplate(inner) => {
+                stack.push(&inner.tag);
+                stack.extend(&inner.quasi.expressions);
+            }
+            Self::Unary(inner) => stack.push(&inner.argument),
+            Self::Update(inner) => stack.push(&inner.argument),
+            Self::Yield(inner) => stack.extend(inner.argument.as_deref()),
+            #[cfg(feature = "jsx")]
+            Self::Jsx(inner) => inner.push_exprs(stack),
+            #[cfg(feature = "jsx")]
+            Self::JsxFragment(inner) => crate::jsx::push_child_exprs(&inner.children, stack),
+            Self::ArrowFunc(_)
+            | Self::ArrowParamPlaceHolder(_, _)
+            | Self::Class(_)
+            | Self::Func(_)
+            | Self::Ident(_)
+            | Self::PrivateIdent(_)
+            | Self::Lit(_)
+            | Self::MetaProp(_)
+            | Self::Super
+            | Self::This => {}
+        }
+        stack[start..].reverse();
+    }
+    /// The number of nodes in this expression, including itself
+    ///
+    /// Identifiers and literals count once, not once for the
+    /// expression and again for what it holds.
+    /// ```
+    /// use resast::prelude::*;
+    ///
+    /// // a + b * c
+    /// let expr = Expr::binary(
+    ///     BinaryOp::Plus,
+    ///     Expr::ident_from("a"),
+    ///     Expr::binary(BinaryOp::Times, Expr::ident_from("b"), Expr::ident_from("c")),
+    /// );
+    /// assert_eq!(expr.node_count(), 5);
+    /// // f(1)
+    /// let call = Expr::call(Expr::ident_from("f"), vec![Expr::lit(Lit::number_from("1"))]);
+    /// assert_eq!(call.node_count(), 3);
+    /// ```
+    pub fn node_count(&self) -> usize {
+        let mut counter = crate::visit::NodeCounter::default();
+        crate::visit::Visit::visit_expr(&mut counter, self);
+        counter.0
+    }
+}
+
+/// Returned when narrowing an `Expr` into a variant it
+/// doesn't hold, the original expression is handed back
+/// ```
+/// use resast::prelude::*;
+/// use std::convert::TryFrom;
+///
+/// let call: CallExpr<&str> = Expr::call(Expr::ident_from("f"), vec![]).try_into().unwrap();
+/// assert_eq!(call.arguments.len(), 0);
+///
+/// let err = MemberExpr::try_from(Expr::ident_from("x")).unwrap_err();
+/// assert_eq!(err.expected, "Member");
+/// assert_eq!(err.expr, Expr::ident_from("x"));
+/// assert_eq!(err.to_string(), "expected a Member expression");
+///
+/// let expr = Expr::ident_from("y");
+/// assert_eq!(expr.as_ident().map(|i| i.name), Some("y"));
+/// assert!(expr.as_call().is_none());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExprKindError<T> {
+    /// The name of the variant that was asked for
+    pub expected: &'static str,
+    /// The expression that didn't match
+    pub expr: Expr<T>,
+}
+
+impl<T> std::fmt::Display for ExprKindError<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "expected a {} expression", self.expected)
+    }
+}
+
+impl<T: std::fmt::Debug> std::error::Error for ExprKindError<T> {}
+
+macro_rules! narrow_expr {
+    ($($variant:ident($ty:ident) => $as_fn:ident,)*) => {
+        $(
+            impl<T> TryFrom<Expr<T>> for $ty<T> {
+                type Error = ExprKindError<T>;
+                fn try_from(expr: Expr<T>) -> Result<Self, Self::Error> {
+                    match expr {
+                        Expr::$variant(inner) => Ok(inner),
+                        expr => Err(ExprKindError {
+                            expected: stringify!($variant),
+                            expr,
+                        }),
+                    }
+                }
+            }
+        )*
+
+        impl<T> Expr<T> {
+            $(
+                #[doc = concat!("The inner `", stringify!($ty), "` if this is `Expr::", stringify!($variant), "`")]
+                pub fn $as_fn(&self) -> Option<&$ty<T>> {
+                    match self {
+                        Self::$variant(inner) => Some(inner),
+                        _ => None,
+                    }
+                }
+            )*
+        }
+    };
+}
+
+narrow_expr! {
+    ArrowFunc(ArrowFuncExpr) => as_arrow_func,
+    Assign(AssignExpr) => as_assign,
+    Binary(BinaryExpr) => as_binary,
+    Class(Class) => as_class,
+    Call(CallExpr) => as_call,
+    Conditional(ConditionalExpr) => as_conditional,
+    Func(Func) => as_func,
+    Ident(Ident) => as_ident,
+    PrivateIdent(PrivateIdent) => as_private_ident,
+    Import(ImportExpr) => as_import,
+    Lit(Lit) => as_lit,
+    Logical(LogicalExpr) => as_logical,
+    Member(MemberExpr) => as_member,
+    MetaProp(MetaProp) => as_meta_prop,
+    New(NewExpr) => as_new,
+    Sequence(SequenceExpr) => as_sequence,
+    TaggedTemplate(TaggedTemplateExpr) => as_tagged_template,
+    Unary(UnaryExpr) => as_unary,
+    Update(UpdateExpr) => as_update,
+    Yield(YieldExpr) => as_yield,
+}
+
+impl<T> Expr<T>
+where
+    T: AsRef<str>,
+{
+    /// If this is the `new.target` meta property
+    /// ```
+    /// use resast::prelude::*;
+    ///
+    /// assert!(Expr::MetaProp(MetaProp::<&str>::new_target()).is_new_target());
+    /// assert!(!Expr::MetaProp(MetaProp::<&str>::import_meta()).is_new_target());
+    /// // new Foo
+    /// let new = Expr::New(NewExpr {
+    ///     callee: Box::new(Expr::ident_from("Foo")),
+    ///     arguments: vec![],
+    /// });
+    /// assert!(!new.is_new_target());
+    /// ```
+    pub fn is_new_target(&self) -> bool {
+        match self {
+            Self::MetaProp(meta) => {
+                meta.meta.name.as_ref() == "new" && meta.property.name.as_ref() == "target"
+            }
+            _ => false,
+        }
+    }
 }
 
 /// `[a, b, c]`
@@ -153,7 +776,7 @@ pub type ArrayExpr<T> = Vec<Option<Expr<T>>>;
 /// `{a: 'b', c, ...d}`
 pub type ObjExpr<T> = Vec<ObjProp<T>>;
 /// A single part of an object literal
-#[derive(PartialEq, Debug, Clone)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub enum ObjProp<T> {
     Prop(Prop<T>),
@@ -167,15 +790,34 @@ where
     type Allocated = ObjProp<String>;
 
     fn into_allocated(self) -> Self::Allocated {
+        self.map_source_with(&mut |source: T| source.to_string())
+    }
+}
+
+impl<'a> AsBorrowed<'a> for ObjProp<String> {
+    type Borrowed = ObjProp<&'a str>;
+
+    fn as_borrowed(&'a self) -> Self::Borrowed {
+        match self {
+            ObjProp::Prop(inner) => ObjProp::Prop(inner.as_borrowed()),
+            ObjProp::Spread(inner) => ObjProp::Spread(inner.as_borrowed()),
+        }
+    }
+}
+
+impl<T, U> MapSource<T, U> for ObjProp<T> {
+    type Output = ObjProp<U>;
+
+    fn map_source_with(self, f: &mut impl FnMut(T) -> U) -> Self::Output {
         match self {
-            ObjProp::Prop(inner) => ObjProp::Prop(inner.into_allocated()),
-            ObjProp::Spread(inner) => ObjProp::Spread(inner.into_allocated()),
+            ObjProp::Prop(inner) => ObjProp::Prop(inner.map_source_with(f)),
+            ObjProp::Spread(inner) => ObjProp::Spread(inner.map_source_with(f)),
         }
     }
 }
 
 /// A single part of an object literal or class
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct Prop<T> {
     pub key: PropKey<T>,
@@ -185,6 +827,7 @@ pub struct Prop<T> {
     pub computed: bool,
     pub short_hand: bool,
     pub is_static: bool,
+    pub decorators: Vec<Decorator<T>>,
 }
 
 impl<T> IntoAllocated for Prop<T>
@@ -194,25 +837,148 @@ where
     type Allocated = Prop<String>;
 
     fn into_allocated(self) -> Self::Allocated {
+        self.map_source_with(&mut |source: T| source.to_string())
+    }
+}
+
+impl<'a> AsBorrowed<'a> for Prop<String> {
+    type Borrowed = Prop<&'a str>;
+
+    fn as_borrowed(&'a self) -> Self::Borrowed {
         Prop {
-            key: self.key.into_allocated(),
-            value: self.value.into_allocated(),
+            key: self.key.as_borrowed(),
+            value: self.value.as_borrowed(),
             kind: self.kind,
             method: self.method,
             computed: self.computed,
             short_hand: self.short_hand,
             is_static: self.is_static,
+            decorators: self
+                .decorators
+                .iter()
+                .map(AsBorrowed::as_borrowed)
+                .collect(),
+        }
+    }
+}
+
+impl<T, U> MapSource<T, U> for Prop<T> {
+    type Output = Prop<U>;
+
+    fn map_source_with(self, f: &mut impl FnMut(T) -> U) -> Self::Output {
+        Prop {
+            key: self.key.map_source_with(f),
+            value: self.value.map_source_with(f),
+            kind: self.kind,
+            method: self.method,
+            computed: self.computed,
+            short_hand: self.short_hand,
+            is_static: self.is_static,
+            decorators: self
+                .decorators
+                .into_iter()
+                .map(|e| e.map_source_with(f))
+                .collect(),
+        }
+    }
+}
+
+impl<T> Prop<T> {
+    /// Check that a getter takes no parameters and a setter takes exactly
+    /// one parameter that isn't a rest element, any other kind of property
+    /// is always valid
+    /// ```
+    /// use resast::prelude::*;
+    /// use resast::expr::AccessorArityError;
+    ///
+    /// let accessor = |kind, params: Vec<&'static str>| Prop {
+    ///     key: PropKey::Expr(Expr::ident_from("x")),
+    ///     value: PropValue::Expr(Expr::Func(
+    ///         Func::builder()
+    ///             .params(params.into_iter().map(|p| FuncArg::Pat(Pat::ident_from(p))))
+    ///             .build(),
+    ///     )),
+    ///     kind,
+    ///     method: false,
+    ///     computed: false,
+    ///     short_hand: false,
+    ///     is_static: false,
+    ///     decorators: Vec::new(),
+    /// };
+    /// // get x() {}
+    /// assert_eq!(accessor(PropKind::Get, vec![]).validate_accessor_arity(), Ok(()));
+    /// // get x(a) {}
+    /// assert_eq!(
+    ///     accessor(PropKind::Get, vec!["a"]).validate_accessor_arity(),
+    ///     Err(AccessorArityError::GetterParams(1))
+    /// );
+    /// // set x(a, b) {}
+    /// assert_eq!(
+    ///     accessor(PropKind::Set, vec!["a", "b"]).validate_accessor_arity(),
+    ///     Err(AccessorArityError::SetterParams(2))
+    /// );
+    /// ```
+    pub fn validate_accessor_arity(&self) -> Result<(), AccessorArityError> {
+        let params = match &self.value {
+            PropValue::Expr(Expr::Func(func)) => &func.params,
+            _ => return Ok(()),
+        };
+        match self.kind {
+            PropKind::Get if !params.is_empty() => {
+                Err(AccessorArityError::GetterParams(params.len()))
+            }
+            PropKind::Set if params.len() != 1 => {
+                Err(AccessorArityError::SetterParams(params.len()))
+            }
+            PropKind::Set
+                if matches!(
+                    params[0],
+                    FuncArg::Pat(Pat::RestElement(_)) | FuncArg::Expr(Expr::Spread(_))
+                ) =>
+            {
+                Err(AccessorArityError::SetterRest)
+            }
+            _ => Ok(()),
         }
     }
 }
 
+/// The ways a getter or setter can declare the wrong parameters
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccessorArityError {
+    /// A getter with this many parameters instead of none
+    GetterParams(usize),
+    /// A setter with this many parameters instead of one
+    SetterParams(usize),
+    /// A setter whose only parameter is a rest element
+    SetterRest,
+}
+
+impl std::fmt::Display for AccessorArityError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::GetterParams(count) => {
+                write!(f, "getters take no parameters, found {}", count)
+            }
+            Self::SetterParams(count) => {
+                write!(f, "setters take exactly one parameter, found {}", count)
+            }
+            Self::SetterRest => write!(f, "a setter parameter cannot be a rest element"),
+        }
+    }
+}
+
+impl std::error::Error for AccessorArityError {}
+
 /// An object literal or class property identifier
-#[derive(PartialEq, Debug, Clone)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub enum PropKey<T> {
     Lit(Lit<T>),
     Expr(Expr<T>),
     Pat(Pat<T>),
+    /// Class static blocks are the only members without a key
+    None,
 }
 
 impl<T> IntoAllocated for PropKey<T>
@@ -222,20 +988,71 @@ where
     type Allocated = PropKey<String>;
 
     fn into_allocated(self) -> Self::Allocated {
+        self.map_source_with(&mut |source: T| source.to_string())
+    }
+}
+
+impl<'a> AsBorrowed<'a> for PropKey<String> {
+    type Borrowed = PropKey<&'a str>;
+
+    fn as_borrowed(&'a self) -> Self::Borrowed {
+        match self {
+            PropKey::Lit(inner) => PropKey::Lit(inner.as_borrowed()),
+            PropKey::Expr(inner) => PropKey::Expr(inner.as_borrowed()),
+            PropKey::Pat(inner) => PropKey::Pat(inner.as_borrowed()),
+            PropKey::None => PropKey::None,
+        }
+    }
+}
+
+impl<T, U> MapSource<T, U> for PropKey<T> {
+    type Output = PropKey<U>;
+
+    fn map_source_with(self, f: &mut impl FnMut(T) -> U) -> Self::Output {
         match self {
-            PropKey::Lit(inner) => PropKey::Lit(inner.into_allocated()),
-            PropKey::Expr(inner) => PropKey::Expr(inner.into_allocated()),
-            PropKey::Pat(inner) => PropKey::Pat(inner.into_allocated()),
+            PropKey::Lit(inner) => PropKey::Lit(inner.map_source_with(f)),
+            PropKey::Expr(inner) => PropKey::Expr(inner.map_source_with(f)),
+            PropKey::Pat(inner) => PropKey::Pat(inner.map_source_with(f)),
+            PropKey::None => PropKey::None,
         }
     }
 }
 
 /// The value of an object literal or class property
-#[derive(PartialEq, Debug, Clone)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub enum PropValue<T> {
     Expr(Expr<T>),
     Pat(Pat<T>),
+    /// The body of a class `static { }` block
+    /// ```
+    /// use resast::prelude::*;
+    /// use resast::codegen::WriteJs;
+    /// use resast::spanned::expr::{Prop as SpannedProp, StaticBlock};
+    ///
+    /// // static { }
+    /// let spanned: SpannedProp<&str> = SpannedProp::StaticBlock(StaticBlock {
+    ///     keyword_static: Default::default(),
+    ///     open_brace: Default::default(),
+    ///     stmts: vec![],
+    ///     close_brace: Default::default(),
+    /// });
+    /// let mut prop = Prop::from(spanned);
+    /// assert_eq!(prop.value, PropValue::Block(FuncBody(vec![])));
+    /// // static { init(); }
+    /// let init = Expr::call(Expr::ident_from("init"), vec![]);
+    /// prop.value = PropValue::Block(FuncBody(vec![ProgramPart::Stmt(Stmt::Expr(init))]));
+    /// let class = Class {
+    ///     decorators: vec![],
+    ///     id: None,
+    ///     super_class: None,
+    ///     body: ClassBody(vec![prop]),
+    /// };
+    /// // the block is walked but only the property is counted
+    /// assert_eq!(Expr::Class(class.clone()).node_count(), 5);
+    /// assert_eq!(Expr::Class(class).to_js_string(), "class { static { init(); } }");
+    /// ```
+    Block(FuncBody<T>),
     None,
 }
 
@@ -246,16 +1063,38 @@ where
     type Allocated = PropValue<String>;
 
     fn into_allocated(self) -> Self::Allocated {
+        self.map_source_with(&mut |source: T| source.to_string())
+    }
+}
+
+impl<'a> AsBorrowed<'a> for PropValue<String> {
+    type Borrowed = PropValue<&'a str>;
+
+    fn as_borrowed(&'a self) -> Self::Borrowed {
+        match self {
+            PropValue::Expr(inner) => PropValue::Expr(inner.as_borrowed()),
+            PropValue::Pat(inner) => PropValue::Pat(inner.as_borrowed()),
+            PropValue::Block(inner) => PropValue::Block(inner.as_borrowed()),
+            PropValue::None => PropValue::None,
+        }
+    }
+}
+
+impl<T, U> MapSource<T, U> for PropValue<T> {
+    type Output = PropValue<U>;
+
+    fn map_source_with(self, f: &mut impl FnMut(T) -> U) -> Self::Output {
         match self {
-            PropValue::Expr(inner) => PropValue::Expr(inner.into_allocated()),
-            PropValue::Pat(inner) => PropValue::Pat(inner.into_allocated()),
+            PropValue::Expr(inner) => PropValue::Expr(inner.map_source_with(f)),
+            PropValue::Pat(inner) => PropValue::Pat(inner.map_source_with(f)),
+            PropValue::Block(inner) => PropValue::Block(inner.map_source_with(f)),
             PropValue::None => PropValue::None,
         }
     }
 }
 
 /// An operation that takes one argument
-#[derive(PartialEq, Debug, Clone)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct UnaryExpr<T> {
     pub operator: UnaryOp,
@@ -270,16 +1109,36 @@ where
     type Allocated = UnaryExpr<String>;
 
     fn into_allocated(self) -> Self::Allocated {
+        self.map_source_with(&mut |source: T| source.to_string())
+    }
+}
+
+impl<'a> AsBorrowed<'a> for UnaryExpr<String> {
+    type Borrowed = UnaryExpr<&'a str>;
+
+    fn as_borrowed(&'a self) -> Self::Borrowed {
+        UnaryExpr {
+            operator: self.operator,
+            prefix: self.prefix,
+            argument: self.argument.as_borrowed(),
+        }
+    }
+}
+
+impl<T, U> MapSource<T, U> for UnaryExpr<T> {
+    type Output = UnaryExpr<U>;
+
+    fn map_source_with(self, f: &mut impl FnMut(T) -> U) -> Self::Output {
         UnaryExpr {
             operator: self.operator,
             prefix: self.prefix,
-            argument: self.argument.into_allocated(),
+            argument: self.argument.map_source_with(f),
         }
     }
 }
 
 /// Increment or decrementing a value
-#[derive(PartialEq, Debug, Clone)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct UpdateExpr<T> {
     pub operator: UpdateOp,
@@ -294,16 +1153,36 @@ where
     type Allocated = UpdateExpr<String>;
 
     fn into_allocated(self) -> Self::Allocated {
+        self.map_source_with(&mut |source: T| source.to_string())
+    }
+}
+
+impl<'a> AsBorrowed<'a> for UpdateExpr<String> {
+    type Borrowed = UpdateExpr<&'a str>;
+
+    fn as_borrowed(&'a self) -> Self::Borrowed {
         UpdateExpr {
             operator: self.operator,
-            argument: self.argument.into_allocated(),
+            argument: self.argument.as_borrowed(),
+            prefix: self.prefix,
+        }
+    }
+}
+
+impl<T, U> MapSource<T, U> for UpdateExpr<T> {
+    type Output = UpdateExpr<U>;
+
+    fn map_source_with(self, f: &mut impl FnMut(T) -> U) -> Self::Output {
+        UpdateExpr {
+            operator: self.operator,
+            argument: self.argument.map_source_with(f),
             prefix: self.prefix,
         }
     }
 }
 
 /// An operation that requires 2 arguments
-#[derive(PartialEq, Debug, Clone)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct BinaryExpr<T> {
     pub operator: BinaryOp,
@@ -318,16 +1197,79 @@ where
     type Allocated = BinaryExpr<String>;
 
     fn into_allocated(self) -> Self::Allocated {
+        self.map_source_with(&mut |source: T| source.to_string())
+    }
+}
+
+impl<'a> AsBorrowed<'a> for BinaryExpr<String> {
+    type Borrowed = BinaryExpr<&'a str>;
+
+    fn as_borrowed(&'a self) -> Self::Borrowed {
         BinaryExpr {
             operator: self.operator,
-            left: self.left.into_allocated(),
-            right: self.right.into_allocated(),
+            left: self.left.as_borrowed(),
+            right: self.right.as_borrowed(),
         }
     }
 }
 
+impl<T, U> MapSource<T, U> for BinaryExpr<T> {
+    type Output = BinaryExpr<U>;
+
+    fn map_source_with(self, f: &mut impl FnMut(T) -> U) -> Self::Output {
+        BinaryExpr {
+            operator: self.operator,
+            left: self.left.map_source_with(f),
+            right: self.right.map_source_with(f),
+        }
+    }
+}
+
+impl<T> BinaryExpr<T> {
+    /// If this expression would be mistaken for the head of a
+    /// `for...in` loop when written as the init of a `for` loop
+    ///
+    /// That is true for an `in` operator that isn't already wrapped,
+    /// on its own or anywhere in either operand, so a reprinter
+    /// should put parentheses around the init. Expressions other than
+    /// a `BinaryExpr` can be checked with
+    /// [`Expr::needs_for_header_parens`].
+    /// ```
+    /// use resast::prelude::*;
+    /// use resast::codegen::WriteJs;
+    /// use resast::stmt::{ForInStmt, ForStmt, LoopInit, LoopLeft};
+    ///
+    /// let a_in_b = BinaryExpr {
+    ///     operator: BinaryOp::In,
+    ///     left: Box::new(Expr::ident_from("a")),
+    ///     right: Box::new(Expr::ident_from("b")),
+    /// };
+    /// assert!(a_in_b.needs_for_header_parens());
+    /// // for ((a in b);;);
+    /// let for_loop = Stmt::For(ForStmt {
+    ///     init: Some(LoopInit::Expr(Expr::Binary(a_in_b))),
+    ///     test: None,
+    ///     update: None,
+    ///     body: Box::new(Stmt::Empty),
+    /// });
+    /// assert_eq!(for_loop.to_js_string(), "for ((a in b);;) ;");
+    /// // for (a in b);
+    /// let for_in = Stmt::ForIn(ForInStmt {
+    ///     left: LoopLeft::Expr(Expr::ident_from("a")),
+    ///     right: Expr::ident_from("b"),
+    ///     body: Box::new(Stmt::Empty),
+    /// });
+    /// assert_eq!(for_in.to_js_string(), "for (a in b) ;");
+    /// ```
+    pub fn needs_for_header_parens(&self) -> bool {
+        self.operator == BinaryOp::In
+            || self.left.needs_for_header_parens()
+            || self.right.needs_for_header_parens()
+    }
+}
+
 /// An assignment or update + assignment operation
-#[derive(PartialEq, Debug, Clone)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct AssignExpr<T> {
     pub operator: AssignOp,
@@ -342,16 +1284,36 @@ where
     type Allocated = AssignExpr<String>;
 
     fn into_allocated(self) -> Self::Allocated {
+        self.map_source_with(&mut |source: T| source.to_string())
+    }
+}
+
+impl<'a> AsBorrowed<'a> for AssignExpr<String> {
+    type Borrowed = AssignExpr<&'a str>;
+
+    fn as_borrowed(&'a self) -> Self::Borrowed {
+        AssignExpr {
+            operator: self.operator,
+            left: self.left.as_borrowed(),
+            right: self.right.as_borrowed(),
+        }
+    }
+}
+
+impl<T, U> MapSource<T, U> for AssignExpr<T> {
+    type Output = AssignExpr<U>;
+
+    fn map_source_with(self, f: &mut impl FnMut(T) -> U) -> Self::Output {
         AssignExpr {
             operator: self.operator,
-            left: self.left.into_allocated(),
-            right: self.right.into_allocated(),
+            left: self.left.map_source_with(f),
+            right: self.right.map_source_with(f),
         }
     }
 }
 
 /// The value being assigned to
-#[derive(PartialEq, Debug, Clone)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub enum AssignLeft<T> {
     Pat(Pat<T>),
@@ -365,9 +1327,98 @@ where
     type Allocated = AssignLeft<String>;
 
     fn into_allocated(self) -> Self::Allocated {
+        self.map_source_with(&mut |source: T| source.to_string())
+    }
+}
+
+impl<'a> AsBorrowed<'a> for AssignLeft<String> {
+    type Borrowed = AssignLeft<&'a str>;
+
+    fn as_borrowed(&'a self) -> Self::Borrowed {
+        match self {
+            AssignLeft::Pat(inner) => AssignLeft::Pat(inner.as_borrowed()),
+            AssignLeft::Expr(inner) => AssignLeft::Expr(inner.as_borrowed()),
+        }
+    }
+}
+
+impl<T, U> MapSource<T, U> for AssignLeft<T> {
+    type Output = AssignLeft<U>;
+
+    fn map_source_with(self, f: &mut impl FnMut(T) -> U) -> Self::Output {
         match self {
-            AssignLeft::Pat(inner) => AssignLeft::Pat(inner.into_allocated()),
-            AssignLeft::Expr(inner) => AssignLeft::Expr(inner.into_allocated()),
+            AssignLeft::Pat(inner) => AssignLeft::Pat(inner.map_source_with(f)),
+            AssignLeft::Expr(inner) => AssignLeft::Expr(inner.map_source_with(f)),
+        }
+    }
+}
+
+impl<T> AssignLeft<T> {
+    /// The pattern if this is a destructuring assignment
+    /// ```
+    /// use resast::pat::ArrayPatPart;
+    /// use resast::prelude::*;
+    /// use std::convert::TryFrom;
+    ///
+    /// // [a] = b
+    /// let left = AssignLeft::from(Pat::Array(vec![Some(ArrayPatPart::Pat(Pat::ident_from("a")))]));
+    /// assert!(left.as_pat().is_some());
+    /// assert!(left.as_expr().is_none());
+    /// let pat = Pat::try_from(left.clone()).unwrap();
+    /// assert_eq!(pat.bound_names(), [&Ident::from("a")]);
+    /// assert_eq!(Expr::try_from(left.clone()), Err(left));
+    ///
+    /// // x = b
+    /// let left = AssignLeft::from(Expr::ident_from("x"));
+    /// assert_eq!(left.as_expr(), Some(&Expr::ident_from("x")));
+    /// assert!(left.as_pat().is_none());
+    /// assert_eq!(Expr::try_from(left), Ok(Expr::ident_from("x")));
+    /// ```
+    pub fn as_pat(&self) -> Option<&Pat<T>> {
+        match self {
+            Self::Pat(inner) => Some(inner),
+            Self::Expr(_) => None,
+        }
+    }
+    /// The expression if this is a simple assignment
+    pub fn as_expr(&self) -> Option<&Expr<T>> {
+        match self {
+            Self::Expr(inner) => Some(inner),
+            Self::Pat(_) => None,
+        }
+    }
+}
+
+impl<T> From<Pat<T>> for AssignLeft<T> {
+    fn from(pat: Pat<T>) -> Self {
+        Self::Pat(pat)
+    }
+}
+
+impl<T> From<Expr<T>> for AssignLeft<T> {
+    fn from(expr: Expr<T>) -> Self {
+        Self::Expr(Box::new(expr))
+    }
+}
+
+/// Hands the `AssignLeft` back if it holds an expression
+impl<T> TryFrom<AssignLeft<T>> for Pat<T> {
+    type Error = AssignLeft<T>;
+    fn try_from(left: AssignLeft<T>) -> Result<Self, Self::Error> {
+        match left {
+            AssignLeft::Pat(inner) => Ok(inner),
+            left => Err(left),
+        }
+    }
+}
+
+/// Hands the `AssignLeft` back if it holds a pattern
+impl<T> TryFrom<AssignLeft<T>> for Expr<T> {
+    type Error = AssignLeft<T>;
+    fn try_from(left: AssignLeft<T>) -> Result<Self, Self::Error> {
+        match left {
+            AssignLeft::Expr(inner) => Ok(*inner),
+            left => Err(left),
         }
     }
 }
@@ -377,7 +1428,7 @@ where
 /// true && true
 /// false || true
 /// ```
-#[derive(PartialEq, Debug, Clone)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct LogicalExpr<T> {
     pub operator: LogicalOp,
@@ -392,10 +1443,30 @@ where
     type Allocated = LogicalExpr<String>;
 
     fn into_allocated(self) -> Self::Allocated {
+        self.map_source_with(&mut |source: T| source.to_string())
+    }
+}
+
+impl<'a> AsBorrowed<'a> for LogicalExpr<String> {
+    type Borrowed = LogicalExpr<&'a str>;
+
+    fn as_borrowed(&'a self) -> Self::Borrowed {
+        LogicalExpr {
+            operator: self.operator,
+            left: self.left.as_borrowed(),
+            right: self.right.as_borrowed(),
+        }
+    }
+}
+
+impl<T, U> MapSource<T, U> for LogicalExpr<T> {
+    type Output = LogicalExpr<U>;
+
+    fn map_source_with(self, f: &mut impl FnMut(T) -> U) -> Self::Output {
         LogicalExpr {
             operator: self.operator,
-            left: self.left.into_allocated(),
-            right: self.right.into_allocated(),
+            left: self.left.map_source_with(f),
+            right: self.right.map_source_with(f),
         }
     }
 }
@@ -405,7 +1476,7 @@ where
 /// b['thing'];
 /// c.stuff;
 /// ```
-#[derive(PartialEq, Debug, Clone)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct MemberExpr<T> {
     pub object: Box<Expr<T>>,
@@ -420,19 +1491,70 @@ where
     type Allocated = MemberExpr<String>;
 
     fn into_allocated(self) -> Self::Allocated {
+        self.map_source_with(&mut |source: T| source.to_string())
+    }
+}
+
+impl<'a> AsBorrowed<'a> for MemberExpr<String> {
+    type Borrowed = MemberExpr<&'a str>;
+
+    fn as_borrowed(&'a self) -> Self::Borrowed {
         MemberExpr {
-            object: self.object.into_allocated(),
-            property: self.property.into_allocated(),
+            object: self.object.as_borrowed(),
+            property: self.property.as_borrowed(),
             indexer: self.indexer,
         }
     }
 }
 
+impl<T, U> MapSource<T, U> for MemberExpr<T> {
+    type Output = MemberExpr<U>;
+
+    fn map_source_with(self, f: &mut impl FnMut(T) -> U) -> Self::Output {
+        MemberExpr {
+            object: self.object.map_source_with(f),
+            property: self.property.map_source_with(f),
+            indexer: self.indexer,
+        }
+    }
+}
+
+impl<T> MemberExpr<T> {
+    /// ```
+    /// use resast::prelude::*;
+    /// use resast::MemberIndexer;
+    ///
+    /// let member = |indexer| MemberExpr {
+    ///     object: Box::new(Expr::ident_from("a")),
+    ///     property: Box::new(Expr::ident_from("b")),
+    ///     indexer,
+    /// };
+    /// // a.b
+    /// let period = member(MemberIndexer::Period);
+    /// assert!(!period.computed() && !period.optional());
+    /// // a[b]
+    /// let computed = member(MemberIndexer::Computed);
+    /// assert!(computed.computed() && !computed.optional());
+    /// // a?.b
+    /// let optional = member(MemberIndexer::Optional);
+    /// assert!(!optional.computed() && optional.optional());
+    /// // a?.[b]
+    /// let both = member(MemberIndexer::OptionalComputed);
+    /// assert!(both.computed() && both.optional());
+    /// ```
+    pub fn computed(&self) -> bool {
+        self.indexer.is_computed()
+    }
+    pub fn optional(&self) -> bool {
+        self.indexer.is_optional()
+    }
+}
+
 /// A ternery expression
 /// ```js
 /// var a = true ? 'stuff' : 'things';
 /// ```
-#[derive(PartialEq, Debug, Clone)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct ConditionalExpr<T> {
     pub test: Box<Expr<T>>,
@@ -447,10 +1569,30 @@ where
     type Allocated = ConditionalExpr<String>;
 
     fn into_allocated(self) -> Self::Allocated {
+        self.map_source_with(&mut |source: T| source.to_string())
+    }
+}
+
+impl<'a> AsBorrowed<'a> for ConditionalExpr<String> {
+    type Borrowed = ConditionalExpr<&'a str>;
+
+    fn as_borrowed(&'a self) -> Self::Borrowed {
         ConditionalExpr {
-            test: self.test.into_allocated(),
-            alternate: self.alternate.into_allocated(),
-            consequent: self.consequent.into_allocated(),
+            test: self.test.as_borrowed(),
+            alternate: self.alternate.as_borrowed(),
+            consequent: self.consequent.as_borrowed(),
+        }
+    }
+}
+
+impl<T, U> MapSource<T, U> for ConditionalExpr<T> {
+    type Output = ConditionalExpr<U>;
+
+    fn map_source_with(self, f: &mut impl FnMut(T) -> U) -> Self::Output {
+        ConditionalExpr {
+            test: self.test.map_source_with(f),
+            alternate: self.alternate.map_source_with(f),
+            consequent: self.consequent.map_source_with(f),
         }
     }
 }
@@ -459,7 +1601,7 @@ where
 /// ```js
 /// Math.random()
 /// ```
-#[derive(PartialEq, Debug, Clone)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct CallExpr<T> {
     pub optional: bool,
@@ -474,49 +1616,277 @@ where
     type Allocated = CallExpr<String>;
 
     fn into_allocated(self) -> Self::Allocated {
+        self.map_source_with(&mut |source: T| source.to_string())
+    }
+}
+
+impl<'a> AsBorrowed<'a> for CallExpr<String> {
+    type Borrowed = CallExpr<&'a str>;
+
+    fn as_borrowed(&'a self) -> Self::Borrowed {
+        CallExpr {
+            optional: self.optional,
+            callee: self.callee.as_borrowed(),
+            arguments: self.arguments.iter().map(AsBorrowed::as_borrowed).collect(),
+        }
+    }
+}
+
+impl<T, U> MapSource<T, U> for CallExpr<T> {
+    type Output = CallExpr<U>;
+
+    fn map_source_with(self, f: &mut impl FnMut(T) -> U) -> Self::Output {
         CallExpr {
             optional: self.optional,
-            callee: self.callee.into_allocated(),
+            callee: self.callee.map_source_with(f),
             arguments: self
                 .arguments
                 .into_iter()
-                .map(IntoAllocated::into_allocated)
+                .map(|e| e.map_source_with(f))
                 .collect(),
         }
     }
 }
 
-/// Calling a constructor
-/// ```js
-/// new Uint8Array(32);
-/// ```
-#[derive(PartialEq, Debug, Clone)]
-#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
-pub struct NewExpr<T> {
-    pub callee: Box<Expr<T>>,
-    pub arguments: Vec<Expr<T>>,
+impl<T> CallExpr<T> {
+    /// The expression at the far left of the callee, following
+    /// member objects and the callees of nested calls
+    /// ```
+    /// use resast::prelude::*;
+    ///
+    /// // a.b.c()
+    /// let callee = Expr::member(
+    ///     Expr::member(Expr::ident_from("a"), Expr::ident_from("b"), false),
+    ///     Expr::ident_from("c"),
+    ///     false,
+    /// );
+    /// let call = CallExpr { optional: false, callee: Box::new(callee), arguments: vec![] };
+    /// assert_eq!(call.root_callee(), &Expr::ident_from("a"));
+    /// let path: Vec<_> = call.member_path();
+    /// assert_eq!(
+    ///     path,
+    ///     [&Expr::ident_from("a"), &Expr::ident_from("b"), &Expr::ident_from("c")]
+    /// );
+    ///
+    /// // f()()
+    /// let inner = Expr::call(Expr::ident_from("f"), vec![]);
+    /// let call = CallExpr { optional: false, callee: Box::new(inner), arguments: vec![] };
+    /// assert_eq!(call.root_callee(), &Expr::ident_from("f"));
+    /// assert_eq!(call.member_path(), [&Expr::ident_from("f")]);
+    ///
+    /// // (a.b).c()
+    /// let a_b = Expr::member(Expr::ident_from("a"), Expr::ident_from("b"), false);
+    /// let callee = Expr::member(Expr::Paren(Box::new(a_b)), Expr::ident_from("c"), false);
+    /// let call = CallExpr { optional: false, callee: Box::new(callee), arguments: vec![] };
+    /// assert_eq!(call.root_callee(), &Expr::ident_from("a"));
+    /// assert_eq!(call.member_path().len(), 3);
+    /// ```
+    pub fn root_callee(&self) -> &Expr<T> {
+        let mut expr = &*self.callee;
+        loop {
+            expr = match expr {
+                Expr::Member(member) => &member.object,
+                Expr::Call(call) => &call.callee,
+                Expr::OptionalChain(inner) | Expr::Paren(inner) => inner,
+                _ => return expr,
+            };
+        }
+    }
+    /// The root callee followed by every member property on the
+    /// way to the called function, `console.log()` is
+    /// `[console, log]`
+    pub fn member_path(&self) -> Vec<&Expr<T>> {
+        let mut path = Vec::new();
+        let mut expr = &*self.callee;
+        loop {
+            expr = match expr {
+                Expr::Member(member) => {
+                    path.push(&*member.property);
+                    &member.object
+                }
+                Expr::Call(call) => &call.callee,
+                Expr::OptionalChain(inner) | Expr::Paren(inner) => inner,
+                _ => break,
+            };
+        }
+        path.push(expr);
+        path.reverse();
+        path
+    }
+}
+
+/// Calling a constructor
+/// ```js
+/// new Uint8Array(32);
+/// ```
+///
+/// A callee that contains a call, including `import()`, is
+/// wrapped when printed
+/// ```
+/// use resast::prelude::*;
+/// use resast::codegen::WriteJs;
+///
+/// let new = Expr::New(NewExpr {
+///     callee: Box::new(Expr::Import(ImportExpr {
+///         source: Box::new(Expr::ident_from("x")),
+///         options: None,
+///     })),
+///     arguments: vec![],
+/// });
+/// assert_eq!(new.to_js_string(), "new (import(x))()");
+/// ```
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct NewExpr<T> {
+    pub callee: Box<Expr<T>>,
+    pub arguments: Vec<Expr<T>>,
+}
+
+impl<T> IntoAllocated for NewExpr<T>
+where
+    T: ToString,
+{
+    type Allocated = NewExpr<String>;
+
+    fn into_allocated(self) -> Self::Allocated {
+        self.map_source_with(&mut |source: T| source.to_string())
+    }
+}
+
+impl<'a> AsBorrowed<'a> for NewExpr<String> {
+    type Borrowed = NewExpr<&'a str>;
+
+    fn as_borrowed(&'a self) -> Self::Borrowed {
+        NewExpr {
+            callee: self.callee.as_borrowed(),
+            arguments: self.arguments.iter().map(|a| a.as_borrowed()).collect(),
+        }
+    }
+}
+
+impl<T, U> MapSource<T, U> for NewExpr<T> {
+    type Output = NewExpr<U>;
+
+    fn map_source_with(self, f: &mut impl FnMut(T) -> U) -> Self::Output {
+        NewExpr {
+            callee: self.callee.map_source_with(f),
+            arguments: self
+                .arguments
+                .into_iter()
+                .map(|a| a.map_source_with(f))
+                .collect(),
+        }
+    }
+}
+
+/// A collection of `Exprs` separated by commas
+/// ```
+/// use resast::prelude::*;
+///
+/// // (a, b, c)
+/// let mut seq = SequenceExpr::from(vec![Expr::ident_from("a"), Expr::ident_from("b")]);
+/// seq.push(Expr::ident_from("c"));
+/// assert_eq!(seq.len(), 3);
+/// assert!(!seq.is_empty());
+/// let names: Vec<_> = seq
+///     .iter()
+///     .filter_map(|e| match e {
+///         Expr::Ident(ident) => Some(ident.name),
+///         _ => None,
+///     })
+///     .collect();
+/// assert_eq!(names, ["a", "b", "c"]);
+/// ```
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct SequenceExpr<T>(pub Vec<Expr<T>>);
+
+impl<T> IntoAllocated for SequenceExpr<T>
+where
+    T: ToString,
+{
+    type Allocated = SequenceExpr<String>;
+
+    fn into_allocated(self) -> Self::Allocated {
+        self.map_source_with(&mut |source: T| source.to_string())
+    }
+}
+
+impl<'a> AsBorrowed<'a> for SequenceExpr<String> {
+    type Borrowed = SequenceExpr<&'a str>;
+
+    fn as_borrowed(&'a self) -> Self::Borrowed {
+        self.iter().map(AsBorrowed::as_borrowed).collect()
+    }
+}
+
+impl<T, U> MapSource<T, U> for SequenceExpr<T> {
+    type Output = SequenceExpr<U>;
+
+    fn map_source_with(self, f: &mut impl FnMut(T) -> U) -> Self::Output {
+        self.into_iter().map(|e| e.map_source_with(f)).collect()
+    }
+}
+
+impl<T> SequenceExpr<T> {
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+    pub fn iter(&self) -> std::slice::Iter<'_, Expr<T>> {
+        self.0.iter()
+    }
+    pub fn push(&mut self, expr: Expr<T>) {
+        self.0.push(expr)
+    }
+}
+
+impl<T> Default for SequenceExpr<T> {
+    fn default() -> Self {
+        Self(Vec::new())
+    }
+}
+
+impl<T> From<Vec<Expr<T>>> for SequenceExpr<T> {
+    fn from(exprs: Vec<Expr<T>>) -> Self {
+        Self(exprs)
+    }
 }
 
-impl<T> IntoAllocated for NewExpr<T>
-where
-    T: ToString,
-{
-    type Allocated = NewExpr<String>;
+impl<T> FromIterator<Expr<T>> for SequenceExpr<T> {
+    fn from_iter<I: IntoIterator<Item = Expr<T>>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
 
-    fn into_allocated(self) -> Self::Allocated {
-        NewExpr {
-            callee: self.callee.into_allocated(),
-            arguments: self
-                .arguments
-                .into_iter()
-                .map(|a| a.into_allocated())
-                .collect(),
-        }
+impl<T> IntoIterator for SequenceExpr<T> {
+    type Item = Expr<T>;
+    type IntoIter = std::vec::IntoIter<Expr<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
     }
 }
 
-/// A collection of `Exprs` separated by commas
-pub type SequenceExpr<T> = Vec<Expr<T>>;
+impl<'a, T> IntoIterator for &'a SequenceExpr<T> {
+    type Item = &'a Expr<T>;
+    type IntoIter = std::slice::Iter<'a, Expr<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut SequenceExpr<T> {
+    type Item = &'a mut Expr<T>;
+    type IntoIter = std::slice::IterMut<'a, Expr<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter_mut()
+    }
+}
 
 /// An arrow function
 /// ```js
@@ -525,7 +1895,7 @@ pub type SequenceExpr<T> = Vec<Expr<T>>;
 ///     return x + 1;
 /// }
 /// ```
-#[derive(PartialEq, Debug, Clone)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct ArrowFuncExpr<T> {
     pub id: Option<Ident<T>>,
@@ -543,14 +1913,37 @@ where
     type Allocated = ArrowFuncExpr<String>;
 
     fn into_allocated(self) -> Self::Allocated {
+        self.map_source_with(&mut |source: T| source.to_string())
+    }
+}
+
+impl<'a> AsBorrowed<'a> for ArrowFuncExpr<String> {
+    type Borrowed = ArrowFuncExpr<&'a str>;
+
+    fn as_borrowed(&'a self) -> Self::Borrowed {
+        ArrowFuncExpr {
+            id: self.id.as_borrowed(),
+            params: self.params.iter().map(|p| p.as_borrowed()).collect(),
+            body: self.body.as_borrowed(),
+            expression: self.expression,
+            generator: self.generator,
+            is_async: self.is_async,
+        }
+    }
+}
+
+impl<T, U> MapSource<T, U> for ArrowFuncExpr<T> {
+    type Output = ArrowFuncExpr<U>;
+
+    fn map_source_with(self, f: &mut impl FnMut(T) -> U) -> Self::Output {
         ArrowFuncExpr {
-            id: self.id.map(|i| i.into_allocated()),
+            id: self.id.map_source_with(f),
             params: self
                 .params
                 .into_iter()
-                .map(|p| p.into_allocated())
+                .map(|p| p.map_source_with(f))
                 .collect(),
-            body: self.body.into_allocated(),
+            body: self.body.map_source_with(f),
             expression: self.expression,
             generator: self.generator,
             is_async: self.is_async,
@@ -558,8 +1951,35 @@ where
     }
 }
 
+impl<T> ArrowFuncExpr<T> {
+    /// Always `true`, an arrow function uses the `this` of
+    /// the scope it was defined in
+    /// ```
+    /// use resast::prelude::*;
+    ///
+    /// // () => this
+    /// let arrow = ArrowFuncExpr {
+    ///     id: None,
+    ///     params: vec![],
+    ///     body: ArrowFuncBody::Expr(Box::new(Expr::<&str>::This)),
+    ///     expression: true,
+    ///     generator: false,
+    ///     is_async: false,
+    /// };
+    /// assert!(arrow.captures_this());
+    /// // function() { return this; }
+    /// let func = Func::builder()
+    ///     .body(vec![ProgramPart::Stmt(Stmt::Return(Some(Expr::<&str>::This)))])
+    ///     .build();
+    /// assert!(!func.captures_this());
+    /// ```
+    pub fn captures_this(&self) -> bool {
+        true
+    }
+}
+
 /// The body portion of an arrow function can be either an expression or a block of statements
-#[derive(PartialEq, Debug, Clone)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub enum ArrowFuncBody<T> {
     FuncBody(FuncBody<T>),
@@ -573,9 +1993,28 @@ where
     type Allocated = ArrowFuncBody<String>;
 
     fn into_allocated(self) -> Self::Allocated {
+        self.map_source_with(&mut |source: T| source.to_string())
+    }
+}
+
+impl<'a> AsBorrowed<'a> for ArrowFuncBody<String> {
+    type Borrowed = ArrowFuncBody<&'a str>;
+
+    fn as_borrowed(&'a self) -> Self::Borrowed {
+        match self {
+            ArrowFuncBody::FuncBody(inner) => ArrowFuncBody::FuncBody(inner.as_borrowed()),
+            ArrowFuncBody::Expr(inner) => ArrowFuncBody::Expr(inner.as_borrowed()),
+        }
+    }
+}
+
+impl<T, U> MapSource<T, U> for ArrowFuncBody<T> {
+    type Output = ArrowFuncBody<U>;
+
+    fn map_source_with(self, f: &mut impl FnMut(T) -> U) -> Self::Output {
         match self {
-            ArrowFuncBody::FuncBody(inner) => ArrowFuncBody::FuncBody(inner.into_allocated()),
-            ArrowFuncBody::Expr(inner) => ArrowFuncBody::Expr(inner.into_allocated()),
+            ArrowFuncBody::FuncBody(inner) => ArrowFuncBody::FuncBody(inner.map_source_with(f)),
+            ArrowFuncBody::Expr(inner) => ArrowFuncBody::Expr(inner.map_source_with(f)),
         }
     }
 }
@@ -588,7 +2027,7 @@ where
 ///     }
 /// }
 /// ```
-#[derive(PartialEq, Debug, Clone)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct YieldExpr<T> {
     pub argument: Option<Box<Expr<T>>>,
@@ -602,16 +2041,35 @@ where
     type Allocated = YieldExpr<String>;
 
     fn into_allocated(self) -> Self::Allocated {
+        self.map_source_with(&mut |source: T| source.to_string())
+    }
+}
+
+impl<'a> AsBorrowed<'a> for YieldExpr<String> {
+    type Borrowed = YieldExpr<&'a str>;
+
+    fn as_borrowed(&'a self) -> Self::Borrowed {
+        YieldExpr {
+            delegate: self.delegate,
+            argument: self.argument.as_borrowed(),
+        }
+    }
+}
+
+impl<T, U> MapSource<T, U> for YieldExpr<T> {
+    type Output = YieldExpr<U>;
+
+    fn map_source_with(self, f: &mut impl FnMut(T) -> U) -> Self::Output {
         YieldExpr {
             delegate: self.delegate,
-            argument: self.argument.map(IntoAllocated::into_allocated),
+            argument: self.argument.map_source_with(f),
         }
     }
 }
 
 /// A Template literal preceded by a function identifier
 /// see [MDN](https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Template_literals#Tagged_templates) for more details
-#[derive(PartialEq, Debug, Clone)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct TaggedTemplateExpr<T> {
     pub tag: Box<Expr<T>>,
@@ -625,9 +2083,28 @@ where
     type Allocated = TaggedTemplateExpr<String>;
 
     fn into_allocated(self) -> Self::Allocated {
+        self.map_source_with(&mut |source: T| source.to_string())
+    }
+}
+
+impl<'a> AsBorrowed<'a> for TaggedTemplateExpr<String> {
+    type Borrowed = TaggedTemplateExpr<&'a str>;
+
+    fn as_borrowed(&'a self) -> Self::Borrowed {
+        TaggedTemplateExpr {
+            tag: self.tag.as_borrowed(),
+            quasi: self.quasi.as_borrowed(),
+        }
+    }
+}
+
+impl<T, U> MapSource<T, U> for TaggedTemplateExpr<T> {
+    type Output = TaggedTemplateExpr<U>;
+
+    fn map_source_with(self, f: &mut impl FnMut(T) -> U) -> Self::Output {
         TaggedTemplateExpr {
-            tag: self.tag.into_allocated(),
-            quasi: self.quasi.into_allocated(),
+            tag: self.tag.map_source_with(f),
+            quasi: self.quasi.map_source_with(f),
         }
     }
 }
@@ -636,7 +2113,7 @@ where
 /// ```js
 /// `I own ${0} birds`;
 /// ```
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct TemplateLit<T> {
     pub quasis: Vec<TemplateElement<T>>,
@@ -650,22 +2127,41 @@ where
     type Allocated = TemplateLit<String>;
 
     fn into_allocated(self) -> Self::Allocated {
+        self.map_source_with(&mut |source: T| source.to_string())
+    }
+}
+
+impl<'a> AsBorrowed<'a> for TemplateLit<String> {
+    type Borrowed = TemplateLit<&'a str>;
+
+    fn as_borrowed(&'a self) -> Self::Borrowed {
+        TemplateLit {
+            quasis: self.quasis.iter().map(|e| e.as_borrowed()).collect(),
+            expressions: self.expressions.iter().map(|e| e.as_borrowed()).collect(),
+        }
+    }
+}
+
+impl<T, U> MapSource<T, U> for TemplateLit<T> {
+    type Output = TemplateLit<U>;
+
+    fn map_source_with(self, f: &mut impl FnMut(T) -> U) -> Self::Output {
         TemplateLit {
             quasis: self
                 .quasis
                 .into_iter()
-                .map(|e| e.into_allocated())
+                .map(|e| e.map_source_with(f))
                 .collect(),
             expressions: self
                 .expressions
                 .into_iter()
-                .map(|e| e.into_allocated())
+                .map(|e| e.map_source_with(f))
                 .collect(),
         }
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub enum QuasiQuote {
     /// `
@@ -677,7 +2173,7 @@ pub enum QuasiQuote {
 }
 
 /// The text part of a `TemplateLiteral`
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct TemplateElement<T> {
     pub open_quote: QuasiQuote,
@@ -693,9 +2189,29 @@ where
     type Allocated = TemplateElement<String>;
 
     fn into_allocated(self) -> Self::Allocated {
+        self.map_source_with(&mut |source: T| source.to_string())
+    }
+}
+
+impl<'a> AsBorrowed<'a> for TemplateElement<String> {
+    type Borrowed = TemplateElement<&'a str>;
+
+    fn as_borrowed(&'a self) -> Self::Borrowed {
+        TemplateElement {
+            open_quote: self.open_quote.clone(),
+            content: self.content.as_str(),
+            close_quote: self.close_quote.clone(),
+        }
+    }
+}
+
+impl<T, U> MapSource<T, U> for TemplateElement<T> {
+    type Output = TemplateElement<U>;
+
+    fn map_source_with(self, f: &mut impl FnMut(T) -> U) -> Self::Output {
         TemplateElement {
             open_quote: self.open_quote,
-            content: self.content.to_string(),
+            content: f(self.content),
             close_quote: self.close_quote,
         }
     }
@@ -710,7 +2226,122 @@ impl<T> TemplateElement<T> {
     }
 }
 
-/// pretty much just `new.target`
+/// Returned when the text of a template element has an escape
+/// sequence that doesn't have a cooked value
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum UnescapeError {
+    /// `\x` not followed by two hex digits
+    InvalidHexEscape,
+    /// `\u` not followed by four hex digits or a braced code point
+    /// no larger than `10FFFF`
+    InvalidUnicodeEscape,
+    /// `\1` through `\9`, or `\0` followed by a digit
+    OctalEscape,
+    /// Half of a surrogate pair without the other half
+    LoneSurrogate,
+    /// A `\` at the very end of the text
+    UnterminatedEscape,
+}
+
+impl std::fmt::Display for UnescapeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let msg = match self {
+            Self::InvalidHexEscape => "invalid hexadecimal escape sequence",
+            Self::InvalidUnicodeEscape => "invalid unicode escape sequence",
+            Self::OctalEscape => "octal escape sequences are not allowed in templates",
+            Self::LoneSurrogate => "unpaired surrogate in unicode escape sequence",
+            Self::UnterminatedEscape => "unterminated escape sequence",
+        };
+        f.write_str(msg)
+    }
+}
+
+impl std::error::Error for UnescapeError {}
+
+/// The cooked value of the raw text of a template element, line
+/// endings are normalized to `\n` and escape sequences are decoded
+pub(crate) fn cook_template(raw: &str) -> Result<String, UnescapeError> {
+    // built as UTF-16 so escaped surrogate pairs can be joined back up
+    let mut cooked: Vec<u16> = Vec::with_capacity(raw.len());
+    let mut buf = [0u16; 2];
+    let mut push = |cooked: &mut Vec<u16>, c: char| {
+        cooked.extend_from_slice(c.encode_utf16(&mut buf));
+    };
+    let mut chars = raw.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\r' => {
+                chars.next_if_eq(&'\n');
+                push(&mut cooked, '\n');
+            }
+            '\\' => {
+                let escaped = chars.next().ok_or(UnescapeError::UnterminatedEscape)?;
+                match escaped {
+                    'b' => push(&mut cooked, '\u{8}'),
+                    't' => push(&mut cooked, '\t'),
+                    'n' => push(&mut cooked, '\n'),
+                    'v' => push(&mut cooked, '\u{b}'),
+                    'f' => push(&mut cooked, '\u{c}'),
+                    'r' => push(&mut cooked, '\r'),
+                    '0' if !matches!(chars.peek(), Some(d) if d.is_ascii_digit()) => {
+                        push(&mut cooked, '\0')
+                    }
+                    '0'..='9' => return Err(UnescapeError::OctalEscape),
+                    'x' => {
+                        let unit =
+                            hex_digits(&mut chars, 2).ok_or(UnescapeError::InvalidHexEscape)?;
+                        cooked.push(unit as u16);
+                    }
+                    'u' if chars.next_if_eq(&'{').is_some() => {
+                        let mut value = 0u32;
+                        let mut digits = 0;
+                        loop {
+                            match chars.next() {
+                                Some('}') if digits > 0 => break,
+                                Some(d) if d.is_ascii_hexdigit() => {
+                                    value = value * 16 + d.to_digit(16).unwrap_or_default();
+                                    digits += 1;
+                                    if value > 0x10FFFF {
+                                        return Err(UnescapeError::InvalidUnicodeEscape);
+                                    }
+                                }
+                                _ => return Err(UnescapeError::InvalidUnicodeEscape),
+                            }
+                        }
+                        match char::from_u32(value) {
+                            Some(c) => push(&mut cooked, c),
+                            // a braced surrogate is a single code unit
+                            None => cooked.push(value as u16),
+                        }
+                    }
+                    'u' => {
+                        let unit =
+                            hex_digits(&mut chars, 4).ok_or(UnescapeError::InvalidUnicodeEscape)?;
+                        cooked.push(unit as u16);
+                    }
+                    // line continuations don't add anything
+                    '\r' => {
+                        chars.next_if_eq(&'\n');
+                    }
+                    '\n' | '\u{2028}' | '\u{2029}' => {}
+                    other => push(&mut cooked, other),
+                }
+            }
+            other => push(&mut cooked, other),
+        }
+    }
+    String::from_utf16(&cooked).map_err(|_| UnescapeError::LoneSurrogate)
+}
+
+fn hex_digits(chars: &mut impl Iterator<Item = char>, count: usize) -> Option<u32> {
+    let mut value = 0;
+    for _ in 0..count {
+        value = value * 16 + chars.next()?.to_digit(16)?;
+    }
+    Some(value)
+}
+
+/// A meta property, either `new.target` or `import.meta`
 /// ```js
 /// function Thing(one, two) {
 ///     if (!new.target) {
@@ -720,7 +2351,7 @@ impl<T> TemplateElement<T> {
 ///     this.two = two;
 /// }
 /// ```
-#[derive(PartialEq, Debug, Clone)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct MetaProp<T> {
     pub meta: Ident<T>,
@@ -734,15 +2365,123 @@ where
     type Allocated = MetaProp<String>;
 
     fn into_allocated(self) -> Self::Allocated {
+        self.map_source_with(&mut |source: T| source.to_string())
+    }
+}
+
+impl<'a> AsBorrowed<'a> for MetaProp<String> {
+    type Borrowed = MetaProp<&'a str>;
+
+    fn as_borrowed(&'a self) -> Self::Borrowed {
+        MetaProp {
+            meta: self.meta.as_borrowed(),
+            property: self.property.as_borrowed(),
+        }
+    }
+}
+
+impl<T, U> MapSource<T, U> for MetaProp<T> {
+    type Output = MetaProp<U>;
+
+    fn map_source_with(self, f: &mut impl FnMut(T) -> U) -> Self::Output {
         MetaProp {
-            meta: self.meta.into_allocated(),
-            property: self.property.into_allocated(),
+            meta: self.meta.map_source_with(f),
+            property: self.property.map_source_with(f),
+        }
+    }
+}
+
+impl<T> MetaProp<T>
+where
+    T: From<&'static str>,
+{
+    /// `new.target`
+    /// ```
+    /// use resast::prelude::*;
+    /// use resast::codegen::WriteJs;
+    ///
+    /// let target = MetaProp::<&str>::new_target();
+    /// assert_eq!(target.meta, Ident::from("new"));
+    /// assert_eq!(target.property, Ident::from("target"));
+    /// assert_eq!(Expr::MetaProp(target).to_js_string(), "new.target");
+    /// ```
+    pub fn new_target() -> Self {
+        Self {
+            meta: Ident { name: "new".into() },
+            property: Ident {
+                name: "target".into(),
+            },
+        }
+    }
+    /// `import.meta`
+    /// ```
+    /// use resast::prelude::*;
+    /// use resast::codegen::WriteJs;
+    ///
+    /// let meta = MetaProp::<&str>::import_meta();
+    /// assert_eq!(meta.meta, Ident::from("import"));
+    /// assert_eq!(meta.property, Ident::from("meta"));
+    /// assert_eq!(Expr::MetaProp(meta).to_js_string(), "import.meta");
+    /// ```
+    pub fn import_meta() -> Self {
+        Self {
+            meta: Ident {
+                name: "import".into(),
+            },
+            property: Ident {
+                name: "meta".into(),
+            },
+        }
+    }
+}
+
+/// A dynamic import, the optional second argument
+/// is the options object
+/// ```js
+/// import('./data.json', { with: { type: 'json' } });
+/// ```
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct ImportExpr<T> {
+    pub source: Box<Expr<T>>,
+    pub options: Option<Box<Expr<T>>>,
+}
+
+impl<T> IntoAllocated for ImportExpr<T>
+where
+    T: ToString,
+{
+    type Allocated = ImportExpr<String>;
+
+    fn into_allocated(self) -> Self::Allocated {
+        self.map_source_with(&mut |source: T| source.to_string())
+    }
+}
+
+impl<'a> AsBorrowed<'a> for ImportExpr<String> {
+    type Borrowed = ImportExpr<&'a str>;
+
+    fn as_borrowed(&'a self) -> Self::Borrowed {
+        ImportExpr {
+            source: self.source.as_borrowed(),
+            options: self.options.as_borrowed(),
+        }
+    }
+}
+
+impl<T, U> MapSource<T, U> for ImportExpr<T> {
+    type Output = ImportExpr<U>;
+
+    fn map_source_with(self, f: &mut impl FnMut(T) -> U) -> Self::Output {
+        ImportExpr {
+            source: self.source.map_source_with(f),
+            options: self.options.map_source_with(f),
         }
     }
 }
 
 /// A literal value
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub enum Lit<T> {
     /// `null`
@@ -759,6 +2498,9 @@ pub enum Lit<T> {
     /// `0o7`
     /// `0b1`
     Number(T),
+    /// `1n`
+    /// `0x1fn`
+    BigInt(T),
     /// `true`
     /// `false`
     Boolean(bool),
@@ -777,13 +2519,38 @@ where
     type Allocated = Lit<String>;
 
     fn into_allocated(self) -> Self::Allocated {
+        self.map_source_with(&mut |source: T| source.to_string())
+    }
+}
+
+impl<'a> AsBorrowed<'a> for Lit<String> {
+    type Borrowed = Lit<&'a str>;
+
+    fn as_borrowed(&'a self) -> Self::Borrowed {
+        match self {
+            Lit::Null => Lit::Null,
+            Lit::String(inner) => Lit::String(inner.as_borrowed()),
+            Lit::Number(inner) => Lit::Number(inner.as_str()),
+            Lit::BigInt(inner) => Lit::BigInt(inner.as_str()),
+            Lit::Boolean(inner) => Lit::Boolean(*inner),
+            Lit::RegEx(inner) => Lit::RegEx(inner.as_borrowed()),
+            Lit::Template(inner) => Lit::Template(inner.as_borrowed()),
+        }
+    }
+}
+
+impl<T, U> MapSource<T, U> for Lit<T> {
+    type Output = Lit<U>;
+
+    fn map_source_with(self, f: &mut impl FnMut(T) -> U) -> Self::Output {
         match self {
             Lit::Null => Lit::Null,
-            Lit::String(inner) => Lit::String(inner.into_allocated()),
-            Lit::Number(inner) => Lit::Number(inner.to_string()),
+            Lit::String(inner) => Lit::String(inner.map_source_with(f)),
+            Lit::Number(inner) => Lit::Number(f(inner)),
+            Lit::BigInt(inner) => Lit::BigInt(f(inner)),
             Lit::Boolean(inner) => Lit::Boolean(inner),
-            Lit::RegEx(inner) => Lit::RegEx(inner.into_allocated()),
-            Lit::Template(inner) => Lit::Template(inner.into_allocated()),
+            Lit::RegEx(inner) => Lit::RegEx(inner.map_source_with(f)),
+            Lit::Template(inner) => Lit::Template(inner.map_source_with(f)),
         }
     }
 }
@@ -792,6 +2559,24 @@ impl<T> Lit<T> {
     pub fn number_from(s: T) -> Self {
         Lit::Number(s)
     }
+    /// A BigInt literal, `s` is the raw source including the `n` suffix
+    /// ```
+    /// use resast::prelude::*;
+    /// use resast::codegen::WriteJs;
+    /// use resast::spanned::{expr::Lit as SpannedLit, Slice};
+    ///
+    /// for raw in ["10n", "0x1fn"] {
+    ///     // spanned to concrete keeps the kind and the raw text
+    ///     let spanned = SpannedLit::BigInt(Slice::new(raw, 1, 0, 1, raw.len() as u32));
+    ///     let lit = Lit::from(spanned);
+    ///     assert_eq!(lit, Lit::bigint_from(raw));
+    ///     assert_ne!(lit, Lit::number_from(raw));
+    ///     assert_eq!(Expr::Lit(lit).to_js_string(), raw);
+    /// }
+    /// ```
+    pub fn bigint_from(s: T) -> Self {
+        Lit::BigInt(s)
+    }
     pub fn single_string_from(s: T) -> Self {
         Lit::String(StringLit::single_from(s))
     }
@@ -800,7 +2585,86 @@ impl<T> Lit<T> {
     }
 }
 
-#[derive(PartialEq, Debug, Clone)]
+/// A string becomes a double quoted string literal
+/// ```
+/// use resast::prelude::*;
+///
+/// assert_eq!(Lit::from("x"), Lit::double_string_from("x"));
+/// assert_eq!(Lit::from(String::from("x")), Lit::double_string_from(String::from("x")));
+/// ```
+impl<'a> From<&'a str> for Lit<&'a str> {
+    fn from(value: &'a str) -> Self {
+        Lit::String(value.into())
+    }
+}
+
+impl From<String> for Lit<String> {
+    fn from(value: String) -> Self {
+        Lit::String(value.into())
+    }
+}
+
+impl<'a> From<Cow<'a, str>> for Lit<Cow<'a, str>> {
+    fn from(value: Cow<'a, str>) -> Self {
+        Lit::String(value.into())
+    }
+}
+
+/// ```
+/// use resast::prelude::*;
+///
+/// assert_eq!(Lit::<&str>::from(true), Lit::Boolean(true));
+/// ```
+impl<T> From<bool> for Lit<T> {
+    fn from(value: bool) -> Self {
+        Lit::Boolean(value)
+    }
+}
+
+/// The number is written out with its `Display` impl, negative
+/// and non-finite values have no numeric literal so they fail
+/// ```
+/// use resast::prelude::*;
+/// use std::convert::TryFrom;
+///
+/// assert_eq!(Lit::try_from(1.5), Ok(Lit::number_from(String::from("1.5"))));
+/// assert_eq!(Lit::try_from(2.0), Ok(Lit::number_from(String::from("2"))));
+/// let err = Lit::try_from(-1.0).unwrap_err();
+/// assert_eq!(err.value(), -1.0);
+/// assert!(Lit::try_from(f64::INFINITY).is_err());
+/// assert!(Lit::try_from(f64::NAN).is_err());
+/// ```
+impl TryFrom<f64> for Lit<String> {
+    type Error = InvalidNumber;
+    fn try_from(value: f64) -> Result<Self, Self::Error> {
+        if !value.is_finite() || value.is_sign_negative() {
+            return Err(InvalidNumber(value));
+        }
+        Ok(Lit::Number(value.to_string()))
+    }
+}
+
+/// The error produced when a number can't be written as a
+/// numeric literal
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct InvalidNumber(f64);
+
+impl InvalidNumber {
+    /// The number that could not be converted
+    pub fn value(&self) -> f64 {
+        self.0
+    }
+}
+
+impl std::fmt::Display for InvalidNumber {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "`{}` has no numeric literal", self.0)
+    }
+}
+
+impl std::error::Error for InvalidNumber {}
+
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub enum StringLit<T> {
     Double(T),
@@ -814,9 +2678,28 @@ where
     type Allocated = StringLit<String>;
 
     fn into_allocated(self) -> Self::Allocated {
+        self.map_source_with(&mut |source: T| source.to_string())
+    }
+}
+
+impl<'a> AsBorrowed<'a> for StringLit<String> {
+    type Borrowed = StringLit<&'a str>;
+
+    fn as_borrowed(&'a self) -> Self::Borrowed {
+        match self {
+            StringLit::Double(inner) => StringLit::Double(inner.as_str()),
+            StringLit::Single(inner) => StringLit::Single(inner.as_str()),
+        }
+    }
+}
+
+impl<T, U> MapSource<T, U> for StringLit<T> {
+    type Output = StringLit<U>;
+
+    fn map_source_with(self, f: &mut impl FnMut(T) -> U) -> Self::Output {
         match self {
-            StringLit::Double(inner) => StringLit::Double(inner.to_string()),
-            StringLit::Single(inner) => StringLit::Single(inner.to_string()),
+            StringLit::Double(inner) => StringLit::Double(f(inner)),
+            StringLit::Single(inner) => StringLit::Single(f(inner)),
         }
     }
 }
@@ -829,6 +2712,24 @@ impl<T> StringLit<T> {
         StringLit::Single(s)
     }
 }
+impl<'a> From<&'a str> for StringLit<&'a str> {
+    fn from(value: &'a str) -> Self {
+        StringLit::Double(value)
+    }
+}
+
+impl From<String> for StringLit<String> {
+    fn from(value: String) -> Self {
+        StringLit::Double(value)
+    }
+}
+
+impl<'a> From<Cow<'a, str>> for StringLit<Cow<'a, str>> {
+    fn from(value: Cow<'a, str>) -> Self {
+        StringLit::Double(value)
+    }
+}
+
 impl<T> StringLit<T>
 where
     T: Clone,
@@ -853,7 +2754,7 @@ where
     }
 }
 /// A regular expression literal
-#[derive(PartialEq, Debug, Clone)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct RegEx<T> {
     pub pattern: T,
@@ -867,9 +2768,28 @@ where
     type Allocated = RegEx<String>;
 
     fn into_allocated(self) -> Self::Allocated {
+        self.map_source_with(&mut |source: T| source.to_string())
+    }
+}
+
+impl<'a> AsBorrowed<'a> for RegEx<String> {
+    type Borrowed = RegEx<&'a str>;
+
+    fn as_borrowed(&'a self) -> Self::Borrowed {
+        RegEx {
+            pattern: self.pattern.as_str(),
+            flags: self.flags.as_deref(),
+        }
+    }
+}
+
+impl<T, U> MapSource<T, U> for RegEx<T> {
+    type Output = RegEx<U>;
+
+    fn map_source_with(self, f: &mut impl FnMut(T) -> U) -> Self::Output {
         RegEx {
-            pattern: self.pattern.to_string(),
-            flags: self.flags.map(|f| f.to_string()),
+            pattern: f(self.pattern),
+            flags: self.flags.map(&mut *f),
         }
     }
 }
@@ -882,3 +2802,100 @@ impl<T> RegEx<T> {
         }
     }
 }
+
+impl<T> RegEx<T>
+where
+    T: AsRef<str>,
+{
+    /// The flags of this regular expression, see [`RegExFlags::parse`]
+    pub fn flags_set(&self) -> RegExFlags {
+        self.flags
+            .as_ref()
+            .map(|f| RegExFlags::parse(f.as_ref()))
+            .unwrap_or_default()
+    }
+}
+
+/// The flags that follow the closing `/` of a regular expression literal
+/// ```
+/// use resast::prelude::*;
+///
+/// let flags = RegEx::from("x", Some("gsd")).flags_set();
+/// assert!(flags.global && flags.dot_all && flags.has_indices);
+/// assert!(!flags.ignore_case);
+/// assert!(flags.is_valid());
+///
+/// let flags = RegExFlags::parse("gqi");
+/// assert!(flags.global && flags.ignore_case);
+/// assert_eq!(flags.unknown, ['q']);
+/// assert!(!flags.is_valid());
+///
+/// let flags = RegExFlags::parse("gig");
+/// assert_eq!(flags.duplicate, ['g']);
+/// assert!(!flags.is_valid());
+///
+/// // `u` and `v` can't be used together
+/// let flags = RegExFlags::parse("uv");
+/// assert!(flags.unicode && flags.unicode_sets);
+/// assert!(!flags.is_valid());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct RegExFlags {
+    /// `d`
+    pub has_indices: bool,
+    /// `g`
+    pub global: bool,
+    /// `i`
+    pub ignore_case: bool,
+    /// `m`
+    pub multiline: bool,
+    /// `s`
+    pub dot_all: bool,
+    /// `u`
+    pub unicode: bool,
+    /// `v`
+    pub unicode_sets: bool,
+    /// `y`
+    pub sticky: bool,
+    /// Any characters that are not a known flag, in the order they appeared
+    pub unknown: Vec<char>,
+    /// Any known flag that appeared more than once, each repeat is
+    /// recorded in the order it appeared
+    pub duplicate: Vec<char>,
+}
+
+impl RegExFlags {
+    /// Read the flags of a regular expression literal, this never
+    /// fails, unknown and repeated flags are collected instead so
+    /// check [`RegExFlags::is_valid`] before relying on the result
+    pub fn parse(flags: &str) -> Self {
+        let mut ret = Self::default();
+        for c in flags.chars() {
+            let flag = match c {
+                'd' => &mut ret.has_indices,
+                'g' => &mut ret.global,
+                'i' => &mut ret.ignore_case,
+                'm' => &mut ret.multiline,
+                's' => &mut ret.dot_all,
+                'u' => &mut ret.unicode,
+                'v' => &mut ret.unicode_sets,
+                'y' => &mut ret.sticky,
+                _ => {
+                    ret.unknown.push(c);
+                    continue;
+                }
+            };
+            if *flag {
+                ret.duplicate.push(c);
+            }
+            *flag = true;
+        }
+        ret
+    }
+
+    /// If every flag was recognized, none was repeated and `u`
+    /// isn't combined with `v`
+    pub fn is_valid(&self) -> bool {
+        self.unknown.is_empty() && self.duplicate.is_empty() && !(self.unicode && self.unicode_sets)
+    }
+}
@@ -0,0 +1,566 @@
+//! JSX syntax, only available with the `jsx` feature enabled
+use crate::expr::{Expr, StringLit};
+use crate::{AsBorrowed, Ident, IntoAllocated, MapSource};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A JSX element with its attributes and children
+/// ```jsx
+/// <div a={x}>text</div>
+/// ```
+///
+/// ```
+/// use resast::prelude::*;
+/// use resast::jsx::*;
+/// use resast::codegen::WriteJs;
+///
+/// let div = JsxElement {
+///     opening: JsxOpeningElement {
+///         name: JsxElementName::Ident(Ident::from("div")),
+///         attributes: vec![JsxAttr::Attribute(JsxAttribute {
+///             name: Ident::from("a"),
+///             value: Some(JsxAttributeValue::Expr(JsxExpressionContainer {
+///                 expr: Some(Expr::ident_from("x")),
+///             })),
+///         })],
+///         self_closing: false,
+///     },
+///     children: vec![JsxChild::Text(JsxText { value: "text" })],
+///     closing: Some(JsxElementName::Ident(Ident::from("div"))),
+/// };
+/// assert_eq!(Expr::Jsx(div).to_js_string(), "<div a={x}>text</div>");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "serde", serde(rename = "JSXElement"))]
+pub struct JsxElement<T> {
+    pub opening: JsxOpeningElement<T>,
+    pub children: Vec<JsxChild<T>>,
+    /// The name in the closing tag, `None` when the
+    /// opening element is self closing
+    pub closing: Option<JsxElementName<T>>,
+}
+
+impl<T> IntoAllocated for JsxElement<T>
+where
+    T: ToString,
+{
+    type Allocated = JsxElement<String>;
+
+    fn into_allocated(self) -> Self::Allocated {
+        self.map_source_with(&mut |source: T| source.to_string())
+    }
+}
+
+impl<'a> AsBorrowed<'a> for JsxElement<String> {
+    type Borrowed = JsxElement<&'a str>;
+
+    fn as_borrowed(&'a self) -> Self::Borrowed {
+        JsxElement {
+            opening: self.opening.as_borrowed(),
+            children: self.children.iter().map(AsBorrowed::as_borrowed).collect(),
+            closing: self.closing.as_borrowed(),
+        }
+    }
+}
+
+impl<T, U> MapSource<T, U> for JsxElement<T> {
+    type Output = JsxElement<U>;
+
+    fn map_source_with(self, f: &mut impl FnMut(T) -> U) -> Self::Output {
+        JsxElement {
+            opening: self.opening.map_source_with(f),
+            children: self
+                .children
+                .into_iter()
+                .map(|e| e.map_source_with(f))
+                .collect(),
+            closing: self.closing.map_source_with(f),
+        }
+    }
+}
+
+impl<T> JsxElement<T> {
+    /// Push every expression in the attributes and children of this
+    /// element, including those of nested elements, onto the stack
+    pub(crate) fn push_exprs<'a>(&'a self, stack: &mut Vec<&'a Expr<T>>) {
+        for attr in &self.opening.attributes {
+            match attr {
+                JsxAttr::Attribute(attr) => match &attr.value {
+                    Some(JsxAttributeValue::Expr(container)) => stack.extend(&container.expr),
+                    Some(JsxAttributeValue::Element(element)) => element.push_exprs(stack),
+                    Some(JsxAttributeValue::Fragment(fragment)) => {
+                        push_child_exprs(&fragment.children, stack)
+                    }
+                    Some(JsxAttributeValue::String(_)) | None => {}
+                },
+                JsxAttr::Spread(expr) => stack.push(expr),
+            }
+        }
+        push_child_exprs(&self.children, stack);
+    }
+}
+
+pub(crate) fn push_child_exprs<'a, T>(children: &'a [JsxChild<T>], stack: &mut Vec<&'a Expr<T>>) {
+    for child in children {
+        match child {
+            JsxChild::Text(_) => {}
+            JsxChild::Expr(container) => stack.extend(&container.expr),
+            JsxChild::Spread(expr) => stack.push(expr),
+            JsxChild::Element(element) => element.push_exprs(stack),
+            JsxChild::Fragment(fragment) => push_child_exprs(&fragment.children, stack),
+        }
+    }
+}
+
+/// A JSX fragment, an element without a name
+/// ```jsx
+/// <>frag</>
+/// ```
+///
+/// ```
+/// use resast::prelude::*;
+/// use resast::jsx::*;
+/// use resast::codegen::WriteJs;
+///
+/// let frag = JsxFragment {
+///     children: vec![JsxChild::Text(JsxText { value: "frag" })],
+/// };
+/// assert_eq!(Expr::JsxFragment(frag).to_js_string(), "<>frag</>");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "serde", serde(rename = "JSXFragment"))]
+pub struct JsxFragment<T> {
+    pub children: Vec<JsxChild<T>>,
+}
+
+impl<T> IntoAllocated for JsxFragment<T>
+where
+    T: ToString,
+{
+    type Allocated = JsxFragment<String>;
+
+    fn into_allocated(self) -> Self::Allocated {
+        self.map_source_with(&mut |source: T| source.to_string())
+    }
+}
+
+impl<'a> AsBorrowed<'a> for JsxFragment<String> {
+    type Borrowed = JsxFragment<&'a str>;
+
+    fn as_borrowed(&'a self) -> Self::Borrowed {
+        JsxFragment {
+            children: self.children.iter().map(AsBorrowed::as_borrowed).collect(),
+        }
+    }
+}
+
+impl<T, U> MapSource<T, U> for JsxFragment<T> {
+    type Output = JsxFragment<U>;
+
+    fn map_source_with(self, f: &mut impl FnMut(T) -> U) -> Self::Output {
+        JsxFragment {
+            children: self
+                .children
+                .into_iter()
+                .map(|e| e.map_source_with(f))
+                .collect(),
+        }
+    }
+}
+
+/// The opening tag of a JSX element
+/// ```jsx
+/// <input type="text" />
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "serde", serde(rename = "JSXOpeningElement"))]
+pub struct JsxOpeningElement<T> {
+    pub name: JsxElementName<T>,
+    pub attributes: Vec<JsxAttr<T>>,
+    pub self_closing: bool,
+}
+
+impl<T> IntoAllocated for JsxOpeningElement<T>
+where
+    T: ToString,
+{
+    type Allocated = JsxOpeningElement<String>;
+
+    fn into_allocated(self) -> Self::Allocated {
+        self.map_source_with(&mut |source: T| source.to_string())
+    }
+}
+
+impl<'a> AsBorrowed<'a> for JsxOpeningElement<String> {
+    type Borrowed = JsxOpeningElement<&'a str>;
+
+    fn as_borrowed(&'a self) -> Self::Borrowed {
+        JsxOpeningElement {
+            name: self.name.as_borrowed(),
+            attributes: self
+                .attributes
+                .iter()
+                .map(AsBorrowed::as_borrowed)
+                .collect(),
+            self_closing: self.self_closing,
+        }
+    }
+}
+
+impl<T, U> MapSource<T, U> for JsxOpeningElement<T> {
+    type Output = JsxOpeningElement<U>;
+
+    fn map_source_with(self, f: &mut impl FnMut(T) -> U) -> Self::Output {
+        JsxOpeningElement {
+            name: self.name.map_source_with(f),
+            attributes: self
+                .attributes
+                .into_iter()
+                .map(|e| e.map_source_with(f))
+                .collect(),
+            self_closing: self.self_closing,
+        }
+    }
+}
+
+/// The name of a JSX element
+/// ```jsx
+/// <div />;
+/// <Foo.Bar />;
+/// <svg:rect />;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum JsxElementName<T> {
+    #[cfg_attr(feature = "serde", serde(rename = "JSXIdentifier"))]
+    Ident(Ident<T>),
+    #[cfg_attr(feature = "serde", serde(rename = "JSXMemberExpression"))]
+    Member(Box<JsxElementName<T>>, Ident<T>),
+    #[cfg_attr(feature = "serde", serde(rename = "JSXNamespacedName"))]
+    Namespaced(Ident<T>, Ident<T>),
+}
+
+impl<T> IntoAllocated for JsxElementName<T>
+where
+    T: ToString,
+{
+    type Allocated = JsxElementName<String>;
+
+    fn into_allocated(self) -> Self::Allocated {
+        self.map_source_with(&mut |source: T| source.to_string())
+    }
+}
+
+impl<'a> AsBorrowed<'a> for JsxElementName<String> {
+    type Borrowed = JsxElementName<&'a str>;
+
+    fn as_borrowed(&'a self) -> Self::Borrowed {
+        match self {
+            JsxElementName::Ident(inner) => JsxElementName::Ident(inner.as_borrowed()),
+            JsxElementName::Member(object, property) => {
+                JsxElementName::Member(object.as_borrowed(), property.as_borrowed())
+            }
+            JsxElementName::Namespaced(namespace, name) => {
+                JsxElementName::Namespaced(namespace.as_borrowed(), name.as_borrowed())
+            }
+        }
+    }
+}
+
+impl<T, U> MapSource<T, U> for JsxElementName<T> {
+    type Output = JsxElementName<U>;
+
+    fn map_source_with(self, f: &mut impl FnMut(T) -> U) -> Self::Output {
+        match self {
+            JsxElementName::Ident(inner) => JsxElementName::Ident(inner.map_source_with(f)),
+            JsxElementName::Member(object, property) => {
+                JsxElementName::Member(object.map_source_with(f), property.map_source_with(f))
+            }
+            JsxElementName::Namespaced(namespace, name) => {
+                JsxElementName::Namespaced(namespace.map_source_with(f), name.map_source_with(f))
+            }
+        }
+    }
+}
+
+/// A single entry in a JSX opening element's attribute list
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum JsxAttr<T> {
+    /// `<div a="b" />`
+    #[cfg_attr(feature = "serde", serde(rename = "JSXAttribute"))]
+    Attribute(JsxAttribute<T>),
+    /// `<div {...props} />`
+    #[cfg_attr(feature = "serde", serde(rename = "JSXSpreadAttribute"))]
+    Spread(Expr<T>),
+}
+
+impl<T> IntoAllocated for JsxAttr<T>
+where
+    T: ToString,
+{
+    type Allocated = JsxAttr<String>;
+
+    fn into_allocated(self) -> Self::Allocated {
+        self.map_source_with(&mut |source: T| source.to_string())
+    }
+}
+
+impl<'a> AsBorrowed<'a> for JsxAttr<String> {
+    type Borrowed = JsxAttr<&'a str>;
+
+    fn as_borrowed(&'a self) -> Self::Borrowed {
+        match self {
+            JsxAttr::Attribute(inner) => JsxAttr::Attribute(inner.as_borrowed()),
+            JsxAttr::Spread(inner) => JsxAttr::Spread(inner.as_borrowed()),
+        }
+    }
+}
+
+impl<T, U> MapSource<T, U> for JsxAttr<T> {
+    type Output = JsxAttr<U>;
+
+    fn map_source_with(self, f: &mut impl FnMut(T) -> U) -> Self::Output {
+        match self {
+            JsxAttr::Attribute(inner) => JsxAttr::Attribute(inner.map_source_with(f)),
+            JsxAttr::Spread(inner) => JsxAttr::Spread(inner.map_source_with(f)),
+        }
+    }
+}
+
+/// A named JSX attribute, the value is `None`
+/// for boolean attributes like `<input disabled />`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "serde", serde(rename = "JSXAttribute"))]
+pub struct JsxAttribute<T> {
+    pub name: Ident<T>,
+    pub value: Option<JsxAttributeValue<T>>,
+}
+
+impl<T> IntoAllocated for JsxAttribute<T>
+where
+    T: ToString,
+{
+    type Allocated = JsxAttribute<String>;
+
+    fn into_allocated(self) -> Self::Allocated {
+        self.map_source_with(&mut |source: T| source.to_string())
+    }
+}
+
+impl<'a> AsBorrowed<'a> for JsxAttribute<String> {
+    type Borrowed = JsxAttribute<&'a str>;
+
+    fn as_borrowed(&'a self) -> Self::Borrowed {
+        JsxAttribute {
+            name: self.name.as_borrowed(),
+            value: self.value.as_borrowed(),
+        }
+    }
+}
+
+impl<T, U> MapSource<T, U> for JsxAttribute<T> {
+    type Output = JsxAttribute<U>;
+
+    fn map_source_with(self, f: &mut impl FnMut(T) -> U) -> Self::Output {
+        JsxAttribute {
+            name: self.name.map_source_with(f),
+            value: self.value.map_source_with(f),
+        }
+    }
+}
+
+/// The right hand side of a JSX attribute
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum JsxAttributeValue<T> {
+    /// `<div a="b" />`
+    String(StringLit<T>),
+    /// `<div a={b} />`
+    Expr(JsxExpressionContainer<T>),
+    /// `<div a=<b /> />`
+    Element(JsxElement<T>),
+    /// `<div a=<>b</> />`
+    Fragment(JsxFragment<T>),
+}
+
+impl<T> IntoAllocated for JsxAttributeValue<T>
+where
+    T: ToString,
+{
+    type Allocated = JsxAttributeValue<String>;
+
+    fn into_allocated(self) -> Self::Allocated {
+        self.map_source_with(&mut |source: T| source.to_string())
+    }
+}
+
+impl<'a> AsBorrowed<'a> for JsxAttributeValue<String> {
+    type Borrowed = JsxAttributeValue<&'a str>;
+
+    fn as_borrowed(&'a self) -> Self::Borrowed {
+        match self {
+            JsxAttributeValue::String(inner) => JsxAttributeValue::String(inner.as_borrowed()),
+            JsxAttributeValue::Expr(inner) => JsxAttributeValue::Expr(inner.as_borrowed()),
+            JsxAttributeValue::Element(inner) => JsxAttributeValue::Element(inner.as_borrowed()),
+            JsxAttributeValue::Fragment(inner) => JsxAttributeValue::Fragment(inner.as_borrowed()),
+        }
+    }
+}
+
+impl<T, U> MapSource<T, U> for JsxAttributeValue<T> {
+    type Output = JsxAttributeValue<U>;
+
+    fn map_source_with(self, f: &mut impl FnMut(T) -> U) -> Self::Output {
+        match self {
+            JsxAttributeValue::String(inner) => JsxAttributeValue::String(inner.map_source_with(f)),
+            JsxAttributeValue::Expr(inner) => JsxAttributeValue::Expr(inner.map_source_with(f)),
+            JsxAttributeValue::Element(inner) => {
+                JsxAttributeValue::Element(inner.map_source_with(f))
+            }
+            JsxAttributeValue::Fragment(inner) => {
+                JsxAttributeValue::Fragment(inner.map_source_with(f))
+            }
+        }
+    }
+}
+
+/// An expression wrapped in curly braces, the expression
+/// is `None` for an empty container like `<div>{}</div>`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "serde", serde(rename = "JSXExpressionContainer"))]
+pub struct JsxExpressionContainer<T> {
+    pub expr: Option<Expr<T>>,
+}
+
+impl<T> IntoAllocated for JsxExpressionContainer<T>
+where
+    T: ToString,
+{
+    type Allocated = JsxExpressionContainer<String>;
+
+    fn into_allocated(self) -> Self::Allocated {
+        self.map_source_with(&mut |source: T| source.to_string())
+    }
+}
+
+impl<'a> AsBorrowed<'a> for JsxExpressionContainer<String> {
+    type Borrowed = JsxExpressionContainer<&'a str>;
+
+    fn as_borrowed(&'a self) -> Self::Borrowed {
+        JsxExpressionContainer {
+            expr: self.expr.as_borrowed(),
+        }
+    }
+}
+
+impl<T, U> MapSource<T, U> for JsxExpressionContainer<T> {
+    type Output = JsxExpressionContainer<U>;
+
+    fn map_source_with(self, f: &mut impl FnMut(T) -> U) -> Self::Output {
+        JsxExpressionContainer {
+            expr: self.expr.map_source_with(f),
+        }
+    }
+}
+
+/// Raw text between JSX tags
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "serde", serde(rename = "JSXText"))]
+pub struct JsxText<T> {
+    pub value: T,
+}
+
+impl<T> IntoAllocated for JsxText<T>
+where
+    T: ToString,
+{
+    type Allocated = JsxText<String>;
+
+    fn into_allocated(self) -> Self::Allocated {
+        self.map_source_with(&mut |source: T| source.to_string())
+    }
+}
+
+impl<'a> AsBorrowed<'a> for JsxText<String> {
+    type Borrowed = JsxText<&'a str>;
+
+    fn as_borrowed(&'a self) -> Self::Borrowed {
+        JsxText {
+            value: self.value.as_str(),
+        }
+    }
+}
+
+impl<T, U> MapSource<T, U> for JsxText<T> {
+    type Output = JsxText<U>;
+
+    fn map_source_with(self, f: &mut impl FnMut(T) -> U) -> Self::Output {
+        JsxText {
+            value: f(self.value),
+        }
+    }
+}
+
+/// A single child of a JSX element or fragment
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum JsxChild<T> {
+    #[cfg_attr(feature = "serde", serde(rename = "JSXText"))]
+    Text(JsxText<T>),
+    #[cfg_attr(feature = "serde", serde(rename = "JSXExpressionContainer"))]
+    Expr(JsxExpressionContainer<T>),
+    /// `<div>{...children}</div>`
+    #[cfg_attr(feature = "serde", serde(rename = "JSXSpreadChild"))]
+    Spread(Expr<T>),
+    #[cfg_attr(feature = "serde", serde(rename = "JSXElement"))]
+    Element(JsxElement<T>),
+    #[cfg_attr(feature = "serde", serde(rename = "JSXFragment"))]
+    Fragment(JsxFragment<T>),
+}
+
+impl<T> IntoAllocated for JsxChild<T>
+where
+    T: ToString,
+{
+    type Allocated = JsxChild<String>;
+
+    fn into_allocated(self) -> Self::Allocated {
+        self.map_source_with(&mut |source: T| source.to_string())
+    }
+}
+
+impl<'a> AsBorrowed<'a> for JsxChild<String> {
+    type Borrowed = JsxChild<&'a str>;
+
+    fn as_borrowed(&'a self) -> Self::Borrowed {
+        match self {
+            JsxChild::Text(inner) => JsxChild::Text(inner.as_borrowed()),
+            JsxChild::Expr(inner) => JsxChild::Expr(inner.as_borrowed()),
+            JsxChild::Spread(inner) => JsxChild::Spread(inner.as_borrowed()),
+            JsxChild::Element(inner) => JsxChild::Element(inner.as_borrowed()),
+            JsxChild::Fragment(inner) => JsxChild::Fragment(inner.as_borrowed()),
+        }
+    }
+}
+
+impl<T, U> MapSource<T, U> for JsxChild<T> {
+    type Output = JsxChild<U>;
+
+    fn map_source_with(self, f: &mut impl FnMut(T) -> U) -> Self::Output {
+        match self {
+            JsxChild::Text(inner) => JsxChild::Text(inner.map_source_with(f)),
+            JsxChild::Expr(inner) => JsxChild::Expr(inner.map_source_with(f)),
+            JsxChild::Spread(inner) => JsxChild::Spread(inner.map_source_with(f)),
+            JsxChild::Element(inner) => JsxChild::Element(inner.map_source_with(f)),
+            JsxChild::Fragment(inner) => JsxChild::Fragment(inner.map_source_with(f)),
+        }
+    }
+}
@@ -1,8 +1,15 @@
+pub mod codegen;
 pub mod decl;
 pub mod expr;
+#[cfg(feature = "jsx")]
+pub mod jsx;
 pub mod pat;
 pub mod spanned;
 pub mod stmt;
+#[cfg(feature = "testing")]
+pub mod testing;
+mod unicode;
+pub mod visit;
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -14,7 +21,7 @@ use expr::{Expr, Lit, Prop};
 use pat::Pat;
 use stmt::Stmt;
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct Ident<T> {
     pub name: T,
@@ -27,12 +34,28 @@ where
     type Allocated = Ident<String>;
 
     fn into_allocated(self) -> Self::Allocated {
+        self.map_source_with(&mut |source: T| source.to_string())
+    }
+}
+
+impl<'a> AsBorrowed<'a> for Ident<String> {
+    type Borrowed = Ident<&'a str>;
+
+    fn as_borrowed(&'a self) -> Self::Borrowed {
         Ident {
-            name: self.name.to_string(),
+            name: self.name.as_str(),
         }
     }
 }
 
+impl<T, U> MapSource<T, U> for Ident<T> {
+    type Output = Ident<U>;
+
+    fn map_source_with(self, f: &mut impl FnMut(T) -> U) -> Self::Output {
+        Ident { name: f(self.name) }
+    }
+}
+
 impl<'a> From<&'a str> for Ident<&'a str> {
     fn from(value: &'a str) -> Self {
         Self { name: value }
@@ -51,18 +74,253 @@ impl<'a> From<Cow<'a, str>> for Ident<Cow<'a, str>> {
     }
 }
 
+impl<T> Ident<T>
+where
+    T: AsRef<str>,
+{
+    /// If `name` is a valid IdentifierName, that is it starts with
+    /// `$`, `_` or an `ID_Start` character and continues with
+    /// those, ZWNJ, ZWJ or any other `ID_Continue` character, like
+    /// a decimal digit, a combining mark or a connector. Both
+    /// properties follow Unicode 14.0.
+    /// Reserved words are valid names, see `is_reserved_word`
+    /// ```
+    /// use resast::Ident;
+    ///
+    /// assert!(Ident::from("foo").is_valid_identifier_name());
+    /// assert!(Ident::from("café").is_valid_identifier_name());
+    /// assert!(Ident::from("$_0").is_valid_identifier_name());
+    /// assert!(Ident::from("class").is_valid_identifier_name());
+    /// // a combining accent, a connector and a non-ASCII digit
+    /// assert!(Ident::from("e\u{301}").is_valid_identifier_name());
+    /// assert!(Ident::from("a‿b").is_valid_identifier_name());
+    /// assert!(Ident::from("x٣").is_valid_identifier_name());
+    /// assert!(!Ident::from("1bad").is_valid_identifier_name());
+    /// assert!(!Ident::from("a-b").is_valid_identifier_name());
+    /// assert!(!Ident::from("").is_valid_identifier_name());
+    /// // superscripts are numeric but not ID_Continue
+    /// assert!(!Ident::from("x²").is_valid_identifier_name());
+    /// // a combining mark can't come first
+    /// assert!(!Ident::from("\u{345}a").is_valid_identifier_name());
+    /// assert!(Ident::from("a\u{345}").is_valid_identifier_name());
+    /// assert!(!Ident::from("aⒶ").is_valid_identifier_name());
+    /// // Other_ID_Start
+    /// assert!(Ident::from("℘").is_valid_identifier_name());
+    /// assert!(Ident::from("℮").is_valid_identifier_name());
+    /// assert!(Ident::from("゛゜").is_valid_identifier_name());
+    /// assert!(Ident::from("\u{1885}\u{1886}").is_valid_identifier_name());
+    /// // a modifier letter that is also Pattern_Syntax
+    /// assert!(!Ident::from("\u{2E2F}").is_valid_identifier_name());
+    /// assert!(!Ident::from("a\u{2E2F}").is_valid_identifier_name());
+    /// ```
+    pub fn is_valid_identifier_name(&self) -> bool {
+        let mut chars = self.name.as_ref().chars();
+        let first = match chars.next() {
+            Some(ch) => ch,
+            None => return false,
+        };
+        (first == '$'
+            || first == '_'
+            || first.is_ascii_alphabetic()
+            || (!first.is_ascii() && unicode::is_id_start(first)))
+            && chars.all(|ch| {
+                ch == '$'
+                    || ch == '_'
+                    || ch == '\u{200C}'
+                    || ch == '\u{200D}'
+                    || ch.is_ascii_alphanumeric()
+                    || (!ch.is_ascii() && unicode::is_id_continue(ch))
+            })
+    }
+    /// If `name` is one of the words that can never be used
+    /// as an identifier, including the literals `null`, `true`
+    /// and `false`. Words only reserved in strict mode code or
+    /// modules, like `let`, `static`, `yield` or `await`, are
+    /// not included
+    /// ```
+    /// use resast::Ident;
+    ///
+    /// assert!(Ident::from("class").is_reserved_word());
+    /// assert!(Ident::from("null").is_reserved_word());
+    /// assert!(!Ident::from("foo").is_reserved_word());
+    /// assert!(!Ident::from("let").is_reserved_word());
+    /// assert!(!Ident::from("yield").is_reserved_word());
+    /// assert!(!Ident::from("await").is_reserved_word());
+    /// ```
+    pub fn is_reserved_word(&self) -> bool {
+        matches!(
+            self.name.as_ref(),
+            "break"
+                | "case"
+                | "catch"
+                | "class"
+                | "const"
+                | "continue"
+                | "debugger"
+                | "default"
+                | "delete"
+                | "do"
+                | "else"
+                | "enum"
+                | "export"
+                | "extends"
+                | "false"
+                | "finally"
+                | "for"
+                | "function"
+                | "if"
+                | "import"
+                | "in"
+                | "instanceof"
+                | "new"
+                | "null"
+                | "return"
+                | "super"
+                | "switch"
+                | "this"
+                | "throw"
+                | "true"
+                | "try"
+                | "typeof"
+                | "var"
+                | "void"
+                | "while"
+                | "with"
+        )
+    }
+}
+
+/// A private class member name, the `name` does not
+/// include the leading `#`
+/// ```js
+/// class A {
+///     #x;
+///     m() {
+///         return this.#x;
+///     }
+/// }
+/// ```
+/// ```
+/// use resast::prelude::*;
+/// use resast::codegen::WriteJs;
+/// use resast::spanned::expr::{Expr as SExpr, MemberExpr, MemberIndexer};
+/// use resast::spanned::spanner::Spanner;
+/// use resast::spanned::{Ident as SIdent, PrivateIdent as SPrivateIdent};
+/// use resast::visit::{Visit, VisitMut};
+///
+/// // this.#x
+/// let spanned = SExpr::Member(MemberExpr {
+///     object: Box::new(SExpr::This(Default::default())),
+///     property: Box::new(SExpr::PrivateIdent(SPrivateIdent {
+///         hash: Default::default(),
+///         ident: SIdent::new_from_source("x", 1, 6),
+///     })),
+///     indexer: MemberIndexer::Period(Default::default()),
+/// });
+/// let mut expr = Expr::from(spanned);
+/// let expected = Expr::member(Expr::This, Expr::PrivateIdent(PrivateIdent { name: "x" }), false);
+/// assert_eq!(expr, expected);
+/// assert_eq!(expr.to_js_string(), "this.#x");
+/// // and back to the spanned tree
+/// let spanned = Spanner::default().expr(expr.clone()).unwrap();
+/// assert_eq!(Expr::from(spanned), expected);
+///
+/// #[derive(Default)]
+/// struct Names(Vec<&'static str>);
+/// impl<'ast> Visit<'ast, &'static str> for Names {
+///     fn visit_private_ident(&mut self, ident: &'ast PrivateIdent<&'static str>) {
+///         self.0.push(ident.name);
+///     }
+/// }
+/// let mut names = Names::default();
+/// names.visit_expr(&expr);
+/// assert_eq!(names.0, ["x"]);
+///
+/// struct Rename;
+/// impl VisitMut<&'static str> for Rename {
+///     fn visit_private_ident_mut(&mut self, ident: &mut PrivateIdent<&'static str>) {
+///         ident.name = "y";
+///     }
+/// }
+/// Rename.visit_expr_mut(&mut expr);
+/// assert_eq!(expr.to_js_string(), "this.#y");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct PrivateIdent<T> {
+    pub name: T,
+}
+
+impl<T> IntoAllocated for PrivateIdent<T>
+where
+    T: ToString,
+{
+    type Allocated = PrivateIdent<String>;
+
+    fn into_allocated(self) -> Self::Allocated {
+        self.map_source_with(&mut |source: T| source.to_string())
+    }
+}
+
+impl<'a> AsBorrowed<'a> for PrivateIdent<String> {
+    type Borrowed = PrivateIdent<&'a str>;
+
+    fn as_borrowed(&'a self) -> Self::Borrowed {
+        PrivateIdent {
+            name: self.name.as_str(),
+        }
+    }
+}
+
+impl<T, U> MapSource<T, U> for PrivateIdent<T> {
+    type Output = PrivateIdent<U>;
+
+    fn map_source_with(self, f: &mut impl FnMut(T) -> U) -> Self::Output {
+        PrivateIdent { name: f(self.name) }
+    }
+}
+
 /// A fully parsed javascript program.
 ///
 /// It is essentially a collection of `ProgramPart`s
 /// with a flag denoting if the representation is
 /// a ES6 Mod or a Script.
-#[derive(Debug, Clone, PartialEq)]
+///
+/// Like every node in the non-spanned tree it is `Hash`, equal
+/// trees hash equally
+/// ```
+/// use std::collections::HashSet;
+/// use resast::prelude::*;
+///
+/// let program = || Program::script(vec![ProgramPart::Stmt(Stmt::Expr(Expr::ident_from("a")))]);
+/// let mut seen = HashSet::new();
+/// assert!(seen.insert(program()));
+/// assert!(!seen.insert(program()));
+/// assert!(seen.insert(Program::module(program().into_parts())));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub enum Program<T> {
     /// An ES6 Mod
-    Mod(Vec<ProgramPart<T>>),
+    Mod {
+        /// The text after `#!` when the first line is a hashbang comment
+        hashbang: Option<T>,
+        parts: Vec<ProgramPart<T>>,
+    },
     /// Not an ES6 Mod
-    Script(Vec<ProgramPart<T>>),
+    Script {
+        /// The text after `#!` when the first line is a hashbang comment
+        hashbang: Option<T>,
+        parts: Vec<ProgramPart<T>>,
+    },
+}
+
+/// If a program is parsed as an ES module or a classic script
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum SourceType {
+    Module,
+    Script,
 }
 
 impl<T> IntoAllocated for Program<T>
@@ -72,29 +330,467 @@ where
     type Allocated = Program<String>;
 
     fn into_allocated(self) -> Self::Allocated {
+        self.map_source_with(&mut |source: T| source.to_string())
+    }
+}
+
+impl<'a> AsBorrowed<'a> for Program<String> {
+    type Borrowed = Program<&'a str>;
+
+    fn as_borrowed(&'a self) -> Self::Borrowed {
         match self {
-            Program::Mod(inner) => {
-                Program::Mod(inner.into_iter().map(|p| p.into_allocated()).collect())
-            }
-            Program::Script(inner) => {
-                Program::Script(inner.into_iter().map(|p| p.into_allocated()).collect())
-            }
+            Program::Mod { hashbang, parts } => Program::Mod {
+                hashbang: hashbang.as_deref(),
+                parts: parts.iter().map(|p| p.as_borrowed()).collect(),
+            },
+            Program::Script { hashbang, parts } => Program::Script {
+                hashbang: hashbang.as_deref(),
+                parts: parts.iter().map(|p| p.as_borrowed()).collect(),
+            },
+        }
+    }
+}
+
+impl<T, U> MapSource<T, U> for Program<T> {
+    type Output = Program<U>;
+
+    fn map_source_with(self, f: &mut impl FnMut(T) -> U) -> Self::Output {
+        match self {
+            Program::Mod { hashbang, parts } => Program::Mod {
+                hashbang: hashbang.map(&mut *f),
+                parts: parts.into_iter().map(|p| p.map_source_with(f)).collect(),
+            },
+            Program::Script { hashbang, parts } => Program::Script {
+                hashbang: hashbang.map(&mut *f),
+                parts: parts.into_iter().map(|p| p.map_source_with(f)).collect(),
+            },
         }
     }
 }
 
 impl<T> Program<T> {
+    /// Pass every source value through `f`, for example to intern
+    /// names or wrap them in a custom type
+    /// ```
+    /// use std::collections::HashMap;
+    /// use resast::prelude::*;
+    ///
+    /// // a + b * a;
+    /// let program = Program::script(vec![ProgramPart::Stmt(Stmt::Expr(Expr::binary(
+    ///     BinaryOp::Plus,
+    ///     Expr::ident_from("a"),
+    ///     Expr::binary(BinaryOp::Times, Expr::ident_from("b"), Expr::ident_from("a")),
+    /// )))]);
+    /// let mut ids: HashMap<&str, usize> = HashMap::new();
+    /// let interned: Program<usize> = program.map_source(|name| {
+    ///     let next = ids.len();
+    ///     *ids.entry(name).or_insert(next)
+    /// });
+    /// let expected = Program::script(vec![ProgramPart::Stmt(Stmt::Expr(Expr::binary(
+    ///     BinaryOp::Plus,
+    ///     Expr::ident_from(0),
+    ///     Expr::binary(BinaryOp::Times, Expr::ident_from(1), Expr::ident_from(0)),
+    /// )))]);
+    /// assert_eq!(interned, expected);
+    /// assert_eq!(ids.len(), 2);
+    /// ```
+    pub fn map_source<U>(self, mut f: impl FnMut(T) -> U) -> Program<U> {
+        self.map_source_with(&mut f)
+    }
+    /// ```
+    /// use resast::prelude::*;
+    ///
+    /// let module = Program::<&str>::new(SourceType::Module, vec![]);
+    /// assert!(matches!(module, Program::Mod { .. }));
+    /// assert_eq!(module.source_type(), SourceType::Module);
+    /// let script = Program::<&str>::new(SourceType::Script, vec![]);
+    /// assert!(matches!(script, Program::Script { .. }));
+    /// assert_eq!(script.source_type(), SourceType::Script);
+    /// ```
+    pub fn new(source_type: SourceType, parts: Vec<ProgramPart<T>>) -> Self {
+        match source_type {
+            SourceType::Module => Program::Mod {
+                hashbang: None,
+                parts,
+            },
+            SourceType::Script => Program::Script {
+                hashbang: None,
+                parts,
+            },
+        }
+    }
     pub fn module(parts: Vec<ProgramPart<T>>) -> Self {
-        Program::Mod(parts)
+        Self::new(SourceType::Module, parts)
     }
     pub fn script(parts: Vec<ProgramPart<T>>) -> Self {
-        Program::Script(parts)
+        Self::new(SourceType::Script, parts)
+    }
+    pub fn source_type(&self) -> SourceType {
+        match self {
+            Program::Mod { .. } => SourceType::Module,
+            Program::Script { .. } => SourceType::Script,
+        }
+    }
+    /// Set the text of the hashbang comment, without the leading `#!`
+    ///
+    /// ```
+    /// use resast::prelude::*;
+    ///
+    /// let program = Program::script(vec![]).with_hashbang("/usr/bin/env node");
+    /// assert_eq!(program.hashbang(), Some(&"/usr/bin/env node"));
+    /// assert_eq!(Program::<&str>::script(vec![]).hashbang(), None);
+    /// ```
+    pub fn with_hashbang(self, hashbang: T) -> Self {
+        match self {
+            Program::Mod { parts, .. } => Program::Mod {
+                hashbang: Some(hashbang),
+                parts,
+            },
+            Program::Script { parts, .. } => Program::Script {
+                hashbang: Some(hashbang),
+                parts,
+            },
+        }
+    }
+    /// The text of the hashbang comment, if the program starts with one
+    pub fn hashbang(&self) -> Option<&T> {
+        match self {
+            Program::Mod { hashbang, .. } | Program::Script { hashbang, .. } => hashbang.as_ref(),
+        }
+    }
+    /// The top level parts of this program
+    /// ```
+    /// use resast::prelude::*;
+    ///
+    /// let program = Program::<&str>::module(vec![
+    ///     ProgramPart::Decl(Decl::Var(VarKind::Let, vec![])),
+    ///     ProgramPart::Stmt(Stmt::Empty),
+    ///     ProgramPart::Stmt(Stmt::Debugger),
+    /// ]);
+    /// let decls = program
+    ///     .iter()
+    ///     .filter(|part| matches!(part, ProgramPart::Decl(_)))
+    ///     .count();
+    /// let stmts = program
+    ///     .iter()
+    ///     .filter(|part| matches!(part, ProgramPart::Stmt(_)))
+    ///     .count();
+    /// assert_eq!((decls, stmts), (1, 2));
+    /// assert_eq!(program.parts().len(), 3);
+    /// ```
+    pub fn parts(&self) -> &[ProgramPart<T>] {
+        match self {
+            Program::Mod { parts, .. } | Program::Script { parts, .. } => parts,
+        }
+    }
+    pub fn parts_mut(&mut self) -> &mut Vec<ProgramPart<T>> {
+        match self {
+            Program::Mod { parts, .. } | Program::Script { parts, .. } => parts,
+        }
+    }
+    pub fn into_parts(self) -> Vec<ProgramPart<T>> {
+        match self {
+            Program::Mod { parts, .. } | Program::Script { parts, .. } => parts,
+        }
+    }
+    pub fn iter(&self) -> std::slice::Iter<'_, ProgramPart<T>> {
+        self.parts().iter()
+    }
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, ProgramPart<T>> {
+        self.parts_mut().iter_mut()
+    }
+    /// Every label declared by a [`LabeledStmt`](stmt::LabeledStmt) anywhere in
+    /// this program, including inside nested functions and classes
+    ///
+    /// The list is flat, it doesn't record which statements a label
+    /// encloses. Together with [`Stmt::referenced_label`] it finds
+    /// references to labels that are never declared, but it can't
+    /// catch `a: {} break a;` or a `break a` inside a different
+    /// function than `a:`, that needs a walk that tracks the
+    /// enclosing labels itself
+    /// ```
+    /// use resast::prelude::*;
+    /// use resast::visit::{walk_stmt, Visit};
+    ///
+    /// // outer: while (true) { break outer; }
+    /// // continue missing;
+    /// let program = Program::script(vec![
+    ///     ProgramPart::Stmt(Stmt::Labeled(LabeledStmt {
+    ///         label: Ident::from("outer"),
+    ///         body: Box::new(Stmt::While(WhileStmt {
+    ///             test: Expr::Lit(Lit::Boolean(true)),
+    ///             body: Box::new(Stmt::Block(BlockStmt(vec![ProgramPart::Stmt(
+    ///                 Stmt::Break(Some(Ident::from("outer"))),
+    ///             )]))),
+    ///         })),
+    ///     })),
+    ///     ProgramPart::Stmt(Stmt::Continue(Some(Ident::from("missing")))),
+    /// ]);
+    ///
+    /// #[derive(Default)]
+    /// struct References<'ast>(Vec<&'ast Ident<&'ast str>>);
+    /// impl<'ast> Visit<'ast, &'ast str> for References<'ast> {
+    ///     fn visit_stmt(&mut self, stmt: &'ast Stmt<&'ast str>) {
+    ///         self.0.extend(stmt.referenced_label());
+    ///         walk_stmt(self, stmt);
+    ///     }
+    /// }
+    /// let mut references = References::default();
+    /// references.visit_program(&program);
+    ///
+    /// let labels = program.collect_labels();
+    /// let undeclared: Vec<_> = references
+    ///     .0
+    ///     .into_iter()
+    ///     .filter(|r| !labels.contains(r))
+    ///     .collect();
+    /// assert_eq!(labels, vec![&Ident::from("outer")]);
+    /// assert_eq!(undeclared, vec![&Ident::from("missing")]);
+    /// ```
+    pub fn collect_labels(&self) -> Vec<&Ident<T>> {
+        struct Labels<'ast, T>(Vec<&'ast Ident<T>>);
+        impl<'ast, T> visit::Visit<'ast, T> for Labels<'ast, T> {
+            fn visit_stmt(&mut self, stmt: &'ast Stmt<T>) {
+                if let Stmt::Labeled(labeled) = stmt {
+                    self.0.push(&labeled.label);
+                }
+                visit::walk_stmt(self, stmt);
+            }
+        }
+        let mut labels = Labels(Vec::new());
+        visit::Visit::visit_program(&mut labels, self);
+        labels.0
+    }
+    /// If this is a module with an `await` expression, a `for await`
+    /// loop or an `await using` declaration outside of any function
+    /// ```
+    /// use resast::prelude::*;
+    ///
+    /// let await_x = || ProgramPart::Stmt(Stmt::Expr(Expr::Await(Box::new(Expr::ident_from("x")))));
+    /// // await x;
+    /// assert!(Program::module(vec![await_x()]).has_top_level_await());
+    /// // scripts can't use top level await
+    /// assert!(!Program::script(vec![await_x()]).has_top_level_await());
+    /// // async function f() { await x; }
+    /// let func = Func::builder()
+    ///     .id("f")
+    ///     .is_async(true)
+    ///     .body(vec![await_x()])
+    ///     .build();
+    /// let program = Program::module(vec![ProgramPart::Decl(Decl::Func(func))]);
+    /// assert!(!program.has_top_level_await());
+    /// // class C { static { await x; } }
+    /// let block = Prop {
+    ///     key: PropKey::None,
+    ///     value: PropValue::Block(FuncBody(vec![await_x()])),
+    ///     kind: PropKind::StaticBlock,
+    ///     method: false,
+    ///     computed: false,
+    ///     short_hand: false,
+    ///     is_static: true,
+    ///     decorators: vec![],
+    /// };
+    /// let class = Class {
+    ///     decorators: vec![],
+    ///     id: Some(Ident::from("C")),
+    ///     super_class: None,
+    ///     body: ClassBody(vec![block]),
+    /// };
+    /// let program = Program::module(vec![ProgramPart::Decl(Decl::Class(class))]);
+    /// assert!(!program.has_top_level_await());
+    /// // await using r = res;
+    /// let decl = VarDecl {
+    ///     id: Pat::ident_from("r"),
+    ///     init: Some(Expr::ident_from("res")),
+    /// };
+    /// let program = Program::module(vec![ProgramPart::Decl(Decl::Var(
+    ///     VarKind::AwaitUsing,
+    ///     vec![decl],
+    /// ))]);
+    /// assert!(program.has_top_level_await());
+    /// // for (await using x of xs) ;
+    /// let for_of = Stmt::ForOf(ForOfStmt {
+    ///     left: LoopLeft::Variable(
+    ///         VarKind::AwaitUsing,
+    ///         VarDecl {
+    ///             id: Pat::ident_from("x"),
+    ///             init: None,
+    ///         },
+    ///     ),
+    ///     right: Expr::ident_from("xs"),
+    ///     body: Box::new(Stmt::Empty),
+    ///     is_await: false,
+    /// });
+    /// assert!(Program::module(vec![ProgramPart::Stmt(for_of)]).has_top_level_await());
+    /// ```
+    pub fn has_top_level_await(&self) -> bool {
+        struct TopLevelAwait(bool);
+        impl<'ast, T> visit::Visit<'ast, T> for TopLevelAwait {
+            fn visit_stmt(&mut self, stmt: &'ast Stmt<T>) {
+                match stmt {
+                    Stmt::ForOf(inner) => {
+                        self.0 |= inner.is_await
+                            || matches!(
+                                inner.left,
+                                stmt::LoopLeft::Variable(VarKind::AwaitUsing, _)
+                            );
+                    }
+                    Stmt::For(inner) => {
+                        self.0 |= matches!(
+                            inner.init,
+                            Some(stmt::LoopInit::Variable(VarKind::AwaitUsing, _))
+                        );
+                    }
+                    _ => {}
+                }
+                visit::walk_stmt(self, stmt);
+            }
+            fn visit_decl(&mut self, decl: &'ast Decl<T>) {
+                if let Decl::Var(VarKind::AwaitUsing, _) = decl {
+                    self.0 = true;
+                }
+                visit::walk_decl(self, decl);
+            }
+            fn visit_expr(&mut self, expr: &'ast Expr<T>) {
+                match expr {
+                    Expr::Await(_) => self.0 = true,
+                    Expr::ArrowFunc(_) => {}
+                    _ => visit::walk_expr(self, expr),
+                }
+            }
+            fn visit_func(&mut self, _func: &'ast Func<T>) {}
+            fn visit_prop(&mut self, prop: &'ast Prop<T>) {
+                // a static block can't use `await`
+                if !matches!(prop.value, expr::PropValue::Block(_)) {
+                    visit::walk_prop(self, prop);
+                }
+            }
+        }
+        match self.source_type() {
+            SourceType::Module => {
+                let mut found = TopLevelAwait(false);
+                visit::Visit::visit_program(&mut found, self);
+                found.0
+            }
+            SourceType::Script => false,
+        }
+    }
+    /// The directives at the start of this program, see
+    /// [`FuncBody::directive_prologue`]
+    pub fn directive_prologue(&self) -> &[ProgramPart<T>] {
+        directive_prologue(self.parts())
+    }
+    /// The number of nodes in this program, including the program
+    /// itself, see [`Expr::node_count`](crate::expr::Expr::node_count)
+    /// ```
+    /// use resast::prelude::*;
+    ///
+    /// // a + b * c;
+    /// let expr = Expr::binary(
+    ///     BinaryOp::Plus,
+    ///     Expr::ident_from("a"),
+    ///     Expr::binary(BinaryOp::Times, Expr::ident_from("b"), Expr::ident_from("c")),
+    /// );
+    /// let program = Program::script(vec![ProgramPart::Stmt(Stmt::Expr(expr))]);
+    /// assert_eq!(program.node_count(), 7);
+    /// // let x = 1;
+    /// let program = Program::script(vec![ProgramPart::Decl(Decl::Var(
+    ///     VarKind::Let,
+    ///     vec![VarDecl {
+    ///         id: Pat::ident_from("x"),
+    ///         init: Some(Expr::lit(Lit::number_from("1"))),
+    ///     }],
+    /// ))]);
+    /// assert_eq!(program.node_count(), 5);
+    /// ```
+    pub fn node_count(&self) -> usize {
+        let mut counter = visit::NodeCounter::default();
+        visit::Visit::visit_program(&mut counter, self);
+        counter.0
     }
 }
 
+impl<T> Program<T>
+where
+    T: AsRef<str>,
+{
+    /// If this program is strict mode code, modules always are and
+    /// scripts are when their directive prologue contains `'use strict'`
+    /// ```
+    /// use resast::prelude::*;
+    ///
+    /// let parts = || vec![ProgramPart::<&str>::Stmt(Stmt::Empty)];
+    /// assert!(Program::module(parts()).is_strict());
+    /// assert!(!Program::script(parts()).is_strict());
+    /// ```
+    pub fn is_strict(&self) -> bool {
+        self.source_type() == SourceType::Module || prologue_is_strict(self.parts())
+    }
+}
+
+/// A program that borrows its source text until a value is replaced,
+/// see [`Program::into_cow`]
+pub type CowProgram<'a> = Program<Cow<'a, str>>;
+
+impl<'a> Program<&'a str> {
+    /// Wrap every source value in `Cow::Borrowed`, only the values
+    /// that are later replaced with `Cow::Owned` will allocate
+    /// ```
+    /// use std::borrow::Cow;
+    /// use resast::prelude::*;
+    /// use resast::visit::VisitMut;
+    ///
+    /// struct Rename;
+    ///
+    /// impl<'a> VisitMut<Cow<'a, str>> for Rename {
+    ///     fn visit_ident_mut(&mut self, ident: &mut Ident<Cow<'a, str>>) {
+    ///         if ident.name == "a" {
+    ///             ident.name = Cow::Owned(String::from("renamed"));
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// // a + b;
+    /// let program = Program::script(vec![ProgramPart::Stmt(Stmt::Expr(Expr::Binary(BinaryExpr {
+    ///     operator: BinaryOp::Plus,
+    ///     left: Box::new(Expr::ident_from("a")),
+    ///     right: Box::new(Expr::ident_from("b")),
+    /// })))]);
+    /// let mut program: CowProgram = program.into_cow();
+    /// Rename.visit_program_mut(&mut program);
+    /// let (left, right) = match &program.parts()[0] {
+    ///     ProgramPart::Stmt(Stmt::Expr(Expr::Binary(sum))) => (&*sum.left, &*sum.right),
+    ///     _ => unreachable!(),
+    /// };
+    /// assert!(matches!(left, Expr::Ident(Ident { name: Cow::Owned(name) }) if name == "renamed"));
+    /// assert!(matches!(right, Expr::Ident(Ident { name: Cow::Borrowed("b") })));
+    /// ```
+    pub fn into_cow(self) -> CowProgram<'a> {
+        self.map_source_with(&mut Cow::Borrowed)
+    }
+}
+
+/// The leading run of directives in `parts`
+fn directive_prologue<T>(parts: &[ProgramPart<T>]) -> &[ProgramPart<T>] {
+    let len = parts
+        .iter()
+        .take_while(|part| matches!(part, ProgramPart::Dir(_)))
+        .count();
+    &parts[..len]
+}
+
+/// Only directives before the first statement or declaration
+/// are part of the prologue
+fn prologue_is_strict<T: AsRef<str>>(parts: &[ProgramPart<T>]) -> bool {
+    parts
+        .iter()
+        .map_while(ProgramPart::as_dir)
+        .any(|dir| matches!(&dir.expr, Lit::String(s) if s.inner_matches("use strict")))
+}
+
 /// A single part of a Javascript program.
 /// This will be either a Directive, Decl or a Stmt
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub enum ProgramPart<T> {
     /// A Directive like `'use strict';`
@@ -112,26 +808,124 @@ where
     type Allocated = ProgramPart<String>;
 
     fn into_allocated(self) -> Self::Allocated {
+        self.map_source_with(&mut |source: T| source.to_string())
+    }
+}
+
+impl<'a> AsBorrowed<'a> for ProgramPart<String> {
+    type Borrowed = ProgramPart<&'a str>;
+
+    fn as_borrowed(&'a self) -> Self::Borrowed {
+        match self {
+            ProgramPart::Dir(inner) => ProgramPart::Dir(inner.as_borrowed()),
+            ProgramPart::Decl(inner) => ProgramPart::Decl(inner.as_borrowed()),
+            ProgramPart::Stmt(inner) => ProgramPart::Stmt(inner.as_borrowed()),
+        }
+    }
+}
+
+impl<T, U> MapSource<T, U> for ProgramPart<T> {
+    type Output = ProgramPart<U>;
+
+    fn map_source_with(self, f: &mut impl FnMut(T) -> U) -> Self::Output {
         match self {
-            ProgramPart::Dir(inner) => ProgramPart::Dir(inner.into_allocated()),
-            ProgramPart::Decl(inner) => ProgramPart::Decl(inner.into_allocated()),
-            ProgramPart::Stmt(inner) => ProgramPart::Stmt(inner.into_allocated()),
+            ProgramPart::Dir(inner) => ProgramPart::Dir(inner.map_source_with(f)),
+            ProgramPart::Decl(inner) => ProgramPart::Decl(inner.map_source_with(f)),
+            ProgramPart::Stmt(inner) => ProgramPart::Stmt(inner.map_source_with(f)),
         }
     }
 }
 
 impl<T> ProgramPart<T> {
+    /// Pass every source value through `f`, see [`Program::map_source`](crate::Program::map_source)
+    pub fn map_source<U>(self, mut f: impl FnMut(T) -> U) -> ProgramPart<U> {
+        self.map_source_with(&mut f)
+    }
     pub fn decl(inner: Decl<T>) -> Self {
         ProgramPart::Decl(inner)
     }
     pub fn stmt(inner: Stmt<T>) -> Self {
         ProgramPart::Stmt(inner)
     }
+    /// ```
+    /// use resast::prelude::*;
+    ///
+    /// let program = Program::script(vec![
+    ///     ProgramPart::Dir(Dir {
+    ///         expr: Lit::single_string_from("use strict"),
+    ///         dir: "use strict",
+    ///     }),
+    ///     ProgramPart::Decl(Decl::Var(VarKind::Const, vec![])),
+    ///     ProgramPart::Stmt(Stmt::Empty),
+    /// ]);
+    /// let parts = program.parts();
+    /// assert!(parts[0].is_dir() && !parts[0].is_decl() && !parts[0].is_stmt());
+    /// assert!(parts[1].is_decl() && !parts[1].is_dir() && !parts[1].is_stmt());
+    /// assert!(parts[2].is_stmt() && !parts[2].is_dir() && !parts[2].is_decl());
+    /// assert_eq!(parts[0].as_dir().map(|d| d.dir), Some("use strict"));
+    /// assert!(parts[0].as_stmt().is_none());
+    /// assert_eq!(parts[1].as_decl(), Some(&Decl::Var(VarKind::Const, vec![])));
+    /// assert_eq!(parts[2].as_stmt(), Some(&Stmt::Empty));
+    /// let mut parts = program.into_parts().into_iter();
+    /// assert!(parts.next().and_then(ProgramPart::into_dir).is_some());
+    /// assert!(parts.next().and_then(ProgramPart::into_stmt).is_none());
+    /// assert_eq!(parts.next().and_then(ProgramPart::into_stmt), Some(Stmt::Empty));
+    /// ```
+    pub fn is_dir(&self) -> bool {
+        matches!(self, ProgramPart::Dir(_))
+    }
+    pub fn is_decl(&self) -> bool {
+        matches!(self, ProgramPart::Decl(_))
+    }
+    pub fn is_stmt(&self) -> bool {
+        matches!(self, ProgramPart::Stmt(_))
+    }
+    pub fn as_dir(&self) -> Option<&Dir<T>> {
+        match self {
+            ProgramPart::Dir(inner) => Some(inner),
+            _ => None,
+        }
+    }
+    pub fn as_decl(&self) -> Option<&Decl<T>> {
+        match self {
+            ProgramPart::Decl(inner) => Some(inner),
+            _ => None,
+        }
+    }
+    pub fn as_stmt(&self) -> Option<&Stmt<T>> {
+        match self {
+            ProgramPart::Stmt(inner) => Some(inner),
+            _ => None,
+        }
+    }
+    pub fn into_dir(self) -> Option<Dir<T>> {
+        match self {
+            ProgramPart::Dir(inner) => Some(inner),
+            _ => None,
+        }
+    }
+    pub fn into_decl(self) -> Option<Decl<T>> {
+        match self {
+            ProgramPart::Decl(inner) => Some(inner),
+            _ => None,
+        }
+    }
+    pub fn into_stmt(self) -> Option<Stmt<T>> {
+        match self {
+            ProgramPart::Stmt(inner) => Some(inner),
+            _ => None,
+        }
+    }
 }
 
 /// pretty much always `'use strict'`, this can appear at the
 /// top of a file or function
-#[derive(Debug, Clone, PartialEq)]
+///
+/// Only string literal statements in the directive prologue, before
+/// any other kind of statement, are directives. An empty string like
+/// `'';` is still a directive there, while the same literal anywhere
+/// later in the body should be a `Stmt::Expr` instead.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct Dir<T> {
     pub expr: Lit<T>,
@@ -145,9 +939,28 @@ where
     type Allocated = Dir<String>;
 
     fn into_allocated(self) -> Self::Allocated {
+        self.map_source_with(&mut |source: T| source.to_string())
+    }
+}
+
+impl<'a> AsBorrowed<'a> for Dir<String> {
+    type Borrowed = Dir<&'a str>;
+
+    fn as_borrowed(&'a self) -> Self::Borrowed {
         Dir {
-            expr: self.expr.into_allocated(),
-            dir: self.dir.to_string(),
+            expr: self.expr.as_borrowed(),
+            dir: self.dir.as_str(),
+        }
+    }
+}
+
+impl<T, U> MapSource<T, U> for Dir<T> {
+    type Output = Dir<U>;
+
+    fn map_source_with(self, f: &mut impl FnMut(T) -> U) -> Self::Output {
+        Dir {
+            expr: self.expr.map_source_with(f),
+            dir: f(self.dir),
         }
     }
 }
@@ -162,7 +975,7 @@ where
 /// var x = function() {}
 /// let y = function q() {}
 /// ```
-#[derive(PartialEq, Debug, Clone)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct Func<T> {
     pub id: Option<Ident<T>>,
@@ -179,14 +992,36 @@ where
     type Allocated = Func<String>;
 
     fn into_allocated(self) -> Self::Allocated {
+        self.map_source_with(&mut |source: T| source.to_string())
+    }
+}
+
+impl<'a> AsBorrowed<'a> for Func<String> {
+    type Borrowed = Func<&'a str>;
+
+    fn as_borrowed(&'a self) -> Self::Borrowed {
         Func {
-            id: self.id.map(IntoAllocated::into_allocated),
+            id: self.id.as_borrowed(),
+            params: self.params.iter().map(|p| p.as_borrowed()).collect(),
+            body: self.body.as_borrowed(),
+            generator: self.generator,
+            is_async: self.is_async,
+        }
+    }
+}
+
+impl<T, U> MapSource<T, U> for Func<T> {
+    type Output = Func<U>;
+
+    fn map_source_with(self, f: &mut impl FnMut(T) -> U) -> Self::Output {
+        Func {
+            id: self.id.map_source_with(f),
             params: self
                 .params
                 .into_iter()
-                .map(|p| p.into_allocated())
+                .map(|p| p.map_source_with(f))
                 .collect(),
-            body: self.body.into_allocated(),
+            body: self.body.map_source_with(f),
             generator: self.generator,
             is_async: self.is_async,
         }
@@ -209,10 +1044,117 @@ impl<T> Func<T> {
             is_async,
         }
     }
+    pub fn builder() -> FuncBuilder<T> {
+        FuncBuilder::default()
+    }
+    /// If this is an `async` function, the same as
+    /// [`spanned::Func::is_async`](crate::spanned::Func::is_async)
+    /// ```
+    /// use resast::prelude::*;
+    ///
+    /// // async function* f() {}
+    /// let func: Func<&str> = Func::builder().id("f").is_async(true).generator(true).build();
+    /// assert!(func.is_async());
+    /// assert!(func.generator());
+    /// // function g() {}
+    /// let func: Func<&str> = Func::builder().id("g").build();
+    /// assert!(!func.is_async());
+    /// assert!(!func.generator());
+    /// ```
+    pub fn is_async(&self) -> bool {
+        self.is_async
+    }
+    /// If this is a generator function, the same as
+    /// [`spanned::Func::generator`](crate::spanned::Func::generator)
+    pub fn generator(&self) -> bool {
+        self.generator
+    }
+    /// Always `false`, a regular function binds its own `this`
+    /// unlike an [`ArrowFuncExpr`](crate::expr::ArrowFuncExpr::captures_this)
+    pub fn captures_this(&self) -> bool {
+        false
+    }
+}
+
+/// Chainable construction of a `Func`, everything not set
+/// defaults to an anonymous, synchronous, non-generator function
+/// with no parameters and an empty body
+/// ```
+/// use resast::prelude::*;
+///
+/// // async function* f(a, b) {}
+/// let func = Func::builder()
+///     .id("f")
+///     .param(FuncArg::pat(Pat::ident_from("a")))
+///     .param(FuncArg::pat(Pat::ident_from("b")))
+///     .generator(true)
+///     .is_async(true)
+///     .build();
+/// assert_eq!(func.id, Some(Ident::from("f")));
+/// assert_eq!(func.params.len(), 2);
+/// assert_eq!(func.body, FuncBody(vec![]));
+/// assert!(func.generator);
+/// assert!(func.is_async);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FuncBuilder<T> {
+    id: Option<Ident<T>>,
+    params: Vec<FuncArg<T>>,
+    body: Vec<ProgramPart<T>>,
+    generator: bool,
+    is_async: bool,
+}
+
+impl<T> Default for FuncBuilder<T> {
+    fn default() -> Self {
+        Self {
+            id: None,
+            params: Vec::new(),
+            body: Vec::new(),
+            generator: false,
+            is_async: false,
+        }
+    }
+}
+
+impl<T> FuncBuilder<T> {
+    pub fn id(mut self, name: T) -> Self {
+        self.id = Some(Ident { name });
+        self
+    }
+    pub fn param(mut self, param: FuncArg<T>) -> Self {
+        self.params.push(param);
+        self
+    }
+    pub fn params(mut self, params: impl IntoIterator<Item = FuncArg<T>>) -> Self {
+        self.params.extend(params);
+        self
+    }
+    pub fn body(mut self, body: Vec<ProgramPart<T>>) -> Self {
+        self.body = body;
+        self
+    }
+    pub fn generator(mut self, generator: bool) -> Self {
+        self.generator = generator;
+        self
+    }
+    pub fn is_async(mut self, is_async: bool) -> Self {
+        self.is_async = is_async;
+        self
+    }
+    pub fn build(self) -> Func<T> {
+        Func {
+            id: self.id,
+            params: self.params,
+            body: FuncBody(self.body),
+            generator: self.generator,
+            is_async: self.is_async,
+        }
+    }
 }
 
 /// A single function argument from a function signature
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub enum FuncArg<T> {
     Expr(Expr<T>),
@@ -226,9 +1168,28 @@ where
     type Allocated = FuncArg<String>;
 
     fn into_allocated(self) -> Self::Allocated {
+        self.map_source_with(&mut |source: T| source.to_string())
+    }
+}
+
+impl<'a> AsBorrowed<'a> for FuncArg<String> {
+    type Borrowed = FuncArg<&'a str>;
+
+    fn as_borrowed(&'a self) -> Self::Borrowed {
+        match self {
+            FuncArg::Expr(inner) => FuncArg::Expr(inner.as_borrowed()),
+            FuncArg::Pat(inner) => FuncArg::Pat(inner.as_borrowed()),
+        }
+    }
+}
+
+impl<T, U> MapSource<T, U> for FuncArg<T> {
+    type Output = FuncArg<U>;
+
+    fn map_source_with(self, f: &mut impl FnMut(T) -> U) -> Self::Output {
         match self {
-            FuncArg::Expr(inner) => FuncArg::Expr(inner.into_allocated()),
-            FuncArg::Pat(inner) => FuncArg::Pat(inner.into_allocated()),
+            FuncArg::Expr(inner) => FuncArg::Expr(inner.map_source_with(f)),
+            FuncArg::Pat(inner) => FuncArg::Pat(inner.map_source_with(f)),
         }
     }
 }
@@ -243,7 +1204,7 @@ impl<T> FuncArg<T> {
 }
 
 /// The block statement that makes up the function's body
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct FuncBody<T>(pub Vec<ProgramPart<T>>);
 
@@ -254,7 +1215,271 @@ where
     type Allocated = FuncBody<String>;
 
     fn into_allocated(self) -> Self::Allocated {
-        FuncBody(self.0.into_iter().map(|p| p.into_allocated()).collect())
+        self.map_source_with(&mut |source: T| source.to_string())
+    }
+}
+
+impl<'a> AsBorrowed<'a> for FuncBody<String> {
+    type Borrowed = FuncBody<&'a str>;
+
+    fn as_borrowed(&'a self) -> Self::Borrowed {
+        FuncBody(self.0.iter().map(|p| p.as_borrowed()).collect())
+    }
+}
+
+impl<T, U> MapSource<T, U> for FuncBody<T> {
+    type Output = FuncBody<U>;
+
+    fn map_source_with(self, f: &mut impl FnMut(T) -> U) -> Self::Output {
+        FuncBody(self.0.into_iter().map(|p| p.map_source_with(f)).collect())
+    }
+}
+
+/// ```
+/// use resast::prelude::*;
+///
+/// let body: FuncBody<&str> = ["a", "b"]
+///     .into_iter()
+///     .map(|name| ProgramPart::Stmt(Stmt::Expr(Expr::ident_from(name))))
+///     .collect();
+/// assert_eq!(body.0.len(), 2);
+/// ```
+impl<T> FromIterator<ProgramPart<T>> for FuncBody<T> {
+    fn from_iter<I: IntoIterator<Item = ProgramPart<T>>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl<T> FuncBody<T> {
+    /// The directives at the start of this body, everything up to
+    /// the first statement or declaration
+    /// ```
+    /// use resast::prelude::*;
+    ///
+    /// let dir = |text| {
+    ///     ProgramPart::Dir(Dir {
+    ///         expr: Lit::single_string_from(text),
+    ///         dir: text,
+    ///     })
+    /// };
+    /// // function f() { 'use strict'; 'use asm'; ; 'not a directive'; }
+    /// let func = Func::builder()
+    ///     .id("f")
+    ///     .body(vec![
+    ///         dir("use strict"),
+    ///         dir("use asm"),
+    ///         ProgramPart::Stmt(Stmt::Empty),
+    ///         dir("not a directive"),
+    ///     ])
+    ///     .build();
+    /// let prologue = func.body.directive_prologue();
+    /// assert_eq!(prologue, [dir("use strict"), dir("use asm")]);
+    /// assert!(FuncBody::<&str>(vec![]).directive_prologue().is_empty());
+    /// ```
+    pub fn directive_prologue(&self) -> &[ProgramPart<T>] {
+        directive_prologue(&self.0)
+    }
+}
+
+impl<T> FuncBody<T>
+where
+    T: AsRef<str>,
+{
+    /// If the directive prologue of this body contains `'use strict'`
+    /// ```
+    /// use resast::prelude::*;
+    ///
+    /// let use_strict = || {
+    ///     ProgramPart::Dir(Dir {
+    ///         expr: Lit::single_string_from("use strict"),
+    ///         dir: "use strict",
+    ///     })
+    /// };
+    /// let strict = FuncBody(vec![use_strict(), ProgramPart::Stmt(Stmt::Empty)]);
+    /// assert!(strict.is_strict());
+    /// let sloppy = FuncBody(vec![ProgramPart::Stmt(Stmt::Empty), use_strict()]);
+    /// assert!(!sloppy.is_strict());
+    /// let double = FuncBody(vec![ProgramPart::Dir(Dir {
+    ///     expr: Lit::double_string_from("use strict"),
+    ///     dir: "use strict",
+    /// })]);
+    /// assert!(double.is_strict());
+    /// assert!(Program::script(double.0).is_strict());
+    /// ```
+    pub fn is_strict(&self) -> bool {
+        prologue_is_strict(&self.0)
+    }
+    /// If this body refers to the enclosing function's `arguments`
+    ///
+    /// Nested arrow functions are searched since they share
+    /// `arguments` with this body, nested regular functions are
+    /// not. A `let`, `const`, function or class declared directly
+    /// in this body named `arguments` shadows it, a `var` named
+    /// `arguments` reuses the existing binding and does not.
+    /// Bindings in arrow parameters, nested blocks and catch clauses
+    /// only shadow it inside of that scope and are ignored.
+    /// ```
+    /// use resast::prelude::*;
+    ///
+    /// let args_0 = || {
+    ///     Expr::member(Expr::ident_from("arguments"), Expr::Lit(Lit::number_from("0")), true)
+    /// };
+    /// let ret = |expr| ProgramPart::Stmt(Stmt::Return(Some(expr)));
+    /// let arrow = |params, body| {
+    ///     Expr::ArrowFunc(ArrowFuncExpr {
+    ///         id: None,
+    ///         params,
+    ///         body: ArrowFuncBody::Expr(Box::new(body)),
+    ///         expression: true,
+    ///         generator: false,
+    ///         is_async: false,
+    ///     })
+    /// };
+    /// let decl = |kind, init| {
+    ///     Decl::Var(
+    ///         kind,
+    ///         vec![VarDecl {
+    ///             id: Pat::ident_from("arguments"),
+    ///             init: Some(init),
+    ///         }],
+    ///     )
+    /// };
+    /// // return arguments[0];
+    /// assert!(FuncBody(vec![ret(args_0())]).references_arguments());
+    /// // return () => arguments[0];
+    /// assert!(FuncBody(vec![ret(arrow(vec![], args_0()))]).references_arguments());
+    /// // return function() { return arguments[0]; };
+    /// let func = Func::builder().body(vec![ret(args_0())]).build();
+    /// assert!(!FuncBody(vec![ret(Expr::Func(func))]).references_arguments());
+    /// // var arguments = []; return arguments[0];
+    /// let reuse = ProgramPart::Decl(decl(VarKind::Var, Expr::Array(vec![])));
+    /// assert!(FuncBody(vec![reuse, ret(args_0())]).references_arguments());
+    /// // let arguments = []; return arguments[0];
+    /// let shadow = ProgramPart::Decl(decl(VarKind::Let, Expr::Array(vec![])));
+    /// assert!(!FuncBody(vec![shadow, ret(args_0())]).references_arguments());
+    /// // return x.arguments;
+    /// let prop = Expr::member(Expr::ident_from("x"), Expr::ident_from("arguments"), false);
+    /// assert!(!FuncBody(vec![ret(prop)]).references_arguments());
+    /// // const g = (arguments) => 1; return arguments;
+    /// let param = vec![FuncArg::Pat(Pat::ident_from("arguments"))];
+    /// let g = Decl::Var(
+    ///     VarKind::Const,
+    ///     vec![VarDecl {
+    ///         id: Pat::ident_from("g"),
+    ///         init: Some(arrow(param, Expr::Lit(Lit::number_from("1")))),
+    ///     }],
+    /// );
+    /// let body = FuncBody(vec![ProgramPart::Decl(g), ret(Expr::ident_from("arguments"))]);
+    /// assert!(body.references_arguments());
+    /// // { let arguments = []; } return arguments;
+    /// let block = Stmt::Block(BlockStmt(vec![ProgramPart::Decl(decl(
+    ///     VarKind::Let,
+    ///     Expr::Array(vec![]),
+    /// ))]));
+    /// let body = FuncBody(vec![ProgramPart::Stmt(block), ret(Expr::ident_from("arguments"))]);
+    /// assert!(body.references_arguments());
+    /// // return class { [arguments[0]]() {} };
+    /// let method = Prop {
+    ///     key: PropKey::Expr(args_0()),
+    ///     value: PropValue::Expr(Expr::Func(Func::builder().build())),
+    ///     kind: PropKind::Method,
+    ///     method: true,
+    ///     computed: true,
+    ///     short_hand: false,
+    ///     is_static: false,
+    ///     decorators: vec![],
+    /// };
+    /// let class = Class {
+    ///     decorators: vec![],
+    ///     id: None,
+    ///     super_class: None,
+    ///     body: ClassBody(vec![method]),
+    /// };
+    /// assert!(FuncBody(vec![ret(Expr::Class(class))]).references_arguments());
+    /// ```
+    pub fn references_arguments(&self) -> bool {
+        #[derive(Default)]
+        struct Arguments {
+            in_pat: bool,
+            referenced: bool,
+            bound: bool,
+        }
+        impl Arguments {
+            fn is_arguments<T: AsRef<str>>(ident: &Ident<T>) -> bool {
+                ident.name.as_ref() == "arguments"
+            }
+            fn bind<'a, T: AsRef<str> + 'a>(
+                &mut self,
+                decls: impl IntoIterator<Item = &'a decl::VarDecl<T>>,
+            ) {
+                for decl in decls {
+                    self.bound |= decl.id.bound_names().into_iter().any(Self::is_arguments);
+                }
+            }
+        }
+        impl<'ast, T: AsRef<str>> visit::Visit<'ast, T> for Arguments {
+            fn visit_expr(&mut self, expr: &'ast Expr<T>) {
+                let in_pat = std::mem::replace(&mut self.in_pat, false);
+                match expr {
+                    Expr::Member(member) if !member.computed() => self.visit_expr(&member.object),
+                    _ => visit::walk_expr(self, expr),
+                }
+                self.in_pat = in_pat;
+            }
+            fn visit_pat(&mut self, pat: &'ast Pat<T>) {
+                // the names in a pattern are bindings or targets, not references
+                let in_pat = std::mem::replace(&mut self.in_pat, true);
+                visit::walk_pat(self, pat);
+                self.in_pat = in_pat;
+            }
+            fn visit_prop(&mut self, prop: &'ast Prop<T>) {
+                // a plain key is a name, not a reference
+                if prop.computed || prop.short_hand {
+                    return visit::walk_prop(self, prop);
+                }
+                match &prop.value {
+                    expr::PropValue::Expr(expr) => self.visit_expr(expr),
+                    expr::PropValue::Pat(pat) => self.visit_pat(pat),
+                    // a static block has its own `arguments`
+                    expr::PropValue::Block(_) | expr::PropValue::None => {}
+                }
+            }
+            fn visit_func(&mut self, _func: &'ast Func<T>) {}
+            fn visit_class(&mut self, class: &'ast Class<T>) {
+                if let Some(super_class) = &class.super_class {
+                    self.visit_expr(super_class);
+                }
+                // computed keys are evaluated in this scope, the rest
+                // of the body is not
+                for prop in &class.body.0 {
+                    if let (true, expr::PropKey::Expr(key)) = (prop.computed, &prop.key) {
+                        self.visit_expr(key);
+                    }
+                }
+            }
+            fn visit_ident(&mut self, ident: &'ast Ident<T>) {
+                if !self.in_pat && Self::is_arguments(ident) {
+                    self.referenced = true;
+                }
+            }
+        }
+        let mut found = Arguments::default();
+        for part in &self.0 {
+            // lexical declarations only shadow it at the top of the body
+            if let ProgramPart::Decl(decl) = part {
+                match decl {
+                    // a `var` reuses the binding of `arguments`
+                    Decl::Var(kind, decls) if *kind != VarKind::Var => found.bind(decls),
+                    Decl::Func(Func { id: Some(id), .. })
+                    | Decl::Class(Class { id: Some(id), .. }) => {
+                        found.bound |= Arguments::is_arguments(id)
+                    }
+                    _ => {}
+                }
+            }
+            visit::Visit::visit_program_part(&mut found, part);
+        }
+        found.referenced && !found.bound
     }
 }
 
@@ -284,9 +1509,10 @@ where
 ///     }
 /// }
 /// ```
-#[derive(PartialEq, Debug, Clone)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct Class<T> {
+    pub decorators: Vec<Decorator<T>>,
     pub id: Option<Ident<T>>,
     pub super_class: Option<Box<Expr<T>>>,
     pub body: ClassBody<T>,
@@ -299,15 +1525,45 @@ where
     type Allocated = Class<String>;
 
     fn into_allocated(self) -> Self::Allocated {
+        self.map_source_with(&mut |source: T| source.to_string())
+    }
+}
+
+impl<'a> AsBorrowed<'a> for Class<String> {
+    type Borrowed = Class<&'a str>;
+
+    fn as_borrowed(&'a self) -> Self::Borrowed {
+        Class {
+            decorators: self
+                .decorators
+                .iter()
+                .map(AsBorrowed::as_borrowed)
+                .collect(),
+            id: self.id.as_borrowed(),
+            super_class: self.super_class.as_borrowed(),
+            body: self.body.as_borrowed(),
+        }
+    }
+}
+
+impl<T, U> MapSource<T, U> for Class<T> {
+    type Output = Class<U>;
+
+    fn map_source_with(self, f: &mut impl FnMut(T) -> U) -> Self::Output {
         Class {
-            id: self.id.map(IntoAllocated::into_allocated),
-            super_class: self.super_class.map(IntoAllocated::into_allocated),
-            body: self.body.into_allocated(),
+            decorators: self
+                .decorators
+                .into_iter()
+                .map(|e| e.map_source_with(f))
+                .collect(),
+            id: self.id.map_source_with(f),
+            super_class: self.super_class.map_source_with(f),
+            body: self.body.map_source_with(f),
         }
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct ClassBody<T>(pub Vec<Prop<T>>);
 
@@ -318,18 +1574,104 @@ where
     type Allocated = ClassBody<String>;
 
     fn into_allocated(self) -> Self::Allocated {
-        ClassBody(
-            self.0
-                .into_iter()
-                .map(IntoAllocated::into_allocated)
-                .collect(),
-        )
+        self.map_source_with(&mut |source: T| source.to_string())
+    }
+}
+
+impl<'a> AsBorrowed<'a> for ClassBody<String> {
+    type Borrowed = ClassBody<&'a str>;
+
+    fn as_borrowed(&'a self) -> Self::Borrowed {
+        ClassBody(self.0.iter().map(AsBorrowed::as_borrowed).collect())
+    }
+}
+
+impl<T, U> MapSource<T, U> for ClassBody<T> {
+    type Output = ClassBody<U>;
+
+    fn map_source_with(self, f: &mut impl FnMut(T) -> U) -> Self::Output {
+        ClassBody(self.0.into_iter().map(|e| e.map_source_with(f)).collect())
+    }
+}
+
+/// A decorator applied to a class or class member
+/// ```js
+/// @sealed
+/// class Thing {
+///     @logged('verbose')
+///     stuff() {}
+/// }
+/// ```
+///
+/// ```
+/// use resast::prelude::*;
+/// use resast::codegen::WriteJs;
+///
+/// let method = Prop {
+///     key: PropKey::Expr(Expr::ident_from("stuff")),
+///     value: PropValue::Expr(Expr::Func(Func::builder().build())),
+///     kind: PropKind::Method,
+///     method: true,
+///     computed: false,
+///     short_hand: false,
+///     is_static: false,
+///     decorators: vec![Decorator {
+///         expr: Expr::call(
+///             Expr::ident_from("logged"),
+///             vec![Expr::lit(Lit::single_string_from("verbose"))],
+///         ),
+///     }],
+/// };
+/// let mut class = Class::new(Some(Ident::from("Thing")), None, vec![method]);
+/// class.decorators.push(Decorator {
+///     expr: Expr::call(Expr::ident_from("sealed"), vec![Expr::lit(Lit::number_from("1"))]),
+/// });
+/// assert_eq!(
+///     Decl::Class(class).to_js_string(),
+///     "@sealed(1) class Thing { @logged('verbose') stuff() {} }"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct Decorator<T> {
+    pub expr: Expr<T>,
+}
+
+impl<T> IntoAllocated for Decorator<T>
+where
+    T: ToString,
+{
+    type Allocated = Decorator<String>;
+
+    fn into_allocated(self) -> Self::Allocated {
+        self.map_source_with(&mut |source: T| source.to_string())
+    }
+}
+
+impl<'a> AsBorrowed<'a> for Decorator<String> {
+    type Borrowed = Decorator<&'a str>;
+
+    fn as_borrowed(&'a self) -> Self::Borrowed {
+        Decorator {
+            expr: self.expr.as_borrowed(),
+        }
+    }
+}
+
+impl<T, U> MapSource<T, U> for Decorator<T> {
+    type Output = Decorator<U>;
+
+    fn map_source_with(self, f: &mut impl FnMut(T) -> U) -> Self::Output {
+        Decorator {
+            expr: self.expr.map_source_with(f),
+        }
     }
 }
 
 impl<T> Class<T> {
     pub fn new(id: Option<Ident<T>>, super_class: Option<Expr<T>>, body: Vec<Prop<T>>) -> Class<T> {
         Class {
+            decorators: Vec::new(),
             id,
             super_class: super_class.map(Box::new),
             body: ClassBody(body),
@@ -339,7 +1681,7 @@ impl<T> Class<T> {
 
 /// The ways to access the member of a value
 /// Either a Period `.`, Computed `[ ]`, Optional `?.` or optional computed `?.[ ]`
-#[derive(Debug, Clone, PartialEq, Copy)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Copy)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub enum MemberIndexer {
     Period,
@@ -348,8 +1690,50 @@ pub enum MemberIndexer {
     OptionalComputed,
 }
 
-/// The kind of variable being defined (`var`/`let`/`const`)
-#[derive(Debug, Clone, PartialEq, Copy)]
+impl MemberIndexer {
+    /// `?.` or `?.[ ]`
+    pub fn is_optional(&self) -> bool {
+        matches!(self, Self::Optional | Self::OptionalComputed)
+    }
+    /// `[ ]` or `?.[ ]`
+    pub fn is_computed(&self) -> bool {
+        matches!(self, Self::Computed | Self::OptionalComputed)
+    }
+}
+
+/// The kind of variable being defined (`var`/`let`/`const`/`using`/`await using`)
+/// ```
+/// use resast::prelude::*;
+/// use resast::codegen::WriteJs;
+///
+/// let resource = VarDecl {
+///     id: Pat::ident_from("x"),
+///     init: Some(Expr::call(Expr::ident_from("getResource"), vec![])),
+/// };
+/// let block = Stmt::Block(BlockStmt(vec![
+///     ProgramPart::Decl(Decl::Var(VarKind::Using, vec![resource.clone()])),
+///     ProgramPart::Decl(Decl::Var(VarKind::AwaitUsing, vec![resource])),
+/// ]));
+/// assert_eq!(
+///     block.to_js_string(),
+///     "{ using x = getResource(); await using x = getResource(); }"
+/// );
+///
+/// let for_of = Stmt::ForOf(ForOfStmt {
+///     left: LoopLeft::Variable(
+///         VarKind::AwaitUsing,
+///         VarDecl {
+///             id: Pat::ident_from("y"),
+///             init: None,
+///         },
+///     ),
+///     right: Expr::ident_from("resources"),
+///     body: Box::new(Stmt::Block(BlockStmt(vec![]))),
+///     is_await: false,
+/// });
+/// assert_eq!(for_of.to_js_string(), "for (await using y of resources) {}");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Copy)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 #[cfg_attr(
     all(feature = "serde", feature = "esprima"),
@@ -359,10 +1743,83 @@ pub enum VarKind {
     Var,
     Let,
     Const,
+    /// `using x = getResource();`
+    Using,
+    /// `await using x = getResource();`
+    #[cfg_attr(
+        all(feature = "serde", feature = "esprima"),
+        serde(rename = "await using")
+    )]
+    AwaitUsing,
 }
 
+impl VarKind {
+    /// The keyword for this kind of declaration
+    /// ```
+    /// use resast::VarKind;
+    ///
+    /// assert_eq!(VarKind::Var.as_str(), "var");
+    /// assert_eq!(VarKind::Let.as_str(), "let");
+    /// assert_eq!(VarKind::Const.as_str(), "const");
+    /// assert_eq!(VarKind::AwaitUsing.as_str(), "await using");
+    /// ```
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            VarKind::Var => "var",
+            VarKind::Let => "let",
+            VarKind::Const => "const",
+            VarKind::Using => "using",
+            VarKind::AwaitUsing => "await using",
+        }
+    }
+}
+
+/// ```
+/// use resast::VarKind;
+///
+/// assert_eq!("var".parse(), Ok(VarKind::Var));
+/// assert_eq!("let".parse(), Ok(VarKind::Let));
+/// assert_eq!("const".parse(), Ok(VarKind::Const));
+/// assert_eq!("using".parse(), Ok(VarKind::Using));
+/// assert_eq!("await using".parse(), Ok(VarKind::AwaitUsing));
+/// assert!("static".parse::<VarKind>().is_err());
+/// ```
+impl std::str::FromStr for VarKind {
+    type Err = InvalidVarKind;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "var" => Ok(VarKind::Var),
+            "let" => Ok(VarKind::Let),
+            "const" => Ok(VarKind::Const),
+            "using" => Ok(VarKind::Using),
+            "await using" => Ok(VarKind::AwaitUsing),
+            _ => Err(InvalidVarKind(s.to_string())),
+        }
+    }
+}
+
+/// The error produced when a string is not a variable declaration keyword
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidVarKind(String);
+
+impl InvalidVarKind {
+    /// The text that could not be converted
+    pub fn value(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::fmt::Display for InvalidVarKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "`{}` is not a variable declaration keyword", self.0)
+    }
+}
+
+impl std::error::Error for InvalidVarKind {}
+
 /// The available operators for assignment Exprs
-#[derive(Debug, Clone, PartialEq, Copy)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Copy)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub enum AssignOp {
     Equal,
@@ -384,7 +1841,7 @@ pub enum AssignOp {
 }
 
 /// The available logical operators
-#[derive(Debug, Clone, PartialEq, Copy)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Copy)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub enum LogicalOp {
     Or,
@@ -393,7 +1850,21 @@ pub enum LogicalOp {
 }
 
 /// The available operations for `Binary` Exprs
-#[derive(Debug, Clone, PartialEq, Copy)]
+///
+/// Like the other operator enums this is ordered by the order
+/// the variants are declared in, which is only useful for keeping
+/// collections of operators sorted
+/// ```
+/// use resast::BinaryOp;
+/// use std::collections::{BTreeSet, HashSet};
+///
+/// let seen: HashSet<_> = [BinaryOp::Plus, BinaryOp::In, BinaryOp::Plus].into_iter().collect();
+/// assert_eq!(seen.len(), 2);
+/// assert!(seen.contains(&BinaryOp::In));
+/// let sorted: Vec<_> = seen.into_iter().collect::<BTreeSet<_>>().into_iter().collect();
+/// assert_eq!(sorted, [BinaryOp::Plus, BinaryOp::In]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Copy)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub enum BinaryOp {
     Equal,
@@ -421,7 +1892,7 @@ pub enum BinaryOp {
 }
 
 /// `++` or `--`
-#[derive(Debug, Clone, PartialEq, Copy)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Copy)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub enum UpdateOp {
     Increment,
@@ -430,7 +1901,7 @@ pub enum UpdateOp {
 
 /// The allowed operators for an Expr
 /// to be `Unary`
-#[derive(Debug, Clone, PartialEq, Copy)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Copy)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub enum UnaryOp {
     Minus,
@@ -442,8 +1913,104 @@ pub enum UnaryOp {
     Delete,
 }
 
+/// Which side of a chain of the same operator groups first
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Copy)]
+pub enum Assoc {
+    /// `a - b - c` is `(a - b) - c`
+    Left,
+    /// `a ** b ** c` is `a ** (b ** c)`
+    Right,
+}
+
+impl AssignOp {
+    /// Assignments always group from the right, `a = b = c`
+    /// is `a = (b = c)`
+    pub fn is_right_associative(&self) -> bool {
+        true
+    }
+}
+
+impl LogicalOp {
+    /// How tightly this operator binds, higher binds tighter. This
+    /// shares a scale with [`BinaryOp::precedence`] and
+    /// [`UnaryOp::precedence`]
+    /// ```
+    /// use resast::LogicalOp;
+    ///
+    /// assert!(LogicalOp::And.precedence() > LogicalOp::Or.precedence());
+    /// // `a ?? b || c` is a syntax error, parentheses are required
+    /// assert!(!LogicalOp::NullishCoalescing.can_mix_with(LogicalOp::Or));
+    /// assert!(!LogicalOp::And.can_mix_with(LogicalOp::NullishCoalescing));
+    /// assert!(LogicalOp::And.can_mix_with(LogicalOp::Or));
+    /// ```
+    pub fn precedence(&self) -> u8 {
+        match self {
+            LogicalOp::NullishCoalescing | LogicalOp::Or => 4,
+            LogicalOp::And => 5,
+        }
+    }
+    pub fn associativity(&self) -> Assoc {
+        Assoc::Left
+    }
+    /// `??` can't appear directly beside `||` or `&&`
+    /// without parentheses
+    pub fn can_mix_with(&self, other: LogicalOp) -> bool {
+        (*self == LogicalOp::NullishCoalescing) == (other == LogicalOp::NullishCoalescing)
+    }
+}
+
+impl BinaryOp {
+    /// How tightly this operator binds, higher binds tighter
+    /// ```
+    /// use resast::{Assoc, BinaryOp};
+    ///
+    /// assert!(BinaryOp::Times.precedence() > BinaryOp::Plus.precedence());
+    /// assert!(BinaryOp::PowerOf.precedence() > BinaryOp::Times.precedence());
+    /// assert_eq!(BinaryOp::PowerOf.associativity(), Assoc::Right);
+    /// assert_eq!(BinaryOp::Minus.associativity(), Assoc::Left);
+    /// ```
+    pub fn precedence(&self) -> u8 {
+        match self {
+            BinaryOp::Or => 6,
+            BinaryOp::XOr => 7,
+            BinaryOp::And => 8,
+            BinaryOp::Equal
+            | BinaryOp::NotEqual
+            | BinaryOp::StrictEqual
+            | BinaryOp::StrictNotEqual => 9,
+            BinaryOp::LessThan
+            | BinaryOp::GreaterThan
+            | BinaryOp::LessThanEqual
+            | BinaryOp::GreaterThanEqual
+            | BinaryOp::In
+            | BinaryOp::InstanceOf => 10,
+            BinaryOp::LeftShift | BinaryOp::RightShift | BinaryOp::UnsignedRightShift => 11,
+            BinaryOp::Plus | BinaryOp::Minus => 12,
+            BinaryOp::Times | BinaryOp::Over | BinaryOp::Mod => 13,
+            BinaryOp::PowerOf => 14,
+        }
+    }
+    pub fn associativity(&self) -> Assoc {
+        if *self == BinaryOp::PowerOf {
+            Assoc::Right
+        } else {
+            Assoc::Left
+        }
+    }
+}
+
+impl UnaryOp {
+    /// Every prefix operator binds tighter than any binary operator
+    pub fn precedence(&self) -> u8 {
+        15
+    }
+    pub fn associativity(&self) -> Assoc {
+        Assoc::Right
+    }
+}
+
 /// A flag for determining what kind of property
-#[derive(Debug, Clone, PartialEq, Copy)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Copy)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub enum PropKind {
     /// A property with a value
@@ -456,6 +2023,10 @@ pub enum PropKind {
     Ctor,
     /// A standard method
     Method,
+    /// A class field
+    Field,
+    /// A class static initialization block
+    StaticBlock,
 }
 
 pub trait IntoAllocated {
@@ -485,16 +2056,125 @@ where
     }
 }
 
+/// ```
+/// use resast::prelude::*;
+/// use resast::IntoAllocated;
+///
+/// let exprs: Vec<Expr<&str>> = vec![Expr::ident_from("a"), Expr::ident_from("b")];
+/// let owned: Vec<Expr<String>> = exprs.into_allocated();
+/// assert_eq!(
+///     owned,
+///     [Expr::ident_from("a".to_string()), Expr::ident_from("b".to_string())]
+/// );
+/// ```
+impl<T> IntoAllocated for Vec<T>
+where
+    T: IntoAllocated,
+{
+    type Allocated = Vec<T::Allocated>;
+    fn into_allocated(self) -> Self::Allocated {
+        self.into_iter()
+            .map(IntoAllocated::into_allocated)
+            .collect()
+    }
+}
+
+impl<A, B> IntoAllocated for (A, B)
+where
+    A: IntoAllocated,
+    B: IntoAllocated,
+{
+    type Allocated = (A::Allocated, B::Allocated);
+    fn into_allocated(self) -> Self::Allocated {
+        (self.0.into_allocated(), self.1.into_allocated())
+    }
+}
+
+/// Rebuild a tree passing every source value through `f`.
+/// `IntoAllocated` and `Program::into_cow` are both this walk with
+/// a fixed `f`. The larger nodes also have an inherent `map_source` that takes
+/// `f` by value, see [`Program::map_source`]
+pub trait MapSource<T, U> {
+    type Output;
+
+    fn map_source_with(self, f: &mut impl FnMut(T) -> U) -> Self::Output;
+}
+
+impl<T, U, N> MapSource<T, U> for Box<N>
+where
+    N: MapSource<T, U>,
+{
+    type Output = Box<N::Output>;
+
+    fn map_source_with(self, f: &mut impl FnMut(T) -> U) -> Self::Output {
+        Box::new((*self).map_source_with(f))
+    }
+}
+
+impl<T, U, N> MapSource<T, U> for Option<N>
+where
+    N: MapSource<T, U>,
+{
+    type Output = Option<N::Output>;
+
+    fn map_source_with(self, f: &mut impl FnMut(T) -> U) -> Self::Output {
+        self.map(|n| n.map_source_with(f))
+    }
+}
+
+/// Borrow every `String` in an allocated tree, producing a tree of
+/// `&str` without cloning
+///
+/// ```
+/// use resast::prelude::*;
+/// use resast::{AsBorrowed, IntoAllocated};
+///
+/// let owned: Program<String> = Program::script(vec![ProgramPart::Stmt(Stmt::Expr(
+///     Expr::ident_from("thing"),
+/// ))])
+/// .into_allocated();
+/// let borrowed: Program<&str> = owned.as_borrowed();
+/// assert_eq!(borrowed.into_allocated(), owned);
+/// ```
+pub trait AsBorrowed<'a> {
+    type Borrowed;
+
+    fn as_borrowed(&'a self) -> Self::Borrowed;
+}
+
+impl<'a, T> AsBorrowed<'a> for Box<T>
+where
+    T: AsBorrowed<'a>,
+{
+    type Borrowed = Box<T::Borrowed>;
+
+    fn as_borrowed(&'a self) -> Self::Borrowed {
+        Box::new((**self).as_borrowed())
+    }
+}
+
+impl<'a, T> AsBorrowed<'a> for Option<T>
+where
+    T: AsBorrowed<'a>,
+{
+    type Borrowed = Option<T::Borrowed>;
+    fn as_borrowed(&'a self) -> Self::Borrowed {
+        self.as_ref().map(AsBorrowed::as_borrowed)
+    }
+}
+
 pub mod prelude {
     pub use crate::decl::{
-        Decl, DefaultExportDecl, ExportSpecifier, ImportSpecifier, ModExport, ModImport,
-        NamedExportDecl, NormalImportSpec, VarDecl,
+        Decl, DefaultExportDecl, ExportSpecifier, ImportAttribute, ImportAttributeKey,
+        ImportAttributesKeyword, ImportBuilder, ImportBuilderError, ImportSpecifier, ModExport,
+        ModImport, NamedExportDecl, NormalImportSpec, VarDecl,
     };
     pub use crate::expr::{
         ArrayExpr, ArrowFuncBody, ArrowFuncExpr, AssignExpr, AssignLeft, BinaryExpr, CallExpr,
-        ConditionalExpr, Expr, Lit, LogicalExpr, MemberExpr, MetaProp, NewExpr, ObjExpr, ObjProp,
-        Prop, PropKey, PropValue, RegEx, StringLit, TaggedTemplateExpr, TemplateElement,
-        TemplateLit, UnaryExpr, UpdateExpr, YieldExpr,
+        ConditionalExpr, Expr, ExprKindError, ImportExpr, InvalidNumber, Lit, LogicalExpr,
+        MemberExpr, MetaProp, NewExpr, ObjExpr, ObjProp, Prop, PropKey, PropValue, RegEx,
+        RegExFlags, SequenceExpr, StringLit, TaggedTemplateExpr, TemplateElement, TemplateLit,
+        UnaryExpr, UnescapeError, UpdateExpr, YieldExpr,
     };
     pub use crate::pat::{ArrayPatPart, AssignPat, ObjPat, ObjPatPart, Pat};
     pub use crate::stmt::{
@@ -502,7 +2182,8 @@ pub mod prelude {
         LoopInit, LoopLeft, Stmt, SwitchCase, SwitchStmt, TryStmt, WhileStmt, WithStmt,
     };
     pub use crate::{
-        AssignOp, BinaryOp, Class, ClassBody, Dir, Func, FuncArg, FuncBody, Ident, LogicalOp,
-        Program, ProgramPart, PropKind, UnaryOp, UpdateOp, VarKind,
+        AssignOp, Assoc, BinaryOp, Class, ClassBody, CowProgram, Decorator, Dir, Func, FuncArg,
+        FuncBody, FuncBuilder, Ident, LogicalOp, PrivateIdent, Program, ProgramPart, PropKind,
+        SourceType, UnaryOp, UpdateOp, VarKind,
     };
 }
@@ -1,12 +1,12 @@
-use crate::expr::{Expr, Prop};
-use crate::{Ident, IntoAllocated};
+use crate::expr::{AssignLeft, Expr, Prop, PropKey, PropValue};
+use crate::{AsBorrowed, Ident, IntoAllocated, MapSource};
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 /// All of the different ways you can declare an identifier
 /// and/or value
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub enum Pat<T> {
     Ident(Ident<T>),
@@ -23,28 +23,155 @@ where
     type Allocated = Pat<String>;
 
     fn into_allocated(self) -> Self::Allocated {
+        self.map_source_with(&mut |source: T| source.to_string())
+    }
+}
+
+impl<'a> AsBorrowed<'a> for Pat<String> {
+    type Borrowed = Pat<&'a str>;
+
+    fn as_borrowed(&'a self) -> Self::Borrowed {
         match self {
-            Pat::Ident(inner) => Pat::Ident(inner.into_allocated()),
-            Pat::Obj(inner) => Pat::Obj(inner.into_iter().map(|a| a.into_allocated()).collect()),
+            Pat::Ident(inner) => Pat::Ident(inner.as_borrowed()),
+            Pat::Obj(inner) => Pat::Obj(inner.iter().map(|a| a.as_borrowed()).collect()),
+            Pat::Array(inner) => Pat::Array(inner.iter().map(|o| o.as_borrowed()).collect()),
+            Pat::RestElement(inner) => Pat::RestElement(inner.as_borrowed()),
+            Pat::Assign(inner) => Pat::Assign(inner.as_borrowed()),
+        }
+    }
+}
+
+impl<T, U> MapSource<T, U> for Pat<T> {
+    type Output = Pat<U>;
+
+    fn map_source_with(self, f: &mut impl FnMut(T) -> U) -> Self::Output {
+        match self {
+            Pat::Ident(inner) => Pat::Ident(inner.map_source_with(f)),
+            Pat::Obj(inner) => Pat::Obj(inner.into_iter().map(|a| a.map_source_with(f)).collect()),
             Pat::Array(inner) => Pat::Array(
                 inner
                     .into_iter()
-                    .map(|o| o.map(|a| a.into_allocated()))
+                    .map(|o| o.map(|a| a.map_source_with(f)))
                     .collect(),
             ),
-            Pat::RestElement(inner) => Pat::RestElement(inner.into_allocated()),
-            Pat::Assign(inner) => Pat::Assign(inner.into_allocated()),
+            Pat::RestElement(inner) => Pat::RestElement(inner.map_source_with(f)),
+            Pat::Assign(inner) => Pat::Assign(inner.map_source_with(f)),
         }
     }
 }
 
 impl<T> Pat<T> {
+    /// Pass every source value through `f`, see [`Program::map_source`](crate::Program::map_source)
+    pub fn map_source<U>(self, mut f: impl FnMut(T) -> U) -> Pat<U> {
+        self.map_source_with(&mut f)
+    }
     pub fn ident_from(inner: T) -> Self {
         Self::Ident(Ident { name: inner })
     }
+    /// Every identifier this pattern binds, in source order
+    ///
+    /// Default values are not searched, only the targets
+    /// they are assigned to.
+    /// ```
+    /// use resast::prelude::*;
+    ///
+    /// fn shorthand(name: &str) -> ObjPatPart<&str> {
+    ///     ObjPatPart::Assign(Prop {
+    ///         key: PropKey::Pat(Pat::ident_from(name)),
+    ///         value: PropValue::None,
+    ///         kind: PropKind::Init,
+    ///         method: false,
+    ///         computed: false,
+    ///         short_hand: true,
+    ///         is_static: false,
+    ///         decorators: Vec::new(),
+    ///     })
+    /// }
+    /// // {a, b: {c}, ...d}
+    /// let obj = Pat::Obj(vec![
+    ///     shorthand("a"),
+    ///     ObjPatPart::Assign(Prop {
+    ///         key: PropKey::Expr(Expr::ident_from("b")),
+    ///         value: PropValue::Pat(Pat::Obj(vec![shorthand("c")])),
+    ///         kind: PropKind::Init,
+    ///         method: false,
+    ///         computed: false,
+    ///         short_hand: false,
+    ///         is_static: false,
+    ///         decorators: Vec::new(),
+    ///     }),
+    ///     ObjPatPart::Rest(Box::new(Pat::ident_from("d"))),
+    /// ]);
+    /// let names: Vec<_> = obj.bound_names().into_iter().map(|i| i.name).collect();
+    /// assert_eq!(names, ["a", "c", "d"]);
+    ///
+    /// // [x, , y = 1]
+    /// let arr = Pat::Array(vec![
+    ///     Some(ArrayPatPart::Pat(Pat::ident_from("x"))),
+    ///     None,
+    ///     Some(ArrayPatPart::Pat(Pat::Assign(AssignPat {
+    ///         left: Box::new(Pat::ident_from("y")),
+    ///         right: Box::new(Expr::Lit(Lit::number_from("1"))),
+    ///     }))),
+    /// ]);
+    /// let names: Vec<_> = arr.bound_names().into_iter().map(|i| i.name).collect();
+    /// assert_eq!(names, ["x", "y"]);
+    /// ```
+    pub fn bound_names(&self) -> Vec<&Ident<T>> {
+        let mut names = Vec::new();
+        self.push_bound_names(&mut names);
+        names
+    }
+
+    fn push_bound_names<'a>(&'a self, names: &mut Vec<&'a Ident<T>>) {
+        match self {
+            Pat::Ident(ident) => names.push(ident),
+            Pat::Obj(parts) => {
+                for part in parts {
+                    match part {
+                        ObjPatPart::Assign(prop) => match &prop.value {
+                            PropValue::Pat(pat) => pat.push_bound_names(names),
+                            PropValue::Expr(expr) => push_target_names(expr, names),
+                            PropValue::Block(_) => {}
+                            PropValue::None => match &prop.key {
+                                PropKey::Pat(pat) => pat.push_bound_names(names),
+                                PropKey::Expr(expr) => push_target_names(expr, names),
+                                PropKey::Lit(_) | PropKey::None => {}
+                            },
+                        },
+                        ObjPatPart::Rest(pat) => pat.push_bound_names(names),
+                    }
+                }
+            }
+            Pat::Array(parts) => {
+                for part in parts.iter().flatten() {
+                    match part {
+                        ArrayPatPart::Pat(pat) => pat.push_bound_names(names),
+                        ArrayPatPart::Expr(expr) => push_target_names(expr, names),
+                    }
+                }
+            }
+            Pat::RestElement(pat) => pat.push_bound_names(names),
+            Pat::Assign(assign) => assign.left.push_bound_names(names),
+        }
+    }
+}
+
+/// The names bound by an expression in pattern position,
+/// `x` or `x = 1` in `[x, y = 1] = arr`
+fn push_target_names<'a, T>(expr: &'a Expr<T>, names: &mut Vec<&'a Ident<T>>) {
+    match expr {
+        Expr::Ident(ident) => names.push(ident),
+        Expr::Assign(assign) => match &assign.left {
+            AssignLeft::Pat(pat) => pat.push_bound_names(names),
+            AssignLeft::Expr(expr) => push_target_names(expr, names),
+        },
+        Expr::Spread(expr) => push_target_names(expr, names),
+        _ => {}
+    }
 }
 
-#[derive(PartialEq, Debug, Clone)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub enum ArrayPatPart<T> {
     Pat(Pat<T>),
@@ -58,9 +185,28 @@ where
     type Allocated = ArrayPatPart<String>;
 
     fn into_allocated(self) -> Self::Allocated {
+        self.map_source_with(&mut |source: T| source.to_string())
+    }
+}
+
+impl<'a> AsBorrowed<'a> for ArrayPatPart<String> {
+    type Borrowed = ArrayPatPart<&'a str>;
+
+    fn as_borrowed(&'a self) -> Self::Borrowed {
         match self {
-            ArrayPatPart::Pat(inner) => ArrayPatPart::Pat(inner.into_allocated()),
-            ArrayPatPart::Expr(inner) => ArrayPatPart::Expr(inner.into_allocated()),
+            ArrayPatPart::Pat(inner) => ArrayPatPart::Pat(inner.as_borrowed()),
+            ArrayPatPart::Expr(inner) => ArrayPatPart::Expr(inner.as_borrowed()),
+        }
+    }
+}
+
+impl<T, U> MapSource<T, U> for ArrayPatPart<T> {
+    type Output = ArrayPatPart<U>;
+
+    fn map_source_with(self, f: &mut impl FnMut(T) -> U) -> Self::Output {
+        match self {
+            ArrayPatPart::Pat(inner) => ArrayPatPart::Pat(inner.map_source_with(f)),
+            ArrayPatPart::Expr(inner) => ArrayPatPart::Expr(inner.map_source_with(f)),
         }
     }
 }
@@ -68,7 +214,35 @@ where
 /// similar to an `ObjectExpr`
 pub type ObjPat<T> = Vec<ObjPatPart<T>>;
 /// A single part of an ObjectPat
-#[derive(PartialEq, Debug, Clone)]
+///
+/// ```
+/// use resast::prelude::*;
+/// use resast::codegen::WriteJs;
+///
+/// // const { a, ...rest } = obj;
+/// let a = Prop {
+///     key: PropKey::Pat(Pat::ident_from("a")),
+///     value: PropValue::None,
+///     kind: PropKind::Init,
+///     method: false,
+///     computed: false,
+///     short_hand: true,
+///     is_static: false,
+///     decorators: Vec::new(),
+/// };
+/// let decl = Decl::Var(
+///     VarKind::Const,
+///     vec![VarDecl {
+///         id: Pat::Obj(vec![
+///             ObjPatPart::Assign(a),
+///             ObjPatPart::Rest(Box::new(Pat::ident_from("rest"))),
+///         ]),
+///         init: Some(Expr::ident_from("obj")),
+///     }],
+/// );
+/// assert_eq!(decl.to_js_string(), "const { a, ...rest } = obj;");
+/// ```
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub enum ObjPatPart<T> {
     Assign(Prop<T>),
@@ -82,15 +256,34 @@ where
     type Allocated = ObjPatPart<String>;
 
     fn into_allocated(self) -> Self::Allocated {
+        self.map_source_with(&mut |source: T| source.to_string())
+    }
+}
+
+impl<'a> AsBorrowed<'a> for ObjPatPart<String> {
+    type Borrowed = ObjPatPart<&'a str>;
+
+    fn as_borrowed(&'a self) -> Self::Borrowed {
+        match self {
+            ObjPatPart::Assign(inner) => ObjPatPart::Assign(inner.as_borrowed()),
+            ObjPatPart::Rest(inner) => ObjPatPart::Rest(inner.as_borrowed()),
+        }
+    }
+}
+
+impl<T, U> MapSource<T, U> for ObjPatPart<T> {
+    type Output = ObjPatPart<U>;
+
+    fn map_source_with(self, f: &mut impl FnMut(T) -> U) -> Self::Output {
         match self {
-            ObjPatPart::Assign(inner) => ObjPatPart::Assign(inner.into_allocated()),
-            ObjPatPart::Rest(inner) => ObjPatPart::Rest(inner.into_allocated()),
+            ObjPatPart::Assign(inner) => ObjPatPart::Assign(inner.map_source_with(f)),
+            ObjPatPart::Rest(inner) => ObjPatPart::Rest(inner.map_source_with(f)),
         }
     }
 }
 
 /// An assignment as a pattern
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct AssignPat<T> {
     pub left: Box<Pat<T>>,
@@ -104,9 +297,28 @@ where
     type Allocated = AssignPat<String>;
 
     fn into_allocated(self) -> Self::Allocated {
+        self.map_source_with(&mut |source: T| source.to_string())
+    }
+}
+
+impl<'a> AsBorrowed<'a> for AssignPat<String> {
+    type Borrowed = AssignPat<&'a str>;
+
+    fn as_borrowed(&'a self) -> Self::Borrowed {
+        AssignPat {
+            left: self.left.as_borrowed(),
+            right: self.right.as_borrowed(),
+        }
+    }
+}
+
+impl<T, U> MapSource<T, U> for AssignPat<T> {
+    type Output = AssignPat<U>;
+
+    fn map_source_with(self, f: &mut impl FnMut(T) -> U) -> Self::Output {
         AssignPat {
-            left: self.left.into_allocated(),
-            right: self.right.into_allocated(),
+            left: self.left.map_source_with(f),
+            right: self.right.map_source_with(f),
         }
     }
 }
@@ -0,0 +1,182 @@
+//! Comments and a side table for attaching them to nodes
+//!
+//! Comments aren't part of the tree, a [`CommentMap`] instead keys
+//! them by the [`SourceLocation`] of the node they belong to.
+//!
+//! ```
+//! use resast::spanned::comment::{Comment, CommentKind, CommentMap};
+//! use resast::spanned::decl::{Decl, VarDecl, VarDecls};
+//! use resast::spanned::{Ident, ListEntry, Node, Slice, VarKind};
+//! use resast::spanned::pat::Pat;
+//! use resast::spanned::Position;
+//!
+//! // // the answer
+//! // var x;
+//! let decl = Decl::Var {
+//!     decls: VarDecls {
+//!         keyword: VarKind::Var(Some(Position::new(2, 0).into())),
+//!         decls: vec![ListEntry::no_comma(VarDecl {
+//!             id: Pat::Ident(Ident::new_from_source("x", 2, 4)),
+//!             eq: None,
+//!             init: None,
+//!         })],
+//!     },
+//!     semi_colon: Some(Position::new(2, 5).into()),
+//! };
+//! let mut comments = CommentMap::new();
+//! comments.attach_leading(&decl, Comment::line(Slice::new(" the answer", 1, 2, 1, 13)));
+//!
+//! let leading = comments.leading(&decl);
+//! assert_eq!(leading.len(), 1);
+//! assert_eq!(leading[0].kind, CommentKind::Line);
+//! assert_eq!(leading[0].text.source, " the answer");
+//! assert_eq!(leading[0].loc().start, Position::new(1, 0));
+//! assert!(comments.trailing(&decl).is_empty());
+//! ```
+use std::collections::HashMap;
+
+use crate::spanned::{Node, Slice, SourceLocation};
+use crate::IntoAllocated;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum CommentKind {
+    /// `// comment`
+    Line,
+    /// `/* comment */`
+    Block,
+}
+
+/// A single comment, `text` holds everything between
+/// the delimiters and `loc` includes them
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct Comment<T> {
+    pub kind: CommentKind,
+    pub text: Slice<T>,
+    pub loc: SourceLocation,
+}
+
+impl<T> Comment<T> {
+    /// A line comment, the `//` is placed directly before `text`
+    pub fn line(text: Slice<T>) -> Self {
+        let loc = SourceLocation {
+            start: text.loc.start - 2,
+            end: text.loc.end,
+        };
+        Self {
+            kind: CommentKind::Line,
+            text,
+            loc,
+        }
+    }
+    /// A block comment, the `/*` and `*/` are placed
+    /// directly around `text`
+    pub fn block(text: Slice<T>) -> Self {
+        let loc = SourceLocation {
+            start: text.loc.start - 2,
+            end: text.loc.end + 2,
+        };
+        Self {
+            kind: CommentKind::Block,
+            text,
+            loc,
+        }
+    }
+}
+
+impl<T> IntoAllocated for Comment<T>
+where
+    T: ToString,
+{
+    type Allocated = Comment<String>;
+    fn into_allocated(self) -> Comment<String> {
+        Comment {
+            kind: self.kind,
+            text: self.text.into_allocated(),
+            loc: self.loc,
+        }
+    }
+}
+
+impl<T> Node for Comment<T> {
+    fn loc(&self) -> SourceLocation {
+        self.loc
+    }
+}
+
+/// Comments that come before or after a node, keyed
+/// by the location of that node
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommentMap<T> {
+    leading: HashMap<SourceLocation, Vec<Comment<T>>>,
+    trailing: HashMap<SourceLocation, Vec<Comment<T>>>,
+}
+
+impl<T> Default for CommentMap<T> {
+    fn default() -> Self {
+        Self {
+            leading: HashMap::new(),
+            trailing: HashMap::new(),
+        }
+    }
+}
+
+impl<T> CommentMap<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Add a comment that comes before `node`
+    pub fn attach_leading(&mut self, node: &impl Node, comment: Comment<T>) {
+        self.leading.entry(node.loc()).or_default().push(comment);
+    }
+    /// Add a comment that comes after `node`
+    pub fn attach_trailing(&mut self, node: &impl Node, comment: Comment<T>) {
+        self.trailing.entry(node.loc()).or_default().push(comment);
+    }
+    /// The comments before `node`, in the order they were attached
+    pub fn leading(&self, node: &impl Node) -> &[Comment<T>] {
+        self.leading
+            .get(&node.loc())
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+    /// The comments after `node`, in the order they were attached
+    pub fn trailing(&self, node: &impl Node) -> &[Comment<T>] {
+        self.trailing
+            .get(&node.loc())
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+    /// If no comments have been attached
+    pub fn is_empty(&self) -> bool {
+        self.leading.is_empty() && self.trailing.is_empty()
+    }
+}
+
+impl<T> IntoAllocated for CommentMap<T>
+where
+    T: ToString,
+{
+    type Allocated = CommentMap<String>;
+    fn into_allocated(self) -> CommentMap<String> {
+        let allocate = |map: HashMap<SourceLocation, Vec<Comment<T>>>| {
+            map.into_iter()
+                .map(|(loc, comments)| {
+                    let comments = comments
+                        .into_iter()
+                        .map(IntoAllocated::into_allocated)
+                        .collect();
+                    (loc, comments)
+                })
+                .collect()
+        };
+        CommentMap {
+            leading: allocate(self.leading),
+            trailing: allocate(self.trailing),
+        }
+    }
+}
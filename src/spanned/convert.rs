@@ -1,19 +1,25 @@
 //! All conversions from spanned into non-spanned types
 //!
+//! Every conversion is implemented twice from one body, see
+//! `convert!`, once for a spanned node which moves its source values
+//! into the new tree and once for a reference to one which leaves the
+//! spanned tree untouched, each `T` in that result is a `&T` pointing
+//! back into the original nodes.
 
 use std::borrow::Cow;
 
 use crate::spanned::{
     decl::{
-        Alias, Decl, DefaultExportDeclValue, DefaultImportSpec, ExportSpecifier, ImportSpecifier,
-        ModExport, ModExportSpecifier, ModImport, NamedExportDecl, NamespaceImportSpec,
-        NormalImportSpec, VarDecl,
+        Alias, Decl, DefaultExportDeclValue, DefaultImportSpec, ExportSpecifier, ImportAttribute,
+        ImportAttributeKey, ImportSpecifier, ModExport, ModExportSpecifier, ModImport,
+        NamedExportDecl, NamespaceImportSpec, NormalImportSpec, VarDecl,
     },
     expr::{
         ArrowFuncBody, ArrowFuncExpr, AssignExpr, AssignLeft, BinaryExpr, CallExpr,
-        ConditionalExpr, Expr, Lit, LogicalExpr, MemberExpr, MetaProp, NewExpr, ObjProp, Prop,
-        PropInitKey, PropKey, PropMethod, PropValue, RegEx, SequenceExprEntry, StringLit,
-        TaggedTemplateExpr, TemplateElement, TemplateLit, UnaryExpr, UpdateExpr, YieldExpr,
+        ConditionalExpr, Expr, ImportExpr, KeepParens, Lit, LogicalExpr, MemberExpr, MetaProp,
+        NewExpr, ObjProp, Prop, PropInitKey, PropKey, PropMethod, PropValue, RegEx,
+        SequenceExprEntry, StringLit, TaggedTemplateExpr, TemplateElement, TemplateLit, UnaryExpr,
+        UpdateExpr, YieldExpr,
     },
     pat::{ArrayElement, ArrayPat, ArrayPatPart, AssignPat, ObjPat, ObjPatPart, Pat},
     stmt::{
@@ -21,150 +27,283 @@ use crate::spanned::{
         LabeledStmt, LoopInit, LoopLeft, Stmt, SwitchCase, SwitchStmt, TryStmt, WhileStmt,
         WithStmt,
     },
-    tokens::{AssignOp, BinaryOp, LogicalOp, UnaryOp, UpdateOp},
-    Class, ClassBody, Dir, Func, FuncArg, FuncArgEntry, FuncBody, Ident, Program, ProgramPart,
-    Slice, VarKind,
+    tokens::{AssignOp, BinaryOp, ImportAttributesKeyword, LogicalOp, UnaryOp, UpdateOp},
+    Class, ClassBody, Decorator, Dir, Func, FuncArg, FuncArgEntry, FuncBody, Ident, PrivateIdent,
+    Program, ProgramPart, Slice, VarKind,
 };
 
+/// Implement `From<$src<T>>` and `From<&'a $src<T>>` with one body.
+///
+/// Inside the body `take!(place)` is the place itself when converting
+/// by value and a reference to it when converting a reference, any
+/// field that isn't `Copy` has to go through it. `unbox` and `opt`
+/// cover a `Box` or `Option` that is bound by a pattern instead.
+macro_rules! convert {
+    ($(
+        impl From<$src:ident> for $($dst:ident)::+ {
+            fn from($other:ident) $body:block
+        }
+    )*) => {
+        $(
+            impl<T> From<$src<T>> for $($dst)::+<T> {
+                fn from($other: $src<T>) -> Self {
+                    #[allow(unused_macros)]
+                    macro_rules! take {
+                        ($e:expr) => {
+                            $e
+                        };
+                    }
+                    $body
+                }
+            }
+
+            #[allow(clippy::into_iter_on_ref)]
+            impl<'a, T> From<&'a $src<T>> for $($dst)::+<&'a T> {
+                fn from($other: &'a $src<T>) -> Self {
+                    #[allow(unused_macros)]
+                    macro_rules! take {
+                        ($e:expr) => {
+                            &$e
+                        };
+                    }
+                    $body
+                }
+            }
+        )*
+    };
+}
+
+/// A `Box` that is either owned or borrowed
+trait Unbox {
+    type Inner;
+    fn unbox(self) -> Self::Inner;
+}
+
+impl<N> Unbox for Box<N> {
+    type Inner = N;
+    fn unbox(self) -> N {
+        *self
+    }
+}
+
+impl<'a, N> Unbox for &'a Box<N> {
+    type Inner = &'a N;
+    fn unbox(self) -> &'a N {
+        self
+    }
+}
+
+/// An `Option` that is either owned or borrowed
+trait Opt {
+    type Item;
+    fn opt(self) -> Option<Self::Item>;
+}
+
+impl<N> Opt for Option<N> {
+    type Item = N;
+    fn opt(self) -> Option<N> {
+        self
+    }
+}
+
+impl<'a, N> Opt for &'a Option<N> {
+    type Item = &'a N;
+    fn opt(self) -> Option<&'a N> {
+        self.as_ref()
+    }
+}
+
+type ArrayPatParts<T> = Vec<Option<crate::pat::ArrayPatPart<T>>>;
+type MaybeArrayPatPart<T> = Option<crate::pat::ArrayPatPart<T>>;
+
 mod decl {
     use super::*;
 
-    impl<T> From<Decl<T>> for crate::decl::Decl<T> {
-        fn from(other: Decl<T>) -> Self {
-            match other {
-                Decl::Var { decls, .. } => crate::decl::Decl::Var(
-                    decls.keyword.into(),
-                    decls.decls.into_iter().map(|d| d.item.into()).collect(),
-                ),
-                Decl::Func(inner) => crate::decl::Decl::Func(inner.into()),
-                Decl::Class(c) => crate::decl::Decl::Class(c.into()),
-                Decl::Import { import, .. } => {
-                    crate::decl::Decl::Import(Box::new((*import).into()))
+    convert! {
+        impl From<Decl> for crate::decl::Decl {
+            fn from(other) {
+                match other {
+                    Decl::Var { decls, .. } => crate::decl::Decl::Var(
+                        take!(decls.keyword).into(),
+                        take!(decls.decls)
+                            .into_iter()
+                            .map(|d| take!(d.item).into())
+                            .collect(),
+                    ),
+                    Decl::Func(inner) => crate::decl::Decl::Func(inner.into()),
+                    Decl::Class(c) => crate::decl::Decl::Class(c.into()),
+                    Decl::Import { import, .. } => {
+                        crate::decl::Decl::Import(Box::new(import.unbox().into()))
+                    }
+                    Decl::Export { export, .. } => {
+                        crate::decl::Decl::Export(Box::new(export.unbox().into()))
+                    }
                 }
-                Decl::Export { export, .. } => {
-                    crate::decl::Decl::Export(Box::new((*export).into()))
+            }
+        }
+
+        impl From<VarDecl> for crate::decl::VarDecl {
+            fn from(other) {
+                Self {
+                    id: take!(other.id).into(),
+                    init: take!(other.init).opt().map(From::from),
                 }
             }
         }
-    }
 
-    impl<T> From<VarDecl<T>> for crate::decl::VarDecl<T> {
-        fn from(other: VarDecl<T>) -> Self {
-            Self {
-                id: other.id.into(),
-                init: other.init.map(From::from),
+        impl From<ModImport> for crate::decl::ModImport {
+            fn from(other) {
+                Self {
+                    source: take!(other.source).into(),
+                    specifiers: take!(other.specifiers)
+                        .into_iter()
+                        .map(|e| take!(e.item).into())
+                        .collect(),
+                    attributes_keyword: other
+                        .attributes
+                        .as_ref()
+                        .map(|a| a.keyword.into())
+                        .unwrap_or_default(),
+                    attributes: take!(other.attributes)
+                        .opt()
+                        .map(|a| {
+                            take!(a.attributes)
+                                .into_iter()
+                                .map(|e| take!(e.item).into())
+                                .collect()
+                        })
+                        .unwrap_or_default(),
+                }
             }
         }
-    }
 
-    impl<T> From<ModImport<T>> for crate::decl::ModImport<T> {
-        fn from(other: ModImport<T>) -> Self {
-            Self {
-                source: other.source.into(),
-                specifiers: other
-                    .specifiers
-                    .into_iter()
-                    .map(|e| e.item.into())
-                    .collect(),
+        impl From<ImportAttribute> for crate::decl::ImportAttribute {
+            fn from(other) {
+                Self {
+                    key: take!(other.key).into(),
+                    value: take!(other.value).into(),
+                }
             }
         }
-    }
 
-    impl<T> From<ImportSpecifier<T>> for crate::decl::ImportSpecifier<T> {
-        fn from(other: ImportSpecifier<T>) -> Self {
-            match other {
-                ImportSpecifier::Normal(inner) => {
-                    Self::Normal(inner.specs.into_iter().map(|e| e.item.into()).collect())
+        impl From<ImportAttributeKey> for crate::decl::ImportAttributeKey {
+            fn from(other) {
+                match other {
+                    ImportAttributeKey::Ident(inner) => Self::Ident(inner.into()),
+                    ImportAttributeKey::String(inner) => Self::String(inner.into()),
                 }
-                ImportSpecifier::Default(inner) => Self::Default(inner.into()),
-                ImportSpecifier::Namespace(inner) => Self::Namespace(inner.into()),
             }
         }
-    }
 
-    impl<T> From<NormalImportSpec<T>> for crate::decl::NormalImportSpec<T> {
-        fn from(other: NormalImportSpec<T>) -> Self {
-            let imported: crate::Ident<T> = other.imported.into();
-            let alias = other.alias.map(|a| a.ident.into());
+        impl From<ImportSpecifier> for crate::decl::ImportSpecifier {
+            fn from(other) {
+                match other {
+                    ImportSpecifier::Normal(inner) => Self::Normal(
+                        take!(inner.specs)
+                            .into_iter()
+                            .map(|e| take!(e.item).into())
+                            .collect(),
+                    ),
+                    ImportSpecifier::Default(inner) => Self::Default(inner.into()),
+                    ImportSpecifier::Namespace(inner) => Self::Namespace(inner.into()),
+                }
+            }
+        }
 
-            Self { imported, alias }
+        impl From<NormalImportSpec> for crate::decl::NormalImportSpec {
+            fn from(other) {
+                let imported = take!(other.imported).into();
+                let alias = take!(other.alias).opt().map(|a| take!(a.ident).into());
+
+                Self { imported, alias }
+            }
         }
-    }
 
-    impl<T> From<DefaultImportSpec<T>> for crate::Ident<T> {
-        fn from(other: DefaultImportSpec<T>) -> Self {
-            other.id.into()
+        impl From<DefaultImportSpec> for crate::Ident {
+            fn from(other) {
+                take!(other.id).into()
+            }
         }
-    }
 
-    impl<T> From<NamespaceImportSpec<T>> for crate::Ident<T> {
-        fn from(other: NamespaceImportSpec<T>) -> Self {
-            other.ident.into()
+        impl From<NamespaceImportSpec> for crate::Ident {
+            fn from(other) {
+                take!(other.ident).into()
+            }
         }
-    }
 
-    impl<T> From<ModExport<T>> for crate::decl::ModExport<T> {
-        fn from(other: ModExport<T>) -> Self {
-            other.spec.into()
+        impl From<ModExport> for crate::decl::ModExport {
+            fn from(other) {
+                take!(other.spec).into()
+            }
         }
-    }
 
-    impl<T> From<ModExportSpecifier<T>> for crate::decl::ModExport<T> {
-        fn from(other: ModExportSpecifier<T>) -> Self {
-            match other {
-                ModExportSpecifier::Default { keyword: _, value } => Self::Default(value.into()),
-                ModExportSpecifier::Named(inner) => Self::Named(inner.into()),
-                ModExportSpecifier::All {
-                    star: _,
-                    alias,
-                    keyword: _,
-                    name,
-                } => Self::All {
-                    alias: alias.map(|a| a.ident.into()),
-                    name: name.into(),
-                },
+        impl From<ModExportSpecifier> for crate::decl::ModExport {
+            fn from(other) {
+                match other {
+                    ModExportSpecifier::Default { keyword: _, value } => {
+                        Self::Default(value.into())
+                    }
+                    ModExportSpecifier::Named(inner) => Self::Named(inner.into()),
+                    ModExportSpecifier::All {
+                        star: _,
+                        alias,
+                        keyword: _,
+                        name,
+                    } => Self::All {
+                        alias: alias.opt().map(|a| take!(a.ident).into()),
+                        name: name.into(),
+                    },
+                }
             }
         }
-    }
-    impl<T> From<NamedExportDecl<T>> for crate::decl::NamedExportDecl<T> {
-        fn from(other: NamedExportDecl<T>) -> Self {
-            match other {
-                NamedExportDecl::Decl(inner) => Self::Decl(inner.into()),
-                NamedExportDecl::Specifier(inner) => Self::Specifier(
-                    inner
-                        .list
-                        .elements
-                        .into_iter()
-                        .map(|e| e.item.into())
-                        .collect(),
-                    inner.source.map(|s| s.module.into()),
-                ),
+
+        impl From<NamedExportDecl> for crate::decl::NamedExportDecl {
+            fn from(other) {
+                match other {
+                    NamedExportDecl::Decl(inner) => Self::Decl(inner.into()),
+                    NamedExportDecl::Specifier(inner) => Self::Specifier(
+                        take!(inner.list.elements)
+                            .into_iter()
+                            .map(|e| take!(e.item).into())
+                            .collect(),
+                        take!(inner.source).opt().map(|s| take!(s.module).into()),
+                    ),
+                }
             }
         }
-    }
 
-    impl<T> From<DefaultExportDeclValue<T>> for crate::decl::DefaultExportDecl<T> {
-        fn from(other: DefaultExportDeclValue<T>) -> Self {
-            match other {
-                DefaultExportDeclValue::Decl(inner) => Self::Decl(inner.into()),
-                DefaultExportDeclValue::Expr(inner) => Self::Expr(inner.into()),
+        impl From<DefaultExportDeclValue> for crate::decl::DefaultExportDecl {
+            fn from(other) {
+                match other {
+                    DefaultExportDeclValue::Decl(inner) => Self::Decl(inner.into()),
+                    DefaultExportDeclValue::Expr(inner) => Self::Expr(inner.into()),
+                }
             }
         }
-    }
 
-    impl<T> From<ExportSpecifier<T>> for crate::decl::ExportSpecifier<T> {
-        fn from(other: ExportSpecifier<T>) -> Self {
-            let local: crate::Ident<T> = other.local.into();
-            Self {
-                local,
-                alias: other.alias.map(|a| a.ident.into()),
+        impl From<ExportSpecifier> for crate::decl::ExportSpecifier {
+            fn from(other) {
+                let local = take!(other.local).into();
+                Self {
+                    local,
+                    alias: take!(other.alias).opt().map(|a| take!(a.ident).into()),
+                }
+            }
+        }
+
+        impl From<Alias> for crate::Ident {
+            fn from(other) {
+                take!(other.ident).into()
             }
         }
     }
 
-    impl<T> From<Alias<T>> for crate::Ident<T> {
-        fn from(other: Alias<T>) -> Self {
-            other.ident.into()
+    impl From<ImportAttributesKeyword> for crate::decl::ImportAttributesKeyword {
+        fn from(other: ImportAttributesKeyword) -> Self {
+            match other {
+                ImportAttributesKeyword::Assert(_) => Self::Assert,
+                ImportAttributesKeyword::With(_) => Self::With,
+            }
         }
     }
 }
@@ -178,234 +317,381 @@ mod expr {
 
     use super::*;
 
-    impl<T> From<Expr<T>> for crate::Expr<T> {
-        fn from(other: Expr<T>) -> Self {
-            match other {
+    convert! {
+        impl From<Expr> for crate::Expr {
+            fn from(other) {
+                match other {
+                    Expr::Array(inner) => Self::Array(
+                        take!(inner.elements)
+                            .into_iter()
+                            // an empty entry without a comma only marks a trailing comma
+                            .filter(|e| e.item.is_some() || e.comma.is_some())
+                            .map(|e| take!(e.item).opt().map(From::from))
+                            .collect(),
+                    ),
+                    Expr::ArrowFunc(inner) => Self::ArrowFunc(inner.into()),
+                    Expr::ArrowParamPlaceHolder(inner) => Self::ArrowParamPlaceHolder(
+                        take!(inner.args)
+                            .into_iter()
+                            .map(|e| From::from(take!(e.item)))
+                            .collect(),
+                        inner.keyword.is_some(),
+                    ),
+                    Expr::Assign(inner) => Self::Assign(inner.into()),
+                    Expr::Await(inner) => Self::Await(Box::new(take!(inner.expr).into())),
+                    Expr::Binary(inner) => Self::Binary(inner.into()),
+                    Expr::Class(inner) => Self::Class(inner.unbox().into()),
+                    Expr::Call(inner) => Self::Call(inner.into()),
+                    Expr::Conditional(inner) => Self::Conditional(inner.into()),
+                    Expr::Func(inner) => Self::Func(inner.into()),
+                    Expr::Ident(inner) => Self::Ident(inner.into()),
+                    Expr::PrivateIdent(inner) => Self::PrivateIdent(inner.into()),
+                    Expr::Import(inner) => Self::Import(inner.unbox().into()),
+                    Expr::Lit(inner) => Self::Lit(inner.into()),
+                    Expr::Logical(inner) => Self::Logical(inner.into()),
+                    Expr::Member(inner) => Self::Member(inner.into()),
+                    Expr::MetaProp(inner) => Self::MetaProp(inner.into()),
+                    Expr::New(inner) => Self::New(inner.into()),
+                    Expr::Obj(inner) => Self::Obj(
+                        take!(inner.props)
+                            .into_iter()
+                            .map(|e| take!(e.item).into())
+                            .collect(),
+                    ),
+                    Expr::Sequence(inner) => {
+                        Self::Sequence(inner.into_iter().map(|e| take!(e.item).into()).collect())
+                    }
+                    Expr::Spread(inner) => Self::Spread(Box::new(take!(inner.expr).into())),
+                    Expr::Super(_) => Self::Super,
+                    Expr::TaggedTemplate(inner) => Self::TaggedTemplate(inner.into()),
+                    Expr::This(_) => Self::This,
+                    Expr::Unary(inner) => Self::Unary(inner.into()),
+                    Expr::Update(inner) => Self::Update(inner.into()),
+                    Expr::Yield(inner) => Self::Yield(inner.into()),
+                    Expr::Wrapped(inner) => take!(inner.expr).into(),
+                    Expr::OptionalChain(inner) => {
+                        Self::OptionalChain(Box::new(take!(*inner.expr).into()))
+                    }
+                    #[cfg(feature = "jsx")]
+                    Expr::Jsx(inner) => Self::Jsx(inner.unbox().into()),
+                    #[cfg(feature = "jsx")]
+                    Expr::JsxFragment(inner) => Self::JsxFragment(inner.unbox().into()),
+                }
+            }
+        }
+    }
+
+    impl<T> From<KeepParens<T>> for crate::Expr<T> {
+        fn from(other: KeepParens<T>) -> Self {
+            let keep = |expr: Expr<T>| Box::new(Self::from(KeepParens(expr)));
+            match other.0 {
+                Expr::Wrapped(inner) => Self::Paren(keep(inner.expr)),
                 Expr::Array(inner) => Self::Array(
                     inner
                         .elements
                         .into_iter()
-                        .map(|e| e.item.map(From::from))
+                        .filter(|e| e.item.is_some() || e.comma.is_some())
+                        .map(|e| e.item.map(|e| *keep(e)))
                         .collect(),
                 ),
-                Expr::ArrowFunc(inner) => Self::ArrowFunc(inner.into()),
-                Expr::ArrowParamPlaceHolder(inner) => Self::ArrowParamPlaceHolder(
-                    inner.args.into_iter().map(|e| From::from(e.item)).collect(),
-                    inner.keyword.is_some(),
-                ),
-                Expr::Assign(inner) => Self::Assign(inner.into()),
-                Expr::Await(inner) => Self::Await(Box::new(inner.expr.into())),
-                Expr::Binary(inner) => Self::Binary(inner.into()),
-                Expr::Class(inner) => Self::Class((*inner).into()),
-                Expr::Call(inner) => Self::Call(inner.into()),
-                Expr::Conditional(inner) => Self::Conditional(inner.into()),
-                Expr::Func(inner) => Self::Func(inner.into()),
-                Expr::Ident(inner) => Self::Ident(inner.into()),
-                Expr::Lit(inner) => Self::Lit(inner.into()),
-                Expr::Logical(inner) => Self::Logical(inner.into()),
-                Expr::Member(inner) => Self::Member(inner.into()),
-                Expr::MetaProp(inner) => Self::MetaProp(inner.into()),
-                Expr::New(inner) => Self::New(inner.into()),
-                Expr::Obj(inner) => {
-                    Self::Obj(inner.props.into_iter().map(|e| e.item.into()).collect())
-                }
+                Expr::Assign(inner) => Self::Assign(crate::expr::AssignExpr {
+                    operator: inner.operator.into(),
+                    left: inner.left.into(),
+                    right: keep(*inner.right),
+                }),
+                Expr::Await(inner) => Self::Await(keep(inner.expr)),
+                Expr::Binary(inner) => Self::Binary(crate::expr::BinaryExpr {
+                    operator: inner.operator.into(),
+                    left: keep(*inner.left),
+                    right: keep(*inner.right),
+                }),
+                Expr::Call(inner) => Self::Call(crate::expr::CallExpr {
+                    optional: inner.optional.is_some(),
+                    callee: keep(*inner.callee),
+                    arguments: inner.arguments.into_iter().map(|e| *keep(e.item)).collect(),
+                }),
+                Expr::Conditional(inner) => Self::Conditional(crate::expr::ConditionalExpr {
+                    test: keep(*inner.test),
+                    alternate: keep(*inner.alternate),
+                    consequent: keep(*inner.consequent),
+                }),
+                Expr::Logical(inner) => Self::Logical(crate::expr::LogicalExpr {
+                    operator: inner.operator.into(),
+                    left: keep(*inner.left),
+                    right: keep(*inner.right),
+                }),
+                Expr::Member(inner) => Self::Member(crate::expr::MemberExpr {
+                    object: keep(*inner.object),
+                    property: keep(*inner.property),
+                    indexer: inner.indexer.into(),
+                }),
+                Expr::New(inner) => Self::New(crate::expr::NewExpr {
+                    callee: keep(*inner.callee),
+                    arguments: inner.arguments.into_iter().map(|e| *keep(e.item)).collect(),
+                }),
                 Expr::Sequence(inner) => {
-                    Self::Sequence(inner.into_iter().map(|e| e.item.into()).collect())
+                    Self::Sequence(inner.into_iter().map(|e| *keep(e.item)).collect())
                 }
-                Expr::Spread(inner) => Self::Spread(Box::new(inner.expr.into())),
-                Expr::Super(_) => Self::Super,
-                Expr::TaggedTemplate(inner) => Self::TaggedTemplate(inner.into()),
-                Expr::This(_) => Self::This,
-                Expr::Unary(inner) => Self::Unary(inner.into()),
-                Expr::Update(inner) => Self::Update(inner.into()),
-                Expr::Yield(inner) => Self::Yield(inner.into()),
-                Expr::Wrapped(inner) => inner.expr.into(),
-                Expr::OptionalChain(inner) => Self::OptionalChain(Box::new((*inner.expr).into())),
+                Expr::Spread(inner) => Self::Spread(keep(inner.expr)),
+                Expr::Unary(inner) => Self::Unary(crate::expr::UnaryExpr {
+                    prefix: inner.prefix(),
+                    operator: inner.operator.into(),
+                    argument: keep(*inner.argument),
+                }),
+                Expr::Update(inner) => Self::Update(crate::expr::UpdateExpr {
+                    prefix: inner.prefix(),
+                    operator: inner.operator.into(),
+                    argument: keep(*inner.argument),
+                }),
+                Expr::Yield(inner) => Self::Yield(crate::expr::YieldExpr {
+                    argument: inner.argument.map(|e| keep(*e)),
+                    delegate: inner.star.is_some(),
+                }),
+                Expr::OptionalChain(inner) => Self::OptionalChain(keep(*inner.expr)),
+                other => other.into(),
             }
         }
     }
 
-    impl<T> From<ObjProp<T>> for crate::expr::ObjProp<T> {
-        fn from(other: ObjProp<T>) -> Self {
-            match other {
-                ObjProp::Prop(inner) => Self::Prop(inner.into()),
-                ObjProp::Spread(inner) => Self::Spread(inner.expr.into()),
+    convert! {
+        impl From<ObjProp> for crate::expr::ObjProp {
+            fn from(other) {
+                match other {
+                    ObjProp::Prop(inner) => Self::Prop(inner.into()),
+                    ObjProp::Spread(inner) => Self::Spread(take!(inner.expr).into()),
+                }
             }
         }
-    }
 
-    impl<T> From<Prop<T>> for crate::expr::Prop<T> {
-        fn from(other: Prop<T>) -> Self {
-            match other {
-                Prop::Init(inner) => Self {
-                    computed: inner.key.brackets.is_some(),
-                    short_hand: inner.colon.is_none(),
-                    key: inner.key.into(),
-                    value: inner
-                        .value
-                        .map(From::from)
-                        .unwrap_or(crate::expr::PropValue::None),
-                    kind: crate::PropKind::Init,
-                    method: false,
-                    is_static: false,
-                },
-                Prop::Method(inner) => Self {
-                    computed: inner.id.brackets.is_some(),
-                    key: inner.id.into(),
-                    value: crate::prelude::PropValue::Expr(crate::Expr::Func(crate::Func {
-                        body: inner.body.into(),
-                        generator: inner.star.is_some(),
-                        id: None,
-                        is_async: inner.keyword_async.is_some(),
-                        params: inner.params.into_iter().map(|e| e.item.into()).collect(),
-                    })),
-                    kind: crate::PropKind::Method,
-                    method: true,
-                    short_hand: false,
-                    is_static: inner.keyword_static.is_some(),
-                },
-                Prop::Ctor(inner) => Self {
-                    computed: inner.keyword.brackets.is_some(),
-                    key: inner.keyword.into(),
-                    value: crate::prelude::PropValue::Expr(crate::Expr::Func(crate::Func {
-                        body: inner.body.into(),
-                        generator: false,
-                        id: None,
-                        is_async: false,
-                        params: inner.params.into_iter().map(|e| e.item.into()).collect(),
-                    })),
-                    kind: crate::PropKind::Ctor,
-                    is_static: false,
-                    method: true,
-                    short_hand: false,
-                },
-                Prop::Get(inner) => Self {
-                    computed: inner.id.brackets.is_some(),
-                    key: inner.id.into(),
-                    value: crate::prelude::PropValue::Expr(crate::Expr::Func(crate::Func {
-                        body: inner.body.into(),
-                        generator: false,
-                        id: None,
-                        is_async: false,
-                        params: Vec::new(),
-                    })),
-                    kind: crate::PropKind::Get,
-                    method: false,
-                    short_hand: false,
-                    is_static: inner.keyword_static.is_some(),
-                },
-                Prop::Set(inner) => Self {
-                    computed: inner.id.brackets.is_some(),
-                    key: inner.id.into(),
-                    value: crate::prelude::PropValue::Expr(crate::Expr::Func(crate::Func {
-                        body: inner.body.into(),
-                        generator: false,
-                        id: None,
-                        is_async: false,
-                        params: vec![inner.arg.item.into()],
-                    })),
-                    kind: crate::PropKind::Set,
-                    method: false,
-                    short_hand: false,
-                    is_static: inner.keyword_static.is_some(),
-                },
+        impl From<Prop> for crate::expr::Prop {
+            fn from(other) {
+                match other {
+                    Prop::Init(inner) => Self {
+                        decorators: Vec::new(),
+                        computed: inner.key.brackets.is_some(),
+                        short_hand: inner.colon.is_none(),
+                        key: take!(inner.key).into(),
+                        value: take!(inner.value)
+                            .opt()
+                            .map(From::from)
+                            .unwrap_or(crate::expr::PropValue::None),
+                        kind: crate::PropKind::Init,
+                        method: false,
+                        is_static: false,
+                    },
+                    Prop::Method(inner) => Self {
+                        decorators: take!(inner.decorators)
+                            .into_iter()
+                            .map(From::from)
+                            .collect(),
+                        computed: inner.id.brackets.is_some(),
+                        key: take!(inner.id).into(),
+                        value: crate::prelude::PropValue::Expr(crate::Expr::Func(crate::Func {
+                            body: take!(inner.body).into(),
+                            generator: inner.star.is_some(),
+                            id: None,
+                            is_async: inner.keyword_async.is_some(),
+                            params: take!(inner.params)
+                                .into_iter()
+                                .map(|e| take!(e.item).into())
+                                .collect(),
+                        })),
+                        kind: crate::PropKind::Method,
+                        method: true,
+                        short_hand: false,
+                        is_static: inner.keyword_static.is_some(),
+                    },
+                    Prop::Ctor(inner) => Self {
+                        decorators: take!(inner.decorators)
+                            .into_iter()
+                            .map(From::from)
+                            .collect(),
+                        computed: inner.keyword.brackets.is_some(),
+                        key: take!(inner.keyword).into(),
+                        value: crate::prelude::PropValue::Expr(crate::Expr::Func(crate::Func {
+                            body: take!(inner.body).into(),
+                            generator: false,
+                            id: None,
+                            is_async: false,
+                            params: take!(inner.params)
+                                .into_iter()
+                                .map(|e| take!(e.item).into())
+                                .collect(),
+                        })),
+                        kind: crate::PropKind::Ctor,
+                        is_static: false,
+                        method: true,
+                        short_hand: false,
+                    },
+                    Prop::Get(inner) => Self {
+                        decorators: take!(inner.decorators)
+                            .into_iter()
+                            .map(From::from)
+                            .collect(),
+                        computed: inner.id.brackets.is_some(),
+                        key: take!(inner.id).into(),
+                        value: crate::prelude::PropValue::Expr(crate::Expr::Func(crate::Func {
+                            body: take!(inner.body).into(),
+                            generator: false,
+                            id: None,
+                            is_async: false,
+                            params: Vec::new(),
+                        })),
+                        kind: crate::PropKind::Get,
+                        method: false,
+                        short_hand: false,
+                        is_static: inner.keyword_static.is_some(),
+                    },
+                    Prop::Set(inner) => Self {
+                        decorators: take!(inner.decorators)
+                            .into_iter()
+                            .map(From::from)
+                            .collect(),
+                        computed: inner.id.brackets.is_some(),
+                        key: take!(inner.id).into(),
+                        value: crate::prelude::PropValue::Expr(crate::Expr::Func(crate::Func {
+                            body: take!(inner.body).into(),
+                            generator: false,
+                            id: None,
+                            is_async: false,
+                            params: vec![take!(inner.arg.item).into()],
+                        })),
+                        kind: crate::PropKind::Set,
+                        method: false,
+                        short_hand: false,
+                        is_static: inner.keyword_static.is_some(),
+                    },
+                    Prop::Field(inner) => Self {
+                        computed: inner.computed(),
+                        is_static: inner.is_static(),
+                        key: take!(inner.key).into(),
+                        decorators: take!(inner.decorators)
+                            .into_iter()
+                            .map(From::from)
+                            .collect(),
+                        value: take!(inner.value)
+                            .opt()
+                            .map(|v| crate::expr::PropValue::Expr(v.into()))
+                            .unwrap_or(crate::expr::PropValue::None),
+                        kind: crate::PropKind::Field,
+                        method: false,
+                        short_hand: false,
+                    },
+                    Prop::StaticBlock(inner) => Self {
+                        decorators: Vec::new(),
+                        computed: false,
+                        key: crate::expr::PropKey::None,
+                        value: crate::expr::PropValue::Block(crate::FuncBody(
+                            take!(inner.stmts).into_iter().map(From::from).collect(),
+                        )),
+                        kind: crate::PropKind::StaticBlock,
+                        method: false,
+                        short_hand: false,
+                        is_static: true,
+                    },
+                }
             }
         }
-    }
 
-    impl<T> From<PropInitKey<T>> for crate::expr::PropKey<T> {
-        fn from(other: PropInitKey<T>) -> Self {
-            other.value.into()
+        impl From<PropInitKey> for crate::expr::PropKey {
+            fn from(other) {
+                take!(other.value).into()
+            }
         }
-    }
 
-    impl<T> From<PropMethod<T>> for crate::Func<T> {
-        fn from(other: PropMethod<T>) -> Self {
-            crate::Func {
-                id: None,
-                params: other.params.into_iter().map(|e| e.item.into()).collect(),
-                body: other.body.into(),
-                generator: other.star.is_some(),
-                is_async: other.keyword_async.is_some(),
+        impl From<PropMethod> for crate::Func {
+            fn from(other) {
+                crate::Func {
+                    id: None,
+                    params: take!(other.params)
+                        .into_iter()
+                        .map(|e| take!(e.item).into())
+                        .collect(),
+                    body: take!(other.body).into(),
+                    generator: other.star.is_some(),
+                    is_async: other.keyword_async.is_some(),
+                }
             }
         }
-    }
 
-    impl<T> From<PropKey<T>> for crate::expr::PropKey<T> {
-        fn from(other: PropKey<T>) -> Self {
-            match other {
-                PropKey::Lit(inner) => Self::Lit(inner.into()),
-                PropKey::Expr(inner) => Self::Expr(inner.into()),
-                PropKey::Pat(inner) => Self::Pat(inner.into()),
+        impl From<PropKey> for crate::expr::PropKey {
+            fn from(other) {
+                match other {
+                    PropKey::Lit(inner) => Self::Lit(inner.into()),
+                    PropKey::Expr(inner) => Self::Expr(inner.into()),
+                    PropKey::Pat(inner) => Self::Pat(inner.into()),
+                }
             }
         }
-    }
 
-    impl<T> From<PropValue<T>> for crate::expr::PropValue<T> {
-        fn from(other: PropValue<T>) -> Self {
-            match other {
-                PropValue::Expr(inner) => Self::Expr(inner.into()),
-                PropValue::Pat(inner) => Self::Pat(inner.into()),
-                PropValue::Method(inner) => Self::Expr(crate::expr::Expr::Func(inner.into())),
+        impl From<PropValue> for crate::expr::PropValue {
+            fn from(other) {
+                match other {
+                    PropValue::Expr(inner) => Self::Expr(inner.into()),
+                    PropValue::Pat(inner) => Self::Pat(inner.into()),
+                    PropValue::Method(inner) => {
+                        Self::Expr(crate::expr::Expr::Func(inner.into()))
+                    }
+                }
             }
         }
-    }
 
-    impl<T> From<UnaryExpr<T>> for crate::expr::UnaryExpr<T> {
-        fn from(other: UnaryExpr<T>) -> Self {
-            Self {
-                prefix: other.prefix(),
-                operator: other.operator.into(),
-                argument: Box::new(From::from(*other.argument)),
+        impl From<UnaryExpr> for crate::expr::UnaryExpr {
+            fn from(other) {
+                Self {
+                    prefix: other.prefix(),
+                    operator: take!(other.operator).into(),
+                    argument: Box::new(From::from(take!(*other.argument))),
+                }
             }
         }
-    }
 
-    impl<T> From<UpdateExpr<T>> for crate::expr::UpdateExpr<T> {
-        fn from(other: UpdateExpr<T>) -> Self {
-            Self {
-                prefix: other.prefix(),
-                operator: other.operator.into(),
-                argument: Box::new(From::from(*other.argument)),
+        impl From<UpdateExpr> for crate::expr::UpdateExpr {
+            fn from(other) {
+                Self {
+                    prefix: other.prefix(),
+                    operator: take!(other.operator).into(),
+                    argument: Box::new(From::from(take!(*other.argument))),
+                }
             }
         }
-    }
 
-    impl<T> From<BinaryExpr<T>> for crate::expr::BinaryExpr<T> {
-        fn from(other: BinaryExpr<T>) -> Self {
-            Self {
-                operator: other.operator.into(),
-                left: Box::new(From::from(*other.left)),
-                right: Box::new(From::from(*other.right)),
+        impl From<BinaryExpr> for crate::expr::BinaryExpr {
+            fn from(other) {
+                Self {
+                    operator: take!(other.operator).into(),
+                    left: Box::new(From::from(take!(*other.left))),
+                    right: Box::new(From::from(take!(*other.right))),
+                }
             }
         }
-    }
 
-    impl<T> From<AssignExpr<T>> for crate::expr::AssignExpr<T> {
-        fn from(other: AssignExpr<T>) -> Self {
-            Self {
-                operator: other.operator.into(),
-                left: other.left.into(),
-                right: Box::new(From::from(*other.right)),
+        impl From<AssignExpr> for crate::expr::AssignExpr {
+            fn from(other) {
+                Self {
+                    operator: take!(other.operator).into(),
+                    left: take!(other.left).into(),
+                    right: Box::new(From::from(take!(*other.right))),
+                }
             }
         }
-    }
 
-    impl<T> From<AssignLeft<T>> for crate::expr::AssignLeft<T> {
-        fn from(other: AssignLeft<T>) -> Self {
-            match other {
-                AssignLeft::Pat(inner) => Self::Pat(inner.into()),
-                AssignLeft::Expr(inner) => Self::Expr(Box::new(From::from(*inner))),
+        impl From<AssignLeft> for crate::expr::AssignLeft {
+            fn from(other) {
+                match other {
+                    AssignLeft::Pat(inner) => Self::Pat(inner.into()),
+                    AssignLeft::Expr(inner) => Self::Expr(Box::new(From::from(inner.unbox()))),
+                }
             }
         }
-    }
 
-    impl<T> From<LogicalExpr<T>> for crate::expr::LogicalExpr<T> {
-        fn from(other: LogicalExpr<T>) -> Self {
-            Self {
-                operator: other.operator.into(),
-                left: Box::new(From::from(*other.left)),
-                right: Box::new(From::from(*other.right)),
+        impl From<LogicalExpr> for crate::expr::LogicalExpr {
+            fn from(other) {
+                Self {
+                    operator: take!(other.operator).into(),
+                    left: Box::new(From::from(take!(*other.left))),
+                    right: Box::new(From::from(take!(*other.right))),
+                }
             }
         }
     }
@@ -421,97 +707,113 @@ mod expr {
         }
     }
 
-    impl<T> From<MemberExpr<T>> for crate::expr::MemberExpr<T> {
-        fn from(other: MemberExpr<T>) -> Self {
-            Self {
-                object: Box::new(From::from(*other.object)),
-                property: Box::new(From::from(*other.property)),
-                indexer: From::from(other.indexer),
+    convert! {
+        impl From<MemberExpr> for crate::expr::MemberExpr {
+            fn from(other) {
+                Self {
+                    object: Box::new(From::from(take!(*other.object))),
+                    property: Box::new(From::from(take!(*other.property))),
+                    indexer: From::from(other.indexer),
+                }
             }
         }
-    }
 
-    impl<T> From<ConditionalExpr<T>> for crate::expr::ConditionalExpr<T> {
-        fn from(other: ConditionalExpr<T>) -> Self {
-            Self {
-                test: Box::new(From::from(*other.test)),
-                alternate: Box::new(From::from(*other.alternate)),
-                consequent: Box::new(From::from(*other.consequent)),
+        impl From<ConditionalExpr> for crate::expr::ConditionalExpr {
+            fn from(other) {
+                Self {
+                    test: Box::new(From::from(take!(*other.test))),
+                    alternate: Box::new(From::from(take!(*other.alternate))),
+                    consequent: Box::new(From::from(take!(*other.consequent))),
+                }
             }
         }
-    }
 
-    impl<T> From<CallExpr<T>> for crate::expr::CallExpr<T> {
-        fn from(other: CallExpr<T>) -> Self {
-            Self {
-                optional: other.optional.is_some(),
-                callee: Box::new(From::from(*other.callee)),
-                arguments: other.arguments.into_iter().map(|e| e.item.into()).collect(),
+        impl From<CallExpr> for crate::expr::CallExpr {
+            fn from(other) {
+                Self {
+                    optional: other.optional.is_some(),
+                    callee: Box::new(From::from(take!(*other.callee))),
+                    arguments: take!(other.arguments)
+                        .into_iter()
+                        .map(|e| take!(e.item).into())
+                        .collect(),
+                }
             }
         }
-    }
 
-    impl<T> From<NewExpr<T>> for crate::expr::NewExpr<T> {
-        fn from(other: NewExpr<T>) -> Self {
-            Self {
-                callee: Box::new(From::from(*other.callee)),
-                arguments: other.arguments.into_iter().map(|e| e.item.into()).collect(),
+        impl From<NewExpr> for crate::expr::NewExpr {
+            fn from(other) {
+                Self {
+                    callee: Box::new(From::from(take!(*other.callee))),
+                    arguments: take!(other.arguments)
+                        .into_iter()
+                        .map(|e| take!(e.item).into())
+                        .collect(),
+                }
             }
         }
-    }
 
-    impl<T> From<ArrowFuncExpr<T>> for crate::expr::ArrowFuncExpr<T> {
-        fn from(other: ArrowFuncExpr<T>) -> Self {
-            let expression = matches!(&other.body, ArrowFuncBody::Expr(_));
-            Self {
-                id: None,
-                params: other.params.into_iter().map(|e| e.item.into()).collect(),
-                body: other.body.into(),
-                expression,
-                generator: other.star.is_some(),
-                is_async: other.keyword.is_some(),
+        impl From<ArrowFuncExpr> for crate::expr::ArrowFuncExpr {
+            fn from(other) {
+                let expression = matches!(&other.body, ArrowFuncBody::Expr(_));
+                Self {
+                    id: None,
+                    params: take!(other.params)
+                        .into_iter()
+                        .map(|e| take!(e.item).into())
+                        .collect(),
+                    body: take!(other.body).into(),
+                    expression,
+                    generator: other.star.is_some(),
+                    is_async: other.keyword.is_some(),
+                }
             }
         }
-    }
 
-    impl<T> From<ArrowFuncBody<T>> for crate::expr::ArrowFuncBody<T> {
-        fn from(other: ArrowFuncBody<T>) -> Self {
-            match other {
-                ArrowFuncBody::FuncBody(inner) => Self::FuncBody(inner.into()),
-                ArrowFuncBody::Expr(inner) => Self::Expr(Box::new(From::from(*inner))),
+        impl From<ArrowFuncBody> for crate::expr::ArrowFuncBody {
+            fn from(other) {
+                match other {
+                    ArrowFuncBody::FuncBody(inner) => Self::FuncBody(inner.into()),
+                    ArrowFuncBody::Expr(inner) => Self::Expr(Box::new(From::from(inner.unbox()))),
+                }
             }
         }
-    }
 
-    impl<T> From<YieldExpr<T>> for crate::expr::YieldExpr<T> {
-        fn from(other: YieldExpr<T>) -> Self {
-            Self {
-                argument: other.argument.map(|e| Box::new(From::from(*e))),
-                delegate: other.star.is_some(),
+        impl From<YieldExpr> for crate::expr::YieldExpr {
+            fn from(other) {
+                Self {
+                    argument: take!(other.argument)
+                        .opt()
+                        .map(|e| Box::new(From::from(e.unbox()))),
+                    delegate: other.star.is_some(),
+                }
             }
         }
-    }
 
-    impl<T> From<TaggedTemplateExpr<T>> for crate::expr::TaggedTemplateExpr<T> {
-        fn from(other: TaggedTemplateExpr<T>) -> Self {
-            Self {
-                tag: Box::new(From::from(*other.tag)),
-                quasi: other.quasi.into(),
+        impl From<TaggedTemplateExpr> for crate::expr::TaggedTemplateExpr {
+            fn from(other) {
+                Self {
+                    tag: Box::new(From::from(take!(*other.tag))),
+                    quasi: take!(other.quasi).into(),
+                }
             }
         }
-    }
 
-    impl<T> From<TemplateLit<T>> for crate::expr::TemplateLit<T> {
-        fn from(other: TemplateLit<T>) -> Self {
-            Self {
-                quasis: other.quasis.into_iter().map(From::from).collect(),
-                expressions: other.expressions.into_iter().map(From::from).collect(),
+        impl From<TemplateLit> for crate::expr::TemplateLit {
+            fn from(other) {
+                Self {
+                    quasis: take!(other.quasis).into_iter().map(From::from).collect(),
+                    expressions: take!(other.expressions)
+                        .into_iter()
+                        .map(From::from)
+                        .collect(),
+                }
             }
         }
     }
 
-    impl From<QuasiQuote> for crate::expr::QuasiQuote {
-        fn from(other: QuasiQuote) -> Self {
+    impl From<&QuasiQuote> for crate::expr::QuasiQuote {
+        fn from(other: &QuasiQuote) -> Self {
             match other {
                 QuasiQuote::BackTick(_) => Self::BackTick,
                 QuasiQuote::CloseBrace(_) => Self::CloseBrace,
@@ -520,177 +822,355 @@ mod expr {
         }
     }
 
-    impl<T> From<TemplateElement<T>> for crate::expr::TemplateElement<T> {
-        fn from(other: TemplateElement<T>) -> Self {
-            Self {
-                open_quote: other.open_quote.into(),
-                content: other.content.source,
-                close_quote: other.close_quote.into(),
-            }
+    impl From<QuasiQuote> for crate::expr::QuasiQuote {
+        fn from(other: QuasiQuote) -> Self {
+            (&other).into()
         }
     }
 
-    impl<T> From<MetaProp<T>> for crate::expr::MetaProp<T> {
-        fn from(other: MetaProp<T>) -> Self {
-            Self {
-                meta: other.meta.into(),
-                property: other.property.into(),
+    convert! {
+        impl From<TemplateElement> for crate::expr::TemplateElement {
+            fn from(other) {
+                Self {
+                    open_quote: take!(other.open_quote).into(),
+                    content: take!(other.content.source),
+                    close_quote: take!(other.close_quote).into(),
+                }
+            }
+        }
+
+        impl From<ImportExpr> for crate::expr::ImportExpr {
+            fn from(other) {
+                Self {
+                    source: Box::new(take!(other.source.item).into()),
+                    options: take!(other.options)
+                        .opt()
+                        .map(|e| Box::new(take!(e.item).into())),
+                }
+            }
+        }
+
+        impl From<MetaProp> for crate::expr::MetaProp {
+            fn from(other) {
+                Self {
+                    meta: take!(other.meta).into(),
+                    property: take!(other.property).into(),
+                }
+            }
+        }
+
+        impl From<Lit> for crate::expr::Lit {
+            fn from(other) {
+                match other {
+                    Lit::Null(_inner) => Self::Null,
+                    Lit::String(inner) => Self::String(inner.into()),
+                    Lit::Number(inner) => Self::Number(take!(inner.source)),
+                    Lit::BigInt(inner) => Self::BigInt(take!(inner.source)),
+                    Lit::Boolean(inner) => Self::Boolean(inner.into()),
+                    Lit::RegEx(inner) => Self::RegEx(inner.into()),
+                    Lit::Template(inner) => Self::Template(inner.into()),
+                }
             }
         }
     }
 
-    impl<T> From<Lit<T>> for crate::expr::Lit<T> {
-        fn from(other: Lit<T>) -> Self {
+    impl From<&Boolean> for bool {
+        fn from(other: &Boolean) -> Self {
             match other {
-                Lit::Null(_inner) => Self::Null,
-                Lit::String(inner) => Self::String(inner.into()),
-                Lit::Number(inner) => Self::Number(inner.source),
-                Lit::Boolean(inner) => Self::Boolean(inner.into()),
-                Lit::RegEx(inner) => Self::RegEx(inner.into()),
-                Lit::Template(inner) => Self::Template(inner.into()),
+                Boolean::True(_) => true,
+                Boolean::False(_) => false,
             }
         }
     }
 
     impl From<Boolean> for bool {
         fn from(other: Boolean) -> Self {
-            match other {
-                Boolean::True(_) => true,
-                Boolean::False(_) => false,
+            (&other).into()
+        }
+    }
+
+    convert! {
+        impl From<StringLit> for crate::expr::StringLit {
+            fn from(other) {
+                if matches!(other.open_quote, Quote::Double(_)) {
+                    Self::Double(take!(other.content.source))
+                } else {
+                    Self::Single(take!(other.content.source))
+                }
+            }
+        }
+
+        impl From<RegEx> for crate::expr::RegEx {
+            fn from(other) {
+                Self {
+                    pattern: take!(other.pattern.source),
+                    flags: take!(other.flags).opt().map(|f| take!(f.source)),
+                }
+            }
+        }
+
+        impl From<SequenceExprEntry> for crate::expr::Expr {
+            fn from(other) {
+                take!(other.expr).into()
             }
         }
     }
+}
 
-    impl<T> From<StringLit<T>> for crate::expr::StringLit<T> {
-        fn from(other: StringLit<T>) -> Self {
-            if matches!(other.open_quote, Quote::Double(_)) {
-                Self::Double(other.content.source)
-            } else {
-                Self::Single(other.content.source)
+#[cfg(feature = "jsx")]
+mod jsx {
+    use super::{Opt, Unbox};
+    use crate::spanned::jsx::{
+        JsxAttr, JsxAttribute, JsxAttributeValue, JsxChild, JsxElement, JsxElementName,
+        JsxExpressionContainer, JsxFragment, JsxOpeningElement, JsxText,
+    };
+
+    convert! {
+        impl From<JsxElement> for crate::jsx::JsxElement {
+            fn from(other) {
+                Self {
+                    opening: take!(other.opening).into(),
+                    children: take!(other.children).into_iter().map(Into::into).collect(),
+                    closing: take!(other.closing).opt().map(|c| take!(c.name).into()),
+                }
+            }
+        }
+
+        impl From<JsxFragment> for crate::jsx::JsxFragment {
+            fn from(other) {
+                Self {
+                    children: take!(other.children).into_iter().map(Into::into).collect(),
+                }
+            }
+        }
+
+        impl From<JsxOpeningElement> for crate::jsx::JsxOpeningElement {
+            fn from(other) {
+                Self {
+                    name: take!(other.name).into(),
+                    attributes: take!(other.attributes)
+                        .into_iter()
+                        .map(Into::into)
+                        .collect(),
+                    self_closing: other.slash.is_some(),
+                }
+            }
+        }
+
+        impl From<JsxElementName> for crate::jsx::JsxElementName {
+            fn from(other) {
+                match other {
+                    JsxElementName::Ident(inner) => Self::Ident(inner.into()),
+                    JsxElementName::Member(object, _, property) => {
+                        Self::Member(Box::new(object.unbox().into()), property.into())
+                    }
+                    JsxElementName::Namespaced(namespace, _, name) => {
+                        Self::Namespaced(namespace.into(), name.into())
+                    }
+                }
+            }
+        }
+
+        impl From<JsxAttr> for crate::jsx::JsxAttr {
+            fn from(other) {
+                match other {
+                    JsxAttr::Attribute(inner) => Self::Attribute(inner.into()),
+                    JsxAttr::Spread(inner) => Self::Spread(take!(inner.expr).into()),
+                }
+            }
+        }
+
+        impl From<JsxAttribute> for crate::jsx::JsxAttribute {
+            fn from(other) {
+                Self {
+                    name: take!(other.name).into(),
+                    value: take!(other.value).opt().map(Into::into),
+                }
+            }
+        }
+
+        impl From<JsxAttributeValue> for crate::jsx::JsxAttributeValue {
+            fn from(other) {
+                match other {
+                    JsxAttributeValue::String(inner) => Self::String(inner.into()),
+                    JsxAttributeValue::Expr(inner) => Self::Expr(inner.into()),
+                    JsxAttributeValue::Element(inner) => Self::Element(inner.into()),
+                    JsxAttributeValue::Fragment(inner) => Self::Fragment(inner.into()),
+                }
+            }
+        }
+
+        impl From<JsxExpressionContainer> for crate::jsx::JsxExpressionContainer {
+            fn from(other) {
+                Self {
+                    expr: take!(other.expr).opt().map(Into::into),
+                }
+            }
+        }
+
+        impl From<JsxText> for crate::jsx::JsxText {
+            fn from(other) {
+                Self {
+                    value: take!(other.value.source),
+                }
+            }
+        }
+
+        impl From<JsxChild> for crate::jsx::JsxChild {
+            fn from(other) {
+                match other {
+                    JsxChild::Text(inner) => Self::Text(inner.into()),
+                    JsxChild::Expr(inner) => Self::Expr(inner.into()),
+                    JsxChild::Spread(inner) => Self::Spread(take!(inner.expr).into()),
+                    JsxChild::Element(inner) => Self::Element(inner.into()),
+                    JsxChild::Fragment(inner) => Self::Fragment(inner.into()),
+                }
             }
         }
     }
+}
 
-    impl<T> From<RegEx<T>> for crate::expr::RegEx<T> {
-        fn from(other: RegEx<T>) -> Self {
+convert! {
+    impl From<Ident> for crate::Ident {
+        fn from(other) {
             Self {
-                pattern: other.pattern.source,
-                flags: other.flags.map(|f| f.source),
+                name: take!(other.slice.source),
             }
         }
     }
 
-    impl<T> From<SequenceExprEntry<T>> for crate::expr::Expr<T> {
-        fn from(other: SequenceExprEntry<T>) -> Self {
-            other.expr.into()
+    impl From<PrivateIdent> for crate::PrivateIdent {
+        fn from(other) {
+            Self {
+                name: take!(other.ident.slice.source),
+            }
         }
     }
-}
 
-impl<T> From<Ident<T>> for crate::Ident<T> {
-    fn from(other: Ident<T>) -> Self {
-        Self {
-            name: other.slice.source,
+    impl From<Dir> for crate::Dir {
+        fn from(other) {
+            Self {
+                expr: take!(other.expr).into(),
+                dir: take!(other.dir),
+            }
         }
     }
-}
 
-impl<T> From<Dir<T>> for crate::Dir<T> {
-    fn from(other: Dir<T>) -> Self {
-        Self {
-            expr: other.expr.into(),
-            dir: other.dir,
+    impl From<Func> for crate::Func {
+        fn from(other) {
+            Self {
+                generator: other.generator(),
+                is_async: other.is_async(),
+                id: take!(other.id).opt().map(From::from),
+                params: take!(other.params)
+                    .into_iter()
+                    .map(|e| From::from(take!(e.item)))
+                    .collect(),
+                body: take!(other.body).into(),
+            }
         }
     }
-}
 
-impl<T> From<Func<T>> for crate::Func<T> {
-    fn from(other: Func<T>) -> Self {
-        Self {
-            generator: other.generator(),
-            is_async: other.is_async(),
-            id: other.id.map(From::from),
-            params: other
-                .params
-                .into_iter()
-                .map(|e| From::from(e.item))
-                .collect(),
-            body: other.body.into(),
+    impl From<FuncArgEntry> for crate::FuncArg {
+        fn from(other) {
+            take!(other.value).into()
         }
     }
-}
 
-impl<T> From<FuncArgEntry<T>> for crate::FuncArg<T> {
-    fn from(other: FuncArgEntry<T>) -> Self {
-        other.value.into()
+    impl From<FuncArg> for crate::FuncArg {
+        fn from(other) {
+            match other {
+                FuncArg::Expr(inner) => Self::Expr(inner.into()),
+                FuncArg::Pat(inner) => Self::Pat(inner.into()),
+                FuncArg::Rest(inner) => {
+                    Self::Pat(crate::pat::Pat::RestElement(Box::new(take!(inner.pat).into())))
+                }
+            }
+        }
     }
-}
 
-impl<T> From<FuncArg<T>> for crate::FuncArg<T> {
-    fn from(other: FuncArg<T>) -> Self {
-        match other {
-            FuncArg::Expr(inner) => Self::Expr(inner.into()),
-            FuncArg::Pat(inner) => Self::Pat(inner.into()),
-            FuncArg::Rest(inner) => {
-                Self::Pat(crate::pat::Pat::RestElement(Box::new(inner.pat.into())))
+    impl From<Program> for crate::Program {
+        fn from(other) {
+            match other {
+                Program::Mod { hashbang, parts } => Self::Mod {
+                    hashbang: hashbang.opt().map(|h| take!(h.content.source)),
+                    parts: parts.into_iter().map(From::from).collect(),
+                },
+                Program::Script { hashbang, parts } => Self::Script {
+                    hashbang: hashbang.opt().map(|h| take!(h.content.source)),
+                    parts: parts.into_iter().map(From::from).collect(),
+                },
             }
         }
     }
-}
 
-impl<T> From<Program<T>> for crate::Program<T> {
-    fn from(other: Program<T>) -> Self {
-        match other {
-            Program::Mod(inner) => Self::Mod(inner.into_iter().map(From::from).collect()),
-            Program::Script(inner) => Self::Script(inner.into_iter().map(From::from).collect()),
+    impl From<ProgramPart> for crate::ProgramPart {
+        fn from(other) {
+            match other {
+                ProgramPart::Dir(inner) => Self::Dir(inner.into()),
+                ProgramPart::Decl(inner) => Self::Decl(inner.into()),
+                ProgramPart::Stmt(inner) => Self::Stmt(inner.into()),
+            }
         }
     }
-}
 
-impl<T> From<ProgramPart<T>> for crate::ProgramPart<T> {
-    fn from(other: ProgramPart<T>) -> Self {
-        match other {
-            ProgramPart::Dir(inner) => Self::Dir(inner.into()),
-            ProgramPart::Decl(inner) => Self::Decl(inner.into()),
-            ProgramPart::Stmt(inner) => Self::Stmt(inner.into()),
+    impl From<FuncBody> for crate::FuncBody {
+        fn from(other) {
+            Self(take!(other.stmts).into_iter().map(From::from).collect())
         }
     }
-}
 
-impl<T> From<FuncBody<T>> for crate::FuncBody<T> {
-    fn from(other: FuncBody<T>) -> Self {
-        Self(other.stmts.into_iter().map(From::from).collect())
+    impl From<Class> for crate::Class {
+        fn from(other) {
+            Self {
+                decorators: take!(other.decorators)
+                    .into_iter()
+                    .map(From::from)
+                    .collect(),
+                id: take!(other.id).opt().map(From::from),
+                super_class: take!(other.super_class)
+                    .opt()
+                    .map(|e| Box::new(From::from(take!(e.expr)))),
+                body: take!(other.body).into(),
+            }
+        }
     }
-}
 
-impl<T> From<Class<T>> for crate::Class<T> {
-    fn from(other: Class<T>) -> Self {
-        Self {
-            id: other.id.map(From::from),
-            super_class: other.super_class.map(|e| Box::new(From::from(e.expr))),
-            body: other.body.into(),
+    impl From<Decorator> for crate::Decorator {
+        fn from(other) {
+            Self {
+                expr: take!(other.expr).into(),
+            }
         }
     }
-}
 
-impl<T> From<ClassBody<T>> for crate::ClassBody<T> {
-    fn from(other: ClassBody<T>) -> Self {
-        Self(other.props.into_iter().map(From::from).collect())
+    impl From<ClassBody> for crate::ClassBody {
+        fn from(other) {
+            Self(take!(other.props).into_iter().map(From::from).collect())
+        }
     }
 }
 
-impl From<VarKind> for crate::VarKind {
-    fn from(other: VarKind) -> Self {
+impl From<&VarKind> for crate::VarKind {
+    fn from(other: &VarKind) -> Self {
         match other {
             VarKind::Var(_) => Self::Var,
             VarKind::Let(_) => Self::Let,
             VarKind::Const(_) => Self::Const,
+            VarKind::Using(_) => Self::Using,
+            VarKind::AwaitUsing(_, _) => Self::AwaitUsing,
         }
     }
 }
 
-impl From<AssignOp> for crate::AssignOp {
-    fn from(other: AssignOp) -> Self {
+impl From<VarKind> for crate::VarKind {
+    fn from(other: VarKind) -> Self {
+        (&other).into()
+    }
+}
+
+impl From<&AssignOp> for crate::AssignOp {
+    fn from(other: &AssignOp) -> Self {
         match other {
             AssignOp::Equal(_) => Self::Equal,
             AssignOp::PlusEqual(_) => Self::PlusEqual,
@@ -712,8 +1192,14 @@ impl From<AssignOp> for crate::AssignOp {
     }
 }
 
-impl From<LogicalOp> for crate::LogicalOp {
-    fn from(other: LogicalOp) -> Self {
+impl From<AssignOp> for crate::AssignOp {
+    fn from(other: AssignOp) -> Self {
+        (&other).into()
+    }
+}
+
+impl From<&LogicalOp> for crate::LogicalOp {
+    fn from(other: &LogicalOp) -> Self {
         match other {
             LogicalOp::Or(_) => Self::Or,
             LogicalOp::And(_) => Self::And,
@@ -722,8 +1208,14 @@ impl From<LogicalOp> for crate::LogicalOp {
     }
 }
 
-impl From<BinaryOp> for crate::BinaryOp {
-    fn from(other: BinaryOp) -> Self {
+impl From<LogicalOp> for crate::LogicalOp {
+    fn from(other: LogicalOp) -> Self {
+        (&other).into()
+    }
+}
+
+impl From<&BinaryOp> for crate::BinaryOp {
+    fn from(other: &BinaryOp) -> Self {
         match other {
             BinaryOp::Equal(_) => Self::Equal,
             BinaryOp::NotEqual(_) => Self::NotEqual,
@@ -751,8 +1243,14 @@ impl From<BinaryOp> for crate::BinaryOp {
     }
 }
 
-impl From<UpdateOp> for crate::UpdateOp {
-    fn from(other: UpdateOp) -> Self {
+impl From<BinaryOp> for crate::BinaryOp {
+    fn from(other: BinaryOp) -> Self {
+        (&other).into()
+    }
+}
+
+impl From<&UpdateOp> for crate::UpdateOp {
+    fn from(other: &UpdateOp) -> Self {
         match other {
             UpdateOp::Increment(_) => Self::Increment,
             UpdateOp::Decrement(_) => Self::Decrement,
@@ -760,8 +1258,14 @@ impl From<UpdateOp> for crate::UpdateOp {
     }
 }
 
-impl From<UnaryOp> for crate::UnaryOp {
-    fn from(other: UnaryOp) -> Self {
+impl From<UpdateOp> for crate::UpdateOp {
+    fn from(other: UpdateOp) -> Self {
+        (&other).into()
+    }
+}
+
+impl From<&UnaryOp> for crate::UnaryOp {
+    fn from(other: &UnaryOp) -> Self {
         match other {
             UnaryOp::Minus(_) => Self::Minus,
             UnaryOp::Plus(_) => Self::Plus,
@@ -774,68 +1278,80 @@ impl From<UnaryOp> for crate::UnaryOp {
     }
 }
 
+impl From<UnaryOp> for crate::UnaryOp {
+    fn from(other: UnaryOp) -> Self {
+        (&other).into()
+    }
+}
+
 mod pat {
     use super::*;
 
-    impl<T> From<Pat<T>> for crate::pat::Pat<T> {
-        fn from(other: Pat<T>) -> Self {
-            match other {
-                Pat::Ident(inner) => Self::Ident(inner.into()),
-                Pat::Obj(inner) => Self::Obj(inner.into()),
-                Pat::Array(inner) => Self::Array(inner.into()),
-                Pat::Assign(inner) => Self::Assign(inner.into()),
+    convert! {
+        impl From<Pat> for crate::pat::Pat {
+            fn from(other) {
+                match other {
+                    Pat::Ident(inner) => Self::Ident(inner.into()),
+                    Pat::Obj(inner) => Self::Obj(inner.into()),
+                    Pat::Array(inner) => Self::Array(inner.into()),
+                    Pat::Assign(inner) => Self::Assign(inner.into()),
+                }
             }
         }
-    }
 
-    impl<T> From<ArrayPat<T>> for Vec<Option<crate::pat::ArrayPatPart<T>>> {
-        fn from(other: ArrayPat<T>) -> Self {
-            other
-                .elements
-                .into_iter()
-                .map(|e| e.item.map(Into::into))
-                .collect()
+        impl From<ArrayPat> for ArrayPatParts {
+            fn from(other) {
+                take!(other.elements)
+                    .into_iter()
+                    // an empty entry without a comma only marks a trailing comma
+                    .filter(|e| e.item.is_some() || e.comma.is_some())
+                    .map(|e| take!(e.item).opt().map(Into::into))
+                    .collect()
+            }
         }
-    }
 
-    impl<T> From<ArrayElement<T>> for Option<crate::pat::ArrayPatPart<T>> {
-        fn from(other: ArrayElement<T>) -> Self {
-            other.part.map(From::from)
+        impl From<ArrayElement> for MaybeArrayPatPart {
+            fn from(other) {
+                take!(other.part).opt().map(From::from)
+            }
         }
-    }
 
-    impl<T> From<ArrayPatPart<T>> for crate::pat::ArrayPatPart<T> {
-        fn from(other: ArrayPatPart<T>) -> Self {
-            match other {
-                ArrayPatPart::Pat(inner) => Self::Pat(inner.into()),
-                ArrayPatPart::Expr(inner) => Self::Expr(inner.into()),
-                ArrayPatPart::Rest(inner) => {
-                    Self::Pat(crate::pat::Pat::RestElement(Box::new(inner.pat.into())))
+        impl From<ArrayPatPart> for crate::pat::ArrayPatPart {
+            fn from(other) {
+                match other {
+                    ArrayPatPart::Pat(inner) => Self::Pat(inner.into()),
+                    ArrayPatPart::Expr(inner) => Self::Expr(inner.into()),
+                    ArrayPatPart::Rest(inner) => {
+                        Self::Pat(crate::pat::Pat::RestElement(Box::new(take!(inner.pat).into())))
+                    }
                 }
             }
         }
-    }
 
-    impl<T> From<ObjPat<T>> for crate::pat::ObjPat<T> {
-        fn from(other: ObjPat<T>) -> Self {
-            other.props.into_iter().map(|e| e.item.into()).collect()
+        impl From<ObjPat> for crate::pat::ObjPat {
+            fn from(other) {
+                take!(other.props)
+                    .into_iter()
+                    .map(|e| take!(e.item).into())
+                    .collect()
+            }
         }
-    }
 
-    impl<T> From<ObjPatPart<T>> for crate::pat::ObjPatPart<T> {
-        fn from(other: ObjPatPart<T>) -> Self {
-            match other {
-                ObjPatPart::Assign(prop) => Self::Assign(prop.into()),
-                ObjPatPart::Rest(inner) => Self::Rest(Box::new(From::from(inner.pat))),
+        impl From<ObjPatPart> for crate::pat::ObjPatPart {
+            fn from(other) {
+                match other {
+                    ObjPatPart::Assign(prop) => Self::Assign(prop.into()),
+                    ObjPatPart::Rest(inner) => Self::Rest(Box::new(From::from(take!(inner.pat)))),
+                }
             }
         }
-    }
 
-    impl<T> From<AssignPat<T>> for crate::pat::AssignPat<T> {
-        fn from(other: AssignPat<T>) -> Self {
-            Self {
-                left: Box::new(From::from(*other.left)),
-                right: Box::new(From::from(*other.right)),
+        impl From<AssignPat> for crate::pat::AssignPat {
+            fn from(other) {
+                Self {
+                    left: Box::new(From::from(take!(*other.left))),
+                    right: Box::new(From::from(take!(*other.right))),
+                }
             }
         }
     }
@@ -843,179 +1359,189 @@ mod pat {
 mod stmt {
     use super::*;
 
-    impl<T> From<Stmt<T>> for crate::stmt::Stmt<T> {
-        fn from(other: Stmt<T>) -> Self {
-            match other {
-                Stmt::Expr { expr, .. } => Self::Expr(expr.into()),
-                Stmt::Block(inner) => Self::Block(inner.into()),
-                Stmt::Empty(_) => Self::Empty,
-                Stmt::Debugger { .. } => Self::Debugger,
-                Stmt::With(inner) => Self::With(inner.into()),
-                Stmt::Return { value, .. } => Self::Return(value.map(From::from)),
-                Stmt::Labeled(inner) => Self::Labeled(inner.into()),
-                Stmt::Break { label, .. } => Self::Break(label.map(From::from)),
-                Stmt::Continue { label, .. } => Self::Continue(label.map(From::from)),
-                Stmt::If(inner) => Self::If(inner.into()),
-                Stmt::Switch(inner) => Self::Switch(inner.into()),
-                Stmt::Throw { expr, .. } => Self::Throw(expr.into()),
-                Stmt::Try(inner) => Self::Try(inner.into()),
-                Stmt::While(inner) => Self::While(inner.into()),
-                Stmt::DoWhile(inner) => Self::DoWhile(inner.into()),
-                Stmt::For(inner) => Self::For(inner.into()),
-                Stmt::ForIn(inner) => Self::ForIn(inner.into()),
-                Stmt::ForOf(inner) => Self::ForOf(inner.into()),
-                Stmt::Var { decls, .. } => {
-                    Self::Var(decls.decls.into_iter().map(|e| e.item.into()).collect())
+    convert! {
+        impl From<Stmt> for crate::stmt::Stmt {
+            fn from(other) {
+                match other {
+                    Stmt::Expr { expr, .. } => Self::Expr(expr.into()),
+                    Stmt::Block(inner) => Self::Block(inner.into()),
+                    Stmt::Empty(_) => Self::Empty,
+                    Stmt::Debugger { .. } => Self::Debugger,
+                    Stmt::With(inner) => Self::With(inner.into()),
+                    Stmt::Return { value, .. } => Self::Return(value.opt().map(From::from)),
+                    Stmt::Labeled(inner) => Self::Labeled(inner.into()),
+                    Stmt::Break { label, .. } => Self::Break(label.opt().map(From::from)),
+                    Stmt::Continue { label, .. } => Self::Continue(label.opt().map(From::from)),
+                    Stmt::If(inner) => Self::If(inner.into()),
+                    Stmt::Switch(inner) => Self::Switch(inner.into()),
+                    Stmt::Throw { expr, .. } => Self::Throw(expr.into()),
+                    Stmt::Try(inner) => Self::Try(inner.into()),
+                    Stmt::While(inner) => Self::While(inner.into()),
+                    Stmt::DoWhile(inner) => Self::DoWhile(inner.into()),
+                    Stmt::For(inner) => Self::For(inner.into()),
+                    Stmt::ForIn(inner) => Self::ForIn(inner.into()),
+                    Stmt::ForOf(inner) => Self::ForOf(inner.into()),
+                    Stmt::Var { decls, .. } => Self::Var(
+                        take!(decls.decls)
+                            .into_iter()
+                            .map(|e| take!(e.item).into())
+                            .collect(),
+                    ),
                 }
             }
         }
-    }
 
-    impl<T> From<WithStmt<T>> for crate::stmt::WithStmt<T> {
-        fn from(other: WithStmt<T>) -> Self {
-            Self {
-                object: other.object.into(),
-                body: Box::new(From::from(*other.body)),
+        impl From<WithStmt> for crate::stmt::WithStmt {
+            fn from(other) {
+                Self {
+                    object: take!(other.object).into(),
+                    body: Box::new(From::from(take!(*other.body))),
+                }
             }
         }
-    }
 
-    impl<T> From<LabeledStmt<T>> for crate::stmt::LabeledStmt<T> {
-        fn from(other: LabeledStmt<T>) -> Self {
-            Self {
-                label: other.label.into(),
-                body: Box::new(From::from(*other.body)),
+        impl From<LabeledStmt> for crate::stmt::LabeledStmt {
+            fn from(other) {
+                Self {
+                    label: take!(other.label).into(),
+                    body: Box::new(From::from(take!(*other.body))),
+                }
             }
         }
-    }
 
-    impl<T> From<IfStmt<T>> for crate::stmt::IfStmt<T> {
-        fn from(other: IfStmt<T>) -> Self {
-            Self {
-                test: other.test.into(),
-                consequent: Box::new(From::from(*other.consequent)),
-                alternate: other.alternate.map(|s| Box::new(From::from(s.body))),
+        impl From<IfStmt> for crate::stmt::IfStmt {
+            fn from(other) {
+                Self {
+                    test: take!(other.test).into(),
+                    consequent: Box::new(From::from(take!(*other.consequent))),
+                    alternate: take!(other.alternate)
+                        .opt()
+                        .map(|s| Box::new(From::from(take!(s.body)))),
+                }
             }
         }
-    }
 
-    impl<T> From<SwitchStmt<T>> for crate::stmt::SwitchStmt<T> {
-        fn from(other: SwitchStmt<T>) -> Self {
-            Self {
-                discriminant: other.discriminant.into(),
-                cases: other.cases.into_iter().map(From::from).collect(),
+        impl From<SwitchStmt> for crate::stmt::SwitchStmt {
+            fn from(other) {
+                Self {
+                    discriminant: take!(other.discriminant).into(),
+                    cases: take!(other.cases).into_iter().map(From::from).collect(),
+                }
             }
         }
-    }
 
-    impl<T> From<SwitchCase<T>> for crate::stmt::SwitchCase<T> {
-        fn from(other: SwitchCase<T>) -> Self {
-            Self {
-                test: other.test.map(From::from),
-                consequent: other.consequent.into_iter().map(From::from).collect(),
+        impl From<SwitchCase> for crate::stmt::SwitchCase {
+            fn from(other) {
+                Self {
+                    test: take!(other.test).opt().map(From::from),
+                    consequent: take!(other.consequent)
+                        .into_iter()
+                        .map(From::from)
+                        .collect(),
+                }
             }
         }
-    }
 
-    impl<T> From<BlockStmt<T>> for crate::stmt::BlockStmt<T> {
-        fn from(other: BlockStmt<T>) -> Self {
-            Self(other.stmts.into_iter().map(From::from).collect())
+        impl From<BlockStmt> for crate::stmt::BlockStmt {
+            fn from(other) {
+                Self(take!(other.stmts).into_iter().map(From::from).collect())
+            }
         }
-    }
 
-    impl<T> From<TryStmt<T>> for crate::stmt::TryStmt<T> {
-        fn from(other: TryStmt<T>) -> Self {
-            Self {
-                block: other.block.into(),
-                handler: other.handler.map(From::from),
-                finalizer: other.finalizer.map(From::from),
+        impl From<TryStmt> for crate::stmt::TryStmt {
+            fn from(other) {
+                Self {
+                    block: take!(other.block).into(),
+                    handler: take!(other.handler).opt().map(From::from),
+                    finalizer: take!(other.finalizer).opt().map(From::from),
+                }
             }
         }
-    }
 
-    impl<T> From<CatchClause<T>> for crate::stmt::CatchClause<T> {
-        fn from(other: CatchClause<T>) -> Self {
-            Self {
-                param: other.param.map(|a| a.param.into()),
-                body: other.body.into(),
+        impl From<CatchClause> for crate::stmt::CatchClause {
+            fn from(other) {
+                Self {
+                    param: take!(other.param).opt().map(|a| take!(a.param).into()),
+                    body: take!(other.body).into(),
+                }
             }
         }
-    }
 
-    impl<T> From<FinallyClause<T>> for crate::stmt::BlockStmt<T> {
-        fn from(other: FinallyClause<T>) -> Self {
-            other.body.into()
+        impl From<FinallyClause> for crate::stmt::BlockStmt {
+            fn from(other) {
+                take!(other.body).into()
+            }
         }
-    }
 
-    impl<T> From<WhileStmt<T>> for crate::stmt::WhileStmt<T> {
-        fn from(other: WhileStmt<T>) -> Self {
-            Self {
-                test: other.test.into(),
-                body: Box::new(From::from(*other.body)),
+        impl From<WhileStmt> for crate::stmt::WhileStmt {
+            fn from(other) {
+                Self {
+                    test: take!(other.test).into(),
+                    body: Box::new(From::from(take!(*other.body))),
+                }
             }
         }
-    }
 
-    impl<T> From<DoWhileStmt<T>> for crate::stmt::DoWhileStmt<T> {
-        fn from(other: DoWhileStmt<T>) -> Self {
-            Self {
-                test: other.test.into(),
-                body: Box::new(From::from(*other.body)),
+        impl From<DoWhileStmt> for crate::stmt::DoWhileStmt {
+            fn from(other) {
+                Self {
+                    test: take!(other.test).into(),
+                    body: Box::new(From::from(take!(*other.body))),
+                }
             }
         }
-    }
 
-    impl<T> From<ForStmt<T>> for crate::stmt::ForStmt<T> {
-        fn from(other: ForStmt<T>) -> Self {
-            Self {
-                init: other.init.map(From::from),
-                test: other.test.map(From::from),
-                update: other.update.map(From::from),
-                body: Box::new(From::from(*other.body)),
+        impl From<ForStmt> for crate::stmt::ForStmt {
+            fn from(other) {
+                Self {
+                    init: take!(other.init).opt().map(From::from),
+                    test: take!(other.test).opt().map(From::from),
+                    update: take!(other.update).opt().map(From::from),
+                    body: Box::new(From::from(take!(*other.body))),
+                }
             }
         }
-    }
 
-    impl<T> From<LoopInit<T>> for crate::stmt::LoopInit<T> {
-        fn from(other: LoopInit<T>) -> Self {
-            match other {
-                LoopInit::Expr(inner) => Self::Expr(inner.into()),
-                LoopInit::Variable(kind, decls) => Self::Variable(
-                    kind.into(),
-                    decls.into_iter().map(|e| e.item.into()).collect(),
-                ),
+        impl From<LoopInit> for crate::stmt::LoopInit {
+            fn from(other) {
+                match other {
+                    LoopInit::Expr(inner) => Self::Expr(inner.into()),
+                    LoopInit::Variable(kind, decls) => Self::Variable(
+                        kind.into(),
+                        decls.into_iter().map(|e| take!(e.item).into()).collect(),
+                    ),
+                }
             }
         }
-    }
 
-    impl<T> From<ForInStmt<T>> for crate::stmt::ForInStmt<T> {
-        fn from(other: ForInStmt<T>) -> Self {
-            Self {
-                left: other.left.into(),
-                right: other.right.into(),
-                body: Box::new(From::from(*other.body)),
+        impl From<ForInStmt> for crate::stmt::ForInStmt {
+            fn from(other) {
+                Self {
+                    left: take!(other.left).into(),
+                    right: take!(other.right).into(),
+                    body: Box::new(From::from(take!(*other.body))),
+                }
             }
         }
-    }
 
-    impl<T> From<ForOfStmt<T>> for crate::stmt::ForOfStmt<T> {
-        fn from(other: ForOfStmt<T>) -> Self {
-            Self {
-                left: other.left.into(),
-                right: other.right.into(),
-                body: Box::new(From::from(*other.body)),
-                is_await: other.is_await,
+        impl From<ForOfStmt> for crate::stmt::ForOfStmt {
+            fn from(other) {
+                Self {
+                    left: take!(other.left).into(),
+                    right: take!(other.right).into(),
+                    body: Box::new(From::from(take!(*other.body))),
+                    is_await: other.is_await,
+                }
             }
         }
-    }
 
-    impl<T> From<LoopLeft<T>> for crate::stmt::LoopLeft<T> {
-        fn from(other: LoopLeft<T>) -> Self {
-            match other {
-                LoopLeft::Expr(inner) => Self::Expr(inner.into()),
-                LoopLeft::Variable(kind, decl) => Self::Variable(kind.into(), decl.into()),
-                LoopLeft::Pat(inner) => Self::Pat(inner.into()),
+        impl From<LoopLeft> for crate::stmt::LoopLeft {
+            fn from(other) {
+                match other {
+                    LoopLeft::Expr(inner) => Self::Expr(inner.into()),
+                    LoopLeft::Variable(kind, decl) => Self::Variable(kind.into(), decl.into()),
+                    LoopLeft::Pat(inner) => Self::Pat(inner.into()),
+                }
             }
         }
     }
@@ -1,11 +1,12 @@
-use crate::spanned::expr::{Expr, Lit};
+use crate::spanned::expr::{Expr, Lit, StringLit};
 use crate::spanned::pat::Pat;
 use crate::spanned::VarKind;
 use crate::spanned::{Class, Func, Ident};
 use crate::IntoAllocated;
 
 use super::tokens::{
-    As, Asterisk, CloseBrace, Default, Equal, Export, From, Import, OpenBrace, Semicolon, Token,
+    As, Asterisk, CloseBrace, Colon, Default, Equal, Export, From, Import, ImportAttributesKeyword,
+    OpenBrace, Semicolon, Token,
 };
 use super::{ListEntry, Node, SourceLocation};
 
@@ -80,6 +81,13 @@ where
     }
 }
 
+impl<T> Decl<T> {
+    /// Drop every span and token, producing the non-spanned tree
+    pub fn into_concrete(self) -> crate::decl::Decl<T> {
+        self.into()
+    }
+}
+
 impl<T> Node for Decl<T> {
     fn loc(&self) -> super::SourceLocation {
         match self {
@@ -230,6 +238,7 @@ pub struct ModImport<T> {
     pub specifiers: Vec<ListEntry<ImportSpecifier<T>>>,
     pub keyword_from: Option<From>,
     pub source: Lit<T>,
+    pub attributes: Option<ImportAttributes<T>>,
 }
 
 impl<T> IntoAllocated for ModImport<T>
@@ -247,15 +256,125 @@ where
                 .collect(),
             keyword_from: self.keyword_from,
             source: self.source.into_allocated(),
+            attributes: self.attributes.into_allocated(),
         }
     }
 }
 
 impl<T> Node for ModImport<T> {
     fn loc(&self) -> SourceLocation {
+        let end = if let Some(attributes) = &self.attributes {
+            attributes.loc().end
+        } else {
+            self.source.loc().end
+        };
         SourceLocation {
             start: self.keyword_import.start(),
-            end: self.source.loc().end,
+            end,
+        }
+    }
+}
+
+/// The `assert` or `with` clause following an import's source
+/// ```js
+/// import data from './data.json' with { type: 'json' };
+/// //                             ^^^^^^^^^^^^^^^^^^^^^
+/// import data from './data.json' assert { type: 'json' };
+/// //                             ^^^^^^^^^^^^^^^^^^^^^^^
+/// ```
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct ImportAttributes<T> {
+    pub keyword: ImportAttributesKeyword,
+    pub open_brace: OpenBrace,
+    pub attributes: Vec<ListEntry<ImportAttribute<T>>>,
+    pub close_brace: CloseBrace,
+}
+
+impl<T> IntoAllocated for ImportAttributes<T>
+where
+    T: ToString,
+{
+    type Allocated = ImportAttributes<String>;
+    fn into_allocated(self) -> ImportAttributes<String> {
+        ImportAttributes {
+            keyword: self.keyword,
+            open_brace: self.open_brace,
+            attributes: self
+                .attributes
+                .into_iter()
+                .map(|a| a.into_allocated())
+                .collect(),
+            close_brace: self.close_brace,
+        }
+    }
+}
+
+impl<T> Node for ImportAttributes<T> {
+    fn loc(&self) -> SourceLocation {
+        SourceLocation {
+            start: self.keyword.start(),
+            end: self.close_brace.end(),
+        }
+    }
+}
+
+/// A single `key: 'value'` entry in an import's attributes clause
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct ImportAttribute<T> {
+    pub key: ImportAttributeKey<T>,
+    pub colon: Colon,
+    pub value: StringLit<T>,
+}
+
+impl<T> IntoAllocated for ImportAttribute<T>
+where
+    T: ToString,
+{
+    type Allocated = ImportAttribute<String>;
+    fn into_allocated(self) -> ImportAttribute<String> {
+        ImportAttribute {
+            key: self.key.into_allocated(),
+            colon: self.colon,
+            value: self.value.into_allocated(),
+        }
+    }
+}
+
+impl<T> Node for ImportAttribute<T> {
+    fn loc(&self) -> SourceLocation {
+        SourceLocation::between(&self.key, &self.value)
+    }
+}
+
+/// The key of an import attribute, either an identifier
+/// or a string literal
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum ImportAttributeKey<T> {
+    Ident(Ident<T>),
+    String(StringLit<T>),
+}
+
+impl<T> IntoAllocated for ImportAttributeKey<T>
+where
+    T: ToString,
+{
+    type Allocated = ImportAttributeKey<String>;
+    fn into_allocated(self) -> ImportAttributeKey<String> {
+        match self {
+            ImportAttributeKey::Ident(inner) => ImportAttributeKey::Ident(inner.into_allocated()),
+            ImportAttributeKey::String(inner) => ImportAttributeKey::String(inner.into_allocated()),
+        }
+    }
+}
+
+impl<T> Node for ImportAttributeKey<T> {
+    fn loc(&self) -> SourceLocation {
+        match self {
+            ImportAttributeKey::Ident(inner) => inner.loc(),
+            ImportAttributeKey::String(inner) => inner.loc(),
         }
     }
 }
@@ -488,6 +607,36 @@ pub enum ModExportSpecifier<T> {
     Named(NamedExportDecl<T>),
     /// ```js
     /// export * from 'mod';
+    /// //or
+    /// export * as ns from 'mod';
+    /// ```
+    ///
+    /// ```
+    /// use resast::codegen::WriteJs;
+    /// use resast::decl::{Decl, ModExport};
+    /// use resast::spanned::decl::{Alias, ModExportSpecifier};
+    /// use resast::spanned::expr::{Lit, StringLit};
+    /// use resast::spanned::tokens::{Quote, SingleQuote};
+    /// use resast::spanned::{Ident, Slice};
+    ///
+    /// let all = |alias| ModExportSpecifier::All {
+    ///     star: Default::default(),
+    ///     alias,
+    ///     keyword: Default::default(),
+    ///     name: Lit::String(StringLit {
+    ///         open_quote: Quote::Single(SingleQuote::default()),
+    ///         content: Slice::new("m", 1, 0, 1, 1),
+    ///         close_quote: Quote::Single(SingleQuote::default()),
+    ///     }),
+    /// };
+    /// let export = |spec| Decl::Export(Box::new(ModExport::from(spec)));
+    /// let plain = export(all(None));
+    /// assert_eq!(plain.to_js_string(), "export * from 'm';");
+    /// let namespace = export(all(Some(Alias {
+    ///     keyword: Default::default(),
+    ///     ident: Ident::new_from_source("ns", 1, 0),
+    /// })));
+    /// assert_eq!(namespace.to_js_string(), "export * as ns from 'm';");
     /// ```
     All {
         star: Asterisk,
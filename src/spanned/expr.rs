@@ -1,14 +1,17 @@
+use crate::expr::AccessorArityError;
 use crate::spanned::pat::Pat;
-use crate::spanned::{Class, Func, FuncArg, FuncBody, Ident};
+use crate::spanned::{Class, Decorator, Func, FuncArg, FuncBody, Ident, PrivateIdent, ProgramPart};
 use crate::IntoAllocated;
 
+#[cfg(feature = "jsx")]
+use super::jsx::{JsxElement, JsxFragment};
 use super::tokens::{
     self, AssignOp, Asterisk, Async, Await, BinaryOp, CloseBrace, CloseBracket, CloseParen, Colon,
-    Comma, Ellipsis, False, FatArrow, ForwardSlash, Get, LogicalOp, New, Null, OpenBrace,
-    OpenBracket, OpenParen, Period, QuasiQuote, QuestionMark, QuestionMarkDot, Quote, Set, Static,
-    Super, This, Token, True, UnaryOp, UpdateOp, Yield,
+    Comma, Ellipsis, Equal, False, FatArrow, ForwardSlash, Get, Import, LogicalOp, New, Null,
+    OpenBrace, OpenBracket, OpenParen, Period, QuasiQuote, QuestionMark, QuestionMarkDot, Quote,
+    Semicolon, Set, Static, Super, This, Token, True, UnaryOp, UpdateOp, Yield,
 };
-use super::{FuncArgEntry, ListEntry, Node, Slice, SourceLocation};
+use super::{FuncArgEntry, ListEntry, Node, Position, Slice, SourceLocation, Spanned};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
@@ -49,6 +52,16 @@ pub enum Expr<T> {
     Func(Func<T>),
     /// An identifier
     Ident(Ident<T>),
+    /// A private class member name
+    /// ```js
+    /// this.#x;
+    /// ```
+    PrivateIdent(PrivateIdent<T>),
+    /// A dynamic import
+    /// ```js
+    /// import('./mod.js');
+    /// ```
+    Import(Box<ImportExpr<T>>),
     /// A literal value, see `Literal`
     Lit(Lit<T>),
     /// A specialized `BinaryExpr` for logical evaluation
@@ -98,6 +111,14 @@ pub enum Expr<T> {
     /// yield a value from inside of a generator function
     Yield(YieldExpr<T>),
     OptionalChain(OptionalChain<T>),
+    /// A JSX element, see `JsxElement`
+    #[cfg(feature = "jsx")]
+    #[cfg_attr(feature = "serde", serde(rename = "JSXElement"))]
+    Jsx(Box<JsxElement<T>>),
+    /// A JSX fragment, see `JsxFragment`
+    #[cfg(feature = "jsx")]
+    #[cfg_attr(feature = "serde", serde(rename = "JSXFragment"))]
+    JsxFragment(Box<JsxFragment<T>>),
 }
 
 impl<T> IntoAllocated for Expr<T>
@@ -120,18 +141,15 @@ where
             Expr::Conditional(inner) => Expr::Conditional(inner.into_allocated()),
             Expr::Func(inner) => Expr::Func(inner.into_allocated()),
             Expr::Ident(inner) => Expr::Ident(inner.into_allocated()),
+            Expr::PrivateIdent(inner) => Expr::PrivateIdent(inner.into_allocated()),
+            Expr::Import(inner) => Expr::Import(inner.into_allocated()),
             Expr::Lit(inner) => Expr::Lit(inner.into_allocated()),
             Expr::Logical(inner) => Expr::Logical(inner.into_allocated()),
             Expr::Member(inner) => Expr::Member(inner.into_allocated()),
             Expr::MetaProp(inner) => Expr::MetaProp(inner.into_allocated()),
             Expr::New(inner) => Expr::New(inner.into_allocated()),
             Expr::Obj(inner) => Expr::Obj(inner.into_allocated()),
-            Expr::Sequence(inner) => Expr::Sequence(
-                inner
-                    .into_iter()
-                    .map(IntoAllocated::into_allocated)
-                    .collect(),
-            ),
+            Expr::Sequence(inner) => Expr::Sequence(inner.into_allocated()),
             Expr::Spread(inner) => Expr::Spread(inner.into_allocated()),
             Expr::Super(inner) => Expr::Super(inner),
             Expr::TaggedTemplate(inner) => Expr::TaggedTemplate(inner.into_allocated()),
@@ -141,6 +159,10 @@ where
             Expr::Wrapped(inner) => Expr::Wrapped(inner.into_allocated()),
             Expr::Yield(inner) => Expr::Yield(inner.into_allocated()),
             Expr::OptionalChain(inner) => Expr::OptionalChain(inner.into_allocated()),
+            #[cfg(feature = "jsx")]
+            Expr::Jsx(inner) => Expr::Jsx(inner.into_allocated()),
+            #[cfg(feature = "jsx")]
+            Expr::JsxFragment(inner) => Expr::JsxFragment(inner.into_allocated()),
         }
     }
 }
@@ -159,6 +181,8 @@ impl<T> Node for Expr<T> {
             Expr::Conditional(inner) => inner.loc(),
             Expr::Func(inner) => inner.loc(),
             Expr::Ident(inner) => inner.loc(),
+            Expr::PrivateIdent(inner) => inner.loc(),
+            Expr::Import(inner) => inner.loc(),
             Expr::Lit(inner) => inner.loc(),
             Expr::Logical(inner) => inner.loc(),
             Expr::Member(inner) => inner.loc(),
@@ -175,6 +199,273 @@ impl<T> Node for Expr<T> {
             Expr::Yield(inner) => inner.loc(),
             Expr::Wrapped(inner) => inner.loc(),
             Expr::OptionalChain(inner) => inner.loc(),
+            #[cfg(feature = "jsx")]
+            Expr::Jsx(inner) => inner.loc(),
+            #[cfg(feature = "jsx")]
+            Expr::JsxFragment(inner) => inner.loc(),
+        }
+    }
+}
+
+impl<T> Expr<T> {
+    /// Drop every span and token, producing the non-spanned tree
+    pub fn into_concrete(self) -> crate::Expr<T> {
+        self.into()
+    }
+    /// Remove any parentheses wrapped around this expression
+    /// ```
+    /// use resast::prelude::*;
+    /// use resast::spanned::expr::Expr as SpannedExpr;
+    /// use resast::spanned::spanner::Spanner;
+    ///
+    /// // ((a))
+    /// let wrapped = Expr::Paren(Box::new(Expr::Paren(Box::new(Expr::ident_from("a")))));
+    /// let spanned = Spanner::default().expr(wrapped).unwrap();
+    /// assert!(matches!(spanned.unwrap_parens(), SpannedExpr::Ident(_)));
+    /// ```
+    pub fn unwrap_parens(self) -> Expr<T> {
+        let mut expr = self;
+        while let Expr::Wrapped(inner) = expr {
+            expr = inner.expr;
+        }
+        expr
+    }
+    /// If this is an immediately invoked function expression,
+    /// a call whose callee is a function or arrow function, allowing
+    /// for any number of parentheses around the callee
+    pub fn is_iife(&self) -> bool {
+        match self {
+            Self::Call(call) => call.callee.unwrapped_is_func(),
+            _ => false,
+        }
+    }
+
+    fn unwrapped_is_func(&self) -> bool {
+        match self {
+            Self::Func(_) | Self::ArrowFunc(_) => true,
+            Self::Wrapped(inner) => inner.expr.unwrapped_is_func(),
+            _ => false,
+        }
+    }
+
+    /// Every expression nested inside this one, in pre-order
+    ///
+    /// Functions and classes are yielded but not entered, nothing
+    /// inside of their bodies or parameters will be included.
+    /// ```
+    /// use resast::prelude::*;
+    /// use resast::spanned::{self, spanner::Spanner};
+    ///
+    /// // f(g(h(x)))
+    /// let call = Expr::call(
+    ///     Expr::ident_from("f"),
+    ///     vec![Expr::call(
+    ///         Expr::ident_from("g"),
+    ///         vec![Expr::call(Expr::ident_from("h"), vec![Expr::ident_from("x")])],
+    ///     )],
+    /// );
+    /// let call = Spanner::default().expr(call).unwrap();
+    /// let names: Vec<_> = call
+    ///     .descendants()
+    ///     .filter_map(|expr| match expr {
+    ///         spanned::expr::Expr::Ident(ident) => Some(ident.slice.source),
+    ///         _ => None,
+    ///     })
+    ///     .collect();
+    /// assert_eq!(names, ["f", "g", "h", "x"]);
+    /// ```
+    pub fn descendants(&self) -> impl Iterator<Item = &Expr<T>> + '_ {
+        // an explicit stack keeps very deep trees from overflowing
+        let mut stack = Vec::new();
+        self.push_children(&mut stack);
+        std::iter::from_fn(move || {
+            let next = stack.pop()?;
+            next.push_children(&mut stack);
+            Some(next)
+        })
+    }
+
+    /// Push the direct children of this expression onto the stack
+    /// so that they are popped in source order
+    fn push_children<'a>(&'a self, stack: &mut Vec<&'a Expr<T>>) {
+        let start = stack.len();
+        match self {
+            Self::Array(inner) => {
+                stack.extend(inner.elements.iter().filter_map(|e| e.item.as_ref()))
+            }
+            Self::Assign(inner) => {
+                if let AssignLeft::Expr(left) = &inner.left {
+                    stack.push(left);
+                }
+                stack.push(&inner.right);
+            }
+            Self::Await(inner) => stack.push(&inner.expr),
+            Self::Binary(inner) => {
+                stack.push(&inner.left);
+                stack.push(&inner.right);
+            }
+            Self::Call(inner) => {
+                stack.push(&inner.callee);
+                stack.extend(inner.arguments.iter().map(|e| &e.item));
+            }
+            Self::Conditional(inner) => {
+                stack.push(&inner.test);
+                stack.push(&inner.alternate);
+                stack.push(&inner.consequent);
+            }
+            Self::Import(inner) => {
+                stack.push(&inner.source.item);
+                stack.extend(inner.options.as_ref().map(|e| &e.item));
+            }
+            Self::Lit(Lit::Template(inner)) => stack.extend(&inner.expressions),
+            Self::Logical(inner) => {
+                stack.push(&inner.left);
+                stack.push(&inner.right);
+            }
+            Self::Member(inner) => {
+                stack.push(&inner.object);
+                stack.push(&inner.property);
+            }
+            Self::New(inner) => {
+                stack.push(&inner.callee);
+                stack.extend(inner.arguments.iter().map(|e| &e.item));
+            }
+            Self::Obj(inner) => {
+                for prop in &inner.props {
+                    match &prop.item {
+                        ObjProp::Prop(Prop::Init(prop)) => {
+                            if let PropKey::Expr(key) = &prop.key.value {
+                                stack.push(key);
+                            }
+                            if let Some(PropValue::Expr(value)) = &prop.value {
+                                stack.push(value);
+                            }
+                        }
+                        ObjProp::Prop(Prop::Method(PropMethod { id, .. }))
+                        | ObjProp::Prop(Prop::Get(PropGet { id, .. }))
+                        | ObjProp::Prop(Prop::Set(PropSet { id, .. })) => {
+                            if let PropKey::Expr(key) = &id.value {
+                                stack.push(key);
+                            }
+                        }
+                        ObjProp::Prop(_) => {}
+                        ObjProp::Spread(spread) => stack.push(&spread.expr),
+                    }
+                }
+            }
+            Self::Sequence(exprs) => stack.extend(exprs.iter().map(|e| &e.item)),
+            Self::Spread(inner) => stack.push(&inner.expr),
+            Self::TaggedTemplate(inner) => {
+                stack.push(&inner.tag);
+                stack.extend(&inner.quasi.expressions);
+            }
+            Self::Unary(inner) => stack.push(&inner.argument),
+            Self::Update(inner) => stack.push(&inner.argument),
+            Self::Wrapped(inner) => stack.push(&inner.expr),
+            Self::Yield(inner) => stack.extend(inner.argument.as_deref()),
+            Self::OptionalChain(inner) => stack.push(&inner.expr),
+            #[cfg(feature = "jsx")]
+            Self::Jsx(inner) => inner.push_exprs(stack),
+            #[cfg(feature = "jsx")]
+            Self::JsxFragment(inner) => super::jsx::push_child_exprs(&inner.children, stack),
+            Self::ArrowFunc(_)
+            | Self::ArrowParamPlaceHolder(_)
+            | Self::Class(_)
+            | Self::Func(_)
+            | Self::Ident(_)
+            | Self::PrivateIdent(_)
+            | Self::Lit(_)
+            | Self::MetaProp(_)
+            | Self::Super(_)
+            | Self::This(_) => {}
+        }
+        stack[start..].reverse();
+    }
+}
+
+impl<T> Expr<T>
+where
+    T: PartialEq,
+{
+    /// Compare two expressions by their structure alone.
+    ///
+    /// Every `Position` is ignored, which covers both `Slice::loc` and the
+    /// location of each token. Tokens that only exist to reproduce the
+    /// source text (parentheses, commas, brackets and semi-colons) are
+    /// ignored as well, so `(a)` is equal to `a`.
+    ///
+    /// ```
+    /// use resast::prelude::*;
+    /// use resast::spanned::spanner::Spanner;
+    /// use resast::spanned::Position;
+    ///
+    /// let expr = Expr::Binary(BinaryExpr {
+    ///     operator: BinaryOp::Plus,
+    ///     left: Box::new(Expr::ident_from("a")),
+    ///     right: Box::new(Expr::Lit(Lit::number_from("1"))),
+    /// });
+    /// let first = Spanner::new(Position::new(1, 0)).expr(expr.clone()).unwrap();
+    /// let second = Spanner::new(Position::new(10, 4)).expr(expr).unwrap();
+    /// assert_ne!(first, second);
+    /// assert!(first.eq_ignore_span(&second));
+    /// ```
+    pub fn eq_ignore_span(&self, other: &Self) -> bool {
+        crate::Expr::from(self) == crate::Expr::from(other)
+    }
+}
+
+impl<T> Expr<T>
+where
+    T: std::hash::Hash,
+{
+    /// Hash this expression by its structure alone, two expressions
+    /// that are [`eq_ignore_span`](Self::eq_ignore_span) hash the same
+    ///
+    /// ```
+    /// use std::collections::hash_map::DefaultHasher;
+    /// use std::hash::Hasher;
+    /// use resast::prelude::*;
+    /// use resast::spanned::spanner::Spanner;
+    /// use resast::spanned::Position;
+    ///
+    /// let expr = Expr::binary(
+    ///     BinaryOp::Plus,
+    ///     Expr::ident_from("a"),
+    ///     Expr::lit(Lit::number_from("1")),
+    /// );
+    /// let hash = |position| {
+    ///     let spanned = Spanner::new(position).expr(expr.clone()).unwrap();
+    ///     let mut hasher = DefaultHasher::new();
+    ///     spanned.hash_ignore_span(&mut hasher);
+    ///     hasher.finish()
+    /// };
+    /// assert_eq!(hash(Position::new(1, 0)), hash(Position::new(7, 3)));
+    /// ```
+    pub fn hash_ignore_span<H: std::hash::Hasher>(&self, state: &mut H) {
+        std::hash::Hash::hash(&crate::Expr::from(self), state)
+    }
+}
+
+impl<T> Expr<T>
+where
+    T: AsRef<str>,
+{
+    /// If this is the `new.target` meta property
+    /// ```
+    /// use resast::spanned::expr::{Expr, MetaProp};
+    /// use resast::spanned::{Ident, Position};
+    ///
+    /// let start = Position::new(1, 0);
+    /// assert!(Expr::MetaProp(MetaProp::<&str>::new_target(start)).is_new_target());
+    /// assert!(!Expr::MetaProp(MetaProp::<&str>::import_meta(start)).is_new_target());
+    /// assert!(!Expr::Ident(Ident::new_from_source("target", 1, 0)).is_new_target());
+    /// ```
+    pub fn is_new_target(&self) -> bool {
+        match self {
+            Self::MetaProp(meta) => {
+                meta.meta.name().as_ref() == "new" && meta.property.name().as_ref() == "target"
+            }
+            _ => false,
         }
     }
 }
@@ -217,6 +508,58 @@ impl<T> Node for ArrayExpr<T> {
     }
 }
 
+impl<T> ArrayExpr<T> {
+    /// The number of elisions, an entry without an item is only a hole
+    /// when a comma follows it so the trailing comma in `[1,]` isn't one
+    /// ```
+    /// use resast::spanned::expr::{ArrayExpr, Expr, Lit};
+    /// use resast::spanned::{ListEntry, Slice};
+    ///
+    /// fn array<'a>(entries: Vec<(Option<&'a str>, bool)>) -> ArrayExpr<&'a str> {
+    ///     let elements = entries
+    ///         .into_iter()
+    ///         .map(|(item, comma)| ListEntry {
+    ///             item: item.map(|n| Expr::Lit(Lit::Number(Slice::new(n, 1, 0, 1, 1)))),
+    ///             comma: comma.then(Default::default),
+    ///         })
+    ///         .collect();
+    ///     ArrayExpr {
+    ///         open_bracket: Default::default(),
+    ///         elements,
+    ///         close_bracket: Default::default(),
+    ///     }
+    /// }
+    /// fn concrete(arr: ArrayExpr<&str>) -> Vec<bool> {
+    ///     match resast::expr::Expr::from(Expr::Array(arr)) {
+    ///         resast::expr::Expr::Array(elements) => elements.iter().map(Option::is_some).collect(),
+    ///         _ => unreachable!(),
+    ///     }
+    /// }
+    /// // [,,]
+    /// let holes = array(vec![(None, true), (None, true)]);
+    /// assert_eq!(holes.holes(), 2);
+    /// assert_eq!(concrete(holes), vec![false, false]);
+    /// // [1,]
+    /// let trailing = array(vec![(Some("1"), true)]);
+    /// assert_eq!(trailing.holes(), 0);
+    /// assert_eq!(concrete(trailing), vec![true]);
+    /// // [1,,2]
+    /// let interior = array(vec![(Some("1"), true), (None, true), (Some("2"), false)]);
+    /// assert_eq!(interior.holes(), 1);
+    /// assert_eq!(concrete(interior), vec![true, false, true]);
+    /// // [1,] with the trailing comma followed by an empty entry
+    /// let empty_last = array(vec![(Some("1"), true), (None, false)]);
+    /// assert_eq!(empty_last.holes(), 0);
+    /// assert_eq!(concrete(empty_last), vec![true]);
+    /// ```
+    pub fn holes(&self) -> usize {
+        self.elements
+            .iter()
+            .filter(|entry| entry.item.is_none() && entry.comma.is_some())
+            .count()
+    }
+}
+
 /// `{a: 'b', c, ...d}`
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
@@ -321,6 +664,8 @@ pub enum Prop<T> {
     Ctor(PropCtor<T>),
     Get(PropGet<T>),
     Set(PropSet<T>),
+    Field(PropField<T>),
+    StaticBlock(StaticBlock<T>),
 }
 
 impl<T> IntoAllocated for Prop<T>
@@ -335,6 +680,8 @@ where
             Prop::Ctor(inner) => Prop::Ctor(inner.into_allocated()),
             Prop::Get(inner) => Prop::Get(inner.into_allocated()),
             Prop::Set(inner) => Prop::Set(inner.into_allocated()),
+            Prop::Field(inner) => Prop::Field(inner.into_allocated()),
+            Prop::StaticBlock(inner) => Prop::StaticBlock(inner.into_allocated()),
         }
     }
 }
@@ -347,16 +694,18 @@ impl<T> Node for Prop<T> {
             Prop::Ctor(inner) => inner.loc(),
             Prop::Get(inner) => inner.loc(),
             Prop::Set(inner) => inner.loc(),
+            Prop::Field(inner) => inner.loc(),
+            Prop::StaticBlock(inner) => inner.loc(),
         }
     }
 }
 
 impl<T> Prop<T> {
     pub fn computed(&self) -> bool {
-        if let Self::Init(init) = self {
-            init.computed()
-        } else {
-            false
+        match self {
+            Self::Init(init) => init.computed(),
+            Self::Field(field) => field.computed(),
+            _ => false,
         }
     }
     pub fn short_hand(&self) -> bool {
@@ -373,6 +722,112 @@ impl<T> Prop<T> {
             false
         }
     }
+    /// If this is a method with a `*`
+    /// ```
+    /// use resast::spanned::expr::{Expr, Prop, PropCtor, PropGet, PropInitKey, PropKey, PropMethod};
+    /// use resast::spanned::{FuncBody, Ident};
+    /// use resast::PropKind;
+    ///
+    /// fn key(name: &str) -> PropInitKey<&str> {
+    ///     PropInitKey {
+    ///         value: PropKey::Expr(Expr::Ident(Ident::new_from_source(name, 1, 0))),
+    ///         brackets: None,
+    ///     }
+    /// }
+    /// fn body<'a>() -> FuncBody<&'a str> {
+    ///     FuncBody {
+    ///         open_brace: Default::default(),
+    ///         stmts: vec![],
+    ///         close_brace: Default::default(),
+    ///     }
+    /// }
+    /// let method = |keyword_async, star| {
+    ///     Prop::Method(PropMethod {
+    ///         decorators: vec![],
+    ///         keyword_static: None,
+    ///         keyword_async,
+    ///         id: key("m"),
+    ///         star,
+    ///         open_paren: Default::default(),
+    ///         params: vec![],
+    ///         close_paren: Default::default(),
+    ///         body: body(),
+    ///     })
+    /// };
+    /// // *m() {}
+    /// let generator = method(None, Some(Default::default()));
+    /// assert!(generator.is_generator());
+    /// assert!(!generator.is_async());
+    /// assert_eq!(generator.kind(), PropKind::Method);
+    /// // async m() {}
+    /// let async_method = method(Some(Default::default()), None);
+    /// assert!(async_method.is_async());
+    /// assert!(!async_method.is_generator());
+    /// // get m() {}
+    /// let getter = Prop::Get(PropGet {
+    ///     decorators: vec![],
+    ///     keyword_static: None,
+    ///     keyword_get: Default::default(),
+    ///     id: key("m"),
+    ///     open_paren: Default::default(),
+    ///     close_paren: Default::default(),
+    ///     body: body(),
+    /// });
+    /// assert!(getter.is_getter());
+    /// assert!(!getter.is_setter());
+    /// assert_eq!(getter.kind(), PropKind::Get);
+    /// // constructor() {}
+    /// let ctor = Prop::Ctor(PropCtor {
+    ///     decorators: vec![],
+    ///     keyword: key("constructor"),
+    ///     open_paren: Default::default(),
+    ///     params: vec![],
+    ///     close_paren: Default::default(),
+    ///     body: body(),
+    /// });
+    /// assert!(ctor.is_ctor());
+    /// assert!(!ctor.is_generator());
+    /// assert_eq!(ctor.kind(), PropKind::Ctor);
+    /// ```
+    pub fn is_generator(&self) -> bool {
+        if let Self::Method(meth) = self {
+            meth.star.is_some()
+        } else {
+            false
+        }
+    }
+    pub fn is_getter(&self) -> bool {
+        matches!(self, Self::Get(_))
+    }
+    pub fn is_setter(&self) -> bool {
+        matches!(self, Self::Set(_))
+    }
+    pub fn is_ctor(&self) -> bool {
+        matches!(self, Self::Ctor(_))
+    }
+    /// The kind this property has in the non-spanned tree
+    pub fn kind(&self) -> crate::PropKind {
+        match self {
+            Self::Init(_) => crate::PropKind::Init,
+            Self::Method(_) => crate::PropKind::Method,
+            Self::Ctor(_) => crate::PropKind::Ctor,
+            Self::Get(_) => crate::PropKind::Get,
+            Self::Set(_) => crate::PropKind::Set,
+            Self::Field(_) => crate::PropKind::Field,
+            Self::StaticBlock(_) => crate::PropKind::StaticBlock,
+        }
+    }
+    /// Check that a setter's parameter isn't a rest element, getters
+    /// and setters can't be written with any other number of
+    /// parameters in the spanned tree
+    pub fn validate_accessor_arity(&self) -> Result<(), AccessorArityError> {
+        match self {
+            Self::Set(set) if matches!(set.param(), FuncArg::Rest(_)) => {
+                Err(AccessorArityError::SetterRest)
+            }
+            _ => Ok(()),
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -452,9 +907,59 @@ impl<T> Node for PropInitKey<T> {
     }
 }
 
+/// A method of an object literal or class
+///
+/// When `id.brackets` is set the key is computed, which carries
+/// through to `computed` on the non-spanned `Prop`
+/// ```
+/// use resast::codegen::WriteJs;
+/// use resast::spanned::{
+///     expr::{Expr, Prop, PropInitKey, PropKey, PropMethod},
+///     Class, ClassBody, FuncBody, Ident, Position, Slice,
+/// };
+///
+/// let at = |column| Position::new(1, column);
+/// // class A { [x]() {} }
+/// let method = PropMethod {
+///     decorators: Vec::new(),
+///     keyword_static: None,
+///     keyword_async: None,
+///     id: PropInitKey {
+///         value: PropKey::Expr(Expr::Ident(Ident::from(Slice::new("x", 1, 11, 1, 12)))),
+///         brackets: Some((at(10).into(), at(12).into())),
+///     },
+///     star: None,
+///     open_paren: at(13).into(),
+///     params: Vec::new(),
+///     close_paren: at(14).into(),
+///     body: FuncBody {
+///         open_brace: at(16).into(),
+///         stmts: Vec::new(),
+///         close_brace: at(17).into(),
+///     },
+/// };
+/// let class = Class {
+///     decorators: Vec::new(),
+///     keyword: at(0).into(),
+///     id: Some(Ident::from(Slice::new("A", 1, 6, 1, 7))),
+///     super_class: None,
+///     body: ClassBody {
+///         open_brace: at(8).into(),
+///         props: vec![Prop::Method(method)],
+///         close_brace: at(19).into(),
+///     },
+/// };
+/// let class = resast::Class::from(class);
+/// assert!(class.body.0[0].computed);
+/// assert_eq!(
+///     resast::decl::Decl::Class(class).to_js_string(),
+///     "class A { [x]() {} }"
+/// );
+/// ```
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct PropMethod<T> {
+    pub decorators: Vec<Decorator<T>>,
     pub keyword_static: Option<Static>,
     pub keyword_async: Option<Async>,
     pub id: PropInitKey<T>,
@@ -472,6 +977,11 @@ where
     type Allocated = PropMethod<String>;
     fn into_allocated(self) -> Self::Allocated {
         PropMethod {
+            decorators: self
+                .decorators
+                .into_iter()
+                .map(IntoAllocated::into_allocated)
+                .collect(),
             keyword_static: self.keyword_static,
             keyword_async: self.keyword_async,
             id: self.id.into_allocated(),
@@ -488,9 +998,18 @@ where
     }
 }
 
+impl<T> PropMethod<T> {
+    /// The parameters of this method without their commas
+    pub fn params(&self) -> impl Iterator<Item = &FuncArg<T>> + '_ {
+        self.params.iter().map(|entry| &entry.item)
+    }
+}
+
 impl<T> Node for PropMethod<T> {
     fn loc(&self) -> SourceLocation {
-        let start = if let Some(keyword) = &self.keyword_async {
+        let start = if let Some(decorator) = self.decorators.first() {
+            decorator.at.start()
+        } else if let Some(keyword) = &self.keyword_async {
             keyword.start()
         } else if let Some(star) = &self.star {
             star.start()
@@ -507,6 +1026,7 @@ impl<T> Node for PropMethod<T> {
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct PropCtor<T> {
+    pub decorators: Vec<Decorator<T>>,
     pub keyword: PropInitKey<T>,
     pub open_paren: OpenParen,
     pub params: Vec<ListEntry<FuncArg<T>>>,
@@ -521,6 +1041,11 @@ where
     type Allocated = PropCtor<String>;
     fn into_allocated(self) -> Self::Allocated {
         PropCtor {
+            decorators: self
+                .decorators
+                .into_iter()
+                .map(IntoAllocated::into_allocated)
+                .collect(),
             keyword: self.keyword.into_allocated(),
             open_paren: self.open_paren,
             params: self
@@ -536,16 +1061,17 @@ where
 
 impl<T> Node for PropCtor<T> {
     fn loc(&self) -> SourceLocation {
-        SourceLocation {
-            start: self.keyword.loc().start,
-            end: self.body.loc().end,
+        if let Some(decorator) = self.decorators.first() {
+            return SourceLocation::between(decorator, &self.body);
         }
+        SourceLocation::between(&self.keyword, &self.body)
     }
 }
 
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct PropGet<T> {
+    pub decorators: Vec<Decorator<T>>,
     pub keyword_static: Option<Static>,
     pub keyword_get: Get,
     pub id: PropInitKey<T>,
@@ -561,6 +1087,11 @@ where
     type Allocated = PropGet<String>;
     fn into_allocated(self) -> Self::Allocated {
         PropGet {
+            decorators: self
+                .decorators
+                .into_iter()
+                .map(IntoAllocated::into_allocated)
+                .collect(),
             keyword_static: self.keyword_static,
             keyword_get: self.keyword_get,
             id: self.id.into_allocated(),
@@ -573,6 +1104,9 @@ where
 
 impl<T> Node for PropGet<T> {
     fn loc(&self) -> SourceLocation {
+        if let Some(decorator) = self.decorators.first() {
+            return SourceLocation::between(decorator, &self.body);
+        }
         if let Some(keyword_static) = &self.keyword_static {
             return SourceLocation {
                 start: keyword_static.start(),
@@ -589,6 +1123,7 @@ impl<T> Node for PropGet<T> {
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct PropSet<T> {
+    pub decorators: Vec<Decorator<T>>,
     pub keyword_static: Option<Static>,
     pub keyword_set: Set,
     pub id: PropInitKey<T>,
@@ -605,6 +1140,11 @@ where
     type Allocated = PropSet<String>;
     fn into_allocated(self) -> Self::Allocated {
         PropSet {
+            decorators: self
+                .decorators
+                .into_iter()
+                .map(IntoAllocated::into_allocated)
+                .collect(),
             keyword_static: self.keyword_static,
             keyword_set: self.keyword_set,
             id: self.id.into_allocated(),
@@ -616,8 +1156,18 @@ where
     }
 }
 
+impl<T> PropSet<T> {
+    /// The single parameter of this setter
+    pub fn param(&self) -> &FuncArg<T> {
+        &self.arg.item
+    }
+}
+
 impl<T> Node for PropSet<T> {
     fn loc(&self) -> SourceLocation {
+        if let Some(decorator) = self.decorators.first() {
+            return SourceLocation::between(decorator, &self.body);
+        }
         if let Some(keyword_static) = &self.keyword_static {
             return SourceLocation {
                 start: keyword_static.start(),
@@ -631,6 +1181,154 @@ impl<T> Node for PropSet<T> {
     }
 }
 
+/// A class field declaration
+/// ```js
+/// class A {
+///     x = 1;
+///     static y;
+///     [z] = 2;
+/// }
+/// ```
+/// ```
+/// use resast::prelude::*;
+/// use resast::spanned::expr::{
+///     Expr as SExpr, Lit as SLit, Prop as SProp, PropField, PropInitKey, PropKey as SPropKey,
+/// };
+/// use resast::spanned::{Ident as SIdent, Slice};
+///
+/// let field = |name: &'static str, is_static: bool, computed: bool, init: Option<&'static str>| {
+///     SProp::Field(PropField {
+///         decorators: vec![],
+///         keyword_static: is_static.then(Default::default),
+///         key: PropInitKey {
+///             value: SPropKey::Expr(SExpr::Ident(SIdent::new_from_source(name, 1, 0))),
+///             brackets: computed.then(Default::default),
+///         },
+///         eq: init.map(|_| Default::default()),
+///         value: init.map(|n| SExpr::Lit(SLit::Number(Slice::new(n, 1, 0, 1, 1)))),
+///         semi_colon: Some(Default::default()),
+///     })
+/// };
+/// // x = 1;
+/// let public = Prop::from(field("x", false, false, Some("1")));
+/// assert_eq!(public.kind, PropKind::Field);
+/// assert!(!public.is_static && !public.computed);
+/// assert_eq!(public.value, PropValue::Expr(Expr::Lit(Lit::number_from("1"))));
+/// // static y;
+/// let stat = Prop::from(field("y", true, false, None));
+/// assert!(stat.is_static);
+/// assert_eq!(stat.value, PropValue::None);
+/// // [z] = 2;
+/// let computed = Prop::from(field("z", false, true, Some("2")));
+/// assert!(computed.computed);
+/// assert_eq!(computed.key, PropKey::Expr(Expr::ident_from("z")));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct PropField<T> {
+    pub decorators: Vec<Decorator<T>>,
+    pub keyword_static: Option<Static>,
+    pub key: PropInitKey<T>,
+    pub eq: Option<Equal>,
+    pub value: Option<Expr<T>>,
+    pub semi_colon: Option<Semicolon>,
+}
+
+impl<T> IntoAllocated for PropField<T>
+where
+    T: ToString,
+{
+    type Allocated = PropField<String>;
+    fn into_allocated(self) -> Self::Allocated {
+        PropField {
+            decorators: self
+                .decorators
+                .into_iter()
+                .map(IntoAllocated::into_allocated)
+                .collect(),
+            keyword_static: self.keyword_static,
+            key: self.key.into_allocated(),
+            eq: self.eq,
+            value: self.value.into_allocated(),
+            semi_colon: self.semi_colon,
+        }
+    }
+}
+
+impl<T> Node for PropField<T> {
+    fn loc(&self) -> SourceLocation {
+        let start = if let Some(decorator) = self.decorators.first() {
+            decorator.at.start()
+        } else if let Some(keyword_static) = &self.keyword_static {
+            keyword_static.start()
+        } else {
+            self.key.loc().start
+        };
+        let end = if let Some(semi) = &self.semi_colon {
+            semi.end()
+        } else if let Some(value) = &self.value {
+            value.loc().end
+        } else {
+            self.key.loc().end
+        };
+        SourceLocation { start, end }
+    }
+}
+
+impl<T> PropField<T> {
+    pub fn computed(&self) -> bool {
+        self.key.brackets.is_some()
+    }
+    pub fn is_static(&self) -> bool {
+        self.keyword_static.is_some()
+    }
+}
+
+/// A class static initialization block
+/// ```js
+/// class A {
+///     static {
+///         init();
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct StaticBlock<T> {
+    pub keyword_static: Static,
+    pub open_brace: OpenBrace,
+    pub stmts: Vec<ProgramPart<T>>,
+    pub close_brace: CloseBrace,
+}
+
+impl<T> IntoAllocated for StaticBlock<T>
+where
+    T: ToString,
+{
+    type Allocated = StaticBlock<String>;
+    fn into_allocated(self) -> Self::Allocated {
+        StaticBlock {
+            keyword_static: self.keyword_static,
+            open_brace: self.open_brace,
+            stmts: self
+                .stmts
+                .into_iter()
+                .map(IntoAllocated::into_allocated)
+                .collect(),
+            close_brace: self.close_brace,
+        }
+    }
+}
+
+impl<T> Node for StaticBlock<T> {
+    fn loc(&self) -> SourceLocation {
+        SourceLocation {
+            start: self.keyword_static.start(),
+            end: self.close_brace.end(),
+        }
+    }
+}
+
 /// An object literal or class property identifier
 #[derive(PartialEq, Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
@@ -805,10 +1503,7 @@ where
 
 impl<T> Node for BinaryExpr<T> {
     fn loc(&self) -> SourceLocation {
-        SourceLocation {
-            start: self.left.loc().start,
-            end: self.right.loc().end,
-        }
+        SourceLocation::between(&*self.left, &*self.right)
     }
 }
 
@@ -837,10 +1532,7 @@ where
 
 impl<T> Node for AssignExpr<T> {
     fn loc(&self) -> SourceLocation {
-        SourceLocation {
-            start: self.left.loc().start,
-            end: self.right.loc().end,
-        }
+        SourceLocation::between(&self.left, &*self.right)
     }
 }
 
@@ -903,6 +1595,83 @@ impl<T> Node for AssignLeft<T> {
     }
 }
 
+impl<T> AssignLeft<T> {
+    /// The pattern if this is a destructuring assignment
+    /// ```
+    /// use resast::spanned::expr::{AssignLeft, Expr};
+    /// use resast::spanned::pat::{ArrayPat, ArrayPatPart, Pat};
+    /// use resast::spanned::{Ident, ListEntry};
+    /// use std::convert::TryFrom;
+    ///
+    /// // [a] = b
+    /// let left = AssignLeft::from(Pat::Array(ArrayPat {
+    ///     open_bracket: Default::default(),
+    ///     elements: vec![ListEntry::no_comma(Some(ArrayPatPart::Pat(Pat::Ident(
+    ///         Ident::new_from_source("a", 1, 1),
+    ///     ))))],
+    ///     close_bracket: Default::default(),
+    /// }));
+    /// assert!(left.as_pat().is_some());
+    /// assert!(left.as_expr().is_none());
+    /// assert!(Expr::try_from(left.clone()).is_err());
+    /// assert!(matches!(Pat::try_from(left), Ok(Pat::Array(_))));
+    ///
+    /// // x = b
+    /// let x = Expr::Ident(Ident::new_from_source("x", 1, 0));
+    /// let left = AssignLeft::from(x.clone());
+    /// assert_eq!(left.as_expr(), Some(&x));
+    /// assert!(left.as_pat().is_none());
+    /// assert_eq!(Expr::try_from(left), Ok(x));
+    /// ```
+    pub fn as_pat(&self) -> Option<&Pat<T>> {
+        match self {
+            Self::Pat(inner) => Some(inner),
+            Self::Expr(_) => None,
+        }
+    }
+    /// The expression if this is a simple assignment
+    pub fn as_expr(&self) -> Option<&Expr<T>> {
+        match self {
+            Self::Expr(inner) => Some(inner),
+            Self::Pat(_) => None,
+        }
+    }
+}
+
+impl<T> From<Pat<T>> for AssignLeft<T> {
+    fn from(pat: Pat<T>) -> Self {
+        Self::Pat(pat)
+    }
+}
+
+impl<T> From<Expr<T>> for AssignLeft<T> {
+    fn from(expr: Expr<T>) -> Self {
+        Self::Expr(Box::new(expr))
+    }
+}
+
+/// Hands the `AssignLeft` back if it holds an expression
+impl<T> TryFrom<AssignLeft<T>> for Pat<T> {
+    type Error = AssignLeft<T>;
+    fn try_from(left: AssignLeft<T>) -> Result<Self, Self::Error> {
+        match left {
+            AssignLeft::Pat(inner) => Ok(inner),
+            left => Err(left),
+        }
+    }
+}
+
+/// Hands the `AssignLeft` back if it holds a pattern
+impl<T> TryFrom<AssignLeft<T>> for Expr<T> {
+    type Error = AssignLeft<T>;
+    fn try_from(left: AssignLeft<T>) -> Result<Self, Self::Error> {
+        match left {
+            AssignLeft::Expr(inner) => Ok(*inner),
+            left => Err(left),
+        }
+    }
+}
+
 /// A specialized `BinaryExpr` for logical evaluation
 /// ```js
 /// true && true
@@ -932,10 +1701,7 @@ where
 
 impl<T> Node for LogicalExpr<T> {
     fn loc(&self) -> SourceLocation {
-        SourceLocation {
-            start: self.left.loc().start,
-            end: self.right.loc().end,
-        }
+        SourceLocation::between(&*self.left, &*self.right)
     }
 }
 
@@ -969,8 +1735,10 @@ where
 
 impl<T> MemberExpr<T> {
     pub fn computed(&self) -> bool {
-        matches!(self.indexer, MemberIndexer::Computed { .. })
-            || matches!(self.indexer, MemberIndexer::OptionalComputed { .. })
+        self.indexer.is_computed()
+    }
+    pub fn optional(&self) -> bool {
+        self.indexer.is_optional()
     }
 }
 
@@ -1012,6 +1780,17 @@ pub enum MemberIndexer {
     },
 }
 
+impl MemberIndexer {
+    /// `?.` or `?.[ ]`
+    pub fn is_optional(&self) -> bool {
+        matches!(self, Self::Optional(_) | Self::OptionalComputed { .. })
+    }
+    /// `[ ]` or `?.[ ]`
+    pub fn is_computed(&self) -> bool {
+        matches!(self, Self::Computed { .. } | Self::OptionalComputed { .. })
+    }
+}
+
 impl Node for MemberIndexer {
     fn loc(&self) -> SourceLocation {
         match self {
@@ -1147,6 +1926,71 @@ impl<T> Node for CallExpr<T> {
     }
 }
 
+impl<T> CallExpr<T> {
+    /// The expression at the far left of the callee, following
+    /// member objects, optional chains and the callees of nested calls
+    /// ```
+    /// use resast::spanned::expr::{CallExpr, Expr, MemberExpr, MemberIndexer};
+    /// use resast::spanned::{Ident, Slice};
+    ///
+    /// let ident = |name| Expr::Ident(Ident::new_from_source(name, 1, 0));
+    /// let member = |object, property| {
+    ///     Expr::Member(MemberExpr {
+    ///         object: Box::new(object),
+    ///         property: Box::new(property),
+    ///         indexer: MemberIndexer::Period(Default::default()),
+    ///     })
+    /// };
+    /// let call = |callee| CallExpr {
+    ///     callee: Box::new(callee),
+    ///     optional: None,
+    ///     open_paren: Default::default(),
+    ///     arguments: vec![],
+    ///     close_paren: Default::default(),
+    /// };
+    /// // a.b.c()
+    /// let abc = call(member(member(ident("a"), ident("b")), ident("c")));
+    /// assert_eq!(abc.root_callee(), &ident("a"));
+    /// assert_eq!(abc.member_path(), [&ident("a"), &ident("b"), &ident("c")]);
+    /// // f()()
+    /// let ff = call(Expr::Call(call(ident("f"))));
+    /// assert_eq!(ff.root_callee(), &ident("f"));
+    /// assert_eq!(ff.member_path(), [&ident("f")]);
+    /// ```
+    pub fn root_callee(&self) -> &Expr<T> {
+        let mut expr = &*self.callee;
+        loop {
+            expr = match expr {
+                Expr::Member(member) => &member.object,
+                Expr::Call(call) => &call.callee,
+                Expr::OptionalChain(chain) => &chain.expr,
+                _ => return expr,
+            };
+        }
+    }
+    /// The root callee followed by every member property on the
+    /// way to the called function, `console.log()` is
+    /// `[console, log]`
+    pub fn member_path(&self) -> Vec<&Expr<T>> {
+        let mut path = Vec::new();
+        let mut expr = &*self.callee;
+        loop {
+            expr = match expr {
+                Expr::Member(member) => {
+                    path.push(&*member.property);
+                    &member.object
+                }
+                Expr::Call(call) => &call.callee,
+                Expr::OptionalChain(chain) => &chain.expr,
+                _ => break,
+            };
+        }
+        path.push(expr);
+        path.reverse();
+        path
+    }
+}
+
 /// Calling a constructor
 /// ```js
 /// new Uint8Array(32);
@@ -1198,25 +2042,105 @@ impl<T> Node for NewExpr<T> {
     }
 }
 
+impl<T> NewExpr<T> {
+    /// The arguments passed to the constructor, empty for both
+    /// `new Foo` and `new Foo()`
+    /// ```
+    /// use resast::spanned::expr::{Expr, NewExpr};
+    /// use resast::spanned::Ident;
+    ///
+    /// let new = |parens: bool| NewExpr {
+    ///     keyword: Default::default(),
+    ///     callee: Box::new(Expr::Ident(Ident::new_from_source("Foo", 1, 4))),
+    ///     open_paren: parens.then(Default::default),
+    ///     arguments: vec![],
+    ///     close_paren: parens.then(Default::default),
+    /// };
+    /// // new Foo
+    /// let bare = new(false);
+    /// assert!(!bare.has_parens());
+    /// assert!(bare.arguments().is_empty());
+    /// // new Foo()
+    /// let called = new(true);
+    /// assert!(called.has_parens());
+    /// assert!(called.arguments().is_empty());
+    /// ```
+    pub fn arguments(&self) -> &[ListEntry<Expr<T>>] {
+        &self.arguments
+    }
+    /// If the arguments are wrapped in parentheses, `new Foo()`
+    /// rather than `new Foo`
+    pub fn has_parens(&self) -> bool {
+        self.open_paren.is_some()
+    }
+}
+
 /// A collection of `Exprs` separated by commas
-pub type SequenceExpr<T> = Vec<ListEntry<Expr<T>>>;
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct SequenceExpr<T>(pub Vec<ListEntry<Expr<T>>>);
+
+impl<T> IntoAllocated for SequenceExpr<T>
+where
+    T: ToString,
+{
+    type Allocated = SequenceExpr<String>;
+    fn into_allocated(self) -> Self::Allocated {
+        SequenceExpr(
+            self.0
+                .into_iter()
+                .map(IntoAllocated::into_allocated)
+                .collect(),
+        )
+    }
+}
+
+impl<T> SequenceExpr<T> {
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+    pub fn iter(&self) -> std::slice::Iter<'_, ListEntry<Expr<T>>> {
+        self.0.iter()
+    }
+    pub fn push(&mut self, entry: ListEntry<Expr<T>>) {
+        self.0.push(entry)
+    }
+}
+
+impl<T> Default for SequenceExpr<T> {
+    fn default() -> Self {
+        Self(Vec::new())
+    }
+}
+
+impl<T> From<Vec<ListEntry<Expr<T>>>> for SequenceExpr<T> {
+    fn from(entries: Vec<ListEntry<Expr<T>>>) -> Self {
+        Self(entries)
+    }
+}
+
+impl<T> IntoIterator for SequenceExpr<T> {
+    type Item = ListEntry<Expr<T>>;
+    type IntoIter = std::vec::IntoIter<ListEntry<Expr<T>>>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a SequenceExpr<T> {
+    type Item = &'a ListEntry<Expr<T>>;
+    type IntoIter = std::slice::Iter<'a, ListEntry<Expr<T>>>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
 
 impl<T> Node for SequenceExpr<T> {
     fn loc(&self) -> SourceLocation {
-        let first_loc = if let Some(first) = self.first() {
-            first.loc()
-        } else {
-            SourceLocation::zero()
-        };
-        let last_loc = if let Some(last) = self.last() {
-            last.loc()
-        } else {
-            SourceLocation::zero()
-        };
-        SourceLocation {
-            start: first_loc.start,
-            end: last_loc.end,
-        }
+        self.0[..].loc()
     }
 }
 
@@ -1404,6 +2328,54 @@ impl<T> Node for YieldExpr<T> {
     }
 }
 
+impl<T> YieldExpr<T> {
+    /// If this is a `yield*` that hands off to another iterator, the
+    /// same as `delegate` in the non-spanned tree
+    /// ```
+    /// use resast::spanned::expr::{CallExpr, Expr, YieldExpr};
+    /// use resast::spanned::Ident;
+    ///
+    /// let ident = |name| Box::new(Expr::Ident(Ident::new_from_source(name, 1, 0)));
+    /// // yield
+    /// let bare = YieldExpr::<&str> {
+    ///     keyword: Default::default(),
+    ///     argument: None,
+    ///     star: None,
+    /// };
+    /// assert!(!bare.is_delegate());
+    /// assert!(!bare.has_argument());
+    /// // yield x
+    /// let value = YieldExpr {
+    ///     keyword: Default::default(),
+    ///     argument: Some(ident("x")),
+    ///     star: None,
+    /// };
+    /// assert!(!value.is_delegate());
+    /// assert!(value.has_argument());
+    /// // yield* gen()
+    /// let delegate = YieldExpr {
+    ///     keyword: Default::default(),
+    ///     argument: Some(Box::new(Expr::Call(CallExpr {
+    ///         callee: ident("gen"),
+    ///         optional: None,
+    ///         open_paren: Default::default(),
+    ///         arguments: vec![],
+    ///         close_paren: Default::default(),
+    ///     }))),
+    ///     star: Some(Default::default()),
+    /// };
+    /// assert!(delegate.is_delegate());
+    /// assert!(delegate.has_argument());
+    /// ```
+    pub fn is_delegate(&self) -> bool {
+        self.star.is_some()
+    }
+    /// If anything follows the `yield` keyword
+    pub fn has_argument(&self) -> bool {
+        self.argument.is_some()
+    }
+}
+
 /// A Template literal preceded by a function identifier
 /// see [MDN](https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Template_literals#Tagged_templates) for more details
 #[derive(PartialEq, Debug, Clone)]
@@ -1428,10 +2400,7 @@ where
 
 impl<T> Node for TaggedTemplateExpr<T> {
     fn loc(&self) -> SourceLocation {
-        SourceLocation {
-            start: self.tag.loc().start,
-            end: self.quasi.loc().end,
-        }
+        SourceLocation::between(&*self.tag, &self.quasi)
     }
 }
 
@@ -1469,20 +2438,7 @@ where
 
 impl<T> Node for TemplateLit<T> {
     fn loc(&self) -> SourceLocation {
-        let start = self
-            .quasis
-            .first()
-            .map(|q| q.loc())
-            .unwrap_or_else(SourceLocation::zero);
-        let end = self
-            .quasis
-            .last()
-            .map(|q| q.loc())
-            .unwrap_or_else(SourceLocation::zero);
-        SourceLocation {
-            start: start.start,
-            end: end.end,
-        }
+        self.quasis[..].loc()
     }
 }
 
@@ -1513,6 +2469,38 @@ impl<T> TemplateElement<T>
 where
     T: AsRef<str>,
 {
+    /// The text between the quotes exactly as it was written
+    pub fn raw(&self) -> &str {
+        self.content.source.as_ref()
+    }
+    /// The text between the quotes with escape sequences decoded and
+    /// line endings normalized to `\n`
+    /// ```
+    /// use resast::expr::UnescapeError;
+    /// use resast::spanned::expr::TemplateElement;
+    /// use resast::spanned::tokens::{BackTick, QuasiQuote};
+    /// use resast::spanned::Slice;
+    ///
+    /// let element = |raw: &'static str| TemplateElement {
+    ///     open_quote: QuasiQuote::BackTick(BackTick::default()),
+    ///     content: Slice::new(raw, 1, 1, 1, 1 + raw.len() as u32),
+    ///     close_quote: QuasiQuote::BackTick(BackTick::default()),
+    /// };
+    /// // `a\nb`
+    /// let newline = element(r"a\nb");
+    /// assert_eq!(newline.raw(), r"a\nb");
+    /// assert_eq!(newline.cooked().unwrap(), "a\nb");
+    /// // `\u{1F600}\uD83D\uDE00`
+    /// assert_eq!(element(r"\u{1F600}\uD83D\uDE00").cooked().unwrap(), "\u{1F600}\u{1F600}");
+    /// // `\xZZ`
+    /// let malformed = element(r"\xZZ");
+    /// assert_eq!(malformed.raw(), r"\xZZ");
+    /// assert_eq!(malformed.cooked(), Err(UnescapeError::InvalidHexEscape));
+    /// assert_eq!(element(r"\01").cooked(), Err(UnescapeError::OctalEscape));
+    /// ```
+    pub fn cooked(&self) -> Result<String, crate::expr::UnescapeError> {
+        crate::expr::cook_template(self.raw())
+    }
     pub fn is_tail(&self) -> bool {
         matches!(
             self.open_quote,
@@ -1530,7 +2518,7 @@ impl<T> Node for TemplateElement<T> {
     }
 }
 
-/// pretty much just `new.target`
+/// A meta property, either `new.target` or `import.meta`
 /// ```js
 /// function Thing(one, two) {
 ///     if (!new.target) {
@@ -1563,10 +2551,128 @@ where
 }
 
 impl<T> Node for MetaProp<T> {
+    fn loc(&self) -> SourceLocation {
+        SourceLocation::between(&self.meta, &self.property)
+    }
+}
+
+impl<T> MetaProp<T>
+where
+    T: From<&'static str>,
+{
+    /// `new.target` with the `new` keyword starting at `start`
+    /// ```
+    /// use resast::spanned::{expr::MetaProp, tokens::Token, Node, Position, SourceLocation};
+    ///
+    /// let target: MetaProp<&str> = MetaProp::new_target(Position::new(2, 4));
+    /// assert_eq!(target.meta.name(), &"new");
+    /// assert_eq!(target.property.name(), &"target");
+    /// assert_eq!(target.dot.start(), Position::new(2, 7));
+    /// assert_eq!(target.loc(), SourceLocation::new(2, 4, 2, 14));
+    /// ```
+    pub fn new_target(start: Position) -> Self {
+        Self::from_parts("new", "target", start)
+    }
+
+    /// `import.meta` with the `import` keyword starting at `start`
+    /// ```
+    /// use resast::spanned::{expr::MetaProp, Node, Position, SourceLocation};
+    ///
+    /// let meta: MetaProp<&str> = MetaProp::import_meta(Position::new(1, 0));
+    /// assert_eq!(meta.meta.name(), &"import");
+    /// assert_eq!(meta.property.name(), &"meta");
+    /// assert_eq!(meta.loc(), SourceLocation::new(1, 0, 1, 11));
+    /// ```
+    pub fn import_meta(start: Position) -> Self {
+        Self::from_parts("import", "meta", start)
+    }
+
+    fn from_parts(meta: &'static str, property: &'static str, start: Position) -> Self {
+        let Position { line, column } = start;
+        let dot_col = column + meta.len() as u32;
+        let prop_col = dot_col + 1;
+        Self {
+            meta: Slice::new(meta.into(), line, column, line, dot_col).into(),
+            dot: Position::new(line, dot_col).into(),
+            property: Slice::new(
+                property.into(),
+                line,
+                prop_col,
+                line,
+                prop_col + property.len() as u32,
+            )
+            .into(),
+        }
+    }
+}
+
+/// A dynamic import, the optional second argument
+/// is the options object
+/// ```js
+/// import('./data.json', { with: { type: 'json' } });
+/// ```
+/// ```
+/// use resast::prelude::*;
+/// use resast::spanned::expr::{Expr as SExpr, ImportExpr as SImportExpr};
+/// use resast::spanned::{Ident as SIdent, ListEntry};
+///
+/// let ident = |name, column| SExpr::Ident(SIdent::new_from_source(name, 1, column));
+/// let import = |options: Option<SExpr<&'static str>>| {
+///     SExpr::Import(Box::new(SImportExpr {
+///         keyword: Default::default(),
+///         open_paren: Default::default(),
+///         source: ListEntry {
+///             item: ident("src", 7),
+///             comma: options.as_ref().map(|_| Default::default()),
+///         },
+///         options: options.map(ListEntry::no_comma),
+///         close_paren: Default::default(),
+///     }))
+/// };
+/// // import(src, opts)
+/// let expected = Expr::Import(ImportExpr {
+///     source: Box::new(Expr::ident_from("src")),
+///     options: Some(Box::new(Expr::ident_from("opts"))),
+/// });
+/// assert_eq!(Expr::from(import(Some(ident("opts", 12)))), expected);
+/// // import(src)
+/// let expected = Expr::Import(ImportExpr {
+///     source: Box::new(Expr::ident_from("src")),
+///     options: None,
+/// });
+/// assert_eq!(Expr::from(import(None)), expected);
+/// ```
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct ImportExpr<T> {
+    pub keyword: Import,
+    pub open_paren: OpenParen,
+    pub source: ListEntry<Expr<T>>,
+    pub options: Option<ListEntry<Expr<T>>>,
+    pub close_paren: CloseParen,
+}
+
+impl<T> IntoAllocated for ImportExpr<T>
+where
+    T: ToString,
+{
+    type Allocated = ImportExpr<String>;
+    fn into_allocated(self) -> Self::Allocated {
+        ImportExpr {
+            keyword: self.keyword,
+            open_paren: self.open_paren,
+            source: self.source.into_allocated(),
+            options: self.options.into_allocated(),
+            close_paren: self.close_paren,
+        }
+    }
+}
+
+impl<T> Node for ImportExpr<T> {
     fn loc(&self) -> SourceLocation {
         SourceLocation {
-            start: self.meta.loc().start,
-            end: self.property.loc().end,
+            start: self.keyword.start(),
+            end: self.close_paren.end(),
         }
     }
 }
@@ -1589,6 +2695,9 @@ pub enum Lit<T> {
     /// `0o7`
     /// `0b1`
     Number(Slice<T>),
+    /// `1n`
+    /// `0x1fn`
+    BigInt(Slice<T>),
     /// `true`
     /// `false`
     Boolean(Boolean),
@@ -1610,6 +2719,7 @@ where
             Lit::Null(inner) => Lit::Null(inner),
             Lit::String(inner) => Lit::String(inner.into_allocated()),
             Lit::Number(inner) => Lit::Number(inner.into_allocated()),
+            Lit::BigInt(inner) => Lit::BigInt(inner.into_allocated()),
             Lit::Boolean(inner) => Lit::Boolean(inner),
             Lit::RegEx(inner) => Lit::RegEx(inner.into_allocated()),
             Lit::Template(inner) => Lit::Template(inner.into_allocated()),
@@ -1673,6 +2783,7 @@ impl<T> Node for Lit<T> {
             Lit::Null(inner) => inner.loc(),
             Lit::String(inner) => inner.loc(),
             Lit::Number(inner) => inner.loc,
+            Lit::BigInt(inner) => inner.loc,
             Lit::Boolean(inner) => inner.loc(),
             Lit::RegEx(inner) => inner.loc(),
             Lit::Template(inner) => inner.loc(),
@@ -1680,6 +2791,18 @@ impl<T> Node for Lit<T> {
     }
 }
 
+impl<T> Spanned<T> for Lit<T> {
+    /// Numbers and strings are backed by a single slice, keywords,
+    /// regular expressions and templates are not
+    fn source(&self) -> Option<&T> {
+        match self {
+            Lit::String(inner) => inner.source(),
+            Lit::Number(inner) | Lit::BigInt(inner) => Some(&inner.source),
+            Lit::Null(_) | Lit::Boolean(_) | Lit::RegEx(_) | Lit::Template(_) => None,
+        }
+    }
+}
+
 #[derive(PartialEq, Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct StringLit<T> {
@@ -1711,6 +2834,13 @@ impl<T> Node for StringLit<T> {
     }
 }
 
+impl<T> Spanned<T> for StringLit<T> {
+    /// The content between the quotes
+    fn source(&self) -> Option<&T> {
+        Some(&self.content.source)
+    }
+}
+
 /// A regular expression literal
 #[derive(PartialEq, Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
@@ -1736,6 +2866,20 @@ where
     }
 }
 
+impl<T> RegEx<T>
+where
+    T: AsRef<str>,
+{
+    /// The flags of this regular expression, see
+    /// [`RegExFlags::parse`](crate::expr::RegExFlags::parse)
+    pub fn flags_set(&self) -> crate::expr::RegExFlags {
+        self.flags
+            .as_ref()
+            .map(|f| crate::expr::RegExFlags::parse(f.source.as_ref()))
+            .unwrap_or_default()
+    }
+}
+
 impl<T> Node for RegEx<T> {
     fn loc(&self) -> SourceLocation {
         let end = if let Some(flags) = &self.flags {
@@ -1750,6 +2894,22 @@ impl<T> Node for RegEx<T> {
     }
 }
 
+/// An expression wrapped in parentheses
+/// ```
+/// use resast::spanned::{
+///     expr::{Expr, WrappedExpr},
+///     tokens::Token,
+///     Ident, Position, Slice,
+/// };
+///
+/// let wrapped = WrappedExpr {
+///     open_paren: Default::default(),
+///     expr: Expr::Ident(Ident::from(Slice::new("a", 0, 1, 0, 2))),
+///     close_paren: Default::default(),
+/// };
+/// assert_eq!(wrapped.open_paren.start(), Position::new(0, 0));
+/// assert_eq!(wrapped.close_paren.as_str(), ")");
+/// ```
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct WrappedExpr<T> {
@@ -1758,6 +2918,35 @@ pub struct WrappedExpr<T> {
     pub close_paren: CloseParen,
 }
 
+/// Converts into the non-spanned tree like `From<Expr>` but every
+/// `Wrapped` expression becomes an `Expr::Paren` instead of being
+/// dropped. Parentheses inside of functions, classes, objects and
+/// templates are still dropped.
+/// ```
+/// use resast::prelude::*;
+/// use resast::spanned::expr::KeepParens;
+/// use resast::spanned::spanner::Spanner;
+///
+/// // (a + b) * c
+/// let sum = Expr::Binary(BinaryExpr {
+///     operator: BinaryOp::Plus,
+///     left: Box::new(Expr::ident_from("a")),
+///     right: Box::new(Expr::ident_from("b")),
+/// });
+/// let product = |left| Expr::Binary(BinaryExpr {
+///     operator: BinaryOp::Times,
+///     left: Box::new(left),
+///     right: Box::new(Expr::ident_from("c")),
+/// });
+/// let grouped = product(Expr::Paren(Box::new(sum.clone())));
+/// let spanned = Spanner::default().expr(grouped.clone()).unwrap();
+///
+/// assert_eq!(Expr::from(KeepParens(spanned.clone())), grouped);
+/// assert_eq!(Expr::from(spanned), product(sum));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeepParens<T>(pub Expr<T>);
+
 impl<T> IntoAllocated for WrappedExpr<T>
 where
     T: ToString,
@@ -0,0 +1,597 @@
+//! JSX syntax, only available with the `jsx` feature enabled
+use crate::spanned::expr::{Expr, StringLit};
+use crate::spanned::{Ident, Node, Slice, SourceLocation};
+use crate::IntoAllocated;
+
+use super::tokens::{
+    CloseBrace, Colon, Ellipsis, Equal, ForwardSlash, GreaterThan, LessThan, OpenBrace, Period,
+    Token,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A JSX element with its attributes and children
+/// ```jsx
+/// <div a={x}>text</div>
+/// ```
+///
+/// ```
+/// use resast::codegen::WriteJs;
+/// use resast::spanned::jsx::*;
+/// use resast::spanned::expr::Expr;
+/// use resast::spanned::{Ident, Node, Position, Slice, SourceLocation};
+///
+/// let ident = |name, col| Ident::new_from_source(name, 1, col);
+/// let at = |col| Position::new(1, col);
+/// let div = JsxElement {
+///     opening: JsxOpeningElement {
+///         open_angle: at(0).into(),
+///         name: JsxElementName::Ident(ident("div", 1)),
+///         attributes: vec![JsxAttr::Attribute(JsxAttribute {
+///             name: ident("a", 5),
+///             eq: Some(at(6).into()),
+///             value: Some(JsxAttributeValue::Expr(JsxExpressionContainer {
+///                 open_brace: at(7).into(),
+///                 expr: Some(Expr::Ident(ident("x", 8))),
+///                 close_brace: at(9).into(),
+///             })),
+///         })],
+///         slash: None,
+///         close_angle: at(10).into(),
+///     },
+///     children: vec![JsxChild::Text(JsxText {
+///         value: Slice::new("text", 1, 11, 1, 15),
+///     })],
+///     closing: Some(JsxClosingElement {
+///         open_angle: at(15).into(),
+///         slash: at(16).into(),
+///         name: JsxElementName::Ident(ident("div", 17)),
+///         close_angle: at(20).into(),
+///     }),
+/// };
+/// assert_eq!(div.loc(), SourceLocation::new(1, 0, 1, 21));
+///
+/// let expr = resast::expr::Expr::from(Expr::Jsx(Box::new(div)));
+/// assert_eq!(expr.to_js_string(), "<div a={x}>text</div>");
+/// ```
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "serde", serde(rename = "JSXElement"))]
+pub struct JsxElement<T> {
+    pub opening: JsxOpeningElement<T>,
+    pub children: Vec<JsxChild<T>>,
+    /// `None` when the opening element is self closing
+    pub closing: Option<JsxClosingElement<T>>,
+}
+
+impl<T> IntoAllocated for JsxElement<T>
+where
+    T: ToString,
+{
+    type Allocated = JsxElement<String>;
+    fn into_allocated(self) -> JsxElement<String> {
+        JsxElement {
+            opening: self.opening.into_allocated(),
+            children: self
+                .children
+                .into_iter()
+                .map(IntoAllocated::into_allocated)
+                .collect(),
+            closing: self.closing.into_allocated(),
+        }
+    }
+}
+
+impl<T> Node for JsxElement<T> {
+    fn loc(&self) -> SourceLocation {
+        let end = if let Some(closing) = &self.closing {
+            closing.loc().end
+        } else {
+            self.opening.loc().end
+        };
+        SourceLocation {
+            start: self.opening.loc().start,
+            end,
+        }
+    }
+}
+
+impl<T> JsxElement<T> {
+    /// Push every expression in the attributes and children of this
+    /// element, including those of nested elements, onto the stack
+    pub(crate) fn push_exprs<'a>(&'a self, stack: &mut Vec<&'a Expr<T>>) {
+        for attr in &self.opening.attributes {
+            match attr {
+                JsxAttr::Attribute(attr) => match &attr.value {
+                    Some(JsxAttributeValue::Expr(container)) => stack.extend(&container.expr),
+                    Some(JsxAttributeValue::Element(element)) => element.push_exprs(stack),
+                    Some(JsxAttributeValue::Fragment(fragment)) => {
+                        push_child_exprs(&fragment.children, stack)
+                    }
+                    Some(JsxAttributeValue::String(_)) | None => {}
+                },
+                JsxAttr::Spread(spread) => stack.push(&spread.expr),
+            }
+        }
+        push_child_exprs(&self.children, stack);
+    }
+}
+
+pub(crate) fn push_child_exprs<'a, T>(children: &'a [JsxChild<T>], stack: &mut Vec<&'a Expr<T>>) {
+    for child in children {
+        match child {
+            JsxChild::Text(_) => {}
+            JsxChild::Expr(container) => stack.extend(&container.expr),
+            JsxChild::Spread(spread) => stack.push(&spread.expr),
+            JsxChild::Element(element) => element.push_exprs(stack),
+            JsxChild::Fragment(fragment) => push_child_exprs(&fragment.children, stack),
+        }
+    }
+}
+
+/// The opening tag of a JSX element, `slash` is only
+/// present for self closing elements
+/// ```jsx
+/// <input type="text" />
+/// ```
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "serde", serde(rename = "JSXOpeningElement"))]
+pub struct JsxOpeningElement<T> {
+    pub open_angle: LessThan,
+    pub name: JsxElementName<T>,
+    pub attributes: Vec<JsxAttr<T>>,
+    pub slash: Option<ForwardSlash>,
+    pub close_angle: GreaterThan,
+}
+
+impl<T> IntoAllocated for JsxOpeningElement<T>
+where
+    T: ToString,
+{
+    type Allocated = JsxOpeningElement<String>;
+    fn into_allocated(self) -> JsxOpeningElement<String> {
+        JsxOpeningElement {
+            open_angle: self.open_angle,
+            name: self.name.into_allocated(),
+            attributes: self
+                .attributes
+                .into_iter()
+                .map(IntoAllocated::into_allocated)
+                .collect(),
+            slash: self.slash,
+            close_angle: self.close_angle,
+        }
+    }
+}
+
+impl<T> Node for JsxOpeningElement<T> {
+    fn loc(&self) -> SourceLocation {
+        SourceLocation {
+            start: self.open_angle.start(),
+            end: self.close_angle.end(),
+        }
+    }
+}
+
+/// The closing tag of a JSX element
+/// ```jsx
+/// </div>
+/// ```
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "serde", serde(rename = "JSXClosingElement"))]
+pub struct JsxClosingElement<T> {
+    pub open_angle: LessThan,
+    pub slash: ForwardSlash,
+    pub name: JsxElementName<T>,
+    pub close_angle: GreaterThan,
+}
+
+impl<T> IntoAllocated for JsxClosingElement<T>
+where
+    T: ToString,
+{
+    type Allocated = JsxClosingElement<String>;
+    fn into_allocated(self) -> JsxClosingElement<String> {
+        JsxClosingElement {
+            open_angle: self.open_angle,
+            slash: self.slash,
+            name: self.name.into_allocated(),
+            close_angle: self.close_angle,
+        }
+    }
+}
+
+impl<T> Node for JsxClosingElement<T> {
+    fn loc(&self) -> SourceLocation {
+        SourceLocation {
+            start: self.open_angle.start(),
+            end: self.close_angle.end(),
+        }
+    }
+}
+
+/// A JSX fragment, an element without a name
+/// ```jsx
+/// <>frag</>
+/// ```
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "serde", serde(rename = "JSXFragment"))]
+pub struct JsxFragment<T> {
+    pub opening: JsxOpeningFragment,
+    pub children: Vec<JsxChild<T>>,
+    pub closing: JsxClosingFragment,
+}
+
+impl<T> IntoAllocated for JsxFragment<T>
+where
+    T: ToString,
+{
+    type Allocated = JsxFragment<String>;
+    fn into_allocated(self) -> JsxFragment<String> {
+        JsxFragment {
+            opening: self.opening,
+            children: self
+                .children
+                .into_iter()
+                .map(IntoAllocated::into_allocated)
+                .collect(),
+            closing: self.closing,
+        }
+    }
+}
+
+impl<T> Node for JsxFragment<T> {
+    fn loc(&self) -> SourceLocation {
+        SourceLocation {
+            start: self.opening.loc().start,
+            end: self.closing.loc().end,
+        }
+    }
+}
+
+/// `<>`
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "serde", serde(rename = "JSXOpeningFragment"))]
+pub struct JsxOpeningFragment {
+    pub open_angle: LessThan,
+    pub close_angle: GreaterThan,
+}
+
+impl Node for JsxOpeningFragment {
+    fn loc(&self) -> SourceLocation {
+        SourceLocation {
+            start: self.open_angle.start(),
+            end: self.close_angle.end(),
+        }
+    }
+}
+
+/// `</>`
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "serde", serde(rename = "JSXClosingFragment"))]
+pub struct JsxClosingFragment {
+    pub open_angle: LessThan,
+    pub slash: ForwardSlash,
+    pub close_angle: GreaterThan,
+}
+
+impl Node for JsxClosingFragment {
+    fn loc(&self) -> SourceLocation {
+        SourceLocation {
+            start: self.open_angle.start(),
+            end: self.close_angle.end(),
+        }
+    }
+}
+
+/// The name of a JSX element
+/// ```jsx
+/// <div />;
+/// <Foo.Bar />;
+/// <svg:rect />;
+/// ```
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum JsxElementName<T> {
+    #[cfg_attr(feature = "serde", serde(rename = "JSXIdentifier"))]
+    Ident(Ident<T>),
+    #[cfg_attr(feature = "serde", serde(rename = "JSXMemberExpression"))]
+    Member(Box<JsxElementName<T>>, Period, Ident<T>),
+    #[cfg_attr(feature = "serde", serde(rename = "JSXNamespacedName"))]
+    Namespaced(Ident<T>, Colon, Ident<T>),
+}
+
+impl<T> IntoAllocated for JsxElementName<T>
+where
+    T: ToString,
+{
+    type Allocated = JsxElementName<String>;
+    fn into_allocated(self) -> JsxElementName<String> {
+        match self {
+            JsxElementName::Ident(inner) => JsxElementName::Ident(inner.into_allocated()),
+            JsxElementName::Member(object, period, property) => {
+                JsxElementName::Member(object.into_allocated(), period, property.into_allocated())
+            }
+            JsxElementName::Namespaced(namespace, colon, name) => {
+                JsxElementName::Namespaced(namespace.into_allocated(), colon, name.into_allocated())
+            }
+        }
+    }
+}
+
+impl<T> Node for JsxElementName<T> {
+    fn loc(&self) -> SourceLocation {
+        match self {
+            JsxElementName::Ident(inner) => inner.loc(),
+            JsxElementName::Member(object, _, property) => SourceLocation {
+                start: object.loc().start,
+                end: property.loc().end,
+            },
+            JsxElementName::Namespaced(namespace, _, name) => SourceLocation {
+                start: namespace.loc().start,
+                end: name.loc().end,
+            },
+        }
+    }
+}
+
+/// A single entry in a JSX opening element's attribute list
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum JsxAttr<T> {
+    /// `<div a="b" />`
+    #[cfg_attr(feature = "serde", serde(rename = "JSXAttribute"))]
+    Attribute(JsxAttribute<T>),
+    /// `<div {...props} />`
+    #[cfg_attr(feature = "serde", serde(rename = "JSXSpreadAttribute"))]
+    Spread(JsxSpread<T>),
+}
+
+impl<T> IntoAllocated for JsxAttr<T>
+where
+    T: ToString,
+{
+    type Allocated = JsxAttr<String>;
+    fn into_allocated(self) -> JsxAttr<String> {
+        match self {
+            JsxAttr::Attribute(inner) => JsxAttr::Attribute(inner.into_allocated()),
+            JsxAttr::Spread(inner) => JsxAttr::Spread(inner.into_allocated()),
+        }
+    }
+}
+
+impl<T> Node for JsxAttr<T> {
+    fn loc(&self) -> SourceLocation {
+        match self {
+            JsxAttr::Attribute(inner) => inner.loc(),
+            JsxAttr::Spread(inner) => inner.loc(),
+        }
+    }
+}
+
+/// A named JSX attribute, `eq` and `value` are `None`
+/// for boolean attributes like `<input disabled />`
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "serde", serde(rename = "JSXAttribute"))]
+pub struct JsxAttribute<T> {
+    pub name: Ident<T>,
+    pub eq: Option<Equal>,
+    pub value: Option<JsxAttributeValue<T>>,
+}
+
+impl<T> IntoAllocated for JsxAttribute<T>
+where
+    T: ToString,
+{
+    type Allocated = JsxAttribute<String>;
+    fn into_allocated(self) -> JsxAttribute<String> {
+        JsxAttribute {
+            name: self.name.into_allocated(),
+            eq: self.eq,
+            value: self.value.into_allocated(),
+        }
+    }
+}
+
+impl<T> Node for JsxAttribute<T> {
+    fn loc(&self) -> SourceLocation {
+        let end = if let Some(value) = &self.value {
+            value.loc().end
+        } else {
+            self.name.loc().end
+        };
+        SourceLocation {
+            start: self.name.loc().start,
+            end,
+        }
+    }
+}
+
+/// The right hand side of a JSX attribute
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum JsxAttributeValue<T> {
+    /// `<div a="b" />`
+    String(StringLit<T>),
+    /// `<div a={b} />`
+    Expr(JsxExpressionContainer<T>),
+    /// `<div a=<b /> />`
+    Element(JsxElement<T>),
+    /// `<div a=<>b</> />`
+    Fragment(JsxFragment<T>),
+}
+
+impl<T> IntoAllocated for JsxAttributeValue<T>
+where
+    T: ToString,
+{
+    type Allocated = JsxAttributeValue<String>;
+    fn into_allocated(self) -> JsxAttributeValue<String> {
+        match self {
+            JsxAttributeValue::String(inner) => JsxAttributeValue::String(inner.into_allocated()),
+            JsxAttributeValue::Expr(inner) => JsxAttributeValue::Expr(inner.into_allocated()),
+            JsxAttributeValue::Element(inner) => JsxAttributeValue::Element(inner.into_allocated()),
+            JsxAttributeValue::Fragment(inner) => {
+                JsxAttributeValue::Fragment(inner.into_allocated())
+            }
+        }
+    }
+}
+
+impl<T> Node for JsxAttributeValue<T> {
+    fn loc(&self) -> SourceLocation {
+        match self {
+            JsxAttributeValue::String(inner) => inner.loc(),
+            JsxAttributeValue::Expr(inner) => inner.loc(),
+            JsxAttributeValue::Element(inner) => inner.loc(),
+            JsxAttributeValue::Fragment(inner) => inner.loc(),
+        }
+    }
+}
+
+/// An expression wrapped in curly braces, the expression
+/// is `None` for an empty container like `<div>{}</div>`
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "serde", serde(rename = "JSXExpressionContainer"))]
+pub struct JsxExpressionContainer<T> {
+    pub open_brace: OpenBrace,
+    pub expr: Option<Expr<T>>,
+    pub close_brace: CloseBrace,
+}
+
+impl<T> IntoAllocated for JsxExpressionContainer<T>
+where
+    T: ToString,
+{
+    type Allocated = JsxExpressionContainer<String>;
+    fn into_allocated(self) -> JsxExpressionContainer<String> {
+        JsxExpressionContainer {
+            open_brace: self.open_brace,
+            expr: self.expr.into_allocated(),
+            close_brace: self.close_brace,
+        }
+    }
+}
+
+impl<T> Node for JsxExpressionContainer<T> {
+    fn loc(&self) -> SourceLocation {
+        SourceLocation {
+            start: self.open_brace.start(),
+            end: self.close_brace.end(),
+        }
+    }
+}
+
+/// A spread attribute or child
+/// ```jsx
+/// <div {...props}>{...children}</div>
+/// ```
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct JsxSpread<T> {
+    pub open_brace: OpenBrace,
+    pub dots: Ellipsis,
+    pub expr: Expr<T>,
+    pub close_brace: CloseBrace,
+}
+
+impl<T> IntoAllocated for JsxSpread<T>
+where
+    T: ToString,
+{
+    type Allocated = JsxSpread<String>;
+    fn into_allocated(self) -> JsxSpread<String> {
+        JsxSpread {
+            open_brace: self.open_brace,
+            dots: self.dots,
+            expr: self.expr.into_allocated(),
+            close_brace: self.close_brace,
+        }
+    }
+}
+
+impl<T> Node for JsxSpread<T> {
+    fn loc(&self) -> SourceLocation {
+        SourceLocation {
+            start: self.open_brace.start(),
+            end: self.close_brace.end(),
+        }
+    }
+}
+
+/// Raw text between JSX tags
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "serde", serde(rename = "JSXText"))]
+pub struct JsxText<T> {
+    pub value: Slice<T>,
+}
+
+impl<T> IntoAllocated for JsxText<T>
+where
+    T: ToString,
+{
+    type Allocated = JsxText<String>;
+    fn into_allocated(self) -> JsxText<String> {
+        JsxText {
+            value: self.value.into_allocated(),
+        }
+    }
+}
+
+impl<T> Node for JsxText<T> {
+    fn loc(&self) -> SourceLocation {
+        self.value.loc
+    }
+}
+
+/// A single child of a JSX element or fragment
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum JsxChild<T> {
+    #[cfg_attr(feature = "serde", serde(rename = "JSXText"))]
+    Text(JsxText<T>),
+    #[cfg_attr(feature = "serde", serde(rename = "JSXExpressionContainer"))]
+    Expr(JsxExpressionContainer<T>),
+    #[cfg_attr(feature = "serde", serde(rename = "JSXSpreadChild"))]
+    Spread(JsxSpread<T>),
+    #[cfg_attr(feature = "serde", serde(rename = "JSXElement"))]
+    Element(JsxElement<T>),
+    #[cfg_attr(feature = "serde", serde(rename = "JSXFragment"))]
+    Fragment(JsxFragment<T>),
+}
+
+impl<T> IntoAllocated for JsxChild<T>
+where
+    T: ToString,
+{
+    type Allocated = JsxChild<String>;
+    fn into_allocated(self) -> JsxChild<String> {
+        match self {
+            JsxChild::Text(inner) => JsxChild::Text(inner.into_allocated()),
+            JsxChild::Expr(inner) => JsxChild::Expr(inner.into_allocated()),
+            JsxChild::Spread(inner) => JsxChild::Spread(inner.into_allocated()),
+            JsxChild::Element(inner) => JsxChild::Element(inner.into_allocated()),
+            JsxChild::Fragment(inner) => JsxChild::Fragment(inner.into_allocated()),
+        }
+    }
+}
+
+impl<T> Node for JsxChild<T> {
+    fn loc(&self) -> SourceLocation {
+        match self {
+            JsxChild::Text(inner) => inner.loc(),
+            JsxChild::Expr(inner) => inner.loc(),
+            JsxChild::Spread(inner) => inner.loc(),
+            JsxChild::Element(inner) => inner.loc(),
+            JsxChild::Fragment(inner) => inner.loc(),
+        }
+    }
+}
@@ -1,16 +1,21 @@
+pub mod comment;
 mod convert;
 pub mod decl;
 pub mod expr;
+#[cfg(feature = "jsx")]
+pub mod jsx;
 pub mod pat;
+pub mod segments;
+pub mod spanner;
 pub mod stmt;
 pub mod tokens;
 
 use decl::Decl;
-use expr::{Expr, Lit, Prop};
+use expr::{Expr, Lit, Prop, PropCtor, PropField, PropGet, PropMethod, PropSet};
 use pat::Pat;
 use stmt::Stmt;
 
-use crate::IntoAllocated;
+use crate::{IntoAllocated, SourceType};
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -18,8 +23,8 @@ use serde::{Deserialize, Serialize};
 use self::{
     pat::RestPat,
     tokens::{
-        AssignOp, Asterisk, Async, CloseBrace, CloseParen, Comma, Const, Extends, Function, Let,
-        OpenBrace, OpenParen, Semicolon, Token, Var,
+        AssignOp, Asterisk, Async, At, Await, CloseBrace, CloseParen, Comma, Const, Extends,
+        Function, Hash, HashBang, Let, OpenBrace, OpenParen, Semicolon, Token, Using, Var,
     },
 };
 
@@ -27,6 +32,112 @@ pub trait Node {
     fn loc(&self) -> SourceLocation;
 }
 
+/// A borrowed declaration, statement, expression, pattern or
+/// identifier, see [`Program::node_at`]
+#[derive(Debug, PartialEq)]
+pub enum NodeRef<'a, T> {
+    Decl(&'a Decl<T>),
+    Stmt(&'a Stmt<T>),
+    Expr(&'a Expr<T>),
+    Pat(&'a Pat<T>),
+    Ident(&'a Ident<T>),
+}
+
+impl<'a, T> Clone for NodeRef<'a, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, T> Copy for NodeRef<'a, T> {}
+
+impl<'a, T> Node for NodeRef<'a, T> {
+    fn loc(&self) -> SourceLocation {
+        match self {
+            NodeRef::Decl(inner) => inner.loc(),
+            NodeRef::Stmt(inner) => inner.loc(),
+            NodeRef::Expr(inner) => inner.loc(),
+            NodeRef::Pat(inner) => inner.loc(),
+            NodeRef::Ident(inner) => inner.loc(),
+        }
+    }
+}
+
+/// A [`Node`] that may also be backed by a single piece of source text
+/// ```
+/// use resast::spanned::{expr::StringLit, tokens::Comma, Ident, Position, Slice, Spanned};
+///
+/// let ident = Ident::from(Slice::new("thing", 1, 4, 1, 9));
+/// assert_eq!(ident.source(), Some(&"thing"));
+/// assert_eq!(ident.span().start, Position::new(1, 4));
+///
+/// let string = StringLit {
+///     open_quote: resast::spanned::tokens::Quote::Single(Position::new(1, 0).into()),
+///     content: Slice::new("text", 1, 1, 1, 5),
+///     close_quote: resast::spanned::tokens::Quote::Single(Position::new(1, 5).into()),
+/// };
+/// assert_eq!(string.source(), Some(&"text"));
+///
+/// // tokens have no backing text of type `T`, see `Token::as_str`
+/// let comma: Comma = Position::new(1, 0).into();
+/// assert_eq!(Spanned::<&str>::source(&comma), None);
+/// ```
+pub trait Spanned<T>: Node {
+    /// The same as [`Node::loc`]
+    fn span(&self) -> SourceLocation {
+        self.loc()
+    }
+    /// The source text of this node, when it is made of a single slice
+    fn source(&self) -> Option<&T>;
+}
+
+impl<T> Spanned<T> for Ident<T> {
+    fn source(&self) -> Option<&T> {
+        Some(&self.slice.source)
+    }
+}
+
+/// A missing node has a zeroed location
+/// ```
+/// use resast::spanned::{tokens::Semicolon, Node, Position, SourceLocation};
+///
+/// let semi: Option<Semicolon> = Some(Position::new(1, 4).into());
+/// assert_eq!(semi.loc(), SourceLocation::new(1, 4, 1, 5));
+/// assert_eq!(None::<Semicolon>.loc(), SourceLocation::new(0, 0, 0, 0));
+/// ```
+impl<N> Node for Option<N>
+where
+    N: Node,
+{
+    fn loc(&self) -> SourceLocation {
+        match self {
+            Some(node) => node.loc(),
+            None => SourceLocation::zero(),
+        }
+    }
+}
+
+/// A list of nodes spans from the start of the first to the end of
+/// the last, an empty list has a zeroed location
+/// ```
+/// use resast::spanned::{tokens::Comma, Node, Position, SourceLocation};
+///
+/// let commas: Vec<Comma> = vec![Position::new(1, 2).into(), Position::new(3, 0).into()];
+/// assert_eq!(commas[..].loc(), SourceLocation::new(1, 2, 3, 1));
+/// assert_eq!(commas[..0].loc(), SourceLocation::new(0, 0, 0, 0));
+/// ```
+impl<N> Node for [N]
+where
+    N: Node,
+{
+    fn loc(&self) -> SourceLocation {
+        match (self.first(), self.last()) {
+            (Some(first), Some(last)) => SourceLocation::between(first, last),
+            _ => SourceLocation::zero(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct Ident<T> {
@@ -73,6 +184,76 @@ impl<T> Ident<T> {
     }
 }
 
+/// A private class member name, `#x`
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct PrivateIdent<T> {
+    pub hash: Hash,
+    pub ident: Ident<T>,
+}
+
+impl<T> IntoAllocated for PrivateIdent<T>
+where
+    T: ToString,
+{
+    type Allocated = PrivateIdent<String>;
+    fn into_allocated(self) -> PrivateIdent<String> {
+        PrivateIdent {
+            hash: self.hash,
+            ident: self.ident.into_allocated(),
+        }
+    }
+}
+
+impl<T> Node for PrivateIdent<T> {
+    fn loc(&self) -> SourceLocation {
+        SourceLocation {
+            start: self.hash.start(),
+            end: self.ident.loc().end,
+        }
+    }
+}
+
+impl<T> PrivateIdent<T> {
+    pub fn name(&self) -> &T {
+        self.ident.name()
+    }
+}
+
+/// A `#!` comment on the very first line of a program
+/// ```js
+/// #!/usr/bin/env node
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct HashbangComment<T> {
+    pub hash_bang: HashBang,
+    /// Everything after the `#!` up to the end of the line
+    pub content: Slice<T>,
+}
+
+impl<T> IntoAllocated for HashbangComment<T>
+where
+    T: ToString,
+{
+    type Allocated = HashbangComment<String>;
+    fn into_allocated(self) -> HashbangComment<String> {
+        HashbangComment {
+            hash_bang: self.hash_bang,
+            content: self.content.into_allocated(),
+        }
+    }
+}
+
+impl<T> Node for HashbangComment<T> {
+    fn loc(&self) -> SourceLocation {
+        SourceLocation {
+            start: self.hash_bang.start(),
+            end: self.content.loc.end,
+        }
+    }
+}
+
 /// A fully parsed javascript program.
 ///
 /// It is essentially a collection of `ProgramPart`s
@@ -82,9 +263,15 @@ impl<T> Ident<T> {
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub enum Program<T> {
     /// An ES6 Mod
-    Mod(Vec<ProgramPart<T>>),
+    Mod {
+        hashbang: Option<HashbangComment<T>>,
+        parts: Vec<ProgramPart<T>>,
+    },
     /// Not an ES6 Mod
-    Script(Vec<ProgramPart<T>>),
+    Script {
+        hashbang: Option<HashbangComment<T>>,
+        parts: Vec<ProgramPart<T>>,
+    },
 }
 
 impl<T> IntoAllocated for Program<T>
@@ -94,51 +281,179 @@ where
     type Allocated = Program<String>;
     fn into_allocated(self) -> Program<String> {
         match self {
-            Program::Mod(parts) => Program::Mod(
-                parts
+            Program::Mod { hashbang, parts } => Program::Mod {
+                hashbang: hashbang.into_allocated(),
+                parts: parts
                     .into_iter()
                     .map(IntoAllocated::into_allocated)
                     .collect(),
-            ),
-            Program::Script(parts) => Program::Script(
-                parts
+            },
+            Program::Script { hashbang, parts } => Program::Script {
+                hashbang: hashbang.into_allocated(),
+                parts: parts
                     .into_iter()
                     .map(IntoAllocated::into_allocated)
                     .collect(),
-            ),
+            },
         }
     }
 }
 
 impl<T> Node for Program<T> {
     fn loc(&self) -> SourceLocation {
-        match self {
-            Self::Mod(inner) => inner.loc(),
-            Self::Script(inner) => inner.loc(),
+        let (hashbang, parts) = match self {
+            Self::Mod { hashbang, parts } => (hashbang, parts),
+            Self::Script { hashbang, parts } => (hashbang, parts),
+        };
+        let hashbang = match hashbang {
+            Some(hashbang) => hashbang.loc(),
+            None => return parts.loc(),
+        };
+        SourceLocation {
+            start: hashbang.start,
+            end: parts.last().map(|p| p.loc().end).unwrap_or(hashbang.end),
         }
     }
 }
 
 impl<T> Program<T> {
+    pub fn new(source_type: SourceType, parts: Vec<ProgramPart<T>>) -> Self {
+        match source_type {
+            SourceType::Module => Program::Mod {
+                hashbang: None,
+                parts,
+            },
+            SourceType::Script => Program::Script {
+                hashbang: None,
+                parts,
+            },
+        }
+    }
     pub fn module(parts: Vec<ProgramPart<T>>) -> Self {
-        Program::Mod(parts)
+        Self::new(SourceType::Module, parts)
     }
     pub fn script(parts: Vec<ProgramPart<T>>) -> Self {
-        Program::Script(parts)
+        Self::new(SourceType::Script, parts)
+    }
+    pub fn source_type(&self) -> SourceType {
+        match self {
+            Program::Mod { .. } => SourceType::Module,
+            Program::Script { .. } => SourceType::Script,
+        }
+    }
+    /// Drop every span and token, producing the non-spanned tree
+    /// ```
+    /// use resast::spanned::{
+    ///     expr::Expr, stmt::Stmt, Ident, Program, ProgramPart, Slice,
+    /// };
+    ///
+    /// let program = Program::script(vec![ProgramPart::Stmt(Stmt::Expr {
+    ///     expr: Expr::Ident(Ident::from(Slice::new("a", 1, 0, 1, 1))),
+    ///     semi_colon: None,
+    /// })]);
+    /// let concrete = program.clone().into_concrete();
+    /// assert_eq!(concrete, resast::Program::from(program));
+    /// assert_eq!(
+    ///     concrete,
+    ///     resast::Program::script(vec![resast::ProgramPart::Stmt(resast::stmt::Stmt::Expr(
+    ///         resast::expr::Expr::ident_from("a")
+    ///     ))])
+    /// );
+    /// ```
+    pub fn into_concrete(self) -> crate::Program<T> {
+        self.into()
+    }
+    /// ```
+    /// use resast::spanned::{HashbangComment, Node, Position, Program, Slice};
+    ///
+    /// let hashbang = HashbangComment {
+    ///     hash_bang: Position::new(1, 0).into(),
+    ///     content: Slice::new("/usr/bin/env node", 1, 2, 1, 19),
+    /// };
+    /// let without = Program::<&str>::script(vec![]);
+    /// assert_eq!(without.hashbang(), None);
+    /// let with = without.with_hashbang(hashbang);
+    /// assert_eq!(with.loc().start, Position::new(1, 0));
+    /// assert_eq!(with.loc().end, Position::new(1, 19));
+    /// ```
+    pub fn with_hashbang(self, hashbang: HashbangComment<T>) -> Self {
+        match self {
+            Program::Mod { parts, .. } => Program::Mod {
+                hashbang: Some(hashbang),
+                parts,
+            },
+            Program::Script { parts, .. } => Program::Script {
+                hashbang: Some(hashbang),
+                parts,
+            },
+        }
+    }
+    pub fn hashbang(&self) -> Option<&HashbangComment<T>> {
+        match self {
+            Program::Mod { hashbang, .. } | Program::Script { hashbang, .. } => hashbang.as_ref(),
+        }
+    }
+    pub fn parts(&self) -> &[ProgramPart<T>] {
+        match self {
+            Program::Mod { parts, .. } | Program::Script { parts, .. } => parts,
+        }
+    }
+    pub fn parts_mut(&mut self) -> &mut Vec<ProgramPart<T>> {
+        match self {
+            Program::Mod { parts, .. } | Program::Script { parts, .. } => parts,
+        }
+    }
+    pub fn into_parts(self) -> Vec<ProgramPart<T>> {
+        match self {
+            Program::Mod { parts, .. } | Program::Script { parts, .. } => parts,
+        }
+    }
+    pub fn iter(&self) -> std::slice::Iter<'_, ProgramPart<T>> {
+        self.parts().iter()
+    }
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, ProgramPart<T>> {
+        self.parts_mut().iter_mut()
+    }
+    /// The directives at the start of this program, see
+    /// [`FuncBody::directive_prologue`]
+    pub fn directive_prologue(&self) -> &[ProgramPart<T>] {
+        directive_prologue(self.parts())
+    }
+}
+impl<T> Program<T>
+where
+    T: PartialEq,
+{
+    /// Compare two programs by their structure alone, see
+    /// [`Expr::eq_ignore_span`](crate::spanned::expr::Expr::eq_ignore_span)
+    /// for the details of what is ignored
+    pub fn eq_ignore_span(&self, other: &Self) -> bool {
+        crate::Program::from(self) == crate::Program::from(other)
+    }
+}
+
+impl<T> Program<T>
+where
+    T: std::hash::Hash,
+{
+    /// Hash this program by its structure alone, see
+    /// [`Expr::hash_ignore_span`](crate::spanned::expr::Expr::hash_ignore_span)
+    pub fn hash_ignore_span<H: std::hash::Hasher>(&self, state: &mut H) {
+        std::hash::Hash::hash(&crate::Program::from(self), state)
+    }
+}
+
+impl<T> Program<T> {
+    /// If this is a module with an `await` outside of any function, see
+    /// [`Program::has_top_level_await`](crate::Program::has_top_level_await)
+    pub fn has_top_level_await(&self) -> bool {
+        crate::Program::from(self).has_top_level_await()
     }
 }
 
 impl<T> Node for Vec<ProgramPart<T>> {
     fn loc(&self) -> SourceLocation {
-        let start = self
-            .first()
-            .map(|p| p.loc())
-            .unwrap_or_else(SourceLocation::zero);
-        let end = self.last().map(|p| p.loc()).unwrap_or(start);
-        SourceLocation {
-            start: start.start,
-            end: end.end,
-        }
+        self[..].loc()
     }
 }
 
@@ -186,6 +501,51 @@ impl<T> ProgramPart<T> {
     pub fn stmt(inner: Stmt<T>) -> Self {
         ProgramPart::Stmt(inner)
     }
+    pub fn is_dir(&self) -> bool {
+        matches!(self, ProgramPart::Dir(_))
+    }
+    pub fn is_decl(&self) -> bool {
+        matches!(self, ProgramPart::Decl(_))
+    }
+    pub fn is_stmt(&self) -> bool {
+        matches!(self, ProgramPart::Stmt(_))
+    }
+    pub fn as_dir(&self) -> Option<&Dir<T>> {
+        match self {
+            ProgramPart::Dir(inner) => Some(inner),
+            _ => None,
+        }
+    }
+    pub fn as_decl(&self) -> Option<&Decl<T>> {
+        match self {
+            ProgramPart::Decl(inner) => Some(inner),
+            _ => None,
+        }
+    }
+    pub fn as_stmt(&self) -> Option<&Stmt<T>> {
+        match self {
+            ProgramPart::Stmt(inner) => Some(inner),
+            _ => None,
+        }
+    }
+    pub fn into_dir(self) -> Option<Dir<T>> {
+        match self {
+            ProgramPart::Dir(inner) => Some(inner),
+            _ => None,
+        }
+    }
+    pub fn into_decl(self) -> Option<Decl<T>> {
+        match self {
+            ProgramPart::Decl(inner) => Some(inner),
+            _ => None,
+        }
+    }
+    pub fn into_stmt(self) -> Option<Stmt<T>> {
+        match self {
+            ProgramPart::Stmt(inner) => Some(inner),
+            _ => None,
+        }
+    }
 }
 
 /// pretty much always `'use strict'`, this can appear at the
@@ -389,6 +749,76 @@ impl<T> Node for FuncBody<T> {
     }
 }
 
+/// The leading run of directives in `parts`
+fn directive_prologue<T>(parts: &[ProgramPart<T>]) -> &[ProgramPart<T>] {
+    let len = parts
+        .iter()
+        .take_while(|part| matches!(part, ProgramPart::Dir(_)))
+        .count();
+    &parts[..len]
+}
+
+impl<T> FuncBody<T> {
+    /// The directives at the start of this body, everything up to
+    /// the first statement or declaration
+    /// ```
+    /// use resast::spanned::expr::{Lit, StringLit};
+    /// use resast::spanned::stmt::Stmt;
+    /// use resast::spanned::tokens::{Quote, SingleQuote};
+    /// use resast::spanned::{Dir, FuncBody, Position, ProgramPart, Slice};
+    ///
+    /// let dir = |text: &'static str, line| {
+    ///     ProgramPart::Dir(Dir {
+    ///         expr: Lit::String(StringLit {
+    ///             open_quote: Quote::Single(SingleQuote::default()),
+    ///             content: Slice::new(text, line, 1, line, 1 + text.len() as u32),
+    ///             close_quote: Quote::Single(SingleQuote::default()),
+    ///         }),
+    ///         dir: text,
+    ///         semi_colon: Some(Default::default()),
+    ///     })
+    /// };
+    /// // {
+    /// //     'use strict';
+    /// //     'use asm';
+    /// //     ;
+    /// // }
+    /// let body = FuncBody::from_parts(vec![
+    ///     dir("use strict", 2),
+    ///     dir("use asm", 3),
+    ///     ProgramPart::Stmt(Stmt::Empty(Position::new(4, 4).into())),
+    /// ]);
+    /// let directives: Vec<_> = body
+    ///     .directive_prologue()
+    ///     .iter()
+    ///     .filter_map(ProgramPart::as_dir)
+    ///     .map(|dir| dir.dir)
+    ///     .collect();
+    /// assert_eq!(directives, ["use strict", "use asm"]);
+    /// ```
+    pub fn directive_prologue(&self) -> &[ProgramPart<T>] {
+        directive_prologue(&self.stmts)
+    }
+    /// A body around `parts` with both braces at line 0, column 0
+    /// ```
+    /// use resast::spanned::stmt::Stmt;
+    /// use resast::spanned::{FuncBody, Position, ProgramPart};
+    /// use resast::spanned::tokens::Token;
+    ///
+    /// let parts = (0..2).map(|col| ProgramPart::<&str>::Stmt(Stmt::Empty(Position::new(1, col).into())));
+    /// let body = FuncBody::from_parts(parts);
+    /// assert_eq!(body.stmts.len(), 2);
+    /// assert_eq!(body.open_brace.start(), Position::new(0, 0));
+    /// ```
+    pub fn from_parts(parts: impl IntoIterator<Item = ProgramPart<T>>) -> Self {
+        Self {
+            open_brace: Default::default(),
+            stmts: parts.into_iter().collect(),
+            close_brace: Default::default(),
+        }
+    }
+}
+
 /// A way to declare object templates
 /// ```js
 /// class Thing {
@@ -418,6 +848,7 @@ impl<T> Node for FuncBody<T> {
 #[derive(PartialEq, Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct Class<T> {
+    pub decorators: Vec<Decorator<T>>,
     pub keyword: tokens::Class,
     pub id: Option<Ident<T>>,
     pub super_class: Option<SuperClass<T>>,
@@ -431,6 +862,11 @@ where
     type Allocated = Class<String>;
     fn into_allocated(self) -> Class<String> {
         Class {
+            decorators: self
+                .decorators
+                .into_iter()
+                .map(IntoAllocated::into_allocated)
+                .collect(),
             keyword: self.keyword,
             id: self.id.map(|i| i.into_allocated()),
             super_class: self.super_class.map(|s| s.into_allocated()),
@@ -441,13 +877,173 @@ where
 
 impl<T> Node for Class<T> {
     fn loc(&self) -> SourceLocation {
+        let start = match self.decorators.first() {
+            Some(decorator) => decorator.at.start(),
+            None => self.keyword.start(),
+        };
         SourceLocation {
-            start: self.keyword.start(),
+            start,
             end: self.body.close_brace.end(),
         }
     }
 }
 
+impl<T> Class<T> {
+    /// The methods of this class, static or not, in source order
+    /// ```
+    /// use resast::spanned::expr::{
+    ///     Expr, Prop, PropCtor, PropField, PropGet, PropInitKey, PropKey, PropMethod,
+    /// };
+    /// use resast::spanned::{Class, ClassBody, FuncBody, Ident};
+    ///
+    /// fn key(name: &str) -> PropInitKey<&str> {
+    ///     PropInitKey {
+    ///         value: PropKey::Expr(Expr::Ident(Ident::new_from_source(name, 1, 0))),
+    ///         brackets: None,
+    ///     }
+    /// }
+    /// fn body<'a>() -> FuncBody<&'a str> {
+    ///     FuncBody {
+    ///         open_brace: Default::default(),
+    ///         stmts: vec![],
+    ///         close_brace: Default::default(),
+    ///     }
+    /// }
+    /// // class Thing {
+    /// //     constructor() {}
+    /// //     static make() {}
+    /// //     get size() {}
+    /// //     count = 0;
+    /// // }
+    /// let class = Class {
+    ///     decorators: vec![],
+    ///     keyword: Default::default(),
+    ///     id: Some(Ident::new_from_source("Thing", 1, 6)),
+    ///     super_class: None,
+    ///     body: ClassBody {
+    ///         open_brace: Default::default(),
+    ///         props: vec![
+    ///             Prop::Ctor(PropCtor {
+    ///                 decorators: vec![],
+    ///                 keyword: key("constructor"),
+    ///                 open_paren: Default::default(),
+    ///                 params: vec![],
+    ///                 close_paren: Default::default(),
+    ///                 body: body(),
+    ///             }),
+    ///             Prop::Method(PropMethod {
+    ///                 decorators: vec![],
+    ///                 keyword_static: Some(Default::default()),
+    ///                 keyword_async: None,
+    ///                 id: key("make"),
+    ///                 star: None,
+    ///                 open_paren: Default::default(),
+    ///                 params: vec![],
+    ///                 close_paren: Default::default(),
+    ///                 body: body(),
+    ///             }),
+    ///             Prop::Get(PropGet {
+    ///                 decorators: vec![],
+    ///                 keyword_static: None,
+    ///                 keyword_get: Default::default(),
+    ///                 id: key("size"),
+    ///                 open_paren: Default::default(),
+    ///                 close_paren: Default::default(),
+    ///                 body: body(),
+    ///             }),
+    ///             Prop::Field(PropField {
+    ///                 decorators: vec![],
+    ///                 keyword_static: None,
+    ///                 key: key("count"),
+    ///                 eq: None,
+    ///                 value: None,
+    ///                 semi_colon: Some(Default::default()),
+    ///             }),
+    ///         ],
+    ///         close_brace: Default::default(),
+    ///     },
+    /// };
+    /// assert!(class.constructor().is_some());
+    /// let methods: Vec<_> = class.methods().collect();
+    /// assert_eq!(methods.len(), 1);
+    /// assert!(methods[0].keyword_static.is_some());
+    /// assert_eq!(class.getters().map(|get| &get.id).collect::<Vec<_>>(), [&key("size")]);
+    /// assert_eq!(class.setters().count(), 0);
+    /// assert_eq!(class.fields().map(|field| &field.key).collect::<Vec<_>>(), [&key("count")]);
+    /// ```
+    pub fn methods(&self) -> impl Iterator<Item = &PropMethod<T>> {
+        self.body.props.iter().filter_map(|prop| match prop {
+            Prop::Method(method) => Some(method),
+            _ => None,
+        })
+    }
+    /// The fields of this class, static or not, in source order
+    pub fn fields(&self) -> impl Iterator<Item = &PropField<T>> {
+        self.body.props.iter().filter_map(|prop| match prop {
+            Prop::Field(field) => Some(field),
+            _ => None,
+        })
+    }
+    /// The getters of this class, static or not, in source order
+    pub fn getters(&self) -> impl Iterator<Item = &PropGet<T>> {
+        self.body.props.iter().filter_map(|prop| match prop {
+            Prop::Get(get) => Some(get),
+            _ => None,
+        })
+    }
+    /// The setters of this class, static or not, in source order
+    pub fn setters(&self) -> impl Iterator<Item = &PropSet<T>> {
+        self.body.props.iter().filter_map(|prop| match prop {
+            Prop::Set(set) => Some(set),
+            _ => None,
+        })
+    }
+    /// The `constructor` of this class if it has one
+    pub fn constructor(&self) -> Option<&PropCtor<T>> {
+        self.body.props.iter().find_map(|prop| match prop {
+            Prop::Ctor(ctor) => Some(ctor),
+            _ => None,
+        })
+    }
+}
+
+/// A decorator applied to a class or class member
+/// ```js
+/// @sealed
+/// class Thing {
+///     @logged('verbose')
+///     stuff() {}
+/// }
+/// ```
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct Decorator<T> {
+    pub at: At,
+    pub expr: Expr<T>,
+}
+
+impl<T> IntoAllocated for Decorator<T>
+where
+    T: ToString,
+{
+    type Allocated = Decorator<String>;
+    fn into_allocated(self) -> Decorator<String> {
+        Decorator {
+            at: self.at,
+            expr: self.expr.into_allocated(),
+        }
+    }
+}
+
+impl<T> Node for Decorator<T> {
+    fn loc(&self) -> SourceLocation {
+        SourceLocation {
+            start: self.at.start(),
+            end: self.expr.loc().end,
+        }
+    }
+}
+
 #[derive(PartialEq, Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct SuperClass<T> {
@@ -468,6 +1064,15 @@ where
     }
 }
 
+impl<T> Node for SuperClass<T> {
+    fn loc(&self) -> SourceLocation {
+        SourceLocation {
+            start: self.keyword_extends.start(),
+            end: self.expr.loc().end,
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct ClassBody<T> {
@@ -509,15 +1114,20 @@ pub enum VarKind {
     Var(Option<Var>),
     Let(Let),
     Const(Const),
+    Using(Using),
+    AwaitUsing(Await, Using),
 }
 
 impl Node for VarKind {
     fn loc(&self) -> SourceLocation {
         match self {
-            VarKind::Var(Some(tok)) => tok.loc(),
+            VarKind::Var(tok) => tok.loc(),
             VarKind::Let(tok) => tok.loc(),
             VarKind::Const(tok) => tok.loc(),
-            _ => SourceLocation::zero(),
+            VarKind::Using(tok) => tok.loc(),
+            VarKind::AwaitUsing(keyword_await, keyword_using) => {
+                SourceLocation::between(keyword_await, keyword_using)
+            }
         }
     }
 }
@@ -526,18 +1136,83 @@ impl VarKind {
     pub fn is_var(&self) -> bool {
         matches!(self, VarKind::Var(_))
     }
-    pub const fn len(&self) -> u32 {
+    /// The number of characters from the start of the first keyword
+    /// to the end of the last
+    ///
+    /// When `await` and `using` are on different lines the text
+    /// between them isn't known, it is counted as a single line
+    /// terminator per line break
+    /// ```
+    /// use resast::spanned::{tokens::{Await, Using}, Position, VarKind};
+    ///
+    /// let kind = |line, column| {
+    ///     VarKind::AwaitUsing(
+    ///         Await::from(Position::new(1, 2)),
+    ///         Using::from(Position::new(line, column)),
+    ///     )
+    /// };
+    /// assert_eq!(kind(1, 8).len(), 11);
+    /// // await  using
+    /// assert_eq!(kind(1, 9).len(), 12);
+    /// // await
+    /// //   using
+    /// assert_eq!(kind(2, 2).len(), 13);
+    /// ```
+    pub fn len(&self) -> u32 {
         match self {
             VarKind::Var(Some(_)) => 3,
             VarKind::Var(None) => 0,
             VarKind::Let(_) => 3,
-            VarKind::Const(_) => 4,
+            VarKind::Const(_) => 5,
+            VarKind::Using(_) => 5,
+            VarKind::AwaitUsing(keyword_await, keyword_using) => {
+                let start = keyword_await.start();
+                let end = keyword_using.end();
+                if start.line == end.line {
+                    end.column.saturating_sub(start.column)
+                } else {
+                    let await_len = keyword_await.end().column - start.column;
+                    await_len + end.line.saturating_sub(start.line) + end.column
+                }
+            }
         }
     }
 
     pub const fn is_empty(&self) -> bool {
         matches!(self, VarKind::Var(None))
     }
+
+    /// The keyword for this kind of declaration, an implicit `var` is still `"var"`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            VarKind::Var(_) => "var",
+            VarKind::Let(_) => "let",
+            VarKind::Const(_) => "const",
+            VarKind::Using(_) => "using",
+            VarKind::AwaitUsing(_, _) => "await using",
+        }
+    }
+
+    /// Parse a declaration keyword, placing its token at `start`
+    /// ```
+    /// use resast::spanned::{Node, Position, VarKind};
+    ///
+    /// let kind = VarKind::from_str_at("const", Position::new(2, 4)).unwrap();
+    /// assert_eq!(kind.as_str(), "const");
+    /// assert_eq!(kind.loc().end, Position::new(2, 9));
+    /// let kind = VarKind::from_str_at("await using", Position::new(1, 0)).unwrap();
+    /// assert_eq!(kind.loc().end, Position::new(1, 11));
+    /// assert!(VarKind::from_str_at("static", Position::new(1, 0)).is_err());
+    /// ```
+    pub fn from_str_at(s: &str, start: Position) -> Result<Self, crate::InvalidVarKind> {
+        Ok(match s.parse()? {
+            crate::VarKind::Var => VarKind::Var(Some(start.into())),
+            crate::VarKind::Let => VarKind::Let(start.into()),
+            crate::VarKind::Const => VarKind::Const(start.into()),
+            crate::VarKind::Using => VarKind::Using(start.into()),
+            crate::VarKind::AwaitUsing => VarKind::AwaitUsing(start.into(), (start + 6).into()),
+        })
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -545,6 +1220,13 @@ impl VarKind {
 pub struct Slice<T> {
     pub source: T,
     pub loc: SourceLocation,
+    /// The start and end byte offsets of this slice in the original
+    /// source text, when the parser tracked them
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub range: Option<(u32, u32)>,
 }
 
 impl<T> IntoAllocated for Slice<T>
@@ -556,6 +1238,7 @@ where
         Slice {
             loc: self.loc,
             source: self.source.to_string(),
+            range: self.range,
         }
     }
 }
@@ -565,11 +1248,25 @@ impl<T> Slice<T> {
         Self {
             source,
             loc: SourceLocation::new(start_line, start_col, end_line, end_column),
+            range: None,
         }
     }
+    /// Attach the start and end byte offsets of this slice
+    /// ```
+    /// use resast::spanned::Slice;
+    /// use resast::IntoAllocated;
+    ///
+    /// let slice = Slice::new("thing", 2, 4, 2, 9).with_range(12, 17);
+    /// assert_eq!(slice.range, Some((12, 17)));
+    /// assert_eq!(slice.into_allocated().range, Some((12, 17)));
+    /// ```
+    pub fn with_range(mut self, start: u32, end: u32) -> Self {
+        self.range = Some((start, end));
+        self
+    }
 }
 
-#[derive(Debug, Clone, PartialEq, Copy)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Copy)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct SourceLocation {
     pub start: Position,
@@ -589,9 +1286,80 @@ impl SourceLocation {
             },
         }
     }
+    /// Like [`new`](Self::new) but if the end comes before the
+    /// start the two positions are swapped
+    /// ```
+    /// use resast::spanned::{Position, SourceLocation};
+    ///
+    /// let loc = SourceLocation::new_clamped(2, 4, 1, 7);
+    /// assert_eq!(loc.start, Position::new(1, 7));
+    /// assert_eq!(loc.end, Position::new(2, 4));
+    /// loc.assert_valid();
+    /// let same_line = SourceLocation::new_clamped(1, 9, 1, 3);
+    /// assert_eq!(same_line, SourceLocation::new(1, 3, 1, 9));
+    /// assert_eq!(SourceLocation::new_clamped(1, 3, 1, 9), same_line);
+    /// ```
+    pub fn new_clamped(start_line: u32, start_column: u32, end_line: u32, end_column: u32) -> Self {
+        let loc = Self::new(start_line, start_column, end_line, end_column);
+        if loc.end < loc.start {
+            Self {
+                start: loc.end,
+                end: loc.start,
+            }
+        } else {
+            loc
+        }
+    }
+    /// Panic in debug builds if the end of this location comes
+    /// before its start
+    ///
+    /// The example isn't run since whether it panics depends on the
+    /// profile resast was built with
+    /// ```no_run
+    /// use resast::spanned::SourceLocation;
+    ///
+    /// SourceLocation::new(1, 3, 1, 9).assert_valid();
+    /// // panics in a debug build
+    /// SourceLocation::new(1, 9, 1, 3).assert_valid();
+    /// ```
+    pub fn assert_valid(&self) {
+        debug_assert!(self.start <= self.end, "{:?} ends before it starts", self);
+    }
     fn zero() -> Self {
         Self::new(0, 0, 0, 0)
     }
+    /// The location from the start of `start` to the end of `end`
+    pub fn between(start: &impl Node, end: &impl Node) -> Self {
+        Self {
+            start: start.loc().start,
+            end: end.loc().end,
+        }
+    }
+    /// The smallest location that covers both `self` and `other`
+    /// ```
+    /// use resast::spanned::SourceLocation;
+    ///
+    /// let overlapping = SourceLocation::new(1, 0, 1, 10).merge(SourceLocation::new(1, 5, 2, 1));
+    /// assert_eq!(overlapping, SourceLocation::new(1, 0, 2, 1));
+    /// let inner = SourceLocation::new(1, 0, 3, 0).merge(SourceLocation::new(2, 0, 2, 5));
+    /// assert_eq!(inner, SourceLocation::new(1, 0, 3, 0));
+    /// let disjoint = SourceLocation::new(4, 2, 4, 8).merge(SourceLocation::new(1, 0, 1, 3));
+    /// assert_eq!(disjoint, SourceLocation::new(1, 0, 4, 8));
+    /// ```
+    pub fn merge(self, other: Self) -> Self {
+        Self {
+            start: if other.start < self.start {
+                other.start
+            } else {
+                self.start
+            },
+            end: if other.end > self.end {
+                other.end
+            } else {
+                self.end
+            },
+        }
+    }
 }
 
 impl core::cmp::PartialOrd for SourceLocation {
@@ -604,7 +1372,7 @@ impl core::cmp::PartialOrd for SourceLocation {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Copy)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Copy, Default)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct Position {
     pub line: u32,
@@ -615,6 +1383,57 @@ impl Position {
     pub fn new(line: u32, column: u32) -> Self {
         Self { line, column }
     }
+    /// Move past `c`, a line terminator (`\n`, `\r`, `\u{2028}` or
+    /// `\u{2029}`) starts the next line at column 0 and anything else
+    /// moves one column to the right
+    /// ```
+    /// use resast::spanned::Position;
+    ///
+    /// for terminator in ['\n', '\r', '\u{2028}', '\u{2029}'] {
+    ///     let mut pos = Position::new(1, 4);
+    ///     pos.advance_char(terminator);
+    ///     assert_eq!(pos, Position::new(2, 0));
+    /// }
+    /// let mut pos = Position::new(1, 4);
+    /// pos.advance_char('\t');
+    /// assert_eq!(pos, Position::new(1, 5));
+    /// ```
+    pub fn advance_char(&mut self, c: char) {
+        if matches!(c, '\n' | '\r' | '\u{2028}' | '\u{2029}') {
+            self.line += 1;
+            self.column = 0;
+        } else {
+            self.column += 1;
+        }
+    }
+    /// Move past every character of `s`, see [`Position::advance_char`],
+    /// a `\r\n` pair only counts as one line terminator
+    /// ```
+    /// use resast::spanned::Position;
+    ///
+    /// let mut pos = Position::new(1, 4);
+    /// pos.advance_str("ab\ncd");
+    /// assert_eq!(pos, Position::new(2, 2));
+    /// pos.advance_str("é");
+    /// assert_eq!(pos, Position::new(2, 3));
+    /// pos.advance_char('\n');
+    /// assert_eq!(pos, Position::new(3, 0));
+    ///
+    /// let mut pos = Position::new(1, 0);
+    /// pos.advance_str("a\r\nb\rc\u{2028}d");
+    /// assert_eq!(pos, Position::new(4, 1));
+    /// pos.advance_str("\r\n\r\n");
+    /// assert_eq!(pos, Position::new(6, 0));
+    /// ```
+    pub fn advance_str(&mut self, s: &str) {
+        let mut chars = s.chars().peekable();
+        while let Some(c) = chars.next() {
+            if c == '\r' && chars.peek() == Some(&'\n') {
+                continue;
+            }
+            self.advance_char(c);
+        }
+    }
 }
 
 impl std::cmp::PartialOrd for Position {
@@ -638,13 +1457,22 @@ impl std::ops::Add for Position {
     }
 }
 
+/// Subtraction is saturating, the line and column are each
+/// clamped to 0 when `rhs` is larger
+/// ```
+/// use resast::spanned::Position;
+///
+/// assert_eq!(Position::new(5, 5) - Position::new(2, 3), Position::new(3, 2));
+/// assert_eq!(Position::new(1, 2) - Position::new(3, 1), Position::new(0, 1));
+/// assert_eq!(Position::new(1, 2) - Position::new(1, 7), Position::new(0, 0));
+/// ```
 impl std::ops::Sub for Position {
     type Output = Self;
 
     fn sub(self, rhs: Self) -> Self::Output {
         Self {
-            line: self.line - rhs.line,
-            column: self.column - rhs.column,
+            line: self.line.saturating_sub(rhs.line),
+            column: self.column.saturating_sub(rhs.column),
         }
     }
 }
@@ -660,13 +1488,20 @@ impl std::ops::Add<u32> for Position {
     }
 }
 
+/// Moves the column back, saturating at 0
+/// ```
+/// use resast::spanned::Position;
+///
+/// assert_eq!(Position::new(1, 5) - 2, Position::new(1, 3));
+/// assert_eq!(Position::new(1, 1) - 4, Position::new(1, 0));
+/// ```
 impl std::ops::Sub<u32> for Position {
     type Output = Self;
 
     fn sub(self, rhs: u32) -> Self::Output {
         Self {
             line: self.line,
-            column: self.column - rhs,
+            column: self.column.saturating_sub(rhs),
         }
     }
 }
@@ -1,4 +1,4 @@
-use crate::spanned::expr::{Expr, Prop};
+use crate::spanned::expr::{AssignLeft, Expr, Prop, PropKey, PropValue};
 use crate::spanned::Ident;
 use crate::IntoAllocated;
 
@@ -44,6 +44,122 @@ impl<T> Node for Pat<T> {
     }
 }
 
+impl<T> Pat<T> {
+    /// Every identifier this pattern binds, in source order
+    ///
+    /// Default values are not searched, only the targets
+    /// they are assigned to.
+    /// ```
+    /// use resast::spanned::expr::{Expr, Lit, Prop, PropInit, PropInitKey, PropKey, PropValue};
+    /// use resast::spanned::pat::{ArrayPat, ArrayPatPart, AssignPat, ObjPat, ObjPatPart, Pat, RestPat};
+    /// use resast::spanned::tokens::AssignOp;
+    /// use resast::spanned::{Ident, ListEntry, Slice};
+    ///
+    /// fn ident(name: &str) -> Ident<&str> {
+    ///     Ident::new_from_source(name, 1, 0)
+    /// }
+    /// fn init<'a>(key: PropKey<&'a str>, value: Option<PropValue<&'a str>>) -> ListEntry<ObjPatPart<&'a str>> {
+    ///     ListEntry::no_comma(ObjPatPart::Assign(Prop::Init(PropInit {
+    ///         key: PropInitKey { value: key, brackets: None },
+    ///         colon: value.as_ref().map(|_| Default::default()),
+    ///         value,
+    ///     })))
+    /// }
+    /// // {a, b: {c}, ...d}
+    /// let obj = Pat::Obj(ObjPat {
+    ///     open_brace: Default::default(),
+    ///     props: vec![
+    ///         init(PropKey::Pat(Pat::Ident(ident("a"))), None),
+    ///         init(
+    ///             PropKey::Expr(Expr::Ident(ident("b"))),
+    ///             Some(PropValue::Pat(Pat::Obj(ObjPat {
+    ///                 open_brace: Default::default(),
+    ///                 props: vec![init(PropKey::Pat(Pat::Ident(ident("c"))), None)],
+    ///                 close_brace: Default::default(),
+    ///             }))),
+    ///         ),
+    ///         ListEntry::no_comma(ObjPatPart::Rest(Box::new(RestPat {
+    ///             dots: Default::default(),
+    ///             pat: Pat::Ident(ident("d")),
+    ///         }))),
+    ///     ],
+    ///     close_brace: Default::default(),
+    /// });
+    /// let names: Vec<_> = obj.bound_names().into_iter().map(|i| i.slice.source).collect();
+    /// assert_eq!(names, ["a", "c", "d"]);
+    ///
+    /// // [x, , y = 1]
+    /// let arr = Pat::Array(ArrayPat {
+    ///     open_bracket: Default::default(),
+    ///     elements: vec![
+    ///         ListEntry::no_comma(Some(ArrayPatPart::Pat(Pat::Ident(ident("x"))))),
+    ///         ListEntry::no_comma(None),
+    ///         ListEntry::no_comma(Some(ArrayPatPart::Pat(Pat::Assign(AssignPat {
+    ///             left: Box::new(Pat::Ident(ident("y"))),
+    ///             operator: AssignOp::Equal(Default::default()),
+    ///             right: Box::new(Expr::Lit(Lit::Number(Slice::new("1", 1, 0, 1, 1)))),
+    ///         })))),
+    ///     ],
+    ///     close_bracket: Default::default(),
+    /// });
+    /// let names: Vec<_> = arr.bound_names().into_iter().map(|i| i.slice.source).collect();
+    /// assert_eq!(names, ["x", "y"]);
+    /// ```
+    pub fn bound_names(&self) -> Vec<&Ident<T>> {
+        let mut names = Vec::new();
+        self.push_bound_names(&mut names);
+        names
+    }
+
+    fn push_bound_names<'a>(&'a self, names: &mut Vec<&'a Ident<T>>) {
+        match self {
+            Pat::Ident(ident) => names.push(ident),
+            Pat::Obj(obj) => {
+                for entry in &obj.props {
+                    match &entry.item {
+                        ObjPatPart::Assign(Prop::Init(init)) => match &init.value {
+                            Some(PropValue::Pat(pat)) => pat.push_bound_names(names),
+                            Some(PropValue::Expr(expr)) => push_target_names(expr, names),
+                            Some(PropValue::Method(_)) => {}
+                            None => match &init.key.value {
+                                PropKey::Pat(pat) => pat.push_bound_names(names),
+                                PropKey::Expr(expr) => push_target_names(expr, names),
+                                PropKey::Lit(_) => {}
+                            },
+                        },
+                        ObjPatPart::Assign(_) => {}
+                        ObjPatPart::Rest(rest) => rest.pat.push_bound_names(names),
+                    }
+                }
+            }
+            Pat::Array(arr) => {
+                for part in arr.elements.iter().filter_map(|entry| entry.item.as_ref()) {
+                    match part {
+                        ArrayPatPart::Pat(pat) => pat.push_bound_names(names),
+                        ArrayPatPart::Expr(expr) => push_target_names(expr, names),
+                        ArrayPatPart::Rest(rest) => rest.pat.push_bound_names(names),
+                    }
+                }
+            }
+            Pat::Assign(assign) => assign.left.push_bound_names(names),
+        }
+    }
+}
+
+/// The names bound by an expression in pattern position,
+/// `x` or `x = 1` in `[x, y = 1] = arr`
+fn push_target_names<'a, T>(expr: &'a Expr<T>, names: &mut Vec<&'a Ident<T>>) {
+    match expr {
+        Expr::Ident(ident) => names.push(ident),
+        Expr::Assign(assign) => match &assign.left {
+            AssignLeft::Pat(pat) => pat.push_bound_names(names),
+            AssignLeft::Expr(expr) => push_target_names(expr, names),
+        },
+        Expr::Spread(spread) => push_target_names(&spread.expr, names),
+        _ => {}
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct ArrayPat<T> {
@@ -79,6 +195,55 @@ impl<T> Node for ArrayPat<T> {
     }
 }
 
+impl<T> ArrayPat<T> {
+    /// The number of elisions, like
+    /// [`ArrayExpr::holes`](crate::spanned::expr::ArrayExpr::holes) the
+    /// trailing comma in `[a,] = x` isn't one
+    /// ```
+    /// use resast::spanned::pat::{ArrayPat, ArrayPatPart, Pat};
+    /// use resast::spanned::{Ident, ListEntry};
+    ///
+    /// fn array<'a>(entries: Vec<(Option<&'a str>, bool)>) -> ArrayPat<&'a str> {
+    ///     let elements = entries
+    ///         .into_iter()
+    ///         .map(|(item, comma)| ListEntry {
+    ///             item: item.map(|n| ArrayPatPart::Pat(Pat::Ident(Ident::new_from_source(n, 1, 1)))),
+    ///             comma: comma.then(Default::default),
+    ///         })
+    ///         .collect();
+    ///     ArrayPat {
+    ///         open_bracket: Default::default(),
+    ///         elements,
+    ///         close_bracket: Default::default(),
+    ///     }
+    /// }
+    /// fn concrete(arr: ArrayPat<&str>) -> Vec<bool> {
+    ///     match resast::pat::Pat::from(Pat::Array(arr)) {
+    ///         resast::pat::Pat::Array(elements) => elements.iter().map(Option::is_some).collect(),
+    ///         _ => unreachable!(),
+    ///     }
+    /// }
+    /// // [a,] = x
+    /// let trailing = array(vec![(Some("a"), true)]);
+    /// assert_eq!(trailing.holes(), 0);
+    /// assert_eq!(concrete(trailing), vec![true]);
+    /// // [a,] = x with the trailing comma followed by an empty entry
+    /// let empty_last = array(vec![(Some("a"), true), (None, false)]);
+    /// assert_eq!(empty_last.holes(), 0);
+    /// assert_eq!(concrete(empty_last), vec![true]);
+    /// // [, a] = x
+    /// let leading = array(vec![(None, true), (Some("a"), false)]);
+    /// assert_eq!(leading.holes(), 1);
+    /// assert_eq!(concrete(leading), vec![false, true]);
+    /// ```
+    pub fn holes(&self) -> usize {
+        self.elements
+            .iter()
+            .filter(|entry| entry.item.is_none() && entry.comma.is_some())
+            .count()
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct ArrayElement<T> {
@@ -253,9 +418,6 @@ where
 
 impl<T> Node for AssignPat<T> {
     fn loc(&self) -> SourceLocation {
-        SourceLocation {
-            start: self.left.loc().start,
-            end: self.right.loc().end,
-        }
+        SourceLocation::between(&*self.left, &*self.right)
     }
 }
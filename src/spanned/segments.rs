@@ -0,0 +1,765 @@
+//! Flattening a spanned tree into the spans of its leaves
+//!
+//! Each keyword, punctuator, identifier and literal becomes one
+//! segment tagged with a coarse [`NodeKind`], these are what a
+//! source map needs to point back into the original file.
+//!
+//! ```
+//! use resast::spanned::decl::{Decl, VarDecl, VarDecls};
+//! use resast::spanned::expr::{Expr, Lit};
+//! use resast::spanned::pat::Pat;
+//! use resast::spanned::segments::NodeKind;
+//! use resast::spanned::{Ident, ListEntry, Position, Program, ProgramPart, Slice, VarKind};
+//!
+//! // let x = 1;
+//! let program = Program::script(vec![ProgramPart::Decl(Decl::Var {
+//!     decls: VarDecls {
+//!         keyword: VarKind::Let(Position::new(1, 0).into()),
+//!         decls: vec![ListEntry::no_comma(VarDecl {
+//!             id: Pat::Ident(Ident::new_from_source("x", 1, 4)),
+//!             eq: Some(Position::new(1, 6).into()),
+//!             init: Some(Expr::Lit(Lit::Number(Slice::new("1", 1, 8, 1, 9)))),
+//!         })],
+//!     },
+//!     semi_colon: Some(Position::new(1, 9).into()),
+//! })]);
+//! let segments: Vec<_> = program
+//!     .segments()
+//!     .into_iter()
+//!     .map(|(loc, kind)| (loc.start.column, loc.end.column, kind))
+//!     .collect();
+//! assert_eq!(
+//!     segments,
+//!     [
+//!         (0, 3, NodeKind::Keyword),
+//!         (4, 5, NodeKind::Ident),
+//!         (6, 7, NodeKind::Punct),
+//!         (8, 9, NodeKind::Lit),
+//!         (9, 10, NodeKind::Punct),
+//!     ]
+//! );
+//! ```
+use crate::spanned::decl::*;
+use crate::spanned::expr::*;
+use crate::spanned::pat::*;
+use crate::spanned::stmt::*;
+use crate::spanned::tokens::{self, Token};
+use crate::spanned::{
+    Class, ClassBody, Decorator, Dir, Func, FuncArg, FuncBody, HashbangComment, Ident, ListEntry,
+    Node, NodeRef, Position, PrivateIdent, Program, ProgramPart, SourceLocation, SuperClass,
+    VarKind,
+};
+
+#[cfg(feature = "jsx")]
+use crate::spanned::jsx::*;
+
+/// The coarse kind of a leaf in the spanned tree
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NodeKind {
+    /// A reserved or contextual word like `let` or `typeof`
+    Keyword,
+    /// An operator or delimiter like `=` or `{`
+    Punct,
+    /// An identifier, including private names
+    Ident,
+    /// A string, number, bigint, boolean, `null` or regex
+    Lit,
+    /// One quasi of a template literal, including its quotes
+    Template,
+    /// A hashbang comment
+    Comment,
+    /// Text between JSX tags
+    Text,
+}
+
+pub(crate) type Segment = (SourceLocation, NodeKind);
+
+/// Collects segments while walking the tree
+pub(crate) struct Sink<'a, T> {
+    pub segments: Vec<Segment>,
+    /// When set, nodes record every segment of theirs that falls
+    /// outside of their own span as `(parent, child)`
+    pub escaped: Option<Vec<(SourceLocation, SourceLocation)>>,
+    /// When set, the last node entered whose span contains this
+    /// position is kept in `found`
+    pub target: Option<Position>,
+    pub found: Option<NodeRef<'a, T>>,
+}
+
+impl<'a, T> Default for Sink<'a, T> {
+    fn default() -> Self {
+        Self {
+            segments: Vec::new(),
+            escaped: None,
+            target: None,
+            found: None,
+        }
+    }
+}
+
+impl<'a, T> Sink<'a, T> {
+    fn push(&mut self, segment: Segment) {
+        self.segments.push(segment);
+    }
+    /// Check the segments pushed since `start` are inside of `parent`
+    fn contain(&mut self, parent: SourceLocation, start: usize) {
+        if let Some(escaped) = &mut self.escaped {
+            for (child, _) in &self.segments[start..] {
+                if child.start < parent.start || child.end > parent.end {
+                    escaped.push((parent, *child));
+                }
+            }
+        }
+    }
+    /// Called before walking into `node`, since the walk is pre-order
+    /// the last node that contains the target is the innermost one
+    fn enter(&mut self, node: NodeRef<'a, T>) {
+        if let Some(target) = self.target {
+            let loc = node.loc();
+            if loc.start <= target && target < loc.end {
+                self.found = Some(node);
+            }
+        }
+    }
+}
+
+pub(crate) trait Segments<'a, T> {
+    fn push_segments(&'a self, out: &mut Sink<'a, T>);
+}
+
+impl<'a, T, K> Segments<'a, T> for K
+where
+    K: Token,
+{
+    fn push_segments(&'a self, out: &mut Sink<'a, T>) {
+        let kind = if self.as_str().starts_with(char::is_alphabetic) {
+            NodeKind::Keyword
+        } else {
+            NodeKind::Punct
+        };
+        out.push((self.loc(), kind));
+    }
+}
+
+impl<'a, T, N> Segments<'a, T> for Option<N>
+where
+    N: Segments<'a, T>,
+{
+    fn push_segments(&'a self, out: &mut Sink<'a, T>) {
+        if let Some(inner) = self {
+            inner.push_segments(out)
+        }
+    }
+}
+
+impl<'a, T, N> Segments<'a, T> for Vec<N>
+where
+    N: Segments<'a, T>,
+{
+    fn push_segments(&'a self, out: &mut Sink<'a, T>) {
+        for inner in self {
+            inner.push_segments(out)
+        }
+    }
+}
+
+impl<'a, T, A, B> Segments<'a, T> for (A, B)
+where
+    A: Segments<'a, T>,
+    B: Segments<'a, T>,
+{
+    fn push_segments(&'a self, out: &mut Sink<'a, T>) {
+        self.0.push_segments(out);
+        self.1.push_segments(out);
+    }
+}
+
+impl<'a, T, N> Segments<'a, T> for ListEntry<N>
+where
+    N: Segments<'a, T>,
+{
+    fn push_segments(&'a self, out: &mut Sink<'a, T>) {
+        self.item.push_segments(out);
+        self.comma.push_segments(out);
+    }
+}
+
+/// Structs whose segments are just those of their fields
+macro_rules! fields {
+    ($($ty:ident { $($field:ident),* $(,)? })*) => {
+        $(
+            impl<'a, T> Segments<'a, T> for $ty<T> {
+                fn push_segments(&'a self, out: &mut Sink<'a, T>) {
+                    let start = out.segments.len();
+                    $(self.$field.push_segments(out);)*
+                    out.contain(self.loc(), start);
+                }
+            }
+        )*
+    };
+}
+
+/// Enums where every variant holds a single node
+macro_rules! variants {
+    ($($ty:ident { $($variant:ident),* $(,)? })*) => {
+        $(
+            impl<'a, T> Segments<'a, T> for $ty<T> {
+                fn push_segments(&'a self, out: &mut Sink<'a, T>) {
+                    match self {
+                        $(Self::$variant(inner) => inner.push_segments(out),)*
+                    }
+                }
+            }
+        )*
+    };
+}
+
+/// Nodes that make up a single segment
+macro_rules! leaves {
+    ($($ty:ident => $kind:ident,)*) => {
+        $(
+            impl<'a, T> Segments<'a, T> for $ty<T> {
+                fn push_segments(&'a self, out: &mut Sink<'a, T>) {
+                    out.push((self.loc(), NodeKind::$kind));
+                }
+            }
+        )*
+    };
+}
+
+leaves! {
+    PrivateIdent => Ident,
+    HashbangComment => Comment,
+    StringLit => Lit,
+    RegEx => Lit,
+    TemplateElement => Template,
+}
+
+fields! {
+    Dir { expr, semi_colon }
+    Func { keyword_async, keyword, star, id, open_paren, params, close_paren, body }
+    FuncBody { open_brace, stmts, close_brace }
+    Class { decorators, keyword, id, super_class, body }
+    ClassBody { open_brace, props, close_brace }
+    Decorator { at, expr }
+    SuperClass { keyword_extends, expr }
+    VarDecls { keyword, decls }
+    VarDecl { id, eq, init }
+    ModImport { keyword_import, specifiers, keyword_from, source, attributes }
+    ImportAttributes { keyword, open_brace, attributes, close_brace }
+    ImportAttribute { key, colon, value }
+    NormalImportSpecs { open_brace, specs, close_brace }
+    NormalImportSpec { imported, alias }
+    DefaultImportSpec { id }
+    NamespaceImportSpec { star, keyword, ident }
+    ModExport { keyword, spec }
+    NamedExportSpec { list, source }
+    NamedExportSource { keyword_from, module }
+    ExportList { open_brace, elements, close_brace }
+    ExportSpecifier { local, alias }
+    Alias { keyword, ident }
+    ArrayExpr { open_bracket, elements, close_bracket }
+    ObjExpr { open_brace, props, close_brace }
+    SpreadExpr { dots, expr }
+    PropInit { key, colon, value }
+    PropInitKey { brackets, value }
+    PropMethod { decorators, keyword_static, keyword_async, star, id, open_paren, params, close_paren, body }
+    PropCtor { decorators, keyword, open_paren, params, close_paren, body }
+    PropGet { decorators, keyword_static, keyword_get, id, open_paren, close_paren, body }
+    PropSet { decorators, keyword_static, keyword_set, id, open_paren, arg, close_paren, body }
+    PropField { decorators, keyword_static, key, eq, value, semi_colon }
+    StaticBlock { keyword_static, open_brace, stmts, close_brace }
+    UnaryExpr { operator, argument }
+    UpdateExpr { operator, argument }
+    BinaryExpr { left, operator, right }
+    AssignExpr { left, operator, right }
+    AwaitExpr { keyword, expr }
+    LogicalExpr { left, operator, right }
+    MemberExpr { object, indexer, property }
+    OptionalChain { expr, op }
+    ConditionalExpr { test, question_mark, alternate, colon, consequent }
+    CallExpr { callee, optional, open_paren, arguments, close_paren }
+    NewExpr { keyword, callee, open_paren, arguments, close_paren }
+    ArrowParamPlaceHolder { keyword, open_paren, args, close_paren }
+    ArrowFuncExpr { keyword, star, open_paren, params, close_paren, arrow, body }
+    TaggedTemplateExpr { tag, quasi }
+    MetaProp { meta, dot, property }
+    ImportExpr { keyword, open_paren, source, options, close_paren }
+    WrappedExpr { open_paren, expr, close_paren }
+    ArrayPat { open_bracket, elements, close_bracket }
+    ObjPat { open_brace, props, close_brace }
+    RestPat { dots, pat }
+    AssignPat { left, operator, right }
+    WithStmt { keyword, open_paren, object, close_paren, body }
+    LabeledStmt { label, colon, body }
+    ElseStmt { keyword, body }
+    SwitchStmt { keyword, open_paren, discriminant, close_paren, open_brace, cases, close_brace }
+    SwitchCase { keyword, test, colon, consequent }
+    BlockStmt { open_brace, stmts, close_brace }
+    TryStmt { keyword, block, handler, finalizer }
+    CatchClause { keyword, param, body }
+    CatchArg { open_paren, param, close_paren }
+    FinallyClause { keyword, body }
+    WhileStmt { keyword, open_paren, test, close_paren, body }
+    DoWhileStmt { keyword_do, body, keyword_while, open_paren, test, close_paren, semi_colon }
+    ForStmt { keyword, open_paren, init, semi1, test, semi2, update, close_paren, body }
+    ForInStmt { keyword_for, open_paren, left, keyword_in, right, close_paren, body }
+    ForOfStmt { keyword_for, open_paren, left, keyword_of, right, close_paren, body }
+}
+
+variants! {
+    ProgramPart { Dir, Decl, Stmt }
+    FuncArg { Expr, Pat, Rest }
+    ImportAttributeKey { Ident, String }
+    ImportSpecifier { Normal, Default, Namespace }
+    NamedExportDecl { Decl, Specifier }
+    DefaultExportDeclValue { Decl, Expr }
+    ObjProp { Prop, Spread }
+    Prop { Init, Method, Ctor, Get, Set, Field, StaticBlock }
+    PropKey { Lit, Expr, Pat }
+    PropValue { Expr, Pat, Method }
+    AssignLeft { Pat, Expr }
+    ArrowFuncBody { FuncBody, Expr }
+    ArrayPatPart { Pat, Expr, Rest }
+    ObjPatPart { Assign, Rest }
+}
+
+impl<T> Program<T> {
+    /// Every leaf of this program with a coarse kind tag, ordered by
+    /// where they start
+    pub fn segments(&self) -> Vec<(SourceLocation, NodeKind)> {
+        let mut out = Sink::default();
+        self.push_segments(&mut out);
+        let mut segments = out.segments;
+        segments.sort_by_key(|(loc, _)| (loc.start.line, loc.start.column));
+        segments
+    }
+}
+
+impl<T> Program<T> {
+    /// The innermost declaration, statement, expression, pattern or
+    /// identifier whose span contains `pos`, a span includes its
+    /// start but not its end
+    /// ```
+    /// use resast::spanned::decl::{Decl, VarDecl, VarDecls};
+    /// use resast::spanned::expr::Expr;
+    /// use resast::spanned::pat::Pat;
+    /// use resast::spanned::{Ident, ListEntry, NodeRef, Position, Program, ProgramPart, VarKind};
+    ///
+    /// // let foo = bar;
+    /// let program = Program::script(vec![ProgramPart::Decl(Decl::Var {
+    ///     decls: VarDecls {
+    ///         keyword: VarKind::Let(Position::new(1, 0).into()),
+    ///         decls: vec![ListEntry::no_comma(VarDecl {
+    ///             id: Pat::Ident(Ident::new_from_source("foo", 1, 4)),
+    ///             eq: Some(Position::new(1, 8).into()),
+    ///             init: Some(Expr::Ident(Ident::new_from_source("bar", 1, 10))),
+    ///         })],
+    ///     },
+    ///     semi_colon: Some(Position::new(1, 13).into()),
+    /// })]);
+    /// let name_at = |column| match program.node_at(Position::new(1, column)) {
+    ///     Some(NodeRef::Ident(ident)) => Some(ident.slice.source),
+    ///     _ => None,
+    /// };
+    /// assert_eq!(name_at(5), Some("foo"));
+    /// assert_eq!(name_at(10), Some("bar"));
+    /// assert_eq!(name_at(12), Some("bar"));
+    /// assert_eq!(name_at(13), None);
+    /// assert!(matches!(program.node_at(Position::new(1, 1)), Some(NodeRef::Decl(_))));
+    /// assert!(program.node_at(Position::new(2, 0)).is_none());
+    /// ```
+    pub fn node_at(&self, pos: Position) -> Option<NodeRef<'_, T>> {
+        let mut out = Sink {
+            target: Some(pos),
+            ..Sink::default()
+        };
+        self.push_segments(&mut out);
+        out.found
+    }
+}
+
+impl<'a, T> Segments<'a, T> for Program<T> {
+    fn push_segments(&'a self, out: &mut Sink<'a, T>) {
+        let start = out.segments.len();
+        if let Some(hashbang) = self.hashbang() {
+            hashbang.push_segments(out);
+        }
+        self.parts().iter().for_each(|part| part.push_segments(out));
+        out.contain(self.loc(), start);
+    }
+}
+
+impl<'a, T> Segments<'a, T> for VarKind {
+    fn push_segments(&'a self, out: &mut Sink<'a, T>) {
+        match self {
+            VarKind::Var(var) => var.push_segments(out),
+            VarKind::Let(tok) => tok.push_segments(out),
+            VarKind::Const(tok) => tok.push_segments(out),
+            VarKind::Using(tok) => tok.push_segments(out),
+            VarKind::AwaitUsing(keyword_await, keyword_using) => {
+                keyword_await.push_segments(out);
+                keyword_using.push_segments(out);
+            }
+        }
+    }
+}
+
+impl<'a, T> Segments<'a, T> for Decl<T> {
+    fn push_segments(&'a self, out: &mut Sink<'a, T>) {
+        out.enter(NodeRef::Decl(self));
+        let start = out.segments.len();
+        match self {
+            Decl::Var { decls, semi_colon } => {
+                decls.push_segments(out);
+                semi_colon.push_segments(out);
+            }
+            Decl::Func(inner) => inner.push_segments(out),
+            Decl::Class(inner) => inner.push_segments(out),
+            Decl::Import { import, semi_colon } => {
+                import.push_segments(out);
+                semi_colon.push_segments(out);
+            }
+            Decl::Export { export, semi_colon } => {
+                export.push_segments(out);
+                semi_colon.push_segments(out);
+            }
+        }
+        out.contain(self.loc(), start);
+    }
+}
+
+impl<'a, T> Segments<'a, T> for ModExportSpecifier<T> {
+    fn push_segments(&'a self, out: &mut Sink<'a, T>) {
+        match self {
+            ModExportSpecifier::Default { keyword, value } => {
+                keyword.push_segments(out);
+                value.push_segments(out);
+            }
+            ModExportSpecifier::Named(inner) => inner.push_segments(out),
+            ModExportSpecifier::All {
+                star,
+                alias,
+                keyword,
+                name,
+            } => {
+                star.push_segments(out);
+                alias.push_segments(out);
+                keyword.push_segments(out);
+                name.push_segments(out);
+            }
+        }
+    }
+}
+
+impl<'a, T> Segments<'a, T> for Expr<T> {
+    fn push_segments(&'a self, out: &mut Sink<'a, T>) {
+        out.enter(NodeRef::Expr(self));
+        match self {
+            Expr::Array(inner) => inner.push_segments(out),
+            Expr::ArrowFunc(inner) => inner.push_segments(out),
+            Expr::ArrowParamPlaceHolder(inner) => inner.push_segments(out),
+            Expr::Assign(inner) => inner.push_segments(out),
+            Expr::Await(inner) => inner.push_segments(out),
+            Expr::Binary(inner) => inner.push_segments(out),
+            Expr::Class(inner) => inner.push_segments(out),
+            Expr::Call(inner) => inner.push_segments(out),
+            Expr::Conditional(inner) => inner.push_segments(out),
+            Expr::Func(inner) => inner.push_segments(out),
+            Expr::Ident(inner) => inner.push_segments(out),
+            Expr::PrivateIdent(inner) => inner.push_segments(out),
+            Expr::Import(inner) => inner.push_segments(out),
+            Expr::Lit(inner) => inner.push_segments(out),
+            Expr::Logical(inner) => inner.push_segments(out),
+            Expr::Member(inner) => inner.push_segments(out),
+            Expr::MetaProp(inner) => inner.push_segments(out),
+            Expr::New(inner) => inner.push_segments(out),
+            Expr::Obj(inner) => inner.push_segments(out),
+            Expr::Sequence(inner) => inner.0.push_segments(out),
+            Expr::Spread(inner) => inner.push_segments(out),
+            Expr::Super(inner) => inner.push_segments(out),
+            Expr::TaggedTemplate(inner) => inner.push_segments(out),
+            Expr::This(inner) => inner.push_segments(out),
+            Expr::Unary(inner) => inner.push_segments(out),
+            Expr::Update(inner) => inner.push_segments(out),
+            Expr::Wrapped(inner) => inner.push_segments(out),
+            Expr::Yield(inner) => inner.push_segments(out),
+            Expr::OptionalChain(inner) => inner.push_segments(out),
+            #[cfg(feature = "jsx")]
+            Expr::Jsx(inner) => inner.push_segments(out),
+            #[cfg(feature = "jsx")]
+            Expr::JsxFragment(inner) => inner.push_segments(out),
+        }
+    }
+}
+
+impl<'a, T> Segments<'a, T> for Pat<T> {
+    fn push_segments(&'a self, out: &mut Sink<'a, T>) {
+        out.enter(NodeRef::Pat(self));
+        match self {
+            Pat::Ident(inner) => inner.push_segments(out),
+            Pat::Obj(inner) => inner.push_segments(out),
+            Pat::Array(inner) => inner.push_segments(out),
+            Pat::Assign(inner) => inner.push_segments(out),
+        }
+    }
+}
+
+impl<'a, T> Segments<'a, T> for Ident<T> {
+    fn push_segments(&'a self, out: &mut Sink<'a, T>) {
+        out.enter(NodeRef::Ident(self));
+        out.push((self.loc(), NodeKind::Ident));
+    }
+}
+
+impl<'a, T> Segments<'a, T> for Lit<T> {
+    fn push_segments(&'a self, out: &mut Sink<'a, T>) {
+        match self {
+            Lit::Null(tok) => out.push((tok.loc(), NodeKind::Lit)),
+            Lit::String(inner) => inner.push_segments(out),
+            Lit::Number(slice) | Lit::BigInt(slice) => out.push((slice.loc, NodeKind::Lit)),
+            Lit::Boolean(inner) => out.push((inner.loc(), NodeKind::Lit)),
+            Lit::RegEx(inner) => inner.push_segments(out),
+            Lit::Template(inner) => inner.push_segments(out),
+        }
+    }
+}
+
+impl<'a, T> Segments<'a, T> for TemplateLit<T> {
+    fn push_segments(&'a self, out: &mut Sink<'a, T>) {
+        self.quasis.push_segments(out);
+        self.expressions.push_segments(out);
+    }
+}
+
+impl<'a, T> Segments<'a, T> for YieldExpr<T> {
+    fn push_segments(&'a self, out: &mut Sink<'a, T>) {
+        self.keyword.push_segments(out);
+        self.star.push_segments(out);
+        if let Some(argument) = &self.argument {
+            argument.push_segments(out);
+        }
+    }
+}
+
+impl<'a, T> Segments<'a, T> for MemberIndexer {
+    fn push_segments(&'a self, out: &mut Sink<'a, T>) {
+        match self {
+            MemberIndexer::Period(tok) => tok.push_segments(out),
+            MemberIndexer::Computed {
+                open_bracket,
+                close_bracket,
+            } => {
+                open_bracket.push_segments(out);
+                close_bracket.push_segments(out);
+            }
+            MemberIndexer::Optional(tok) => tok.push_segments(out),
+            MemberIndexer::OptionalComputed {
+                optional,
+                open_bracket,
+                close_bracket,
+            } => {
+                optional.push_segments(out);
+                open_bracket.push_segments(out);
+                close_bracket.push_segments(out);
+            }
+        }
+    }
+}
+
+/// Operators are punctuators unless they are spelled as a word
+macro_rules! operators {
+    ($($ty:ident $(: $($keyword:ident)|+)?,)*) => {
+        $(
+            impl<'a, T> Segments<'a, T> for tokens::$ty {
+                fn push_segments(&'a self, out: &mut Sink<'a, T>) {
+                    #[allow(unused_mut)]
+                    let mut kind = NodeKind::Punct;
+                    $(
+                        if matches!(self, $(Self::$keyword(_))|+) {
+                            kind = NodeKind::Keyword;
+                        }
+                    )?
+                    out.push((self.loc(), kind));
+                }
+            }
+        )*
+    };
+}
+
+operators! {
+    AssignOp,
+    LogicalOp,
+    BinaryOp: In | InstanceOf,
+    UpdateOp,
+    UnaryOp: TypeOf | Void | Delete,
+}
+
+impl<'a, T> Segments<'a, T> for Stmt<T> {
+    fn push_segments(&'a self, out: &mut Sink<'a, T>) {
+        out.enter(NodeRef::Stmt(self));
+        let start = out.segments.len();
+        match self {
+            Stmt::Expr { expr, semi_colon } => {
+                expr.push_segments(out);
+                semi_colon.push_segments(out);
+            }
+            Stmt::Block(inner) => inner.push_segments(out),
+            Stmt::Empty(tok) => tok.push_segments(out),
+            Stmt::Debugger {
+                keyword,
+                semi_colon,
+            } => {
+                keyword.push_segments(out);
+                semi_colon.push_segments(out);
+            }
+            Stmt::With(inner) => inner.push_segments(out),
+            Stmt::Return {
+                keyword,
+                value,
+                semi_colon,
+            } => {
+                keyword.push_segments(out);
+                value.push_segments(out);
+                semi_colon.push_segments(out);
+            }
+            Stmt::Labeled(inner) => inner.push_segments(out),
+            Stmt::Break {
+                keyword,
+                label,
+                semi_colon,
+            } => {
+                keyword.push_segments(out);
+                label.push_segments(out);
+                semi_colon.push_segments(out);
+            }
+            Stmt::Continue {
+                keyword,
+                label,
+                semi_colon,
+            } => {
+                keyword.push_segments(out);
+                label.push_segments(out);
+                semi_colon.push_segments(out);
+            }
+            Stmt::If(inner) => inner.push_segments(out),
+            Stmt::Switch(inner) => inner.push_segments(out),
+            Stmt::Throw {
+                keyword,
+                expr,
+                semi_colon,
+            } => {
+                keyword.push_segments(out);
+                expr.push_segments(out);
+                semi_colon.push_segments(out);
+            }
+            Stmt::Try(inner) => inner.push_segments(out),
+            Stmt::While(inner) => inner.push_segments(out),
+            Stmt::DoWhile(inner) => inner.push_segments(out),
+            Stmt::For(inner) => inner.push_segments(out),
+            Stmt::ForIn(inner) => inner.push_segments(out),
+            Stmt::ForOf(inner) => inner.push_segments(out),
+            Stmt::Var { decls, semi_colon } => {
+                decls.push_segments(out);
+                semi_colon.push_segments(out);
+            }
+        }
+        out.contain(self.loc(), start);
+    }
+}
+
+impl<'a, T> Segments<'a, T> for IfStmt<T> {
+    fn push_segments(&'a self, out: &mut Sink<'a, T>) {
+        self.keyword.push_segments(out);
+        self.open_paren.push_segments(out);
+        self.test.push_segments(out);
+        self.close_paren.push_segments(out);
+        self.consequent.push_segments(out);
+        if let Some(alternate) = &self.alternate {
+            alternate.push_segments(out);
+        }
+    }
+}
+
+impl<'a, T> Segments<'a, T> for LoopInit<T> {
+    fn push_segments(&'a self, out: &mut Sink<'a, T>) {
+        match self {
+            LoopInit::Variable(kind, decls) => {
+                kind.push_segments(out);
+                decls.push_segments(out);
+            }
+            LoopInit::Expr(inner) => inner.push_segments(out),
+        }
+    }
+}
+
+impl<'a, T> Segments<'a, T> for LoopLeft<T> {
+    fn push_segments(&'a self, out: &mut Sink<'a, T>) {
+        match self {
+            LoopLeft::Expr(inner) => inner.push_segments(out),
+            LoopLeft::Variable(kind, decl) => {
+                kind.push_segments(out);
+                decl.push_segments(out);
+            }
+            LoopLeft::Pat(inner) => inner.push_segments(out),
+        }
+    }
+}
+
+#[cfg(feature = "jsx")]
+mod jsx {
+    use super::*;
+
+    leaves! {
+        JsxText => Text,
+    }
+
+    fields! {
+        JsxElement { opening, children, closing }
+        JsxOpeningElement { open_angle, name, attributes, slash, close_angle }
+        JsxClosingElement { open_angle, slash, name, close_angle }
+        JsxFragment { opening, children, closing }
+        JsxAttribute { name, eq, value }
+        JsxExpressionContainer { open_brace, expr, close_brace }
+        JsxSpread { open_brace, dots, expr, close_brace }
+    }
+
+    variants! {
+        JsxAttr { Attribute, Spread }
+        JsxAttributeValue { String, Expr, Element, Fragment }
+        JsxChild { Text, Expr, Spread, Element, Fragment }
+    }
+
+    impl<'a, T> Segments<'a, T> for JsxOpeningFragment {
+        fn push_segments(&'a self, out: &mut Sink<'a, T>) {
+            self.open_angle.push_segments(out);
+            self.close_angle.push_segments(out);
+        }
+    }
+
+    impl<'a, T> Segments<'a, T> for JsxClosingFragment {
+        fn push_segments(&'a self, out: &mut Sink<'a, T>) {
+            self.open_angle.push_segments(out);
+            self.slash.push_segments(out);
+            self.close_angle.push_segments(out);
+        }
+    }
+
+    impl<'a, T> Segments<'a, T> for JsxElementName<T> {
+        fn push_segments(&'a self, out: &mut Sink<'a, T>) {
+            match self {
+                JsxElementName::Ident(ident) => ident.push_segments(out),
+                JsxElementName::Member(object, dot, property) => {
+                    object.push_segments(out);
+                    dot.push_segments(out);
+                    property.push_segments(out);
+                }
+                JsxElementName::Namespaced(namespace, colon, name) => {
+                    namespace.push_segments(out);
+                    colon.push_segments(out);
+                    name.push_segments(out);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,1128 @@
+//! Conversions from non-spanned expressions back into spanned ones
+//!
+//! The non-spanned tree has no position information so every token
+//! and slice produced here is placed at the [`Spanner`]'s position. The only
+//! exception is the operand of a unary or update operator, it is shifted one
+//! column so that `prefix` is still reported correctly on the spanned node.
+//!
+//! ```
+//! use resast::prelude::*;
+//! use resast::spanned::spanner::Spanner;
+//! use resast::spanned::Position;
+//!
+//! let expr = Expr::Binary(BinaryExpr {
+//!     operator: BinaryOp::Plus,
+//!     left: Box::new(Expr::ident_from("a")),
+//!     right: Box::new(Expr::Lit(Lit::number_from("1"))),
+//! });
+//! let spanned = Spanner::new(Position::new(1, 0)).expr(expr.clone()).unwrap();
+//! assert_eq!(Expr::from(spanned), expr);
+//!
+//! // function f({ a, b: [c] }) { return a; }
+//! let shorthand = Prop {
+//!     key: PropKey::Expr(Expr::ident_from("a")),
+//!     value: PropValue::None,
+//!     kind: PropKind::Init,
+//!     method: false,
+//!     computed: false,
+//!     short_hand: true,
+//!     is_static: false,
+//!     decorators: vec![],
+//! };
+//! let renamed = Prop {
+//!     key: PropKey::Expr(Expr::ident_from("b")),
+//!     value: PropValue::Pat(Pat::Array(vec![Some(ArrayPatPart::Pat(Pat::ident_from("c")))])),
+//!     short_hand: false,
+//!     ..shorthand.clone()
+//! };
+//! let func = Func::builder()
+//!     .id("f")
+//!     .param(FuncArg::Pat(Pat::Obj(vec![
+//!         ObjPatPart::Assign(shorthand),
+//!         ObjPatPart::Assign(renamed),
+//!     ])))
+//!     .body(vec![ProgramPart::Stmt(Stmt::Return(Some(Expr::ident_from("a"))))])
+//!     .build();
+//! let expr = Expr::Func(func);
+//! let spanned = Spanner::default().expr(expr.clone()).unwrap();
+//! assert_eq!(Expr::from(spanned), expr);
+//! ```
+use std::convert::TryFrom;
+use std::fmt;
+
+use super::decl::{Decl, VarDecl, VarDecls};
+use super::expr::{
+    ArrayExpr, ArrowFuncBody, ArrowFuncExpr, AssignExpr, AssignLeft, AwaitExpr, BinaryExpr,
+    Boolean, CallExpr, ConditionalExpr, Expr, ImportExpr, Lit, LogicalExpr, MemberExpr,
+    MemberIndexer, MetaProp, NewExpr, ObjExpr, ObjProp, OptionalChain, Prop, PropCtor, PropField,
+    PropGet, PropInit, PropInitKey, PropKey, PropMethod, PropSet, PropValue, RegEx, SpreadExpr,
+    StaticBlock, StringLit, TaggedTemplateExpr, TemplateElement, TemplateLit, UnaryExpr,
+    UpdateExpr, WrappedExpr, YieldExpr,
+};
+#[cfg(feature = "jsx")]
+use super::jsx::{
+    JsxAttr, JsxAttribute, JsxAttributeValue, JsxChild, JsxClosingElement, JsxClosingFragment,
+    JsxElement, JsxElementName, JsxExpressionContainer, JsxFragment, JsxOpeningElement,
+    JsxOpeningFragment, JsxSpread, JsxText,
+};
+use super::pat::{ArrayPat, ArrayPatPart, AssignPat, ObjPat, ObjPatPart, Pat, RestPat};
+use super::stmt::{
+    BlockStmt, CatchArg, CatchClause, DoWhileStmt, ElseStmt, FinallyClause, ForInStmt, ForOfStmt,
+    ForStmt, IfStmt, LabeledStmt, LoopInit, LoopLeft, Stmt, SwitchCase, SwitchStmt, TryStmt,
+    WhileStmt, WithStmt,
+};
+use super::tokens::{
+    AssignOp, BinaryOp, LogicalOp, QuasiQuote, Quote, SwitchCaseKeyword, UnaryOp, UpdateOp,
+};
+use super::{
+    Class, ClassBody, Decorator, Dir, Func, FuncArg, FuncBody, Ident, ListEntry, Position,
+    PrivateIdent, ProgramPart, Slice, SourceLocation, SuperClass, VarKind,
+};
+
+/// Builds spanned nodes from non-spanned ones, placing every
+/// token at a single position
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Spanner {
+    position: Position,
+}
+
+impl Default for Spanner {
+    fn default() -> Self {
+        Self::new(Position::new(0, 0))
+    }
+}
+
+/// A non-spanned node that could not be converted
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SpannerError {
+    node: &'static str,
+}
+
+impl SpannerError {
+    fn new(node: &'static str) -> Self {
+        Self { node }
+    }
+
+    /// The kind of node that could not be converted
+    pub fn node(&self) -> &'static str {
+        self.node
+    }
+}
+
+impl fmt::Display for SpannerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "unable to produce a spanned {} from a non-spanned one",
+            self.node
+        )
+    }
+}
+
+impl std::error::Error for SpannerError {}
+
+type Res<T> = Result<T, SpannerError>;
+
+impl<T> TryFrom<crate::Expr<T>> for Expr<T> {
+    type Error = SpannerError;
+
+    fn try_from(value: crate::Expr<T>) -> Res<Self> {
+        Spanner::default().expr(value)
+    }
+}
+
+impl Spanner {
+    pub fn new(position: Position) -> Self {
+        Self { position }
+    }
+
+    /// Convert a non-spanned expression, only an
+    /// `ArrowParamPlaceHolder` is not supported and will return an error
+    pub fn expr<T>(&self, expr: crate::Expr<T>) -> Res<Expr<T>> {
+        use crate::Expr as E;
+        Ok(match expr {
+            E::Array(elements) => Expr::Array(ArrayExpr {
+                open_bracket: self.token(),
+                elements: self
+                    .list(elements, |s, e| e.map(|e| s.expr(e)).transpose())?
+                    .into_iter()
+                    .map(|mut entry| {
+                        // a hole is only kept by the comma after it
+                        if entry.item.is_none() {
+                            entry.comma.get_or_insert_with(|| self.token());
+                        }
+                        entry
+                    })
+                    .collect(),
+                close_bracket: self.token(),
+            }),
+            E::Assign(inner) => Expr::Assign(AssignExpr {
+                operator: self.assign_op(inner.operator),
+                left: match inner.left {
+                    crate::expr::AssignLeft::Expr(e) => AssignLeft::Expr(self.boxed(*e)?),
+                    crate::expr::AssignLeft::Pat(p) => AssignLeft::Pat(self.pat(p)?),
+                },
+                right: self.boxed(*inner.right)?,
+            }),
+            E::Await(inner) => Expr::Await(Box::new(AwaitExpr {
+                keyword: self.token(),
+                expr: self.expr(*inner)?,
+            })),
+            E::Binary(inner) => Expr::Binary(BinaryExpr {
+                operator: self.binary_op(inner.operator),
+                left: self.boxed(*inner.left)?,
+                right: self.boxed(*inner.right)?,
+            }),
+            E::Call(inner) => Expr::Call(CallExpr {
+                callee: self.boxed(*inner.callee)?,
+                optional: inner.optional.then(|| self.token()),
+                open_paren: self.token(),
+                arguments: self.list(inner.arguments, Self::expr)?,
+                close_paren: self.token(),
+            }),
+            E::Conditional(inner) => Expr::Conditional(ConditionalExpr {
+                test: self.boxed(*inner.test)?,
+                question_mark: self.token(),
+                alternate: self.boxed(*inner.alternate)?,
+                colon: self.token(),
+                consequent: self.boxed(*inner.consequent)?,
+            }),
+            E::Ident(inner) => Expr::Ident(self.ident(inner)),
+            E::PrivateIdent(inner) => Expr::PrivateIdent(PrivateIdent {
+                hash: self.token(),
+                ident: self.slice(inner.name).into(),
+            }),
+            E::Import(inner) => Expr::Import(Box::new(ImportExpr {
+                keyword: self.token(),
+                open_paren: self.token(),
+                source: ListEntry {
+                    item: self.expr(*inner.source)?,
+                    comma: inner.options.is_some().then(|| self.token()),
+                },
+                options: inner
+                    .options
+                    .map(|o| self.expr(*o).map(ListEntry::no_comma))
+                    .transpose()?,
+                close_paren: self.token(),
+            })),
+            E::Lit(inner) => Expr::Lit(self.lit(inner)?),
+            E::Logical(inner) => Expr::Logical(LogicalExpr {
+                operator: self.logical_op(inner.operator),
+                left: self.boxed(*inner.left)?,
+                right: self.boxed(*inner.right)?,
+            }),
+            E::Member(inner) => Expr::Member(MemberExpr {
+                object: self.boxed(*inner.object)?,
+                property: self.boxed(*inner.property)?,
+                indexer: self.member_indexer(inner.indexer),
+            }),
+            E::MetaProp(inner) => Expr::MetaProp(MetaProp {
+                meta: self.ident(inner.meta),
+                dot: self.token(),
+                property: self.ident(inner.property),
+            }),
+            E::New(inner) => Expr::New(NewExpr {
+                keyword: self.token(),
+                callee: self.boxed(*inner.callee)?,
+                open_paren: Some(self.token()),
+                arguments: self.list(inner.arguments, Self::expr)?,
+                close_paren: Some(self.token()),
+            }),
+            E::Sequence(exprs) => Expr::Sequence(self.list(exprs.0, Self::expr)?.into()),
+            E::Spread(inner) => Expr::Spread(Box::new(SpreadExpr {
+                dots: self.token(),
+                expr: self.expr(*inner)?,
+            })),
+            E::Super => Expr::Super(self.token()),
+            E::TaggedTemplate(inner) => Expr::TaggedTemplate(TaggedTemplateExpr {
+                tag: self.boxed(*inner.tag)?,
+                quasi: self.template(inner.quasi)?,
+            }),
+            E::This => Expr::This(self.token()),
+            E::Unary(inner) => Expr::Unary(UnaryExpr {
+                operator: self.unary_op(inner.operator),
+                argument: self.shifted().boxed(*inner.argument)?,
+            }),
+            E::Update(inner) => {
+                let (op, arg) = if inner.prefix {
+                    (*self, self.shifted())
+                } else {
+                    (self.shifted(), *self)
+                };
+                Expr::Update(UpdateExpr {
+                    operator: op.update_op(inner.operator),
+                    argument: arg.boxed(*inner.argument)?,
+                })
+            }
+            E::Yield(inner) => Expr::Yield(YieldExpr {
+                keyword: self.token(),
+                argument: inner.argument.map(|a| self.boxed(*a)).transpose()?,
+                star: inner.delegate.then(|| self.token()),
+            }),
+            E::OptionalChain(inner) => Expr::OptionalChain(OptionalChain {
+                expr: self.boxed(*inner)?,
+                op: self.token(),
+            }),
+            E::Paren(inner) => Expr::Wrapped(Box::new(WrappedExpr {
+                open_paren: self.token(),
+                expr: self.expr(*inner)?,
+                close_paren: self.token(),
+            })),
+            E::ArrowFunc(inner) => Expr::ArrowFunc(ArrowFuncExpr {
+                keyword: inner.is_async.then(|| self.token()),
+                star: inner.generator.then(|| self.token()),
+                open_paren: Some(self.token()),
+                params: self.list(inner.params, Self::func_arg)?,
+                close_paren: Some(self.token()),
+                arrow: self.token(),
+                body: match inner.body {
+                    crate::expr::ArrowFuncBody::FuncBody(body) => {
+                        ArrowFuncBody::FuncBody(self.func_body(body)?)
+                    }
+                    crate::expr::ArrowFuncBody::Expr(expr) => {
+                        ArrowFuncBody::Expr(self.boxed(*expr)?)
+                    }
+                },
+            }),
+            E::ArrowParamPlaceHolder(_, _) => {
+                return Err(SpannerError::new("ArrowParamPlaceHolder"))
+            }
+            E::Class(inner) => Expr::Class(Box::new(self.class(inner)?)),
+            E::Func(inner) => Expr::Func(self.func(inner)?),
+            E::Obj(props) => Expr::Obj(ObjExpr {
+                open_brace: self.token(),
+                props: self.list(props, |s, prop| match prop {
+                    crate::expr::ObjProp::Prop(prop) => s.prop(prop).map(ObjProp::Prop),
+                    crate::expr::ObjProp::Spread(expr) => Ok(ObjProp::Spread(SpreadExpr {
+                        dots: s.token(),
+                        expr: s.expr(expr)?,
+                    })),
+                })?,
+                close_brace: self.token(),
+            }),
+            #[cfg(feature = "jsx")]
+            E::Jsx(inner) => Expr::Jsx(Box::new(self.jsx_element(inner)?)),
+            #[cfg(feature = "jsx")]
+            E::JsxFragment(inner) => Expr::JsxFragment(Box::new(self.jsx_fragment(inner)?)),
+        })
+    }
+
+    /// Convert a non-spanned program part, import and export
+    /// declarations are not supported and will return an error
+    pub fn program_part<T>(&self, part: crate::ProgramPart<T>) -> Res<ProgramPart<T>> {
+        Ok(match part {
+            crate::ProgramPart::Dir(dir) => ProgramPart::Dir(Dir {
+                expr: self.lit(dir.expr)?,
+                dir: dir.dir,
+                semi_colon: Some(self.token()),
+            }),
+            crate::ProgramPart::Decl(decl) => ProgramPart::Decl(self.decl(decl)?),
+            crate::ProgramPart::Stmt(stmt) => ProgramPart::Stmt(self.stmt(stmt)?),
+        })
+    }
+
+    /// Convert a non-spanned declaration, import and export
+    /// declarations are not supported and will return an error
+    pub fn decl<T>(&self, decl: crate::decl::Decl<T>) -> Res<Decl<T>> {
+        use crate::decl::Decl as D;
+        Ok(match decl {
+            D::Var(kind, decls) => Decl::Var {
+                decls: VarDecls {
+                    keyword: self.var_kind(kind),
+                    decls: self.list(decls, Self::var_decl)?,
+                },
+                semi_colon: Some(self.token()),
+            },
+            D::Func(inner) => Decl::Func(self.func(inner)?),
+            D::Class(inner) => Decl::Class(self.class(inner)?),
+            D::Import(_) => return Err(SpannerError::new("Import")),
+            D::Export(_) => return Err(SpannerError::new("Export")),
+        })
+    }
+
+    /// Convert a non-spanned statement
+    pub fn stmt<T>(&self, stmt: crate::stmt::Stmt<T>) -> Res<Stmt<T>> {
+        use crate::stmt::Stmt as S;
+        Ok(match stmt {
+            S::Expr(expr) => Stmt::Expr {
+                expr: self.expr(expr)?,
+                semi_colon: Some(self.token()),
+            },
+            S::Block(inner) => Stmt::Block(self.block(inner)?),
+            S::Empty => Stmt::Empty(self.token()),
+            S::Debugger => Stmt::Debugger {
+                keyword: self.token(),
+                semi_colon: Some(self.token()),
+            },
+            S::With(inner) => Stmt::With(WithStmt {
+                keyword: self.token(),
+                open_paren: self.token(),
+                object: self.expr(inner.object)?,
+                close_paren: self.token(),
+                body: self.boxed_stmt(*inner.body)?,
+            }),
+            S::Return(value) => Stmt::Return {
+                keyword: self.token(),
+                value: value.map(|v| self.expr(v)).transpose()?,
+                semi_colon: Some(self.token()),
+            },
+            S::Labeled(inner) => Stmt::Labeled(LabeledStmt {
+                label: self.ident(inner.label),
+                colon: self.token(),
+                body: self.boxed_stmt(*inner.body)?,
+            }),
+            S::Break(label) => Stmt::Break {
+                keyword: self.token(),
+                label: label.map(|l| self.ident(l)),
+                semi_colon: Some(self.token()),
+            },
+            S::Continue(label) => Stmt::Continue {
+                keyword: self.token(),
+                label: label.map(|l| self.ident(l)),
+                semi_colon: Some(self.token()),
+            },
+            S::If(inner) => Stmt::If(IfStmt {
+                keyword: self.token(),
+                open_paren: self.token(),
+                test: self.expr(inner.test)?,
+                close_paren: self.token(),
+                consequent: self.boxed_stmt(*inner.consequent)?,
+                alternate: inner
+                    .alternate
+                    .map(|alt| {
+                        Ok(Box::new(ElseStmt {
+                            keyword: self.token(),
+                            body: self.stmt(*alt)?,
+                        }))
+                    })
+                    .transpose()?,
+            }),
+            S::Switch(inner) => Stmt::Switch(SwitchStmt {
+                keyword: self.token(),
+                open_paren: self.token(),
+                discriminant: self.expr(inner.discriminant)?,
+                close_paren: self.token(),
+                open_brace: self.token(),
+                cases: inner
+                    .cases
+                    .into_iter()
+                    .map(|case| {
+                        Ok(SwitchCase {
+                            keyword: if case.test.is_some() {
+                                SwitchCaseKeyword::Case(self.token())
+                            } else {
+                                SwitchCaseKeyword::Default(self.token())
+                            },
+                            test: case.test.map(|t| self.expr(t)).transpose()?,
+                            colon: self.token(),
+                            consequent: self.parts(case.consequent)?,
+                        })
+                    })
+                    .collect::<Res<_>>()?,
+                close_brace: self.token(),
+            }),
+            S::Throw(expr) => Stmt::Throw {
+                keyword: self.token(),
+                expr: self.expr(expr)?,
+                semi_colon: Some(self.token()),
+            },
+            S::Try(inner) => Stmt::Try(TryStmt {
+                keyword: self.token(),
+                block: self.block(inner.block)?,
+                handler: inner
+                    .handler
+                    .map(|handler| {
+                        Ok(CatchClause {
+                            keyword: self.token(),
+                            param: handler
+                                .param
+                                .map(|param| {
+                                    Ok(CatchArg {
+                                        open_paren: self.token(),
+                                        param: self.pat(param)?,
+                                        close_paren: self.token(),
+                                    })
+                                })
+                                .transpose()?,
+                            body: self.block(handler.body)?,
+                        })
+                    })
+                    .transpose()?,
+                finalizer: inner
+                    .finalizer
+                    .map(|body| {
+                        Ok(FinallyClause {
+                            keyword: self.token(),
+                            body: self.block(body)?,
+                        })
+                    })
+                    .transpose()?,
+            }),
+            S::While(inner) => Stmt::While(WhileStmt {
+                keyword: self.token(),
+                open_paren: self.token(),
+                test: self.expr(inner.test)?,
+                close_paren: self.token(),
+                body: self.boxed_stmt(*inner.body)?,
+            }),
+            S::DoWhile(inner) => Stmt::DoWhile(DoWhileStmt {
+                keyword_do: self.token(),
+                body: self.boxed_stmt(*inner.body)?,
+                keyword_while: self.token(),
+                open_paren: self.token(),
+                test: self.expr(inner.test)?,
+                close_paren: self.token(),
+                semi_colon: Some(self.token()),
+            }),
+            S::For(inner) => Stmt::For(ForStmt {
+                keyword: self.token(),
+                open_paren: self.token(),
+                init: inner
+                    .init
+                    .map(|init| {
+                        Ok(match init {
+                            crate::stmt::LoopInit::Variable(kind, decls) => LoopInit::Variable(
+                                self.var_kind(kind),
+                                self.list(decls, Self::var_decl)?,
+                            ),
+                            crate::stmt::LoopInit::Expr(expr) => LoopInit::Expr(self.expr(expr)?),
+                        })
+                    })
+                    .transpose()?,
+                semi1: self.token(),
+                test: inner.test.map(|t| self.expr(t)).transpose()?,
+                semi2: self.token(),
+                update: inner.update.map(|u| self.expr(u)).transpose()?,
+                close_paren: self.token(),
+                body: self.boxed_stmt(*inner.body)?,
+            }),
+            S::ForIn(inner) => Stmt::ForIn(ForInStmt {
+                keyword_for: self.token(),
+                open_paren: self.token(),
+                left: self.loop_left(inner.left)?,
+                keyword_in: self.token(),
+                right: self.expr(inner.right)?,
+                close_paren: self.token(),
+                body: self.boxed_stmt(*inner.body)?,
+            }),
+            S::ForOf(inner) => Stmt::ForOf(ForOfStmt {
+                keyword_for: self.token(),
+                open_paren: self.token(),
+                left: self.loop_left(inner.left)?,
+                keyword_of: self.token(),
+                right: self.expr(inner.right)?,
+                close_paren: self.token(),
+                body: self.boxed_stmt(*inner.body)?,
+                is_await: inner.is_await,
+            }),
+            S::Var(decls) => Stmt::Var {
+                decls: VarDecls {
+                    keyword: self.var_kind(crate::VarKind::Var),
+                    decls: self.list(decls, Self::var_decl)?,
+                },
+                semi_colon: Some(self.token()),
+            },
+        })
+    }
+
+    /// Convert a non-spanned literal
+    pub fn lit<T>(&self, lit: crate::Lit<T>) -> Res<Lit<T>> {
+        use crate::Lit as L;
+        Ok(match lit {
+            L::Null => Lit::Null(self.token()),
+            L::String(inner) => Lit::String(self.string_lit(inner)),
+            L::Number(raw) => Lit::Number(self.slice(raw)),
+            L::BigInt(raw) => Lit::BigInt(self.slice(raw)),
+            L::Boolean(true) => Lit::Boolean(Boolean::True(self.token())),
+            L::Boolean(false) => Lit::Boolean(Boolean::False(self.token())),
+            L::RegEx(inner) => Lit::RegEx(RegEx {
+                open_slash: self.token(),
+                pattern: self.slice(inner.pattern),
+                close_slash: self.token(),
+                flags: inner.flags.map(|f| self.slice(f)),
+            }),
+            L::Template(inner) => Lit::Template(self.template(inner)?),
+        })
+    }
+
+    /// Convert a non-spanned pattern, a rest element is only supported
+    /// inside of an array or object pattern or a parameter list
+    pub fn pat<T>(&self, pat: crate::Pat<T>) -> Res<Pat<T>> {
+        use crate::pat::Pat as P;
+        Ok(match pat {
+            P::Ident(ident) => Pat::Ident(self.ident(ident)),
+            P::Obj(parts) => Pat::Obj(ObjPat {
+                open_brace: self.token(),
+                props: self.list(parts, |s, part| {
+                    Ok(match part {
+                        crate::pat::ObjPatPart::Assign(prop) => ObjPatPart::Assign(s.prop(prop)?),
+                        crate::pat::ObjPatPart::Rest(pat) => {
+                            ObjPatPart::Rest(Box::new(s.rest(*pat)?))
+                        }
+                    })
+                })?,
+                close_brace: self.token(),
+            }),
+            P::Array(elements) => Pat::Array(ArrayPat {
+                open_bracket: self.token(),
+                elements: self
+                    .list(elements, |s, part| {
+                        part.map(|part| {
+                            Ok(match part {
+                                crate::pat::ArrayPatPart::Pat(P::RestElement(pat)) => {
+                                    ArrayPatPart::Rest(s.rest(*pat)?)
+                                }
+                                crate::pat::ArrayPatPart::Pat(pat) => {
+                                    ArrayPatPart::Pat(s.pat(pat)?)
+                                }
+                                crate::pat::ArrayPatPart::Expr(expr) => {
+                                    ArrayPatPart::Expr(s.expr(expr)?)
+                                }
+                            })
+                        })
+                        .transpose()
+                    })?
+                    .into_iter()
+                    .map(|mut entry| {
+                        // a hole is only kept by the comma after it
+                        if entry.item.is_none() {
+                            entry.comma.get_or_insert_with(|| self.token());
+                        }
+                        entry
+                    })
+                    .collect(),
+                close_bracket: self.token(),
+            }),
+            P::RestElement(_) => return Err(SpannerError::new("RestElement")),
+            P::Assign(inner) => Pat::Assign(AssignPat {
+                left: Box::new(self.pat(*inner.left)?),
+                operator: AssignOp::Equal(self.token()),
+                right: self.boxed(*inner.right)?,
+            }),
+        })
+    }
+
+    fn rest<T>(&self, pat: crate::Pat<T>) -> Res<RestPat<T>> {
+        Ok(RestPat {
+            dots: self.token(),
+            pat: self.pat(pat)?,
+        })
+    }
+
+    fn func<T>(&self, func: crate::Func<T>) -> Res<Func<T>> {
+        Ok(Func {
+            keyword_async: func.is_async.then(|| self.token()),
+            keyword: self.token(),
+            star: func.generator.then(|| self.token()),
+            id: func.id.map(|id| self.ident(id)),
+            open_paren: self.token(),
+            params: self.list(func.params, Self::func_arg)?,
+            close_paren: self.token(),
+            body: self.func_body(func.body)?,
+        })
+    }
+
+    fn func_arg<T>(&self, arg: crate::FuncArg<T>) -> Res<FuncArg<T>> {
+        Ok(match arg {
+            crate::FuncArg::Expr(expr) => FuncArg::Expr(self.expr(expr)?),
+            crate::FuncArg::Pat(crate::Pat::RestElement(pat)) => {
+                FuncArg::Rest(Box::new(self.rest(*pat)?))
+            }
+            crate::FuncArg::Pat(pat) => FuncArg::Pat(self.pat(pat)?),
+        })
+    }
+
+    fn func_body<T>(&self, body: crate::FuncBody<T>) -> Res<FuncBody<T>> {
+        Ok(FuncBody {
+            open_brace: self.token(),
+            stmts: self.parts(body.0)?,
+            close_brace: self.token(),
+        })
+    }
+
+    fn class<T>(&self, class: crate::Class<T>) -> Res<Class<T>> {
+        Ok(Class {
+            decorators: self.decorators(class.decorators)?,
+            keyword: self.token(),
+            id: class.id.map(|id| self.ident(id)),
+            super_class: class
+                .super_class
+                .map(|expr| {
+                    Ok(SuperClass {
+                        keyword_extends: self.token(),
+                        expr: self.expr(*expr)?,
+                    })
+                })
+                .transpose()?,
+            body: ClassBody {
+                open_brace: self.token(),
+                props: class
+                    .body
+                    .0
+                    .into_iter()
+                    .map(|prop| self.prop(prop))
+                    .collect::<Res<_>>()?,
+                close_brace: self.token(),
+            },
+        })
+    }
+
+    fn decorators<T>(&self, decorators: Vec<crate::Decorator<T>>) -> Res<Vec<Decorator<T>>> {
+        decorators
+            .into_iter()
+            .map(|d| {
+                Ok(Decorator {
+                    at: self.token(),
+                    expr: self.expr(d.expr)?,
+                })
+            })
+            .collect()
+    }
+
+    fn prop<T>(&self, prop: crate::expr::Prop<T>) -> Res<Prop<T>> {
+        use crate::expr::PropValue as V;
+        use crate::PropKind as K;
+        let keyword_static = prop.is_static.then(|| self.token());
+        let decorators = self.decorators(prop.decorators)?;
+        Ok(match (prop.kind, prop.value) {
+            (K::StaticBlock, V::Block(body)) => Prop::StaticBlock(StaticBlock {
+                keyword_static: self.token(),
+                open_brace: self.token(),
+                stmts: self.parts(body.0)?,
+                close_brace: self.token(),
+            }),
+            (K::Method, V::Expr(crate::Expr::Func(func))) => Prop::Method(PropMethod {
+                decorators,
+                keyword_static,
+                keyword_async: func.is_async.then(|| self.token()),
+                id: self.prop_key(prop.key, prop.computed)?,
+                star: func.generator.then(|| self.token()),
+                open_paren: self.token(),
+                params: self.list(func.params, Self::func_arg)?,
+                close_paren: self.token(),
+                body: self.func_body(func.body)?,
+            }),
+            (K::Ctor, V::Expr(crate::Expr::Func(func))) => Prop::Ctor(PropCtor {
+                decorators,
+                keyword: self.prop_key(prop.key, prop.computed)?,
+                open_paren: self.token(),
+                params: self.list(func.params, Self::func_arg)?,
+                close_paren: self.token(),
+                body: self.func_body(func.body)?,
+            }),
+            (K::Get, V::Expr(crate::Expr::Func(func))) => Prop::Get(PropGet {
+                decorators,
+                keyword_static,
+                keyword_get: self.token(),
+                id: self.prop_key(prop.key, prop.computed)?,
+                open_paren: self.token(),
+                close_paren: self.token(),
+                body: self.func_body(func.body)?,
+            }),
+            (K::Set, V::Expr(crate::Expr::Func(mut func))) if func.params.len() == 1 => {
+                let arg = func.params.remove(0);
+                Prop::Set(PropSet {
+                    decorators,
+                    keyword_static,
+                    keyword_set: self.token(),
+                    id: self.prop_key(prop.key, prop.computed)?,
+                    open_paren: self.token(),
+                    arg: ListEntry::no_comma(self.func_arg(arg)?),
+                    close_paren: self.token(),
+                    body: self.func_body(func.body)?,
+                })
+            }
+            (K::Field, value @ (V::Expr(_) | V::None)) => {
+                let value = match value {
+                    V::Expr(expr) => Some(self.expr(expr)?),
+                    _ => None,
+                };
+                Prop::Field(PropField {
+                    decorators,
+                    keyword_static,
+                    key: self.prop_key(prop.key, prop.computed)?,
+                    eq: value.is_some().then(|| self.token()),
+                    value,
+                    semi_colon: Some(self.token()),
+                })
+            }
+            (K::Init, value) => Prop::Init(PropInit {
+                key: self.prop_key(prop.key, prop.computed)?,
+                colon: (!prop.short_hand).then(|| self.token()),
+                value: match value {
+                    V::Expr(expr) => Some(PropValue::Expr(self.expr(expr)?)),
+                    V::Pat(pat) => Some(PropValue::Pat(self.pat(pat)?)),
+                    V::None => None,
+                    V::Block(_) => return Err(SpannerError::new("Prop")),
+                },
+            }),
+            _ => return Err(SpannerError::new("Prop")),
+        })
+    }
+
+    fn prop_key<T>(&self, key: crate::expr::PropKey<T>, computed: bool) -> Res<PropInitKey<T>> {
+        use crate::expr::PropKey as K;
+        Ok(PropInitKey {
+            value: match key {
+                K::Lit(lit) => PropKey::Lit(self.lit(lit)?),
+                K::Expr(expr) => PropKey::Expr(self.expr(expr)?),
+                K::Pat(pat) => PropKey::Pat(self.pat(pat)?),
+                K::None => return Err(SpannerError::new("PropKey")),
+            },
+            brackets: computed.then(|| (self.token(), self.token())),
+        })
+    }
+
+    fn var_kind(&self, kind: crate::VarKind) -> VarKind {
+        match kind {
+            crate::VarKind::Var => VarKind::Var(Some(self.token())),
+            crate::VarKind::Let => VarKind::Let(self.token()),
+            crate::VarKind::Const => VarKind::Const(self.token()),
+            crate::VarKind::Using => VarKind::Using(self.token()),
+            crate::VarKind::AwaitUsing => VarKind::AwaitUsing(self.token(), self.token()),
+        }
+    }
+
+    fn var_decl<T>(&self, decl: crate::decl::VarDecl<T>) -> Res<VarDecl<T>> {
+        Ok(VarDecl {
+            id: self.pat(decl.id)?,
+            eq: decl.init.is_some().then(|| self.token()),
+            init: decl.init.map(|init| self.expr(init)).transpose()?,
+        })
+    }
+
+    fn loop_left<T>(&self, left: crate::stmt::LoopLeft<T>) -> Res<LoopLeft<T>> {
+        Ok(match left {
+            crate::stmt::LoopLeft::Expr(expr) => LoopLeft::Expr(self.expr(expr)?),
+            crate::stmt::LoopLeft::Variable(kind, decl) => {
+                LoopLeft::Variable(self.var_kind(kind), self.var_decl(decl)?)
+            }
+            crate::stmt::LoopLeft::Pat(pat) => LoopLeft::Pat(self.pat(pat)?),
+        })
+    }
+
+    fn block<T>(&self, block: crate::stmt::BlockStmt<T>) -> Res<BlockStmt<T>> {
+        Ok(BlockStmt {
+            open_brace: self.token(),
+            stmts: self.parts(block.0)?,
+            close_brace: self.token(),
+        })
+    }
+
+    fn boxed_stmt<T>(&self, stmt: crate::stmt::Stmt<T>) -> Res<Box<Stmt<T>>> {
+        self.stmt(stmt).map(Box::new)
+    }
+
+    fn parts<T>(&self, parts: Vec<crate::ProgramPart<T>>) -> Res<Vec<ProgramPart<T>>> {
+        parts
+            .into_iter()
+            .map(|part| self.program_part(part))
+            .collect()
+    }
+
+    #[cfg(feature = "jsx")]
+    fn jsx_element<T>(&self, element: crate::jsx::JsxElement<T>) -> Res<JsxElement<T>> {
+        Ok(JsxElement {
+            opening: JsxOpeningElement {
+                open_angle: self.token(),
+                name: self.jsx_name(element.opening.name),
+                attributes: element
+                    .opening
+                    .attributes
+                    .into_iter()
+                    .map(|attr| {
+                        Ok(match attr {
+                            crate::jsx::JsxAttr::Attribute(attr) => {
+                                JsxAttr::Attribute(JsxAttribute {
+                                    name: self.ident(attr.name),
+                                    eq: attr.value.is_some().then(|| self.token()),
+                                    value: attr
+                                        .value
+                                        .map(|value| self.jsx_attr_value(value))
+                                        .transpose()?,
+                                })
+                            }
+                            crate::jsx::JsxAttr::Spread(expr) => {
+                                JsxAttr::Spread(self.jsx_spread(expr)?)
+                            }
+                        })
+                    })
+                    .collect::<Res<_>>()?,
+                slash: element.opening.self_closing.then(|| self.token()),
+                close_angle: self.token(),
+            },
+            children: self.jsx_children(element.children)?,
+            closing: element.closing.map(|name| JsxClosingElement {
+                open_angle: self.token(),
+                slash: self.token(),
+                name: self.jsx_name(name),
+                close_angle: self.token(),
+            }),
+        })
+    }
+
+    #[cfg(feature = "jsx")]
+    fn jsx_fragment<T>(&self, fragment: crate::jsx::JsxFragment<T>) -> Res<JsxFragment<T>> {
+        Ok(JsxFragment {
+            opening: JsxOpeningFragment {
+                open_angle: self.token(),
+                close_angle: self.token(),
+            },
+            children: self.jsx_children(fragment.children)?,
+            closing: JsxClosingFragment {
+                open_angle: self.token(),
+                slash: self.token(),
+                close_angle: self.token(),
+            },
+        })
+    }
+
+    #[cfg(feature = "jsx")]
+    fn jsx_name<T>(&self, name: crate::jsx::JsxElementName<T>) -> JsxElementName<T> {
+        use crate::jsx::JsxElementName as N;
+        match name {
+            N::Ident(ident) => JsxElementName::Ident(self.ident(ident)),
+            N::Member(object, property) => JsxElementName::Member(
+                Box::new(self.jsx_name(*object)),
+                self.token(),
+                self.ident(property),
+            ),
+            N::Namespaced(namespace, name) => {
+                JsxElementName::Namespaced(self.ident(namespace), self.token(), self.ident(name))
+            }
+        }
+    }
+
+    #[cfg(feature = "jsx")]
+    fn jsx_attr_value<T>(
+        &self,
+        value: crate::jsx::JsxAttributeValue<T>,
+    ) -> Res<JsxAttributeValue<T>> {
+        use crate::jsx::JsxAttributeValue as V;
+        Ok(match value {
+            V::String(lit) => JsxAttributeValue::String(self.string_lit(lit)),
+            V::Expr(expr) => JsxAttributeValue::Expr(self.jsx_expr(expr)?),
+            V::Element(element) => JsxAttributeValue::Element(self.jsx_element(element)?),
+            V::Fragment(fragment) => JsxAttributeValue::Fragment(self.jsx_fragment(fragment)?),
+        })
+    }
+
+    #[cfg(feature = "jsx")]
+    fn jsx_children<T>(&self, children: Vec<crate::jsx::JsxChild<T>>) -> Res<Vec<JsxChild<T>>> {
+        use crate::jsx::JsxChild as C;
+        children
+            .into_iter()
+            .map(|child| {
+                Ok(match child {
+                    C::Text(text) => JsxChild::Text(JsxText {
+                        value: self.slice(text.value),
+                    }),
+                    C::Expr(expr) => JsxChild::Expr(self.jsx_expr(expr)?),
+                    C::Spread(expr) => JsxChild::Spread(self.jsx_spread(expr)?),
+                    C::Element(element) => JsxChild::Element(self.jsx_element(element)?),
+                    C::Fragment(fragment) => JsxChild::Fragment(self.jsx_fragment(fragment)?),
+                })
+            })
+            .collect()
+    }
+
+    #[cfg(feature = "jsx")]
+    fn jsx_expr<T>(
+        &self,
+        container: crate::jsx::JsxExpressionContainer<T>,
+    ) -> Res<JsxExpressionContainer<T>> {
+        Ok(JsxExpressionContainer {
+            open_brace: self.token(),
+            expr: container.expr.map(|e| self.expr(e)).transpose()?,
+            close_brace: self.token(),
+        })
+    }
+
+    #[cfg(feature = "jsx")]
+    fn jsx_spread<T>(&self, expr: crate::Expr<T>) -> Res<JsxSpread<T>> {
+        Ok(JsxSpread {
+            open_brace: self.token(),
+            dots: self.token(),
+            expr: self.expr(expr)?,
+            close_brace: self.token(),
+        })
+    }
+
+    fn string_lit<T>(&self, lit: crate::expr::StringLit<T>) -> StringLit<T> {
+        use crate::expr::StringLit as S;
+        match lit {
+            S::Double(content) => StringLit {
+                open_quote: Quote::Double(self.token()),
+                content: self.slice(content),
+                close_quote: Quote::Double(self.token()),
+            },
+            S::Single(content) => StringLit {
+                open_quote: Quote::Single(self.token()),
+                content: self.slice(content),
+                close_quote: Quote::Single(self.token()),
+            },
+        }
+    }
+
+    fn template<T>(&self, template: crate::expr::TemplateLit<T>) -> Res<TemplateLit<T>> {
+        Ok(TemplateLit {
+            quasis: template
+                .quasis
+                .into_iter()
+                .map(|q| TemplateElement {
+                    open_quote: self.quasi_quote(q.open_quote),
+                    content: self.slice(q.content),
+                    close_quote: self.quasi_quote(q.close_quote),
+                })
+                .collect(),
+            expressions: template
+                .expressions
+                .into_iter()
+                .map(|e| self.expr(e))
+                .collect::<Res<_>>()?,
+        })
+    }
+
+    fn quasi_quote(&self, quote: crate::expr::QuasiQuote) -> QuasiQuote {
+        match quote {
+            crate::expr::QuasiQuote::BackTick => QuasiQuote::BackTick(self.token()),
+            crate::expr::QuasiQuote::OpenBrace => QuasiQuote::OpenBrace(self.token()),
+            crate::expr::QuasiQuote::CloseBrace => QuasiQuote::CloseBrace(self.token()),
+        }
+    }
+
+    fn member_indexer(&self, indexer: crate::MemberIndexer) -> MemberIndexer {
+        match indexer {
+            crate::MemberIndexer::Period => MemberIndexer::Period(self.token()),
+            crate::MemberIndexer::Computed => MemberIndexer::Computed {
+                open_bracket: self.token(),
+                close_bracket: self.token(),
+            },
+            crate::MemberIndexer::Optional => MemberIndexer::Optional(self.token()),
+            crate::MemberIndexer::OptionalComputed => MemberIndexer::OptionalComputed {
+                optional: self.token(),
+                open_bracket: self.token(),
+                close_bracket: self.token(),
+            },
+        }
+    }
+
+    fn assign_op(&self, op: crate::AssignOp) -> AssignOp {
+        use crate::AssignOp as A;
+        match op {
+            A::Equal => AssignOp::Equal(self.token()),
+            A::PlusEqual => AssignOp::PlusEqual(self.token()),
+            A::MinusEqual => AssignOp::MinusEqual(self.token()),
+            A::TimesEqual => AssignOp::TimesEqual(self.token()),
+            A::DivEqual => AssignOp::DivEqual(self.token()),
+            A::ModEqual => AssignOp::ModEqual(self.token()),
+            A::LeftShiftEqual => AssignOp::LeftShiftEqual(self.token()),
+            A::RightShiftEqual => AssignOp::RightShiftEqual(self.token()),
+            A::UnsignedRightShiftEqual => AssignOp::UnsignedRightShiftEqual(self.token()),
+            A::OrEqual => AssignOp::OrEqual(self.token()),
+            A::XOrEqual => AssignOp::XOrEqual(self.token()),
+            A::AndEqual => AssignOp::AndEqual(self.token()),
+            A::PowerOfEqual => AssignOp::PowerOfEqual(self.token()),
+            A::DoubleAmpersandEqual => AssignOp::DoubleAmpersandEqual(self.token()),
+            A::DoublePipeEqual => AssignOp::DoublePipeEqual(self.token()),
+            A::DoubleQuestionmarkEqual => AssignOp::DoubleQuestionmarkEqual(self.token()),
+        }
+    }
+
+    fn binary_op(&self, op: crate::BinaryOp) -> BinaryOp {
+        use crate::BinaryOp as B;
+        match op {
+            B::Equal => BinaryOp::Equal(self.token()),
+            B::NotEqual => BinaryOp::NotEqual(self.token()),
+            B::StrictEqual => BinaryOp::StrictEqual(self.token()),
+            B::StrictNotEqual => BinaryOp::StrictNotEqual(self.token()),
+            B::LessThan => BinaryOp::LessThan(self.token()),
+            B::GreaterThan => BinaryOp::GreaterThan(self.token()),
+            B::LessThanEqual => BinaryOp::LessThanEqual(self.token()),
+            B::GreaterThanEqual => BinaryOp::GreaterThanEqual(self.token()),
+            B::LeftShift => BinaryOp::LeftShift(self.token()),
+            B::RightShift => BinaryOp::RightShift(self.token()),
+            B::UnsignedRightShift => BinaryOp::UnsignedRightShift(self.token()),
+            B::Plus => BinaryOp::Plus(self.token()),
+            B::Minus => BinaryOp::Minus(self.token()),
+            B::Times => BinaryOp::Times(self.token()),
+            B::Over => BinaryOp::Over(self.token()),
+            B::Mod => BinaryOp::Mod(self.token()),
+            B::Or => BinaryOp::Or(self.token()),
+            B::XOr => BinaryOp::XOr(self.token()),
+            B::And => BinaryOp::And(self.token()),
+            B::In => BinaryOp::In(self.token()),
+            B::InstanceOf => BinaryOp::InstanceOf(self.token()),
+            B::PowerOf => BinaryOp::PowerOf(self.token()),
+        }
+    }
+
+    fn logical_op(&self, op: crate::LogicalOp) -> LogicalOp {
+        match op {
+            crate::LogicalOp::Or => LogicalOp::Or(self.token()),
+            crate::LogicalOp::And => LogicalOp::And(self.token()),
+            crate::LogicalOp::NullishCoalescing => LogicalOp::NullishCoalescing(self.token()),
+        }
+    }
+
+    fn unary_op(&self, op: crate::UnaryOp) -> UnaryOp {
+        use crate::UnaryOp as U;
+        match op {
+            U::Minus => UnaryOp::Minus(self.token()),
+            U::Plus => UnaryOp::Plus(self.token()),
+            U::Not => UnaryOp::Not(self.token()),
+            U::Tilde => UnaryOp::Tilde(self.token()),
+            U::TypeOf => UnaryOp::TypeOf(self.token()),
+            U::Void => UnaryOp::Void(self.token()),
+            U::Delete => UnaryOp::Delete(self.token()),
+        }
+    }
+
+    fn update_op(&self, op: crate::UpdateOp) -> UpdateOp {
+        match op {
+            crate::UpdateOp::Increment => UpdateOp::Increment(self.token()),
+            crate::UpdateOp::Decrement => UpdateOp::Decrement(self.token()),
+        }
+    }
+
+    fn boxed<T>(&self, expr: crate::Expr<T>) -> Res<Box<Expr<T>>> {
+        self.expr(expr).map(Box::new)
+    }
+
+    fn list<I, O>(&self, items: Vec<I>, f: impl Fn(&Self, I) -> Res<O>) -> Res<Vec<ListEntry<O>>> {
+        let len = items.len();
+        items
+            .into_iter()
+            .enumerate()
+            .map(|(i, item)| {
+                Ok(ListEntry {
+                    item: f(self, item)?,
+                    comma: (i + 1 < len).then(|| self.token()),
+                })
+            })
+            .collect()
+    }
+
+    fn ident<T>(&self, ident: crate::Ident<T>) -> Ident<T> {
+        self.slice(ident.name).into()
+    }
+
+    fn slice<T>(&self, source: T) -> Slice<T> {
+        Slice {
+            source,
+            loc: SourceLocation {
+                start: self.position,
+                end: self.position,
+            },
+            range: None,
+        }
+    }
+
+    fn token<K: From<Position>>(&self) -> K {
+        K::from(self.position)
+    }
+
+    fn shifted(&self) -> Self {
+        Self::new(self.position + 1)
+    }
+}
@@ -405,6 +405,45 @@ impl<T> Node for Stmt<T> {
     }
 }
 
+impl<T> Stmt<T> {
+    /// Drop every span and token, producing the non-spanned tree
+    pub fn into_concrete(self) -> crate::stmt::Stmt<T> {
+        self.into()
+    }
+    /// The label a `break label;` or `continue label;` refers to
+    ///
+    /// This only reports the reference, checking that an enclosing
+    /// [`LabeledStmt`] actually declares it is left to the caller
+    pub fn referenced_label(&self) -> Option<&Ident<T>> {
+        match self {
+            Stmt::Break { label, .. } | Stmt::Continue { label, .. } => label.as_ref(),
+            _ => None,
+        }
+    }
+}
+
+impl<T> Stmt<T>
+where
+    T: PartialEq,
+{
+    /// Compare two statements by their structure alone, see
+    /// [`Expr::eq_ignore_span`] for the details of what is ignored
+    pub fn eq_ignore_span(&self, other: &Self) -> bool {
+        crate::stmt::Stmt::from(self) == crate::stmt::Stmt::from(other)
+    }
+}
+
+impl<T> Stmt<T>
+where
+    T: std::hash::Hash,
+{
+    /// Hash this statement by its structure alone, see
+    /// [`Expr::hash_ignore_span`]
+    pub fn hash_ignore_span<H: std::hash::Hasher>(&self, state: &mut H) {
+        std::hash::Hash::hash(&crate::stmt::Stmt::from(self), state)
+    }
+}
+
 /// A with statement, this puts one object at the top of
 /// the identifier search tree.
 /// > note: this cannot be used in a strict context
@@ -485,10 +524,7 @@ where
 
 impl<T> Node for LabeledStmt<T> {
     fn loc(&self) -> SourceLocation {
-        SourceLocation {
-            start: self.label.loc().start,
-            end: self.body.loc().end,
-        }
+        SourceLocation::between(&self.label, &*self.body)
     }
 }
 
@@ -710,6 +746,26 @@ impl<T> Node for BlockStmt<T> {
     }
 }
 
+impl<T> BlockStmt<T> {
+    /// A block around `parts` with both braces at line 0, column 0
+    /// ```
+    /// use resast::spanned::stmt::{BlockStmt, Stmt};
+    /// use resast::spanned::{Position, ProgramPart};
+    ///
+    /// let block = BlockStmt::from_parts(vec![ProgramPart::<&str>::Stmt(Stmt::Empty(
+    ///     Position::new(1, 2).into(),
+    /// ))]);
+    /// assert_eq!(block.stmts.len(), 1);
+    /// ```
+    pub fn from_parts(parts: impl IntoIterator<Item = ProgramPart<T>>) -> Self {
+        Self {
+            open_brace: Default::default(),
+            stmts: parts.into_iter().collect(),
+            close_brace: Default::default(),
+        }
+    }
+}
+
 /// A try/catch block
 /// ```js
 /// try {
@@ -760,7 +816,39 @@ impl<T> Node for TryStmt<T> {
     }
 }
 
-/// The error handling part of a `TryStmt`
+/// The error handling part of a `TryStmt`, `param` is `None` for
+/// an optional catch binding like `catch {}`
+/// ```
+/// use resast::codegen::WriteJs;
+/// use resast::spanned::pat::Pat;
+/// use resast::spanned::stmt::{BlockStmt, CatchArg, CatchClause, Stmt, TryStmt};
+/// use resast::spanned::Ident;
+///
+/// let try_catch = |param| {
+///     let stmt = Stmt::Try(TryStmt {
+///         keyword: Default::default(),
+///         block: BlockStmt::from_parts(vec![]),
+///         handler: Some(CatchClause {
+///             keyword: Default::default(),
+///             param,
+///             body: BlockStmt::from_parts(vec![]),
+///         }),
+///         finalizer: None,
+///     });
+///     resast::stmt::Stmt::from(stmt)
+/// };
+/// // try {} catch {}
+/// let bare = try_catch(None);
+/// assert!(matches!(&bare, resast::stmt::Stmt::Try(t) if t.handler.as_ref().unwrap().param.is_none()));
+/// assert_eq!(bare.to_js_string(), "try {} catch {}");
+/// // try {} catch (e) {}
+/// let bound = try_catch(Some(CatchArg {
+///     open_paren: Default::default(),
+///     param: Pat::Ident(Ident::new_from_source("e", 1, 14)),
+///     close_paren: Default::default(),
+/// }));
+/// assert_eq!(bound.to_js_string(), "try {} catch (e) {}");
+/// ```
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct CatchClause<T> {
@@ -1051,6 +1139,19 @@ impl<T> Node for LoopInit<T> {
     }
 }
 
+impl<T> LoopInit<T> {
+    /// If this loop head declares its own bindings
+    pub fn is_var_decl(&self) -> bool {
+        matches!(self, LoopInit::Variable(_, _))
+    }
+    pub fn var_kind(&self) -> Option<&VarKind> {
+        match self {
+            LoopInit::Variable(kind, _) => Some(kind),
+            LoopInit::Expr(_) => None,
+        }
+    }
+}
+
 /// A for in statement, this kind of for statement
 /// will extract each key from an indexable thing
 /// ```js
@@ -1189,3 +1290,16 @@ impl<T> Node for LoopLeft<T> {
         }
     }
 }
+
+impl<T> LoopLeft<T> {
+    /// If this loop head declares its own binding
+    pub fn is_var_decl(&self) -> bool {
+        matches!(self, LoopLeft::Variable(_, _))
+    }
+    pub fn var_kind(&self) -> Option<&VarKind> {
+        match self {
+            LoopLeft::Variable(kind, _) => Some(kind),
+            LoopLeft::Expr(_) | LoopLeft::Pat(_) => None,
+        }
+    }
+}
@@ -1,6 +1,6 @@
 //! This modules contains a collection of discrete tokens
 
-use crate::spanned::{Node, Position, SourceLocation};
+use crate::spanned::{Node, Position, SourceLocation, Spanned};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
@@ -36,10 +36,12 @@ macro_rules! impl_token {
 }
 
 /// Defines a token struct and implements the Token trait for the newly defined type.
+/// A defaulted token is placed at line 0, column 0 which is handy when
+/// building spanned nodes by hand.
 /// The output of this macro produces something like the following.
 /// ```rust
 /// # use resast::spanned::{Position, tokens::Token};
-/// #[derive(Debug, Clone, Copy, PartialEq)]
+/// #[derive(Debug, Clone, Copy, PartialEq, Default)]
 /// #[doc = "as"]
 /// pub struct As(Position);
 ///
@@ -72,7 +74,7 @@ macro_rules! impl_token {
 /// ```
 macro_rules! define_token {
     ($name:ident, $s:expr) => {
-        #[derive(Debug, Clone, Copy, PartialEq)]
+        #[derive(Debug, Clone, Copy, PartialEq, Default)]
         #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
         #[doc = $s]
         pub struct $name(Position);
@@ -98,8 +100,18 @@ where
     }
 }
 
+impl<T, K> Spanned<T> for K
+where
+    K: Token,
+{
+    fn source(&self) -> Option<&T> {
+        None
+    }
+}
+
 // Keywords
 define_token!(As, "as");
+define_token!(Assert, "assert");
 define_token!(Async, "async");
 define_token!(Await, "await");
 define_token!(Break, "break");
@@ -139,6 +151,7 @@ define_token!(Throw, "throw");
 define_token!(True, "true");
 define_token!(Try, "try");
 define_token!(TypeOf, "typeof");
+define_token!(Using, "using");
 define_token!(Var, "var");
 define_token!(Void, "void");
 define_token!(While, "while");
@@ -150,6 +163,7 @@ define_token!(Ampersand, "&");
 define_token!(AmpersandEqual, "&=");
 define_token!(Asterisk, "*");
 define_token!(AsteriskEqual, "*=");
+define_token!(At, "@");
 define_token!(BackTick, "`");
 define_token!(Bang, "!");
 define_token!(BangEqual, "!=");
@@ -183,6 +197,8 @@ define_token!(ForwardSlash, "/");
 define_token!(ForwardSlashEqual, "/=");
 define_token!(GreaterThan, ">");
 define_token!(GreaterThanEqual, ">=");
+define_token!(Hash, "#");
+define_token!(HashBang, "#!");
 define_token!(LessThan, "<");
 define_token!(LessThanEqual, "<=");
 define_token!(Minus, "-");
@@ -466,3 +482,33 @@ impl Token for SwitchCaseKeyword {
         }
     }
 }
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum ImportAttributesKeyword {
+    Assert(Assert),
+    With(With),
+}
+
+impl Token for ImportAttributesKeyword {
+    fn as_str(&self) -> &str {
+        match self {
+            ImportAttributesKeyword::Assert(inner) => inner.as_str(),
+            ImportAttributesKeyword::With(inner) => inner.as_str(),
+        }
+    }
+
+    fn start(&self) -> Position {
+        match self {
+            ImportAttributesKeyword::Assert(inner) => inner.start(),
+            ImportAttributesKeyword::With(inner) => inner.start(),
+        }
+    }
+
+    fn end(&self) -> Position {
+        match self {
+            ImportAttributesKeyword::Assert(inner) => inner.end(),
+            ImportAttributesKeyword::With(inner) => inner.end(),
+        }
+    }
+}
@@ -1,14 +1,14 @@
 use crate::decl::VarDecl;
 use crate::expr::Expr;
 use crate::pat::Pat;
+use crate::{AsBorrowed, IntoAllocated, MapSource, VarKind};
 use crate::{Ident, ProgramPart};
-use crate::{IntoAllocated, VarKind};
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 /// A slightly more granular part of an es program than ProgramPart
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub enum Stmt<T> {
     /// Any expression
@@ -183,26 +183,81 @@ where
     type Allocated = Stmt<String>;
 
     fn into_allocated(self) -> Self::Allocated {
+        self.map_source_with(&mut |source: T| source.to_string())
+    }
+}
+
+impl<'a> AsBorrowed<'a> for Stmt<String> {
+    type Borrowed = Stmt<&'a str>;
+
+    fn as_borrowed(&'a self) -> Self::Borrowed {
         match self {
-            Stmt::Expr(inner) => Stmt::Expr(inner.into_allocated()),
-            Stmt::Block(inner) => Stmt::Block(inner.into_allocated()),
+            Stmt::Expr(inner) => Stmt::Expr(inner.as_borrowed()),
+            Stmt::Block(inner) => Stmt::Block(inner.as_borrowed()),
             Stmt::Empty => Stmt::Empty,
             Stmt::Debugger => Stmt::Debugger,
-            Stmt::With(inner) => Stmt::With(inner.into_allocated()),
-            Stmt::Return(inner) => Stmt::Return(inner.map(IntoAllocated::into_allocated)),
-            Stmt::Labeled(inner) => Stmt::Labeled(inner.into_allocated()),
-            Stmt::Break(inner) => Stmt::Break(inner.map(IntoAllocated::into_allocated)),
-            Stmt::Continue(inner) => Stmt::Continue(inner.map(IntoAllocated::into_allocated)),
-            Stmt::If(inner) => Stmt::If(inner.into_allocated()),
-            Stmt::Switch(inner) => Stmt::Switch(inner.into_allocated()),
-            Stmt::Throw(inner) => Stmt::Throw(inner.into_allocated()),
-            Stmt::Try(inner) => Stmt::Try(inner.into_allocated()),
-            Stmt::While(inner) => Stmt::While(inner.into_allocated()),
-            Stmt::DoWhile(inner) => Stmt::DoWhile(inner.into_allocated()),
-            Stmt::For(inner) => Stmt::For(inner.into_allocated()),
-            Stmt::ForIn(inner) => Stmt::ForIn(inner.into_allocated()),
-            Stmt::ForOf(inner) => Stmt::ForOf(inner.into_allocated()),
-            Stmt::Var(inner) => Stmt::Var(inner.into_iter().map(|v| v.into_allocated()).collect()),
+            Stmt::With(inner) => Stmt::With(inner.as_borrowed()),
+            Stmt::Return(inner) => Stmt::Return(inner.as_borrowed()),
+            Stmt::Labeled(inner) => Stmt::Labeled(inner.as_borrowed()),
+            Stmt::Break(inner) => Stmt::Break(inner.as_borrowed()),
+            Stmt::Continue(inner) => Stmt::Continue(inner.as_borrowed()),
+            Stmt::If(inner) => Stmt::If(inner.as_borrowed()),
+            Stmt::Switch(inner) => Stmt::Switch(inner.as_borrowed()),
+            Stmt::Throw(inner) => Stmt::Throw(inner.as_borrowed()),
+            Stmt::Try(inner) => Stmt::Try(inner.as_borrowed()),
+            Stmt::While(inner) => Stmt::While(inner.as_borrowed()),
+            Stmt::DoWhile(inner) => Stmt::DoWhile(inner.as_borrowed()),
+            Stmt::For(inner) => Stmt::For(inner.as_borrowed()),
+            Stmt::ForIn(inner) => Stmt::ForIn(inner.as_borrowed()),
+            Stmt::ForOf(inner) => Stmt::ForOf(inner.as_borrowed()),
+            Stmt::Var(inner) => Stmt::Var(inner.iter().map(|v| v.as_borrowed()).collect()),
+        }
+    }
+}
+
+impl<T, U> MapSource<T, U> for Stmt<T> {
+    type Output = Stmt<U>;
+
+    fn map_source_with(self, f: &mut impl FnMut(T) -> U) -> Self::Output {
+        match self {
+            Stmt::Expr(inner) => Stmt::Expr(inner.map_source_with(f)),
+            Stmt::Block(inner) => Stmt::Block(inner.map_source_with(f)),
+            Stmt::Empty => Stmt::Empty,
+            Stmt::Debugger => Stmt::Debugger,
+            Stmt::With(inner) => Stmt::With(inner.map_source_with(f)),
+            Stmt::Return(inner) => Stmt::Return(inner.map(|e| e.map_source_with(f))),
+            Stmt::Labeled(inner) => Stmt::Labeled(inner.map_source_with(f)),
+            Stmt::Break(inner) => Stmt::Break(inner.map(|e| e.map_source_with(f))),
+            Stmt::Continue(inner) => Stmt::Continue(inner.map(|e| e.map_source_with(f))),
+            Stmt::If(inner) => Stmt::If(inner.map_source_with(f)),
+            Stmt::Switch(inner) => Stmt::Switch(inner.map_source_with(f)),
+            Stmt::Throw(inner) => Stmt::Throw(inner.map_source_with(f)),
+            Stmt::Try(inner) => Stmt::Try(inner.map_source_with(f)),
+            Stmt::While(inner) => Stmt::While(inner.map_source_with(f)),
+            Stmt::DoWhile(inner) => Stmt::DoWhile(inner.map_source_with(f)),
+            Stmt::For(inner) => Stmt::For(inner.map_source_with(f)),
+            Stmt::ForIn(inner) => Stmt::ForIn(inner.map_source_with(f)),
+            Stmt::ForOf(inner) => Stmt::ForOf(inner.map_source_with(f)),
+            Stmt::Var(inner) => {
+                Stmt::Var(inner.into_iter().map(|v| v.map_source_with(f)).collect())
+            }
+        }
+    }
+}
+
+impl<T> Stmt<T> {
+    /// Pass every source value through `f`, see [`Program::map_source`](crate::Program::map_source)
+    pub fn map_source<U>(self, mut f: impl FnMut(T) -> U) -> Stmt<U> {
+        self.map_source_with(&mut f)
+    }
+    /// The label a `break label;` or `continue label;` refers to
+    ///
+    /// This only reports the reference, checking that an enclosing
+    /// [`LabeledStmt`] actually declares it is left to the caller
+    pub fn referenced_label(&self) -> Option<&Ident<T>> {
+        match self {
+            Stmt::Break(label) | Stmt::Continue(label) => label.as_ref(),
+            _ => None,
         }
     }
 }
@@ -220,7 +275,7 @@ where
 /// }
 /// //rand !== 0
 /// ```
-#[derive(PartialEq, Debug, Clone)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct WithStmt<T> {
     pub object: Expr<T>,
@@ -234,9 +289,28 @@ where
     type Allocated = WithStmt<String>;
 
     fn into_allocated(self) -> Self::Allocated {
+        self.map_source_with(&mut |source: T| source.to_string())
+    }
+}
+
+impl<'a> AsBorrowed<'a> for WithStmt<String> {
+    type Borrowed = WithStmt<&'a str>;
+
+    fn as_borrowed(&'a self) -> Self::Borrowed {
+        WithStmt {
+            object: self.object.as_borrowed(),
+            body: self.body.as_borrowed(),
+        }
+    }
+}
+
+impl<T, U> MapSource<T, U> for WithStmt<T> {
+    type Output = WithStmt<U>;
+
+    fn map_source_with(self, f: &mut impl FnMut(T) -> U) -> Self::Output {
         WithStmt {
-            object: self.object.into_allocated(),
-            body: self.body.into_allocated(),
+            object: self.object.map_source_with(f),
+            body: self.body.map_source_with(f),
         }
     }
 }
@@ -250,7 +324,7 @@ where
 ///     break;
 /// }
 /// ```
-#[derive(PartialEq, Debug, Clone)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct LabeledStmt<T> {
     pub label: Ident<T>,
@@ -264,9 +338,28 @@ where
     type Allocated = LabeledStmt<String>;
 
     fn into_allocated(self) -> Self::Allocated {
+        self.map_source_with(&mut |source: T| source.to_string())
+    }
+}
+
+impl<'a> AsBorrowed<'a> for LabeledStmt<String> {
+    type Borrowed = LabeledStmt<&'a str>;
+
+    fn as_borrowed(&'a self) -> Self::Borrowed {
         LabeledStmt {
-            label: self.label.into_allocated(),
-            body: self.body.into_allocated(),
+            label: self.label.as_borrowed(),
+            body: self.body.as_borrowed(),
+        }
+    }
+}
+
+impl<T, U> MapSource<T, U> for LabeledStmt<T> {
+    type Output = LabeledStmt<U>;
+
+    fn map_source_with(self, f: &mut impl FnMut(T) -> U) -> Self::Output {
+        LabeledStmt {
+            label: self.label.map_source_with(f),
+            body: self.body.map_source_with(f),
         }
     }
 }
@@ -279,7 +372,7 @@ where
 ///     console.log('Never true');
 /// }
 /// ```
-#[derive(PartialEq, Debug, Clone)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct IfStmt<T> {
     pub test: Expr<T>,
@@ -294,10 +387,30 @@ where
     type Allocated = IfStmt<String>;
 
     fn into_allocated(self) -> Self::Allocated {
+        self.map_source_with(&mut |source: T| source.to_string())
+    }
+}
+
+impl<'a> AsBorrowed<'a> for IfStmt<String> {
+    type Borrowed = IfStmt<&'a str>;
+
+    fn as_borrowed(&'a self) -> Self::Borrowed {
+        IfStmt {
+            test: self.test.as_borrowed(),
+            consequent: self.consequent.as_borrowed(),
+            alternate: self.alternate.as_borrowed(),
+        }
+    }
+}
+
+impl<T, U> MapSource<T, U> for IfStmt<T> {
+    type Output = IfStmt<U>;
+
+    fn map_source_with(self, f: &mut impl FnMut(T) -> U) -> Self::Output {
         IfStmt {
-            test: self.test.into_allocated(),
-            consequent: self.consequent.into_allocated(),
-            alternate: self.alternate.map(IntoAllocated::into_allocated),
+            test: self.test.map_source_with(f),
+            consequent: self.consequent.map_source_with(f),
+            alternate: self.alternate.map_source_with(f),
         }
     }
 }
@@ -316,7 +429,7 @@ where
 ///         return true;
 /// }
 /// ```
-#[derive(PartialEq, Debug, Clone)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct SwitchStmt<T> {
     pub discriminant: Expr<T>,
@@ -330,15 +443,38 @@ where
     type Allocated = SwitchStmt<String>;
 
     fn into_allocated(self) -> Self::Allocated {
+        self.map_source_with(&mut |source: T| source.to_string())
+    }
+}
+
+impl<'a> AsBorrowed<'a> for SwitchStmt<String> {
+    type Borrowed = SwitchStmt<&'a str>;
+
+    fn as_borrowed(&'a self) -> Self::Borrowed {
         SwitchStmt {
-            discriminant: self.discriminant.into_allocated(),
-            cases: self.cases.into_iter().map(|c| c.into_allocated()).collect(),
+            discriminant: self.discriminant.as_borrowed(),
+            cases: self.cases.iter().map(|c| c.as_borrowed()).collect(),
+        }
+    }
+}
+
+impl<T, U> MapSource<T, U> for SwitchStmt<T> {
+    type Output = SwitchStmt<U>;
+
+    fn map_source_with(self, f: &mut impl FnMut(T) -> U) -> Self::Output {
+        SwitchStmt {
+            discriminant: self.discriminant.map_source_with(f),
+            cases: self
+                .cases
+                .into_iter()
+                .map(|c| c.map_source_with(f))
+                .collect(),
         }
     }
 }
 
 /// A single case part of a switch statement
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct SwitchCase<T> {
     pub test: Option<Expr<T>>,
@@ -352,19 +488,38 @@ where
     type Allocated = SwitchCase<String>;
 
     fn into_allocated(self) -> Self::Allocated {
+        self.map_source_with(&mut |source: T| source.to_string())
+    }
+}
+
+impl<'a> AsBorrowed<'a> for SwitchCase<String> {
+    type Borrowed = SwitchCase<&'a str>;
+
+    fn as_borrowed(&'a self) -> Self::Borrowed {
+        SwitchCase {
+            test: self.test.as_borrowed(),
+            consequent: self.consequent.iter().map(|c| c.as_borrowed()).collect(),
+        }
+    }
+}
+
+impl<T, U> MapSource<T, U> for SwitchCase<T> {
+    type Output = SwitchCase<U>;
+
+    fn map_source_with(self, f: &mut impl FnMut(T) -> U) -> Self::Output {
         SwitchCase {
-            test: self.test.map(IntoAllocated::into_allocated),
+            test: self.test.map_source_with(f),
             consequent: self
                 .consequent
                 .into_iter()
-                .map(|c| c.into_allocated())
+                .map(|c| c.map_source_with(f))
                 .collect(),
         }
     }
 }
 
 /// A collection of program parts wrapped in curly braces
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct BlockStmt<T>(pub Vec<ProgramPart<T>>);
 
@@ -375,7 +530,37 @@ where
     type Allocated = BlockStmt<String>;
 
     fn into_allocated(self) -> Self::Allocated {
-        BlockStmt(self.0.into_iter().map(|s| s.into_allocated()).collect())
+        self.map_source_with(&mut |source: T| source.to_string())
+    }
+}
+
+impl<'a> AsBorrowed<'a> for BlockStmt<String> {
+    type Borrowed = BlockStmt<&'a str>;
+
+    fn as_borrowed(&'a self) -> Self::Borrowed {
+        BlockStmt(self.0.iter().map(|s| s.as_borrowed()).collect())
+    }
+}
+
+impl<T, U> MapSource<T, U> for BlockStmt<T> {
+    type Output = BlockStmt<U>;
+
+    fn map_source_with(self, f: &mut impl FnMut(T) -> U) -> Self::Output {
+        BlockStmt(self.0.into_iter().map(|s| s.map_source_with(f)).collect())
+    }
+}
+
+/// ```
+/// use resast::prelude::*;
+///
+/// let block: BlockStmt<&str> = std::iter::repeat_with(|| ProgramPart::Stmt(Stmt::Empty))
+///     .take(3)
+///     .collect();
+/// assert_eq!(block.0.len(), 3);
+/// ```
+impl<T> FromIterator<ProgramPart<T>> for BlockStmt<T> {
+    fn from_iter<I: IntoIterator<Item = ProgramPart<T>>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
     }
 }
 
@@ -389,7 +574,7 @@ where
 ///
 /// }
 /// ```
-#[derive(PartialEq, Debug, Clone)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct TryStmt<T> {
     pub block: BlockStmt<T>,
@@ -404,16 +589,37 @@ where
     type Allocated = TryStmt<String>;
 
     fn into_allocated(self) -> Self::Allocated {
+        self.map_source_with(&mut |source: T| source.to_string())
+    }
+}
+
+impl<'a> AsBorrowed<'a> for TryStmt<String> {
+    type Borrowed = TryStmt<&'a str>;
+
+    fn as_borrowed(&'a self) -> Self::Borrowed {
+        TryStmt {
+            block: self.block.as_borrowed(),
+            handler: self.handler.as_borrowed(),
+            finalizer: self.finalizer.as_borrowed(),
+        }
+    }
+}
+
+impl<T, U> MapSource<T, U> for TryStmt<T> {
+    type Output = TryStmt<U>;
+
+    fn map_source_with(self, f: &mut impl FnMut(T) -> U) -> Self::Output {
         TryStmt {
-            block: self.block.into_allocated(),
-            handler: self.handler.map(|h| h.into_allocated()),
-            finalizer: self.finalizer.map(|f| f.into_allocated()),
+            block: self.block.map_source_with(f),
+            handler: self.handler.map_source_with(f),
+            finalizer: self.finalizer.map_source_with(f),
         }
     }
 }
 
-/// The error handling part of a `TryStmt`
-#[derive(Debug, Clone, PartialEq)]
+/// The error handling part of a `TryStmt`, `param` is `None` for
+/// an optional catch binding like `catch {}`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct CatchClause<T> {
     pub param: Option<Pat<T>>,
@@ -427,9 +633,28 @@ where
     type Allocated = CatchClause<String>;
 
     fn into_allocated(self) -> Self::Allocated {
+        self.map_source_with(&mut |source: T| source.to_string())
+    }
+}
+
+impl<'a> AsBorrowed<'a> for CatchClause<String> {
+    type Borrowed = CatchClause<&'a str>;
+
+    fn as_borrowed(&'a self) -> Self::Borrowed {
+        CatchClause {
+            param: self.param.as_borrowed(),
+            body: self.body.as_borrowed(),
+        }
+    }
+}
+
+impl<T, U> MapSource<T, U> for CatchClause<T> {
+    type Output = CatchClause<U>;
+
+    fn map_source_with(self, f: &mut impl FnMut(T) -> U) -> Self::Output {
         CatchClause {
-            param: self.param.map(IntoAllocated::into_allocated),
-            body: self.body.into_allocated(),
+            param: self.param.map_source_with(f),
+            body: self.body.map_source_with(f),
         }
     }
 }
@@ -448,7 +673,7 @@ where
 ///     }
 /// }
 /// ```
-#[derive(PartialEq, Debug, Clone)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct WhileStmt<T> {
     pub test: Expr<T>,
@@ -462,9 +687,28 @@ where
     type Allocated = WhileStmt<String>;
 
     fn into_allocated(self) -> Self::Allocated {
+        self.map_source_with(&mut |source: T| source.to_string())
+    }
+}
+
+impl<'a> AsBorrowed<'a> for WhileStmt<String> {
+    type Borrowed = WhileStmt<&'a str>;
+
+    fn as_borrowed(&'a self) -> Self::Borrowed {
         WhileStmt {
-            test: self.test.into_allocated(),
-            body: self.body.into_allocated(),
+            test: self.test.as_borrowed(),
+            body: self.body.as_borrowed(),
+        }
+    }
+}
+
+impl<T, U> MapSource<T, U> for WhileStmt<T> {
+    type Output = WhileStmt<U>;
+
+    fn map_source_with(self, f: &mut impl FnMut(T) -> U) -> Self::Output {
+        WhileStmt {
+            test: self.test.map_source_with(f),
+            body: self.body.map_source_with(f),
         }
     }
 }
@@ -475,7 +719,7 @@ where
 ///     console.log(Tt least once')
 /// } while (Math.floor(Math.random() * 100) < 75)
 /// ```
-#[derive(PartialEq, Debug, Clone)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct DoWhileStmt<T> {
     pub test: Expr<T>,
@@ -489,9 +733,28 @@ where
     type Allocated = DoWhileStmt<String>;
 
     fn into_allocated(self) -> Self::Allocated {
+        self.map_source_with(&mut |source: T| source.to_string())
+    }
+}
+
+impl<'a> AsBorrowed<'a> for DoWhileStmt<String> {
+    type Borrowed = DoWhileStmt<&'a str>;
+
+    fn as_borrowed(&'a self) -> Self::Borrowed {
         DoWhileStmt {
-            test: self.test.into_allocated(),
-            body: self.body.into_allocated(),
+            test: self.test.as_borrowed(),
+            body: self.body.as_borrowed(),
+        }
+    }
+}
+
+impl<T, U> MapSource<T, U> for DoWhileStmt<T> {
+    type Output = DoWhileStmt<U>;
+
+    fn map_source_with(self, f: &mut impl FnMut(T) -> U) -> Self::Output {
+        DoWhileStmt {
+            test: self.test.map_source_with(f),
+            body: self.body.map_source_with(f),
         }
     }
 }
@@ -503,7 +766,7 @@ where
 ///     console.log('forever!');
 /// }
 /// ```
-#[derive(PartialEq, Debug, Clone)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct ForStmt<T> {
     pub init: Option<LoopInit<T>>,
@@ -519,11 +782,32 @@ where
     type Allocated = ForStmt<String>;
 
     fn into_allocated(self) -> Self::Allocated {
+        self.map_source_with(&mut |source: T| source.to_string())
+    }
+}
+
+impl<'a> AsBorrowed<'a> for ForStmt<String> {
+    type Borrowed = ForStmt<&'a str>;
+
+    fn as_borrowed(&'a self) -> Self::Borrowed {
+        ForStmt {
+            init: self.init.as_borrowed(),
+            test: self.test.as_borrowed(),
+            update: self.update.as_borrowed(),
+            body: self.body.as_borrowed(),
+        }
+    }
+}
+
+impl<T, U> MapSource<T, U> for ForStmt<T> {
+    type Output = ForStmt<U>;
+
+    fn map_source_with(self, f: &mut impl FnMut(T) -> U) -> Self::Output {
         ForStmt {
-            init: self.init.map(|i| i.into_allocated()),
-            test: self.test.map(|t| t.into_allocated()),
-            update: self.update.map(|u| u.into_allocated()),
-            body: self.body.into_allocated(),
+            init: self.init.map_source_with(f),
+            test: self.test.map_source_with(f),
+            update: self.update.map_source_with(f),
+            body: self.body.map_source_with(f),
         }
     }
 }
@@ -532,7 +816,7 @@ where
 /// ```js
 ///  //  vvvvvvvvv
 /// for (var i = 0;i < 100; i++)
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub enum LoopInit<T> {
     Variable(VarKind, Vec<VarDecl<T>>),
@@ -546,11 +830,67 @@ where
     type Allocated = LoopInit<String>;
 
     fn into_allocated(self) -> Self::Allocated {
+        self.map_source_with(&mut |source: T| source.to_string())
+    }
+}
+
+impl<'a> AsBorrowed<'a> for LoopInit<String> {
+    type Borrowed = LoopInit<&'a str>;
+
+    fn as_borrowed(&'a self) -> Self::Borrowed {
+        match self {
+            LoopInit::Variable(k, v) => {
+                LoopInit::Variable(*k, v.iter().map(|v| v.as_borrowed()).collect())
+            }
+            LoopInit::Expr(inner) => LoopInit::Expr(inner.as_borrowed()),
+        }
+    }
+}
+
+impl<T, U> MapSource<T, U> for LoopInit<T> {
+    type Output = LoopInit<U>;
+
+    fn map_source_with(self, f: &mut impl FnMut(T) -> U) -> Self::Output {
         match self {
             LoopInit::Variable(k, v) => {
-                LoopInit::Variable(k, v.into_iter().map(|v| v.into_allocated()).collect())
+                LoopInit::Variable(k, v.into_iter().map(|v| v.map_source_with(f)).collect())
             }
-            LoopInit::Expr(inner) => LoopInit::Expr(inner.into_allocated()),
+            LoopInit::Expr(inner) => LoopInit::Expr(inner.map_source_with(f)),
+        }
+    }
+}
+
+impl<T> LoopInit<T> {
+    /// If this loop head declares its own bindings
+    /// ```
+    /// use resast::prelude::*;
+    ///
+    /// // for (let i = 0;;)
+    /// let init = LoopInit::Variable(
+    ///     VarKind::Let,
+    ///     vec![VarDecl {
+    ///         id: Pat::ident_from("i"),
+    ///         init: Some(Expr::Lit(Lit::number_from("0"))),
+    ///     }],
+    /// );
+    /// assert!(init.is_var_decl());
+    /// assert_eq!(init.var_kind(), Some(VarKind::Let));
+    /// // for (i = 0;;)
+    /// let init = LoopInit::Expr(Expr::assign(
+    ///     AssignOp::Equal,
+    ///     Expr::ident_from("i"),
+    ///     Expr::Lit(Lit::number_from("0")),
+    /// ));
+    /// assert!(!init.is_var_decl());
+    /// assert_eq!(init.var_kind(), None);
+    /// ```
+    pub fn is_var_decl(&self) -> bool {
+        matches!(self, LoopInit::Variable(_, _))
+    }
+    pub fn var_kind(&self) -> Option<VarKind> {
+        match self {
+            LoopInit::Variable(kind, _) => Some(*kind),
+            LoopInit::Expr(_) => None,
         }
     }
 }
@@ -567,7 +907,7 @@ where
 /// }
 /// //prints a, b
 /// ```
-#[derive(PartialEq, Debug, Clone)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct ForInStmt<T> {
     pub left: LoopLeft<T>,
@@ -582,10 +922,30 @@ where
     type Allocated = ForInStmt<String>;
 
     fn into_allocated(self) -> Self::Allocated {
+        self.map_source_with(&mut |source: T| source.to_string())
+    }
+}
+
+impl<'a> AsBorrowed<'a> for ForInStmt<String> {
+    type Borrowed = ForInStmt<&'a str>;
+
+    fn as_borrowed(&'a self) -> Self::Borrowed {
         ForInStmt {
-            left: self.left.into_allocated(),
-            right: self.right.into_allocated(),
-            body: self.body.into_allocated(),
+            left: self.left.as_borrowed(),
+            right: self.right.as_borrowed(),
+            body: self.body.as_borrowed(),
+        }
+    }
+}
+
+impl<T, U> MapSource<T, U> for ForInStmt<T> {
+    type Output = ForInStmt<U>;
+
+    fn map_source_with(self, f: &mut impl FnMut(T) -> U) -> Self::Output {
+        ForInStmt {
+            left: self.left.map_source_with(f),
+            right: self.right.map_source_with(f),
+            body: self.body.map_source_with(f),
         }
     }
 }
@@ -598,7 +958,7 @@ where
 /// }
 /// //prints 2, 3, 4, 5, 6
 /// ```
-#[derive(PartialEq, Debug, Clone)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct ForOfStmt<T> {
     pub left: LoopLeft<T>,
@@ -614,10 +974,31 @@ where
     type Allocated = ForOfStmt<String>;
 
     fn into_allocated(self) -> Self::Allocated {
+        self.map_source_with(&mut |source: T| source.to_string())
+    }
+}
+
+impl<'a> AsBorrowed<'a> for ForOfStmt<String> {
+    type Borrowed = ForOfStmt<&'a str>;
+
+    fn as_borrowed(&'a self) -> Self::Borrowed {
         ForOfStmt {
-            left: self.left.into_allocated(),
-            right: self.right.into_allocated(),
-            body: self.body.into_allocated(),
+            left: self.left.as_borrowed(),
+            right: self.right.as_borrowed(),
+            body: self.body.as_borrowed(),
+            is_await: self.is_await,
+        }
+    }
+}
+
+impl<T, U> MapSource<T, U> for ForOfStmt<T> {
+    type Output = ForOfStmt<U>;
+
+    fn map_source_with(self, f: &mut impl FnMut(T) -> U) -> Self::Output {
+        ForOfStmt {
+            left: self.left.map_source_with(f),
+            right: self.right.map_source_with(f),
+            body: self.body.map_source_with(f),
             is_await: self.is_await,
         }
     }
@@ -625,7 +1006,7 @@ where
 
 /// The values on the left hand side of the keyword
 /// in a for in or for of loop
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub enum LoopLeft<T> {
     Expr(Expr<T>),
@@ -640,10 +1021,61 @@ where
     type Allocated = LoopLeft<String>;
 
     fn into_allocated(self) -> Self::Allocated {
+        self.map_source_with(&mut |source: T| source.to_string())
+    }
+}
+
+impl<'a> AsBorrowed<'a> for LoopLeft<String> {
+    type Borrowed = LoopLeft<&'a str>;
+
+    fn as_borrowed(&'a self) -> Self::Borrowed {
+        match self {
+            LoopLeft::Expr(inner) => LoopLeft::Expr(inner.as_borrowed()),
+            LoopLeft::Variable(k, v) => LoopLeft::Variable(*k, v.as_borrowed()),
+            LoopLeft::Pat(inner) => LoopLeft::Pat(inner.as_borrowed()),
+        }
+    }
+}
+
+impl<T, U> MapSource<T, U> for LoopLeft<T> {
+    type Output = LoopLeft<U>;
+
+    fn map_source_with(self, f: &mut impl FnMut(T) -> U) -> Self::Output {
+        match self {
+            LoopLeft::Expr(inner) => LoopLeft::Expr(inner.map_source_with(f)),
+            LoopLeft::Variable(k, v) => LoopLeft::Variable(k, v.map_source_with(f)),
+            LoopLeft::Pat(inner) => LoopLeft::Pat(inner.map_source_with(f)),
+        }
+    }
+}
+
+impl<T> LoopLeft<T> {
+    /// If this loop head declares its own binding
+    /// ```
+    /// use resast::prelude::*;
+    ///
+    /// // for (i in x)
+    /// let left = LoopLeft::Expr(Expr::ident_from("i"));
+    /// assert!(!left.is_var_decl());
+    /// assert_eq!(left.var_kind(), None);
+    /// // for (const k of y)
+    /// let left = LoopLeft::Variable(
+    ///     VarKind::Const,
+    ///     VarDecl {
+    ///         id: Pat::ident_from("k"),
+    ///         init: None,
+    ///     },
+    /// );
+    /// assert!(left.is_var_decl());
+    /// assert_eq!(left.var_kind(), Some(VarKind::Const));
+    /// ```
+    pub fn is_var_decl(&self) -> bool {
+        matches!(self, LoopLeft::Variable(_, _))
+    }
+    pub fn var_kind(&self) -> Option<VarKind> {
         match self {
-            LoopLeft::Expr(inner) => LoopLeft::Expr(inner.into_allocated()),
-            LoopLeft::Variable(k, v) => LoopLeft::Variable(k, v.into_allocated()),
-            LoopLeft::Pat(inner) => LoopLeft::Pat(inner.into_allocated()),
+            LoopLeft::Variable(kind, _) => Some(*kind),
+            LoopLeft::Expr(_) | LoopLeft::Pat(_) => None,
         }
     }
 }
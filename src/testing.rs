@@ -0,0 +1,72 @@
+//! Assertions for parsers that produce these trees, enabled
+//! with the `testing` feature
+//!
+//! ```
+//! use resast::spanned::decl::{Decl, VarDecl, VarDecls};
+//! use resast::spanned::expr::{Expr, Lit};
+//! use resast::spanned::pat::Pat;
+//! use resast::spanned::{Ident, ListEntry, Position, Program, ProgramPart, Slice, VarKind};
+//! use resast::testing::assert_span_monotonic;
+//!
+//! // let x = 1;
+//! let program = Program::script(vec![ProgramPart::Decl(Decl::Var {
+//!     decls: VarDecls {
+//!         keyword: VarKind::Let(Position::new(1, 0).into()),
+//!         decls: vec![ListEntry::no_comma(VarDecl {
+//!             id: Pat::Ident(Ident::new_from_source("x", 1, 4)),
+//!             eq: Some(Position::new(1, 6).into()),
+//!             init: Some(Expr::Lit(Lit::Number(Slice::new("1", 1, 8, 1, 9)))),
+//!         })],
+//!     },
+//!     semi_colon: Some(Position::new(1, 9).into()),
+//! })]);
+//! assert_span_monotonic(&program);
+//! ```
+use crate::spanned::segments::{Segments, Sink};
+use crate::spanned::{Program, SourceLocation};
+
+/// Every place a node of `program` has a child that falls outside of
+/// its own span, as `(parent, child)` pairs in tree order
+pub fn escaped_spans<T>(program: &Program<T>) -> Vec<(SourceLocation, SourceLocation)> {
+    let mut sink = Sink {
+        escaped: Some(Vec::new()),
+        ..Sink::default()
+    };
+    program.push_segments(&mut sink);
+    sink.escaped.unwrap_or_default()
+}
+
+/// Panic if any node of `program` has a child that falls outside of
+/// its own span, see [`escaped_spans`]
+/// ```should_panic
+/// use resast::spanned::expr::{BinaryExpr, Expr};
+/// use resast::spanned::stmt::Stmt;
+/// use resast::spanned::tokens::{BinaryOp, Plus};
+/// use resast::spanned::{Ident, Position, Program, ProgramPart};
+/// use resast::testing::assert_span_monotonic;
+///
+/// // a + b; with the `+` placed after the `b`
+/// let program = Program::script(vec![ProgramPart::Stmt(Stmt::Expr {
+///     expr: Expr::Binary(BinaryExpr {
+///         left: Box::new(Expr::Ident(Ident::new_from_source("a", 1, 0))),
+///         operator: BinaryOp::Plus(Plus::from(Position::new(1, 9))),
+///         right: Box::new(Expr::Ident(Ident::new_from_source("b", 1, 4))),
+///     }),
+///     semi_colon: Some(Position::new(1, 5).into()),
+/// })]);
+/// assert_span_monotonic(&program);
+/// ```
+pub fn assert_span_monotonic<T>(program: &Program<T>) {
+    let escaped = escaped_spans(program);
+    if !escaped.is_empty() {
+        let lines: Vec<_> = escaped
+            .iter()
+            .map(|(parent, child)| format!("  {:?} is outside of {:?}", child, parent))
+            .collect();
+        panic!(
+            "{} spans escape their parent node\n{}",
+            escaped.len(),
+            lines.join("\n")
+        );
+    }
+}
@@ -0,0 +1,1253 @@
+//! Traversal of the concrete AST
+//!
+//! Implement [`Visit`] (or [`VisitMut`] to rewrite nodes in place) and
+//! override only the methods you care about, the default implementations
+//! call the matching `walk_*` function which will recurse into every child
+//! node and call back into the visitor.
+//!
+//! ```
+//! use resast::prelude::*;
+//! use resast::visit::Visit;
+//!
+//! #[derive(Default)]
+//! struct IdentCounter(usize);
+//!
+//! impl<'ast> Visit<'ast, &'ast str> for IdentCounter {
+//!     fn visit_ident(&mut self, _ident: &'ast Ident<&'ast str>) {
+//!         self.0 += 1;
+//!     }
+//! }
+//!
+//! // a + b(c);
+//! let program = Program::script(vec![ProgramPart::Stmt(Stmt::Expr(Expr::Binary(BinaryExpr {
+//!     operator: BinaryOp::Plus,
+//!     left: Box::new(Expr::ident_from("a")),
+//!     right: Box::new(Expr::Call(CallExpr {
+//!         optional: false,
+//!         callee: Box::new(Expr::ident_from("b")),
+//!         arguments: vec![Expr::ident_from("c")],
+//!     })),
+//! })))]);
+//! let mut counter = IdentCounter::default();
+//! counter.visit_program(&program);
+//! assert_eq!(counter.0, 3);
+//! ```
+use crate::decl::{
+    DefaultExportDecl, ExportSpecifier, ImportAttributeKey, ImportSpecifier, ModExport, ModImport,
+    NamedExportDecl, VarDecl,
+};
+use crate::expr::{ArrowFuncBody, AssignLeft, ObjProp, Prop, PropKey, PropValue, TemplateLit};
+#[cfg(feature = "jsx")]
+use crate::jsx::{JsxAttr, JsxAttributeValue, JsxChild, JsxElement};
+use crate::pat::{ArrayPatPart, ObjPatPart};
+use crate::stmt::{BlockStmt, LoopInit, LoopLeft};
+use crate::{
+    Class, Decl, Dir, Expr, Func, FuncArg, FuncBody, Ident, Lit, Pat, PrivateIdent, Program,
+    ProgramPart, Stmt,
+};
+
+/// A read-only visitor over the concrete AST
+pub trait Visit<'ast, T> {
+    fn visit_program(&mut self, program: &'ast Program<T>) {
+        walk_program(self, program)
+    }
+    fn visit_program_part(&mut self, part: &'ast ProgramPart<T>) {
+        walk_program_part(self, part)
+    }
+    fn visit_dir(&mut self, dir: &'ast Dir<T>) {
+        walk_dir(self, dir)
+    }
+    fn visit_decl(&mut self, decl: &'ast Decl<T>) {
+        walk_decl(self, decl)
+    }
+    fn visit_var_decl(&mut self, decl: &'ast VarDecl<T>) {
+        walk_var_decl(self, decl)
+    }
+    fn visit_stmt(&mut self, stmt: &'ast Stmt<T>) {
+        walk_stmt(self, stmt)
+    }
+    fn visit_expr(&mut self, expr: &'ast Expr<T>) {
+        walk_expr(self, expr)
+    }
+    fn visit_pat(&mut self, pat: &'ast Pat<T>) {
+        walk_pat(self, pat)
+    }
+    fn visit_func(&mut self, func: &'ast Func<T>) {
+        walk_func(self, func)
+    }
+    fn visit_class(&mut self, class: &'ast Class<T>) {
+        walk_class(self, class)
+    }
+    fn visit_prop(&mut self, prop: &'ast Prop<T>) {
+        walk_prop(self, prop)
+    }
+    fn visit_lit(&mut self, lit: &'ast Lit<T>) {
+        walk_lit(self, lit)
+    }
+    fn visit_ident(&mut self, _ident: &'ast Ident<T>) {}
+    fn visit_private_ident(&mut self, _ident: &'ast PrivateIdent<T>) {}
+}
+
+pub fn walk_program<'ast, T, V>(v: &mut V, program: &'ast Program<T>)
+where
+    V: Visit<'ast, T> + ?Sized,
+{
+    match program {
+        Program::Mod { parts, .. } | Program::Script { parts, .. } => {
+            for part in parts {
+                v.visit_program_part(part);
+            }
+        }
+    }
+}
+
+pub fn walk_program_part<'ast, T, V>(v: &mut V, part: &'ast ProgramPart<T>)
+where
+    V: Visit<'ast, T> + ?Sized,
+{
+    match part {
+        ProgramPart::Dir(dir) => v.visit_dir(dir),
+        ProgramPart::Decl(decl) => v.visit_decl(decl),
+        ProgramPart::Stmt(stmt) => v.visit_stmt(stmt),
+    }
+}
+
+pub fn walk_dir<'ast, T, V>(v: &mut V, dir: &'ast Dir<T>)
+where
+    V: Visit<'ast, T> + ?Sized,
+{
+    v.visit_lit(&dir.expr);
+}
+
+pub fn walk_decl<'ast, T, V>(v: &mut V, decl: &'ast Decl<T>)
+where
+    V: Visit<'ast, T> + ?Sized,
+{
+    match decl {
+        Decl::Var(_, decls) => {
+            for decl in decls {
+                v.visit_var_decl(decl);
+            }
+        }
+        Decl::Func(func) => v.visit_func(func),
+        Decl::Class(class) => v.visit_class(class),
+        Decl::Import(import) => walk_mod_import(v, import),
+        Decl::Export(export) => walk_mod_export(v, export),
+    }
+}
+
+pub fn walk_var_decl<'ast, T, V>(v: &mut V, decl: &'ast VarDecl<T>)
+where
+    V: Visit<'ast, T> + ?Sized,
+{
+    v.visit_pat(&decl.id);
+    if let Some(init) = &decl.init {
+        v.visit_expr(init);
+    }
+}
+
+fn walk_mod_import<'ast, T, V>(v: &mut V, import: &'ast ModImport<T>)
+where
+    V: Visit<'ast, T> + ?Sized,
+{
+    for spec in &import.specifiers {
+        match spec {
+            ImportSpecifier::Normal(specs) => {
+                for spec in specs {
+                    v.visit_ident(&spec.imported);
+                    if let Some(alias) = &spec.alias {
+                        v.visit_ident(alias);
+                    }
+                }
+            }
+            ImportSpecifier::Default(ident) | ImportSpecifier::Namespace(ident) => {
+                v.visit_ident(ident)
+            }
+        }
+    }
+    v.visit_lit(&import.source);
+    // values are always a `StringLit`, like a jsx string attribute
+    // there is no `Lit` to visit
+    for attr in &import.attributes {
+        if let ImportAttributeKey::Ident(key) = &attr.key {
+            v.visit_ident(key);
+        }
+    }
+}
+
+fn walk_mod_export<'ast, T, V>(v: &mut V, export: &'ast ModExport<T>)
+where
+    V: Visit<'ast, T> + ?Sized,
+{
+    match export {
+        ModExport::Default(DefaultExportDecl::Decl(decl)) => v.visit_decl(decl),
+        ModExport::Default(DefaultExportDecl::Expr(expr)) => v.visit_expr(expr),
+        ModExport::Named(NamedExportDecl::Decl(decl)) => v.visit_decl(decl),
+        ModExport::Named(NamedExportDecl::Specifier(specs, source)) => {
+            for ExportSpecifier { local, alias } in specs {
+                v.visit_ident(local);
+                if let Some(alias) = alias {
+                    v.visit_ident(alias);
+                }
+            }
+            if let Some(source) = source {
+                v.visit_lit(source);
+            }
+        }
+        ModExport::All { alias, name } => {
+            if let Some(alias) = alias {
+                v.visit_ident(alias);
+            }
+            v.visit_lit(name);
+        }
+    }
+}
+
+pub fn walk_stmt<'ast, T, V>(v: &mut V, stmt: &'ast Stmt<T>)
+where
+    V: Visit<'ast, T> + ?Sized,
+{
+    match stmt {
+        Stmt::Expr(expr) | Stmt::Throw(expr) => v.visit_expr(expr),
+        Stmt::Block(block) => walk_block(v, block),
+        Stmt::Empty | Stmt::Debugger => {}
+        Stmt::With(with) => {
+            v.visit_expr(&with.object);
+            v.visit_stmt(&with.body);
+        }
+        Stmt::Return(arg) => {
+            if let Some(arg) = arg {
+                v.visit_expr(arg);
+            }
+        }
+        Stmt::Labeled(labeled) => {
+            v.visit_ident(&labeled.label);
+            v.visit_stmt(&labeled.body);
+        }
+        Stmt::Break(label) | Stmt::Continue(label) => {
+            if let Some(label) = label {
+                v.visit_ident(label);
+            }
+        }
+        Stmt::If(stmt) => {
+            v.visit_expr(&stmt.test);
+            v.visit_stmt(&stmt.consequent);
+            if let Some(alt) = &stmt.alternate {
+                v.visit_stmt(alt);
+            }
+        }
+        Stmt::Switch(switch) => {
+            v.visit_expr(&switch.discriminant);
+            for case in &switch.cases {
+                if let Some(test) = &case.test {
+                    v.visit_expr(test);
+                }
+                for part in &case.consequent {
+                    v.visit_program_part(part);
+                }
+            }
+        }
+        Stmt::Try(stmt) => {
+            walk_block(v, &stmt.block);
+            if let Some(handler) = &stmt.handler {
+                if let Some(param) = &handler.param {
+                    v.visit_pat(param);
+                }
+                walk_block(v, &handler.body);
+            }
+            if let Some(finalizer) = &stmt.finalizer {
+                walk_block(v, finalizer);
+            }
+        }
+        Stmt::While(stmt) => {
+            v.visit_expr(&stmt.test);
+            v.visit_stmt(&stmt.body);
+        }
+        Stmt::DoWhile(stmt) => {
+            v.visit_stmt(&stmt.body);
+            v.visit_expr(&stmt.test);
+        }
+        Stmt::For(stmt) => {
+            match &stmt.init {
+                Some(LoopInit::Variable(_, decls)) => {
+                    for decl in decls {
+                        v.visit_var_decl(decl);
+                    }
+                }
+                Some(LoopInit::Expr(expr)) => v.visit_expr(expr),
+                None => {}
+            }
+            if let Some(test) = &stmt.test {
+                v.visit_expr(test);
+            }
+            if let Some(update) = &stmt.update {
+                v.visit_expr(update);
+            }
+            v.visit_stmt(&stmt.body);
+        }
+        Stmt::ForIn(stmt) => {
+            walk_loop_left(v, &stmt.left);
+            v.visit_expr(&stmt.right);
+            v.visit_stmt(&stmt.body);
+        }
+        Stmt::ForOf(stmt) => {
+            walk_loop_left(v, &stmt.left);
+            v.visit_expr(&stmt.right);
+            v.visit_stmt(&stmt.body);
+        }
+        Stmt::Var(decls) => {
+            for decl in decls {
+                v.visit_var_decl(decl);
+            }
+        }
+    }
+}
+
+fn walk_block<'ast, T, V>(v: &mut V, block: &'ast BlockStmt<T>)
+where
+    V: Visit<'ast, T> + ?Sized,
+{
+    for part in &block.0 {
+        v.visit_program_part(part);
+    }
+}
+
+fn walk_loop_left<'ast, T, V>(v: &mut V, left: &'ast LoopLeft<T>)
+where
+    V: Visit<'ast, T> + ?Sized,
+{
+    match left {
+        LoopLeft::Expr(expr) => v.visit_expr(expr),
+        LoopLeft::Variable(_, decl) => v.visit_var_decl(decl),
+        LoopLeft::Pat(pat) => v.visit_pat(pat),
+    }
+}
+
+pub fn walk_expr<'ast, T, V>(v: &mut V, expr: &'ast Expr<T>)
+where
+    V: Visit<'ast, T> + ?Sized,
+{
+    match expr {
+        Expr::Array(elements) => {
+            for expr in elements.iter().flatten() {
+                v.visit_expr(expr);
+            }
+        }
+        Expr::ArrowFunc(arrow) => {
+            if let Some(id) = &arrow.id {
+                v.visit_ident(id);
+            }
+            walk_func_args(v, &arrow.params);
+            match &arrow.body {
+                ArrowFuncBody::FuncBody(body) => walk_func_body(v, body),
+                ArrowFuncBody::Expr(expr) => v.visit_expr(expr),
+            }
+        }
+        Expr::ArrowParamPlaceHolder(args, _) => walk_func_args(v, args),
+        Expr::Assign(assign) => {
+            match &assign.left {
+                AssignLeft::Pat(pat) => v.visit_pat(pat),
+                AssignLeft::Expr(expr) => v.visit_expr(expr),
+            }
+            v.visit_expr(&assign.right);
+        }
+        Expr::Await(expr) | Expr::Spread(expr) | Expr::OptionalChain(expr) | Expr::Paren(expr) => {
+            v.visit_expr(expr)
+        }
+        Expr::Binary(bin) => {
+            v.visit_expr(&bin.left);
+            v.visit_expr(&bin.right);
+        }
+        Expr::Class(class) => v.visit_class(class),
+        Expr::Call(call) => {
+            v.visit_expr(&call.callee);
+            for arg in &call.arguments {
+                v.visit_expr(arg);
+            }
+        }
+        Expr::Conditional(cond) => {
+            v.visit_expr(&cond.test);
+            v.visit_expr(&cond.consequent);
+            v.visit_expr(&cond.alternate);
+        }
+        Expr::Func(func) => v.visit_func(func),
+        Expr::Ident(ident) => v.visit_ident(ident),
+        Expr::PrivateIdent(ident) => v.visit_private_ident(ident),
+        Expr::Import(import) => {
+            v.visit_expr(&import.source);
+            if let Some(options) = &import.options {
+                v.visit_expr(options);
+            }
+        }
+        Expr::Lit(lit) => v.visit_lit(lit),
+        Expr::Logical(logical) => {
+            v.visit_expr(&logical.left);
+            v.visit_expr(&logical.right);
+        }
+        Expr::Member(member) => {
+            v.visit_expr(&member.object);
+            v.visit_expr(&member.property);
+        }
+        Expr::MetaProp(meta) => {
+            v.visit_ident(&meta.meta);
+            v.visit_ident(&meta.property);
+        }
+        Expr::New(new) => {
+            v.visit_expr(&new.callee);
+            for arg in &new.arguments {
+                v.visit_expr(arg);
+            }
+        }
+        Expr::Obj(props) => {
+            for prop in props {
+                match prop {
+                    ObjProp::Prop(prop) => v.visit_prop(prop),
+                    ObjProp::Spread(expr) => v.visit_expr(expr),
+                }
+            }
+        }
+        Expr::Sequence(exprs) => {
+            for expr in exprs {
+                v.visit_expr(expr);
+            }
+        }
+        Expr::Super | Expr::This => {}
+        Expr::TaggedTemplate(tagged) => {
+            v.visit_expr(&tagged.tag);
+            walk_template(v, &tagged.quasi);
+        }
+        Expr::Unary(unary) => v.visit_expr(&unary.argument),
+        Expr::Update(update) => v.visit_expr(&update.argument),
+        Expr::Yield(yield_expr) => {
+            if let Some(arg) = &yield_expr.argument {
+                v.visit_expr(arg);
+            }
+        }
+        #[cfg(feature = "jsx")]
+        Expr::Jsx(element) => walk_jsx_element(v, element),
+        #[cfg(feature = "jsx")]
+        Expr::JsxFragment(fragment) => walk_jsx_children(v, &fragment.children),
+    }
+}
+
+pub fn walk_pat<'ast, T, V>(v: &mut V, pat: &'ast Pat<T>)
+where
+    V: Visit<'ast, T> + ?Sized,
+{
+    match pat {
+        Pat::Ident(ident) => v.visit_ident(ident),
+        Pat::Obj(parts) => {
+            for part in parts {
+                match part {
+                    ObjPatPart::Assign(prop) => v.visit_prop(prop),
+                    ObjPatPart::Rest(pat) => v.visit_pat(pat),
+                }
+            }
+        }
+        Pat::Array(parts) => {
+            for part in parts.iter().flatten() {
+                match part {
+                    ArrayPatPart::Pat(pat) => v.visit_pat(pat),
+                    ArrayPatPart::Expr(expr) => v.visit_expr(expr),
+                }
+            }
+        }
+        Pat::RestElement(pat) => v.visit_pat(pat),
+        Pat::Assign(assign) => {
+            v.visit_pat(&assign.left);
+            v.visit_expr(&assign.right);
+        }
+    }
+}
+
+pub fn walk_func<'ast, T, V>(v: &mut V, func: &'ast Func<T>)
+where
+    V: Visit<'ast, T> + ?Sized,
+{
+    if let Some(id) = &func.id {
+        v.visit_ident(id);
+    }
+    walk_func_args(v, &func.params);
+    walk_func_body(v, &func.body);
+}
+
+fn walk_func_args<'ast, T, V>(v: &mut V, args: &'ast [FuncArg<T>])
+where
+    V: Visit<'ast, T> + ?Sized,
+{
+    for arg in args {
+        match arg {
+            FuncArg::Expr(expr) => v.visit_expr(expr),
+            FuncArg::Pat(pat) => v.visit_pat(pat),
+        }
+    }
+}
+
+fn walk_func_body<'ast, T, V>(v: &mut V, body: &'ast FuncBody<T>)
+where
+    V: Visit<'ast, T> + ?Sized,
+{
+    for part in &body.0 {
+        v.visit_program_part(part);
+    }
+}
+
+pub fn walk_class<'ast, T, V>(v: &mut V, class: &'ast Class<T>)
+where
+    V: Visit<'ast, T> + ?Sized,
+{
+    for decorator in &class.decorators {
+        v.visit_expr(&decorator.expr);
+    }
+    if let Some(id) = &class.id {
+        v.visit_ident(id);
+    }
+    if let Some(super_class) = &class.super_class {
+        v.visit_expr(super_class);
+    }
+    for prop in &class.body.0 {
+        v.visit_prop(prop);
+    }
+}
+
+pub fn walk_prop<'ast, T, V>(v: &mut V, prop: &'ast Prop<T>)
+where
+    V: Visit<'ast, T> + ?Sized,
+{
+    for decorator in &prop.decorators {
+        v.visit_expr(&decorator.expr);
+    }
+    match &prop.key {
+        PropKey::Lit(lit) => v.visit_lit(lit),
+        PropKey::Expr(expr) => v.visit_expr(expr),
+        PropKey::Pat(pat) => v.visit_pat(pat),
+        PropKey::None => {}
+    }
+    match &prop.value {
+        PropValue::Expr(expr) => v.visit_expr(expr),
+        PropValue::Pat(pat) => v.visit_pat(pat),
+        PropValue::Block(body) => walk_func_body(v, body),
+        PropValue::None => {}
+    }
+}
+
+pub fn walk_lit<'ast, T, V>(v: &mut V, lit: &'ast Lit<T>)
+where
+    V: Visit<'ast, T> + ?Sized,
+{
+    if let Lit::Template(template) = lit {
+        walk_template(v, template);
+    }
+}
+
+fn walk_template<'ast, T, V>(v: &mut V, template: &'ast TemplateLit<T>)
+where
+    V: Visit<'ast, T> + ?Sized,
+{
+    for expr in &template.expressions {
+        v.visit_expr(expr);
+    }
+}
+
+#[cfg(feature = "jsx")]
+fn walk_jsx_element<'ast, T, V>(v: &mut V, element: &'ast JsxElement<T>)
+where
+    V: Visit<'ast, T> + ?Sized,
+{
+    for attr in &element.opening.attributes {
+        match attr {
+            JsxAttr::Attribute(attr) => match &attr.value {
+                Some(JsxAttributeValue::Expr(container)) => {
+                    if let Some(expr) = &container.expr {
+                        v.visit_expr(expr);
+                    }
+                }
+                Some(JsxAttributeValue::Element(element)) => walk_jsx_element(v, element),
+                Some(JsxAttributeValue::Fragment(fragment)) => {
+                    walk_jsx_children(v, &fragment.children)
+                }
+                Some(JsxAttributeValue::String(_)) | None => {}
+            },
+            JsxAttr::Spread(expr) => v.visit_expr(expr),
+        }
+    }
+    walk_jsx_children(v, &element.children);
+}
+
+#[cfg(feature = "jsx")]
+fn walk_jsx_children<'ast, T, V>(v: &mut V, children: &'ast [JsxChild<T>])
+where
+    V: Visit<'ast, T> + ?Sized,
+{
+    for child in children {
+        match child {
+            JsxChild::Text(_) => {}
+            JsxChild::Expr(container) => {
+                if let Some(expr) = &container.expr {
+                    v.visit_expr(expr);
+                }
+            }
+            JsxChild::Spread(expr) => v.visit_expr(expr),
+            JsxChild::Element(element) => walk_jsx_element(v, element),
+            JsxChild::Fragment(fragment) => walk_jsx_children(v, &fragment.children),
+        }
+    }
+}
+
+/// Counts the nodes of a tree for `node_count`
+///
+/// Wrappers that only hold another visited node, like an
+/// `Expr::Ident` or a `Decl::Func`, are counted once as the
+/// inner node and `ProgramPart`s aren't counted at all.
+#[derive(Default)]
+pub(crate) struct NodeCounter(pub usize);
+
+impl<'ast, T> Visit<'ast, T> for NodeCounter {
+    fn visit_program(&mut self, program: &'ast Program<T>) {
+        self.0 += 1;
+        walk_program(self, program)
+    }
+    fn visit_dir(&mut self, dir: &'ast Dir<T>) {
+        self.0 += 1;
+        walk_dir(self, dir)
+    }
+    fn visit_decl(&mut self, decl: &'ast Decl<T>) {
+        if !matches!(decl, Decl::Func(_) | Decl::Class(_)) {
+            self.0 += 1;
+        }
+        walk_decl(self, decl)
+    }
+    fn visit_var_decl(&mut self, decl: &'ast VarDecl<T>) {
+        self.0 += 1;
+        walk_var_decl(self, decl)
+    }
+    fn visit_stmt(&mut self, stmt: &'ast Stmt<T>) {
+        self.0 += 1;
+        walk_stmt(self, stmt)
+    }
+    fn visit_expr(&mut self, expr: &'ast Expr<T>) {
+        if !matches!(
+            expr,
+            Expr::Ident(_) | Expr::PrivateIdent(_) | Expr::Lit(_) | Expr::Func(_) | Expr::Class(_)
+        ) {
+            self.0 += 1;
+        }
+        walk_expr(self, expr)
+    }
+    fn visit_pat(&mut self, pat: &'ast Pat<T>) {
+        if !matches!(pat, Pat::Ident(_)) {
+            self.0 += 1;
+        }
+        walk_pat(self, pat)
+    }
+    fn visit_func(&mut self, func: &'ast Func<T>) {
+        self.0 += 1;
+        walk_func(self, func)
+    }
+    fn visit_class(&mut self, class: &'ast Class<T>) {
+        self.0 += 1;
+        walk_class(self, class)
+    }
+    fn visit_prop(&mut self, prop: &'ast Prop<T>) {
+        self.0 += 1;
+        walk_prop(self, prop)
+    }
+    fn visit_lit(&mut self, lit: &'ast Lit<T>) {
+        self.0 += 1;
+        walk_lit(self, lit)
+    }
+    fn visit_ident(&mut self, _ident: &'ast Ident<T>) {
+        self.0 += 1;
+    }
+    fn visit_private_ident(&mut self, _ident: &'ast PrivateIdent<T>) {
+        self.0 += 1;
+    }
+}
+
+/// A visitor that can rewrite the concrete AST in place
+///
+/// ```
+/// use resast::prelude::*;
+/// use resast::visit::{walk_lit_mut, VisitMut};
+///
+/// struct Falsify;
+///
+/// impl<T> VisitMut<T> for Falsify {
+///     fn visit_lit_mut(&mut self, lit: &mut Lit<T>) {
+///         if let Lit::Boolean(value) = lit {
+///             *value = false;
+///         }
+///         walk_lit_mut(self, lit);
+///     }
+/// }
+///
+/// // if (true) { x = true }
+/// let mut program = Program::script(vec![ProgramPart::Stmt(Stmt::If(IfStmt {
+///     test: Expr::Lit(Lit::Boolean(true)),
+///     consequent: Box::new(Stmt::Block(BlockStmt(vec![ProgramPart::Stmt(Stmt::Expr(
+///         Expr::Assign(AssignExpr {
+///             operator: AssignOp::Equal,
+///             left: AssignLeft::Expr(Box::new(Expr::ident_from("x"))),
+///             right: Box::new(Expr::Lit(Lit::Boolean(true))),
+///         }),
+///     ))]))),
+///     alternate: None,
+/// }))]);
+/// Falsify.visit_program_mut(&mut program);
+/// let expected = Program::script(vec![ProgramPart::Stmt(Stmt::If(IfStmt {
+///     test: Expr::Lit(Lit::Boolean(false)),
+///     consequent: Box::new(Stmt::Block(BlockStmt(vec![ProgramPart::Stmt(Stmt::Expr(
+///         Expr::Assign(AssignExpr {
+///             operator: AssignOp::Equal,
+///             left: AssignLeft::Expr(Box::new(Expr::ident_from("x"))),
+///             right: Box::new(Expr::Lit(Lit::Boolean(false))),
+///         }),
+///     ))]))),
+///     alternate: None,
+/// }))]);
+/// assert_eq!(program, expected);
+/// ```
+pub trait VisitMut<T> {
+    fn visit_program_mut(&mut self, program: &mut Program<T>) {
+        walk_program_mut(self, program)
+    }
+    fn visit_program_part_mut(&mut self, part: &mut ProgramPart<T>) {
+        walk_program_part_mut(self, part)
+    }
+    fn visit_dir_mut(&mut self, dir: &mut Dir<T>) {
+        walk_dir_mut(self, dir)
+    }
+    fn visit_decl_mut(&mut self, decl: &mut Decl<T>) {
+        walk_decl_mut(self, decl)
+    }
+    fn visit_var_decl_mut(&mut self, decl: &mut VarDecl<T>) {
+        walk_var_decl_mut(self, decl)
+    }
+    fn visit_stmt_mut(&mut self, stmt: &mut Stmt<T>) {
+        walk_stmt_mut(self, stmt)
+    }
+    fn visit_expr_mut(&mut self, expr: &mut Expr<T>) {
+        walk_expr_mut(self, expr)
+    }
+    fn visit_pat_mut(&mut self, pat: &mut Pat<T>) {
+        walk_pat_mut(self, pat)
+    }
+    fn visit_func_mut(&mut self, func: &mut Func<T>) {
+        walk_func_mut(self, func)
+    }
+    fn visit_class_mut(&mut self, class: &mut Class<T>) {
+        walk_class_mut(self, class)
+    }
+    fn visit_prop_mut(&mut self, prop: &mut Prop<T>) {
+        walk_prop_mut(self, prop)
+    }
+    fn visit_lit_mut(&mut self, lit: &mut Lit<T>) {
+        walk_lit_mut(self, lit)
+    }
+    fn visit_ident_mut(&mut self, _ident: &mut Ident<T>) {}
+    fn visit_private_ident_mut(&mut self, _ident: &mut PrivateIdent<T>) {}
+}
+
+pub fn walk_program_mut<T, V>(v: &mut V, program: &mut Program<T>)
+where
+    V: VisitMut<T> + ?Sized,
+{
+    match program {
+        Program::Mod { parts, .. } | Program::Script { parts, .. } => {
+            for part in parts {
+                v.visit_program_part_mut(part);
+            }
+        }
+    }
+}
+
+pub fn walk_program_part_mut<T, V>(v: &mut V, part: &mut ProgramPart<T>)
+where
+    V: VisitMut<T> + ?Sized,
+{
+    match part {
+        ProgramPart::Dir(dir) => v.visit_dir_mut(dir),
+        ProgramPart::Decl(decl) => v.visit_decl_mut(decl),
+        ProgramPart::Stmt(stmt) => v.visit_stmt_mut(stmt),
+    }
+}
+
+pub fn walk_dir_mut<T, V>(v: &mut V, dir: &mut Dir<T>)
+where
+    V: VisitMut<T> + ?Sized,
+{
+    v.visit_lit_mut(&mut dir.expr);
+}
+
+pub fn walk_decl_mut<T, V>(v: &mut V, decl: &mut Decl<T>)
+where
+    V: VisitMut<T> + ?Sized,
+{
+    match decl {
+        Decl::Var(_, decls) => {
+            for decl in decls {
+                v.visit_var_decl_mut(decl);
+            }
+        }
+        Decl::Func(func) => v.visit_func_mut(func),
+        Decl::Class(class) => v.visit_class_mut(class),
+        Decl::Import(import) => walk_mod_import_mut(v, import),
+        Decl::Export(export) => walk_mod_export_mut(v, export),
+    }
+}
+
+pub fn walk_var_decl_mut<T, V>(v: &mut V, decl: &mut VarDecl<T>)
+where
+    V: VisitMut<T> + ?Sized,
+{
+    v.visit_pat_mut(&mut decl.id);
+    if let Some(init) = &mut decl.init {
+        v.visit_expr_mut(init);
+    }
+}
+
+fn walk_mod_import_mut<T, V>(v: &mut V, import: &mut ModImport<T>)
+where
+    V: VisitMut<T> + ?Sized,
+{
+    for spec in &mut import.specifiers {
+        match spec {
+            ImportSpecifier::Normal(specs) => {
+                for spec in specs {
+                    v.visit_ident_mut(&mut spec.imported);
+                    if let Some(alias) = &mut spec.alias {
+                        v.visit_ident_mut(alias);
+                    }
+                }
+            }
+            ImportSpecifier::Default(ident) | ImportSpecifier::Namespace(ident) => {
+                v.visit_ident_mut(ident)
+            }
+        }
+    }
+    v.visit_lit_mut(&mut import.source);
+    for attr in &mut import.attributes {
+        if let ImportAttributeKey::Ident(key) = &mut attr.key {
+            v.visit_ident_mut(key);
+        }
+    }
+}
+
+fn walk_mod_export_mut<T, V>(v: &mut V, export: &mut ModExport<T>)
+where
+    V: VisitMut<T> + ?Sized,
+{
+    match export {
+        ModExport::Default(DefaultExportDecl::Decl(decl)) => v.visit_decl_mut(decl),
+        ModExport::Default(DefaultExportDecl::Expr(expr)) => v.visit_expr_mut(expr),
+        ModExport::Named(NamedExportDecl::Decl(decl)) => v.visit_decl_mut(decl),
+        ModExport::Named(NamedExportDecl::Specifier(specs, source)) => {
+            for ExportSpecifier { local, alias } in specs {
+                v.visit_ident_mut(local);
+                if let Some(alias) = alias {
+                    v.visit_ident_mut(alias);
+                }
+            }
+            if let Some(source) = source {
+                v.visit_lit_mut(source);
+            }
+        }
+        ModExport::All { alias, name } => {
+            if let Some(alias) = alias {
+                v.visit_ident_mut(alias);
+            }
+            v.visit_lit_mut(name);
+        }
+    }
+}
+
+pub fn walk_stmt_mut<T, V>(v: &mut V, stmt: &mut Stmt<T>)
+where
+    V: VisitMut<T> + ?Sized,
+{
+    match stmt {
+        Stmt::Expr(expr) | Stmt::Throw(expr) => v.visit_expr_mut(expr),
+        Stmt::Block(block) => walk_block_mut(v, block),
+        Stmt::Empty | Stmt::Debugger => {}
+        Stmt::With(with) => {
+            v.visit_expr_mut(&mut with.object);
+            v.visit_stmt_mut(&mut with.body);
+        }
+        Stmt::Return(arg) => {
+            if let Some(arg) = arg {
+                v.visit_expr_mut(arg);
+            }
+        }
+        Stmt::Labeled(labeled) => {
+            v.visit_ident_mut(&mut labeled.label);
+            v.visit_stmt_mut(&mut labeled.body);
+        }
+        Stmt::Break(label) | Stmt::Continue(label) => {
+            if let Some(label) = label {
+                v.visit_ident_mut(label);
+            }
+        }
+        Stmt::If(stmt) => {
+            v.visit_expr_mut(&mut stmt.test);
+            v.visit_stmt_mut(&mut stmt.consequent);
+            if let Some(alt) = &mut stmt.alternate {
+                v.visit_stmt_mut(alt);
+            }
+        }
+        Stmt::Switch(switch) => {
+            v.visit_expr_mut(&mut switch.discriminant);
+            for case in &mut switch.cases {
+                if let Some(test) = &mut case.test {
+                    v.visit_expr_mut(test);
+                }
+                for part in &mut case.consequent {
+                    v.visit_program_part_mut(part);
+                }
+            }
+        }
+        Stmt::Try(stmt) => {
+            walk_block_mut(v, &mut stmt.block);
+            if let Some(handler) = &mut stmt.handler {
+                if let Some(param) = &mut handler.param {
+                    v.visit_pat_mut(param);
+                }
+                walk_block_mut(v, &mut handler.body);
+            }
+            if let Some(finalizer) = &mut stmt.finalizer {
+                walk_block_mut(v, finalizer);
+            }
+        }
+        Stmt::While(stmt) => {
+            v.visit_expr_mut(&mut stmt.test);
+            v.visit_stmt_mut(&mut stmt.body);
+        }
+        Stmt::DoWhile(stmt) => {
+            v.visit_stmt_mut(&mut stmt.body);
+            v.visit_expr_mut(&mut stmt.test);
+        }
+        Stmt::For(stmt) => {
+            match &mut stmt.init {
+                Some(LoopInit::Variable(_, decls)) => {
+                    for decl in decls {
+                        v.visit_var_decl_mut(decl);
+                    }
+                }
+                Some(LoopInit::Expr(expr)) => v.visit_expr_mut(expr),
+                None => {}
+            }
+            if let Some(test) = &mut stmt.test {
+                v.visit_expr_mut(test);
+            }
+            if let Some(update) = &mut stmt.update {
+                v.visit_expr_mut(update);
+            }
+            v.visit_stmt_mut(&mut stmt.body);
+        }
+        Stmt::ForIn(stmt) => {
+            walk_loop_left_mut(v, &mut stmt.left);
+            v.visit_expr_mut(&mut stmt.right);
+            v.visit_stmt_mut(&mut stmt.body);
+        }
+        Stmt::ForOf(stmt) => {
+            walk_loop_left_mut(v, &mut stmt.left);
+            v.visit_expr_mut(&mut stmt.right);
+            v.visit_stmt_mut(&mut stmt.body);
+        }
+        Stmt::Var(decls) => {
+            for decl in decls {
+                v.visit_var_decl_mut(decl);
+            }
+        }
+    }
+}
+
+fn walk_block_mut<T, V>(v: &mut V, block: &mut BlockStmt<T>)
+where
+    V: VisitMut<T> + ?Sized,
+{
+    for part in &mut block.0 {
+        v.visit_program_part_mut(part);
+    }
+}
+
+fn walk_loop_left_mut<T, V>(v: &mut V, left: &mut LoopLeft<T>)
+where
+    V: VisitMut<T> + ?Sized,
+{
+    match left {
+        LoopLeft::Expr(expr) => v.visit_expr_mut(expr),
+        LoopLeft::Variable(_, decl) => v.visit_var_decl_mut(decl),
+        LoopLeft::Pat(pat) => v.visit_pat_mut(pat),
+    }
+}
+
+pub fn walk_expr_mut<T, V>(v: &mut V, expr: &mut Expr<T>)
+where
+    V: VisitMut<T> + ?Sized,
+{
+    match expr {
+        Expr::Array(elements) => {
+            for expr in elements.iter_mut().flatten() {
+                v.visit_expr_mut(expr);
+            }
+        }
+        Expr::ArrowFunc(arrow) => {
+            if let Some(id) = &mut arrow.id {
+                v.visit_ident_mut(id);
+            }
+            walk_func_args_mut(v, &mut arrow.params);
+            match &mut arrow.body {
+                ArrowFuncBody::FuncBody(body) => walk_func_body_mut(v, body),
+                ArrowFuncBody::Expr(expr) => v.visit_expr_mut(expr),
+            }
+        }
+        Expr::ArrowParamPlaceHolder(args, _) => walk_func_args_mut(v, args),
+        Expr::Assign(assign) => {
+            match &mut assign.left {
+                AssignLeft::Pat(pat) => v.visit_pat_mut(pat),
+                AssignLeft::Expr(expr) => v.visit_expr_mut(expr),
+            }
+            v.visit_expr_mut(&mut assign.right);
+        }
+        Expr::Await(expr) | Expr::Spread(expr) | Expr::OptionalChain(expr) | Expr::Paren(expr) => {
+            v.visit_expr_mut(expr)
+        }
+        Expr::Binary(bin) => {
+            v.visit_expr_mut(&mut bin.left);
+            v.visit_expr_mut(&mut bin.right);
+        }
+        Expr::Class(class) => v.visit_class_mut(class),
+        Expr::Call(call) => {
+            v.visit_expr_mut(&mut call.callee);
+            for arg in &mut call.arguments {
+                v.visit_expr_mut(arg);
+            }
+        }
+        Expr::Conditional(cond) => {
+            v.visit_expr_mut(&mut cond.test);
+            v.visit_expr_mut(&mut cond.consequent);
+            v.visit_expr_mut(&mut cond.alternate);
+        }
+        Expr::Func(func) => v.visit_func_mut(func),
+        Expr::Ident(ident) => v.visit_ident_mut(ident),
+        Expr::PrivateIdent(ident) => v.visit_private_ident_mut(ident),
+        Expr::Import(import) => {
+            v.visit_expr_mut(&mut import.source);
+            if let Some(options) = &mut import.options {
+                v.visit_expr_mut(options);
+            }
+        }
+        Expr::Lit(lit) => v.visit_lit_mut(lit),
+        Expr::Logical(logical) => {
+            v.visit_expr_mut(&mut logical.left);
+            v.visit_expr_mut(&mut logical.right);
+        }
+        Expr::Member(member) => {
+            v.visit_expr_mut(&mut member.object);
+            v.visit_expr_mut(&mut member.property);
+        }
+        Expr::MetaProp(meta) => {
+            v.visit_ident_mut(&mut meta.meta);
+            v.visit_ident_mut(&mut meta.property);
+        }
+        Expr::New(new) => {
+            v.visit_expr_mut(&mut new.callee);
+            for arg in &mut new.arguments {
+                v.visit_expr_mut(arg);
+            }
+        }
+        Expr::Obj(props) => {
+            for prop in props {
+                match prop {
+                    ObjProp::Prop(prop) => v.visit_prop_mut(prop),
+                    ObjProp::Spread(expr) => v.visit_expr_mut(expr),
+                }
+            }
+        }
+        Expr::Sequence(exprs) => {
+            for expr in exprs {
+                v.visit_expr_mut(expr);
+            }
+        }
+        Expr::Super | Expr::This => {}
+        Expr::TaggedTemplate(tagged) => {
+            v.visit_expr_mut(&mut tagged.tag);
+            walk_template_mut(v, &mut tagged.quasi);
+        }
+        Expr::Unary(unary) => v.visit_expr_mut(&mut unary.argument),
+        Expr::Update(update) => v.visit_expr_mut(&mut update.argument),
+        Expr::Yield(yield_expr) => {
+            if let Some(arg) = &mut yield_expr.argument {
+                v.visit_expr_mut(arg);
+            }
+        }
+        #[cfg(feature = "jsx")]
+        Expr::Jsx(element) => walk_jsx_element_mut(v, element),
+        #[cfg(feature = "jsx")]
+        Expr::JsxFragment(fragment) => walk_jsx_children_mut(v, &mut fragment.children),
+    }
+}
+
+pub fn walk_pat_mut<T, V>(v: &mut V, pat: &mut Pat<T>)
+where
+    V: VisitMut<T> + ?Sized,
+{
+    match pat {
+        Pat::Ident(ident) => v.visit_ident_mut(ident),
+        Pat::Obj(parts) => {
+            for part in parts {
+                match part {
+                    ObjPatPart::Assign(prop) => v.visit_prop_mut(prop),
+                    ObjPatPart::Rest(pat) => v.visit_pat_mut(pat),
+                }
+            }
+        }
+        Pat::Array(parts) => {
+            for part in parts.iter_mut().flatten() {
+                match part {
+                    ArrayPatPart::Pat(pat) => v.visit_pat_mut(pat),
+                    ArrayPatPart::Expr(expr) => v.visit_expr_mut(expr),
+                }
+            }
+        }
+        Pat::RestElement(pat) => v.visit_pat_mut(pat),
+        Pat::Assign(assign) => {
+            v.visit_pat_mut(&mut assign.left);
+            v.visit_expr_mut(&mut assign.right);
+        }
+    }
+}
+
+pub fn walk_func_mut<T, V>(v: &mut V, func: &mut Func<T>)
+where
+    V: VisitMut<T> + ?Sized,
+{
+    if let Some(id) = &mut func.id {
+        v.visit_ident_mut(id);
+    }
+    walk_func_args_mut(v, &mut func.params);
+    walk_func_body_mut(v, &mut func.body);
+}
+
+fn walk_func_args_mut<T, V>(v: &mut V, args: &mut [FuncArg<T>])
+where
+    V: VisitMut<T> + ?Sized,
+{
+    for arg in args {
+        match arg {
+            FuncArg::Expr(expr) => v.visit_expr_mut(expr),
+            FuncArg::Pat(pat) => v.visit_pat_mut(pat),
+        }
+    }
+}
+
+fn walk_func_body_mut<T, V>(v: &mut V, body: &mut FuncBody<T>)
+where
+    V: VisitMut<T> + ?Sized,
+{
+    for part in &mut body.0 {
+        v.visit_program_part_mut(part);
+    }
+}
+
+pub fn walk_class_mut<T, V>(v: &mut V, class: &mut Class<T>)
+where
+    V: VisitMut<T> + ?Sized,
+{
+    for decorator in &mut class.decorators {
+        v.visit_expr_mut(&mut decorator.expr);
+    }
+    if let Some(id) = &mut class.id {
+        v.visit_ident_mut(id);
+    }
+    if let Some(super_class) = &mut class.super_class {
+        v.visit_expr_mut(super_class);
+    }
+    for prop in &mut class.body.0 {
+        v.visit_prop_mut(prop);
+    }
+}
+
+pub fn walk_prop_mut<T, V>(v: &mut V, prop: &mut Prop<T>)
+where
+    V: VisitMut<T> + ?Sized,
+{
+    for decorator in &mut prop.decorators {
+        v.visit_expr_mut(&mut decorator.expr);
+    }
+    match &mut prop.key {
+        PropKey::Lit(lit) => v.visit_lit_mut(lit),
+        PropKey::Expr(expr) => v.visit_expr_mut(expr),
+        PropKey::Pat(pat) => v.visit_pat_mut(pat),
+        PropKey::None => {}
+    }
+    match &mut prop.value {
+        PropValue::Expr(expr) => v.visit_expr_mut(expr),
+        PropValue::Pat(pat) => v.visit_pat_mut(pat),
+        PropValue::Block(body) => walk_func_body_mut(v, body),
+        PropValue::None => {}
+    }
+}
+
+pub fn walk_lit_mut<T, V>(v: &mut V, lit: &mut Lit<T>)
+where
+    V: VisitMut<T> + ?Sized,
+{
+    if let Lit::Template(template) = lit {
+        walk_template_mut(v, template);
+    }
+}
+
+fn walk_template_mut<T, V>(v: &mut V, template: &mut TemplateLit<T>)
+where
+    V: VisitMut<T> + ?Sized,
+{
+    for expr in &mut template.expressions {
+        v.visit_expr_mut(expr);
+    }
+}
+
+#[cfg(feature = "jsx")]
+fn walk_jsx_element_mut<T, V>(v: &mut V, element: &mut JsxElement<T>)
+where
+    V: VisitMut<T> + ?Sized,
+{
+    for attr in &mut element.opening.attributes {
+        match attr {
+            JsxAttr::Attribute(attr) => match &mut attr.value {
+                Some(JsxAttributeValue::Expr(container)) => {
+                    if let Some(expr) = &mut container.expr {
+                        v.visit_expr_mut(expr);
+                    }
+                }
+                Some(JsxAttributeValue::Element(element)) => walk_jsx_element_mut(v, element),
+                Some(JsxAttributeValue::Fragment(fragment)) => {
+                    walk_jsx_children_mut(v, &mut fragment.children)
+                }
+                Some(JsxAttributeValue::String(_)) | None => {}
+            },
+            JsxAttr::Spread(expr) => v.visit_expr_mut(expr),
+        }
+    }
+    walk_jsx_children_mut(v, &mut element.children);
+}
+
+#[cfg(feature = "jsx")]
+fn walk_jsx_children_mut<T, V>(v: &mut V, children: &mut [JsxChild<T>])
+where
+    V: VisitMut<T> + ?Sized,
+{
+    for child in children {
+        match child {
+            JsxChild::Text(_) => {}
+            JsxChild::Expr(container) => {
+                if let Some(expr) = &mut container.expr {
+                    v.visit_expr_mut(expr);
+                }
+            }
+            JsxChild::Spread(expr) => v.visit_expr_mut(expr),
+            JsxChild::Element(element) => walk_jsx_element_mut(v, element),
+            JsxChild::Fragment(fragment) => walk_jsx_children_mut(v, &mut fragment.children),
+        }
+    }
+}